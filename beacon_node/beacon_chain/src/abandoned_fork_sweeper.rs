@@ -0,0 +1,217 @@
+//! Periodically deletes the blocks and states of abandoned forks from the hot database.
+//!
+//! The migrator prunes abandoned forks at finalization by walking back from the heads in the
+//! head tracker, so states from forks that never became heads (or whose heads were forgotten) are
+//! never deleted. The sweeper instead reconciles every hot state summary against fork choice,
+//! deleting each state whose latest block doesn't descend from the finalized checkpoint, along
+//! with that block.
+//!
+//! Only blocks and states with slots greater than both the split slot and the slot of the
+//! finalized block are considered. Canonical blocks from before the finalized block may already
+//! have been pruned from fork choice, and are left for the migrator.
+//!
+//! Every run that deletes something is recorded in the garbage collection log in the database.
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use parking_lot::RwLockWriteGuard;
+use slog::{debug, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use store::garbage_collection::{GcDeletion, GcRun};
+use store::{Error as StoreError, StoreOp};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// Default number of epochs between runs of the sweeper.
+pub const DEFAULT_EPOCHS_PER_FORK_SWEEP: u64 = 8;
+
+/// Run the sweeper half way through the epoch, away from the migrations which are triggered by
+/// finalization at the start of the epoch.
+const EPOCH_DELAY_FACTOR: u32 = 2;
+
+/// Spawn the sweeper, unless it has been disabled by setting `epochs_per_fork_sweep` to 0.
+pub fn start_abandoned_fork_sweeper_service<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+) {
+    if chain.config.epochs_per_fork_sweep > 0 {
+        executor.spawn(
+            async move { abandoned_fork_sweeper_service(chain).await },
+            "abandoned_fork_sweeper",
+        );
+    }
+}
+
+async fn abandoned_fork_sweeper_service<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>) {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let epoch_duration = chain.slot_clock.slot_duration() * slots_per_epoch as u32;
+    loop {
+        let Some(duration) = chain.slot_clock.duration_to_next_epoch(slots_per_epoch) else {
+            // The slot clock is unavailable, e.g. before genesis. Try again later.
+            sleep(chain.slot_clock.slot_duration()).await;
+            continue;
+        };
+        sleep(duration + epoch_duration / EPOCH_DELAY_FACTOR).await;
+
+        let Ok(epoch) = chain.epoch() else {
+            continue;
+        };
+        if epoch % chain.config.epochs_per_fork_sweep != 0 {
+            continue;
+        }
+
+        let inner_chain = chain.clone();
+        match chain
+            .spawn_blocking_handle(
+                move || inner_chain.sweep_abandoned_forks(),
+                "abandoned_fork_sweeper",
+            )
+            .await
+        {
+            Ok(Ok(_)) => (),
+            Ok(Err(e)) | Err(e) => {
+                warn!(
+                    chain.log,
+                    "Abandoned fork sweep failed";
+                    "error" => ?e,
+                );
+            }
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Delete the blocks and states of abandoned forks from the hot database, returning a record
+    /// of what was deleted.
+    ///
+    /// The record is appended to the garbage collection log if anything was deleted.
+    pub fn sweep_abandoned_forks(&self) -> Result<GcRun, BeaconChainError> {
+        let split = self.store.get_split_info();
+
+        // Read the database before fork choice. Blocks are added to fork choice before they're
+        // written to the database (under the fork choice write lock), so every block referenced
+        // by a summary read here is already known to fork choice.
+        let summaries = self.store.load_hot_state_summaries_after(split.slot)?;
+
+        let (finalized_slot, abandoned_states, abandoned_block_roots) = {
+            let fork_choice = self.canonical_head.fork_choice_read_lock();
+            let finalized_slot = fork_choice.get_finalized_block()?.slot;
+            let min_slot = std::cmp::max(split.slot, finalized_slot);
+
+            let mut abandoned_states = vec![];
+            let mut abandoned_block_roots = HashSet::new();
+            for (state_root, summary) in summaries {
+                if summary.slot > min_slot
+                    && !fork_choice.is_finalized_checkpoint_or_descendant(summary.latest_block_root)
+                {
+                    abandoned_states.push(GcDeletion {
+                        root: state_root,
+                        slot: summary.slot,
+                    });
+                    abandoned_block_roots.insert(summary.latest_block_root);
+                }
+            }
+            for (head_block_root, _) in self.head_tracker.heads() {
+                if !fork_choice.is_finalized_checkpoint_or_descendant(head_block_root) {
+                    abandoned_block_roots.insert(head_block_root);
+                }
+            }
+            (finalized_slot, abandoned_states, abandoned_block_roots)
+        };
+        let min_slot = std::cmp::max(split.slot, finalized_slot);
+
+        // Blocks are only deleted if they're newer than the finalized block, as older blocks may
+        // be canonical even though they're no longer in fork choice.
+        let mut abandoned_blocks = vec![];
+        for block_root in abandoned_block_roots {
+            match self.store.get_blinded_block(&block_root) {
+                Ok(Some(block)) if block.slot() > min_slot => {
+                    abandoned_blocks.push(GcDeletion {
+                        root: block_root,
+                        slot: block.slot(),
+                    });
+                }
+                Ok(_) => (),
+                Err(StoreError::SszDecodeError(e)) => {
+                    debug!(
+                        self.log,
+                        "Skipping undecodable block in fork sweep";
+                        "block_root" => ?block_root,
+                        "error" => ?e,
+                    );
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        abandoned_blocks.sort_by_key(|deletion| deletion.slot);
+        abandoned_states.sort_by_key(|deletion| deletion.slot);
+
+        let run = GcRun {
+            timestamp_millis: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_millis() as u64),
+            split_slot: split.slot,
+            finalized_slot,
+            deleted_blocks: abandoned_blocks,
+            deleted_states: abandoned_states,
+        };
+
+        if run.is_empty() {
+            debug!(
+                self.log,
+                "No abandoned forks to sweep";
+                "split_slot" => split.slot,
+                "finalized_slot" => finalized_slot,
+            );
+            return Ok(run);
+        }
+
+        let mut batch: Vec<StoreOp<T::EthSpec>> = run
+            .deleted_blocks
+            .iter()
+            .flat_map(|deletion| {
+                [
+                    StoreOp::DeleteBlock(deletion.root),
+                    StoreOp::DeleteExecutionPayload(deletion.root),
+                    StoreOp::DeleteBlobs(deletion.root),
+                ]
+            })
+            .chain(
+                run.deleted_states
+                    .iter()
+                    .map(|deletion| StoreOp::DeleteState(deletion.root, Some(deletion.slot))),
+            )
+            .collect();
+        batch.extend(
+            self.store
+                .gc_log_append_ops(&run)?
+                .into_iter()
+                .map(StoreOp::KeyValueOp),
+        );
+
+        // Remove deleted blocks from the head tracker and persist it in the same batch, so that
+        // every block in the head tracker remains present in the database.
+        let mut head_tracker_lock = self.head_tracker.0.write();
+        for deletion in &run.deleted_blocks {
+            head_tracker_lock.remove(&deletion.root);
+        }
+        let head_tracker_lock = RwLockWriteGuard::downgrade(head_tracker_lock);
+        batch.push(StoreOp::KeyValueOp(
+            self.persist_head_in_batch(&head_tracker_lock),
+        ));
+        self.store.do_atomically_with_block_and_blobs_cache(batch)?;
+        drop(head_tracker_lock);
+
+        info!(
+            self.log,
+            "Swept abandoned forks";
+            "deleted_blocks" => run.deleted_blocks.len(),
+            "deleted_states" => run.deleted_states.len(),
+            "finalized_slot" => finalized_slot,
+        );
+
+        Ok(run)
+    }
+}
@@ -84,8 +84,15 @@ pub struct ChainConfig {
     pub always_prepare_payload: bool,
     /// Number of epochs between each migration of data from the hot database to the freezer.
     pub epochs_per_migration: u64,
+    /// Number of epochs between each sweep of abandoned forks from the hot database.
+    ///
+    /// Set to 0 to disable the sweeper.
+    pub epochs_per_fork_sweep: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// Whether blobs fetched from the execution layer's mempool should be published on gossip if
+    /// they haven't already been seen there.
+    pub publish_engine_blobs: bool,
 }
 
 impl Default for ChainConfig {
@@ -117,7 +124,9 @@ impl Default for ChainConfig {
             genesis_backfill: false,
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
+            epochs_per_fork_sweep: crate::abandoned_fork_sweeper::DEFAULT_EPOCHS_PER_FORK_SWEEP,
             enable_light_client_server: false,
+            publish_engine_blobs: true,
         }
     }
 }
@@ -163,6 +163,7 @@ fn get_sync_status<E: EthSpec>(
         voting_target_timestamp,
         eth1_node_sync_status_percentage,
         lighthouse_is_cached_and_ready,
+        execution_engines: vec![],
     })
 }
 
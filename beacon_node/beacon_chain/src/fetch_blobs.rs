@@ -0,0 +1,173 @@
+//! Provides functions for fetching a block's blobs from the execution layer's blob mempool.
+//!
+//! Blobs are usually received over gossip or requested from peers via `BlobsByRoot`. However, a
+//! block's blobs are very likely to already be in the local execution node's mempool, in which
+//! case they can be made available as soon as the block has been received.
+//!
+//! Blobs fetched from the execution layer are KZG verified before they are cached, since the
+//! execution layer is not trusted to have done so.
+use crate::blob_verification::KzgVerifiedBlobList;
+use crate::{metrics, AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError};
+use execution_layer::BlobAndProofV1;
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use state_processing::per_block_processing::deneb::kzg_commitment_to_versioned_hash;
+use std::sync::Arc;
+use types::blob_sidecar::BlobSidecarError;
+use types::{BlobSidecar, EthSpec, Hash256, SignedBeaconBlock};
+
+#[derive(Debug)]
+pub enum FetchEngineBlobError<E: EthSpec> {
+    ExecutionLayerMissing,
+    KzgNotInitialized,
+    UnableToReadSlot,
+    RequestFailed(execution_layer::Error),
+    UnexpectedResponseLength { expected: usize, received: usize },
+    BlobSidecarError(BlobSidecarError),
+    KzgError(kzg::Error),
+    BlobProcessingError(BlockError<E>),
+}
+
+/// Fetches any of the blobs for `block` that have not yet been received from the execution
+/// layer's mempool and processes them.
+///
+/// If `chain.config.publish_engine_blobs` is set, any fetched blobs that haven't yet been seen on
+/// gossip are passed to `publish_fn` so they can be published.
+///
+/// Returns `Ok(None)` if no blobs were fetched, either because the block has no outstanding blobs,
+/// the execution layer doesn't support `engine_getBlobsV1` or it didn't have any of the blobs.
+pub async fn fetch_and_process_engine_blobs<T: BeaconChainTypes>(
+    chain: &Arc<BeaconChain<T>>,
+    block_root: Hash256,
+    block: Arc<SignedBeaconBlock<T::EthSpec>>,
+    publish_fn: impl FnOnce(Vec<Arc<BlobSidecar<T::EthSpec>>>),
+) -> Result<Option<AvailabilityProcessingStatus>, FetchEngineBlobError<T::EthSpec>> {
+    let Ok(kzg_commitments) = block.message().body().blob_kzg_commitments() else {
+        return Ok(None);
+    };
+
+    // Only request the blobs that haven't already been received over gossip or RPC.
+    let known_indices = chain
+        .data_availability_checker
+        .imported_blob_indexes(&block_root)
+        .unwrap_or_default();
+    let (indices, versioned_hashes): (Vec<_>, Vec<_>) = kzg_commitments
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !known_indices.contains(&(*index as u64)))
+        .map(|(index, commitment)| (index, kzg_commitment_to_versioned_hash(commitment)))
+        .unzip();
+
+    if versioned_hashes.is_empty() {
+        return Ok(None);
+    }
+
+    let execution_layer = chain
+        .execution_layer
+        .as_ref()
+        .ok_or(FetchEngineBlobError::ExecutionLayerMissing)?;
+
+    let capabilities = execution_layer
+        .get_engine_capabilities(None)
+        .await
+        .map_err(FetchEngineBlobError::RequestFailed)?;
+    if !capabilities.get_blobs_v1 {
+        return Ok(None);
+    }
+
+    metrics::inc_counter(&metrics::BLOBS_FROM_EL_REQUESTS);
+    metrics::inc_counter_by(&metrics::BLOBS_FROM_EL_EXPECTED, indices.len() as u64);
+
+    let timer = metrics::start_timer(&metrics::BLOBS_FROM_EL_FETCH_TIMES);
+    let response = execution_layer
+        .get_blobs(versioned_hashes)
+        .await
+        .map_err(FetchEngineBlobError::RequestFailed)?;
+    drop(timer);
+
+    if response.len() != indices.len() {
+        return Err(FetchEngineBlobError::UnexpectedResponseLength {
+            expected: indices.len(),
+            received: response.len(),
+        });
+    }
+
+    let blobs = indices
+        .into_iter()
+        .zip(response)
+        .filter_map(|(index, blob_and_proof)| {
+            blob_and_proof.map(|BlobAndProofV1 { blob, proof }| {
+                BlobSidecar::new(index, blob, &block, proof).map(Arc::new)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(FetchEngineBlobError::BlobSidecarError)?;
+
+    metrics::inc_counter_by(&metrics::BLOBS_FROM_EL_RECEIVED, blobs.len() as u64);
+
+    if blobs.is_empty() {
+        debug!(
+            chain.log,
+            "No blobs fetched from the EL";
+            "block_root" => ?block_root,
+        );
+        return Ok(None);
+    }
+
+    debug!(
+        chain.log,
+        "Fetched blobs from the EL";
+        "block_root" => ?block_root,
+        "num_blobs" => blobs.len(),
+        "num_commitments" => kzg_commitments.len(),
+    );
+
+    let kzg = chain
+        .kzg
+        .as_ref()
+        .ok_or(FetchEngineBlobError::KzgNotInitialized)?;
+    let seen_timestamp = chain
+        .slot_clock
+        .now_duration()
+        .ok_or(FetchEngineBlobError::UnableToReadSlot)?;
+    let verified_blobs = KzgVerifiedBlobList::new(blobs.iter().cloned(), kzg, seen_timestamp)
+        .map_err(FetchEngineBlobError::KzgError)?;
+
+    // Publish the blobs before importing them, so that peers receive them as early as possible.
+    // Blobs that have been seen on gossip in the meantime will already have been propagated.
+    if chain.config.publish_engine_blobs {
+        let blobs_to_publish = {
+            let mut observed_blob_sidecars = chain.observed_blob_sidecars.write();
+            blobs
+                .into_iter()
+                .filter(|blob| match observed_blob_sidecars.observe_sidecar(blob) {
+                    Ok(already_observed) => !already_observed,
+                    Err(e) => {
+                        warn!(
+                            chain.log,
+                            "Unable to observe blob fetched from the EL";
+                            "block_root" => ?block_root,
+                            "index" => blob.index,
+                            "error" => ?e,
+                        );
+                        false
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if !blobs_to_publish.is_empty() {
+            metrics::inc_counter_by(
+                &metrics::BLOBS_FROM_EL_PUBLISHED,
+                blobs_to_publish.len() as u64,
+            );
+            publish_fn(blobs_to_publish);
+        }
+    }
+
+    chain
+        .process_engine_blobs(block.slot(), block_root, verified_blobs)
+        .await
+        .map(Some)
+        .map_err(FetchEngineBlobError::BlobProcessingError)
+}
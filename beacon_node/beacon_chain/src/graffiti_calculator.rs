@@ -1,6 +1,9 @@
 use crate::BeaconChain;
+use crate::BeaconChainError;
 use crate::BeaconChainTypes;
-use execution_layer::{http::ENGINE_GET_CLIENT_VERSION_V1, CommitPrefix, ExecutionLayer};
+use execution_layer::{
+    http::ENGINE_GET_CLIENT_VERSION_V1, ClientVersionV1, CommitPrefix, ExecutionLayer,
+};
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, warn, Logger};
 use slot_clock::SlotClock;
@@ -137,6 +140,32 @@ impl<T: BeaconChainTypes> GraffitiCalculator<T> {
             }
         }
     }
+
+    /// Returns the client versions reported by the execution engine, reusing the cached response
+    /// if it is no older than the one used for graffiti calculation.
+    ///
+    /// An empty list indicates that the EL doesn't support `engine_getClientVersionV1`.
+    pub async fn get_engine_versions(&self) -> Result<Vec<ClientVersionV1>, BeaconChainError> {
+        let execution_layer = self
+            .execution_layer
+            .as_ref()
+            .ok_or(BeaconChainError::ExecutionLayerMissing)?;
+        execution_layer
+            .get_engine_version(Some(
+                self.epoch_duration * ENGINE_VERSION_AGE_LIMIT_EPOCH_MULTIPLE,
+            ))
+            .await
+            .map_err(|e| BeaconChainError::EngineGetClientVersionFailed(Box::new(e)))
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns the client versions reported by the execution engine.
+    ///
+    /// See `GraffitiCalculator::get_engine_versions`.
+    pub async fn get_engine_versions(&self) -> Result<Vec<ClientVersionV1>, BeaconChainError> {
+        self.graffiti_calculator.get_engine_versions().await
+    }
 }
 
 pub fn start_engine_version_cache_refresh_service<T: BeaconChainTypes>(
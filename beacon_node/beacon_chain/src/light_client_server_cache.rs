@@ -4,16 +4,21 @@ use parking_lot::{Mutex, RwLock};
 use slog::{debug, Logger};
 use ssz_types::FixedVector;
 use std::num::NonZeroUsize;
-use types::light_client_update::{FinalizedRootProofLen, FINALIZED_ROOT_INDEX};
+use std::sync::Arc;
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
     BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, Slot, SyncAggregate,
+    LightClientOptimisticUpdate, LightClientUpdate, Slot, SyncAggregate, SyncCommittee,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
-/// prev block cache are very small 32 * (6 + 1) = 224 bytes. 32 is an arbitrary number that
-/// represents unlikely re-orgs, while keeping the cache very small.
+/// prev block cache are small, 32 * (6 + 5 + 1) = 384 bytes plus a shared reference to the next
+/// sync committee. 32 is an arbitrary number that represents unlikely re-orgs, while keeping the
+/// cache very small.
 const PREV_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
 
 /// This cache computes light client messages ahead of time, required to satisfy p2p and API
@@ -30,8 +35,11 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// Tracks a single global latest optimistic update out of all imported blocks.
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Tracks the best update of the most recent sync committee period, by period. The best update
+    /// of every period is persisted in the store.
+    best_light_client_update: RwLock<Option<(u64, LightClientUpdate<T::EthSpec>)>>,
     /// Caches state proofs by block root
-    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData>>,
+    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -39,6 +47,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Self {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
+            best_light_client_update: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
         }
     }
@@ -127,26 +136,93 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             }
             None => true,
         };
-        if is_latest_finality & !cached_parts.finalized_block_root.is_zero() {
-            // Immediately after checkpoint sync the finalized block may not be available yet.
-            if let Some(finalized_block) =
-                store.get_full_block(&cached_parts.finalized_block_root)?
-            {
-                *self.latest_finality_update.write() = Some(LightClientFinalityUpdate::new(
-                    &attested_block,
-                    &finalized_block,
-                    cached_parts.finality_branch.clone(),
-                    sync_aggregate.clone(),
-                    signature_slot,
-                    chain_spec,
-                )?);
-            } else {
-                debug!(
-                    log,
-                    "Finalized block not available in store for light_client server";
-                    "finalized_block_root" => format!("{}", cached_parts.finalized_block_root),
-                );
+        if cached_parts.finalized_block_root.is_zero() {
+            return Ok(());
+        }
+
+        // Immediately after checkpoint sync the finalized block may not be available yet.
+        let Some(finalized_block) = store.get_full_block(&cached_parts.finalized_block_root)?
+        else {
+            debug!(
+                log,
+                "Finalized block not available in store for light_client server";
+                "finalized_block_root" => format!("{}", cached_parts.finalized_block_root),
+            );
+            return Ok(());
+        };
+
+        if is_latest_finality {
+            *self.latest_finality_update.write() = Some(LightClientFinalityUpdate::new(
+                &attested_block,
+                &finalized_block,
+                cached_parts.finality_branch.clone(),
+                sync_aggregate.clone(),
+                signature_slot,
+                chain_spec,
+            )?);
+        }
+
+        // Spec: Full nodes SHOULD provide the best derivable LightClientUpdate for each sync
+        // committee period
+        let attested_period = attested_slot
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let signature_period = signature_slot
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let participants = sync_aggregate.num_set_bits();
+        if attested_period == signature_period
+            && participants >= chain_spec.min_sync_committee_participants as usize
+        {
+            let new_update = LightClientUpdate::new(
+                sync_aggregate,
+                signature_slot,
+                cached_parts.next_sync_committee.clone(),
+                cached_parts.next_sync_committee_branch.clone(),
+                cached_parts.finality_branch.clone(),
+                &attested_block,
+                &finalized_block,
+                chain_spec,
+            )?;
+            self.store_light_client_update_if_better(
+                &store,
+                attested_period,
+                new_update,
+                chain_spec,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Persist `new_update` as the best update of `sync_committee_period`, unless a better update
+    /// is already known.
+    fn store_light_client_update_if_better(
+        &self,
+        store: &BeaconStore<T>,
+        sync_committee_period: u64,
+        new_update: LightClientUpdate<T::EthSpec>,
+        chain_spec: &ChainSpec,
+    ) -> Result<(), BeaconChainError> {
+        let mut best_update = self.best_light_client_update.write();
+
+        let prev_update = match &*best_update {
+            Some((period, update)) if *period == sync_committee_period => Some(update.clone()),
+            _ => store.get_light_client_update(sync_committee_period)?,
+        };
+
+        let is_better = match &prev_update {
+            Some(prev_update) => {
+                prev_update.is_better_light_client_update(&new_update, chain_spec)?
             }
+            None => true,
+        };
+
+        if is_better {
+            store.put_light_client_update(sync_committee_period, &new_update)?;
+            *best_update = Some((sync_committee_period, new_update));
+        } else if let Some(prev_update) = prev_update {
+            *best_update = Some((sync_committee_period, prev_update));
         }
 
         Ok(())
@@ -162,7 +238,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         block_root: &Hash256,
         block_state_root: &Hash256,
         block_slot: Slot,
-    ) -> Result<LightClientCachedData, BeaconChainError> {
+    ) -> Result<LightClientCachedData<T::EthSpec>, BeaconChainError> {
         // Attempt to get the value from the cache first.
         if let Some(cached_parts) = self.prev_block_cache.lock().get(block_root) {
             return Ok(cached_parts.clone());
@@ -200,17 +276,24 @@ impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
 }
 
 type FinalityBranch = FixedVector<Hash256, FinalizedRootProofLen>;
+type NextSyncCommitteeBranch = FixedVector<Hash256, NextSyncCommitteeProofLen>;
 
 #[derive(Clone)]
-struct LightClientCachedData {
+struct LightClientCachedData<E: EthSpec> {
     finality_branch: FinalityBranch,
+    next_sync_committee_branch: NextSyncCommitteeBranch,
+    next_sync_committee: Arc<SyncCommittee<E>>,
     finalized_block_root: Hash256,
 }
 
-impl LightClientCachedData {
-    fn from_state<E: EthSpec>(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
+impl<E: EthSpec> LightClientCachedData<E> {
+    fn from_state(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
         Ok(Self {
             finality_branch: state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?.into(),
+            next_sync_committee_branch: state
+                .compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?
+                .into(),
+            next_sync_committee: state.next_sync_committee()?.clone(),
             finalized_block_root: state.finalized_checkpoint().root,
         })
     }
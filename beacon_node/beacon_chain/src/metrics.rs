@@ -1045,6 +1045,30 @@ lazy_static! {
         "blob_sidecar_inclusion_proof_computation_seconds",
         "Time taken to compute blob sidecar inclusion proof"
     );
+
+    /*
+     * Blobs fetched from the execution layer
+     */
+    pub static ref BLOBS_FROM_EL_REQUESTS: Result<IntCounter> = try_create_int_counter(
+        "beacon_blobs_from_el_requests_total",
+        "Number of requests for blobs made to the EL"
+    );
+    pub static ref BLOBS_FROM_EL_EXPECTED: Result<IntCounter> = try_create_int_counter(
+        "beacon_blobs_from_el_expected_total",
+        "Number of blobs requested from the EL"
+    );
+    pub static ref BLOBS_FROM_EL_RECEIVED: Result<IntCounter> = try_create_int_counter(
+        "beacon_blobs_from_el_received_total",
+        "Number of requested blobs that were returned by the EL"
+    );
+    pub static ref BLOBS_FROM_EL_PUBLISHED: Result<IntCounter> = try_create_int_counter(
+        "beacon_blobs_from_el_published_total",
+        "Number of blobs returned by the EL that were published on gossip"
+    );
+    pub static ref BLOBS_FROM_EL_FETCH_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_blobs_from_el_fetch_seconds",
+        "Time taken to fetch blobs from the EL"
+    );
 }
 
 // Fifth lazy-static block is used to account for macro recursion limit.
@@ -0,0 +1,190 @@
+//! Feed historical blocks from the database through the slasher.
+//!
+//! The slasher only sees blocks and attestations as they arrive, so enabling it on an existing
+//! node leaves past epochs uncovered. A rescan replays the canonical blocks from a range of
+//! epochs, and the attestations they contain, into the slasher. Rescanned items are queued and
+//! processed separately from those received from the network, and never in the same batch.
+use crate::historical_blocks::HistoricalBlockError;
+use crate::{errors::BeaconChainError as Error, BeaconChain, BeaconChainTypes, StateSkipConfig};
+use slasher::Slasher;
+use slog::{debug, error, info};
+use state_processing::common::get_indexed_attestation;
+use std::sync::Arc;
+use types::{Epoch, EthSpec};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Rescan the canonical blocks from `start_epoch` to `end_epoch` (inclusive) on a background
+    /// thread.
+    ///
+    /// Progress can be tracked using `Slasher::rescan_stats`.
+    pub fn spawn_slasher_rescan(
+        self: &Arc<Self>,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<(), Error> {
+        self.start_slasher_rescan(start_epoch, end_epoch)?;
+
+        let chain = self.clone();
+        self.task_executor.spawn_blocking(
+            move || {
+                if let Err(e) = chain.process_slasher_rescan(start_epoch, end_epoch) {
+                    error!(
+                        chain.log,
+                        "Slasher rescan failed";
+                        "error" => ?e,
+                    );
+                }
+            },
+            "slasher_rescan",
+        );
+        Ok(())
+    }
+
+    /// Rescan the canonical blocks from `start_epoch` to `end_epoch` (inclusive), blocking until
+    /// the rescan is complete.
+    pub fn slasher_rescan(&self, start_epoch: Epoch, end_epoch: Epoch) -> Result<(), Error> {
+        self.start_slasher_rescan(start_epoch, end_epoch)?;
+        self.process_slasher_rescan(start_epoch, end_epoch)
+    }
+
+    fn start_slasher_rescan(&self, start_epoch: Epoch, end_epoch: Epoch) -> Result<(), Error> {
+        let slasher = self.slasher.as_ref().ok_or(Error::SlasherNotConfigured)?;
+
+        let start_slot = start_epoch.start_slot(T::EthSpec::slots_per_epoch());
+        let oldest_block_slot = self.store.get_oldest_block_slot();
+        if start_slot < oldest_block_slot {
+            return Err(HistoricalBlockError::BlockOutOfRange {
+                slot: start_slot,
+                oldest_block_slot,
+            }
+            .into());
+        }
+
+        slasher.start_rescan(start_epoch, end_epoch, self.epoch()?)?;
+        Ok(())
+    }
+
+    fn process_slasher_rescan(&self, start_epoch: Epoch, end_epoch: Epoch) -> Result<(), Error> {
+        let slasher = self.slasher.as_ref().ok_or(Error::SlasherNotConfigured)?;
+
+        let result = (start_epoch.as_u64()..=end_epoch.as_u64())
+            .map(Epoch::new)
+            .try_for_each(|epoch| self.slasher_rescan_epoch(slasher, epoch));
+
+        slasher.update_rescan_stats(|stats| match &result {
+            Ok(()) => stats.complete = true,
+            Err(e) => stats.error = Some(format!("{:?}", e)),
+        });
+
+        if result.is_ok() {
+            info!(
+                self.log,
+                "Slasher rescan complete";
+                "start_epoch" => start_epoch,
+                "end_epoch" => end_epoch,
+            );
+        }
+        result
+    }
+
+    /// Queue the canonical blocks from `epoch` and their attestations, and apply them to the
+    /// slasher database as a single batch.
+    fn slasher_rescan_epoch(
+        &self,
+        slasher: &Slasher<T::EthSpec>,
+        epoch: Epoch,
+    ) -> Result<(), Error> {
+        let (num_blocks, num_attestations) = self.queue_slasher_rescan_blocks(slasher, epoch)?;
+        let batch = slasher.process_rescan_queued(self.epoch()?)?;
+
+        debug!(
+            self.log,
+            "Slasher rescan processed epoch";
+            "epoch" => epoch,
+            "num_blocks" => num_blocks,
+            "num_attestations" => num_attestations,
+        );
+        slasher.update_rescan_stats(|stats| {
+            stats.add_epoch(epoch, num_blocks, num_attestations, &batch)
+        });
+        Ok(())
+    }
+
+    /// Queue the canonical blocks from `epoch` and their attestations with the slasher.
+    ///
+    /// Returns `(num_blocks, num_attestations)`.
+    fn queue_slasher_rescan_blocks(
+        &self,
+        slasher: &Slasher<T::EthSpec>,
+        epoch: Epoch,
+    ) -> Result<(usize, usize), Error> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let start_slot = epoch.start_slot(slots_per_epoch);
+
+        // No blocks from this epoch have been imported yet.
+        if start_slot > self.canonical_head.cached_head().head_slot() {
+            return Ok((0, 0));
+        }
+
+        // Blocks only include attestations from the current and previous epoch, so the committee
+        // caches of the state at the start of the epoch are sufficient.
+        let mut state = self.state_at_slot(start_slot, StateSkipConfig::WithoutStateRoots)?;
+        state
+            .build_all_committee_caches(&self.spec)
+            .map_err(Error::BeaconStateError)?;
+
+        let mut num_blocks = 0;
+        let mut num_attestations = 0;
+        let mut prev_block_root = None;
+
+        for res in
+            self.forwards_iter_block_roots_until(start_slot, epoch.end_slot(slots_per_epoch))?
+        {
+            let (block_root, slot) = res?;
+
+            // Skipped slots repeat the root of the previous block, and the genesis block is
+            // unsigned.
+            if prev_block_root == Some(block_root) || slot == self.spec.genesis_slot {
+                continue;
+            }
+            prev_block_root = Some(block_root);
+
+            let block = self
+                .get_blinded_block(&block_root)?
+                .ok_or(Error::MissingBeaconBlock(block_root))?;
+
+            // The first slot of the epoch may be skipped, in which case its root belongs to a block
+            // from an earlier epoch.
+            if block.slot() != slot {
+                continue;
+            }
+
+            slasher.accept_rescan_block_header(block.signed_block_header());
+            num_blocks += 1;
+
+            for attestation in block.message().body().attestations() {
+                let indexed_attestation = match state
+                    .get_beacon_committee(attestation.data.slot, attestation.data.index)
+                    .map_err(Into::into)
+                    .and_then(|committee| get_indexed_attestation(committee.committee, attestation))
+                {
+                    Ok(indexed) => indexed,
+                    Err(e) => {
+                        debug!(
+                            self.log,
+                            "Failed to get indexed attestation";
+                            "purpose" => "slasher rescan",
+                            "attestation_slot" => attestation.data.slot,
+                            "error" => ?e,
+                        );
+                        continue;
+                    }
+                };
+                slasher.accept_rescan_attestation(indexed_attestation);
+                num_attestations += 1;
+            }
+        }
+
+        Ok((num_blocks, num_attestations))
+    }
+}
@@ -3,7 +3,8 @@
 use beacon_chain::block_verification_types::{AsBlock, ExecutedBlock, RpcBlock};
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
-    AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, ExecutionPendingBlock,
+    AvailabilityProcessingStatus, BeaconChain, BeaconChainError, BeaconChainTypes,
+    ExecutionPendingBlock,
 };
 use beacon_chain::{
     BeaconSnapshot, BlockError, ChainConfig, ChainSegmentResult, IntoExecutionPendingBlock,
@@ -1185,6 +1186,64 @@ async fn verify_block_for_gossip_slashing_detection() {
     slasher_dir.close().unwrap();
 }
 
+#[tokio::test]
+async fn slasher_rescan_historical_blocks() {
+    let slasher_dir = tempdir().unwrap();
+    let slasher = Arc::new(
+        Slasher::open(SlasherConfig::new(slasher_dir.path().into()), test_logger()).unwrap(),
+    );
+
+    let inner_slasher = slasher.clone();
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS.to_vec())
+        .fresh_ephemeral_store()
+        .initial_mutator(Box::new(move |builder| builder.slasher(inner_slasher)))
+        .mock_execution_layer()
+        .build();
+
+    let num_blocks = 3 * E::slots_per_epoch() as usize;
+    harness
+        .extend_chain(
+            num_blocks,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let current_epoch = harness.chain.epoch().unwrap();
+
+    // Clear out the blocks and attestations which were seen as they were imported.
+    slasher.process_queued(current_epoch).unwrap();
+
+    // Epochs in the future can't be rescanned.
+    assert!(matches!(
+        harness
+            .chain
+            .slasher_rescan(Epoch::new(0), current_epoch + 1)
+            .unwrap_err(),
+        BeaconChainError::SlasherError(slasher::Error::RescanOutOfRange { .. })
+    ));
+    assert_eq!(slasher.rescan_stats(), None);
+
+    harness
+        .chain
+        .slasher_rescan(Epoch::new(0), current_epoch)
+        .unwrap();
+
+    let stats = slasher.rescan_stats().unwrap();
+    assert!(stats.complete);
+    assert_eq!(stats.error, None);
+    assert_eq!(stats.last_processed_epoch, Some(current_epoch));
+    assert_eq!(stats.num_blocks, num_blocks);
+    assert!(stats.num_attestations > 0);
+    assert_eq!(stats.num_proposer_slashings, 0);
+    assert!(slasher.get_attester_slashings().is_empty());
+
+    drop(harness);
+    drop(slasher);
+    slasher_dir.close().unwrap();
+}
+
 #[tokio::test]
 async fn verify_block_for_gossip_doppelganger_detection() {
     let harness = get_harness(VALIDATOR_COUNT);
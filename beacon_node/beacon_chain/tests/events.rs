@@ -1,11 +1,13 @@
 use beacon_chain::blob_verification::GossipVerifiedBlob;
+use beacon_chain::fetch_blobs::fetch_and_process_engine_blobs;
 use beacon_chain::test_utils::BeaconChainHarness;
+use beacon_chain::{AvailabilityProcessingStatus, NotifyExecutionLayer};
 use eth2::types::{EventKind, SseBlobSidecar};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use std::sync::Arc;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, ForkName, MinimalEthSpec};
+use types::{BlobSidecar, BlockImportSource, EthSpec, ForkName, MinimalEthSpec};
 
 type E = MinimalEthSpec;
 
@@ -91,3 +93,85 @@ async fn blob_sidecar_event_on_process_rpc_blobs() {
     }
     assert_eq!(sse_blobs, expected_sse_blobs);
 }
+
+/// Verifies that blobs fetched from the EL for a gossip block are published, emitted as events and
+/// make the block available.
+#[tokio::test]
+async fn blob_sidecar_event_on_fetch_engine_blobs() {
+    let spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    // the mock EL includes a random number of blobs in each payload, so produce blocks until one
+    // has blobs
+    let (block, blobs) = loop {
+        harness.advance_slot();
+        let state = harness.get_current_state();
+        let ((block, blobs), _) = harness.make_block(state, harness.get_current_slot()).await;
+        match blobs {
+            Some((_, blobs)) if !blobs.is_empty() => break (block, blobs),
+            blobs => {
+                harness.process_block_result((block, blobs)).await.unwrap();
+            }
+        }
+    };
+    let num_blobs = blobs.len();
+
+    // subscribe to blob sidecar events
+    let event_handler = harness.chain.event_handler.as_ref().unwrap();
+    let mut blob_event_receiver = event_handler.subscribe_blob_sidecar();
+
+    // import the block without its blobs
+    let gossip_verified_block = harness
+        .chain
+        .verify_block_for_gossip(block.clone())
+        .await
+        .unwrap();
+    let block_root = gossip_verified_block.block_root;
+    let status = harness
+        .chain
+        .process_block(
+            block_root,
+            gossip_verified_block,
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Gossip,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        AvailabilityProcessingStatus::MissingComponents(..)
+    ));
+
+    // fetch the blobs from the mock EL
+    let mut published_blobs = vec![];
+    let status = fetch_and_process_engine_blobs(&harness.chain, block_root, block, |blobs| {
+        published_blobs = blobs
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        status,
+        Some(AvailabilityProcessingStatus::Imported(block_root))
+    );
+    assert_eq!(published_blobs.len(), num_blobs);
+
+    let expected_sse_blobs = published_blobs
+        .iter()
+        .map(|blob| SseBlobSidecar::from_blob_sidecar(blob.as_ref()))
+        .collect::<Vec<_>>();
+    let mut sse_blobs: Vec<SseBlobSidecar> = vec![];
+    while let Ok(sidecar_event) = blob_event_receiver.try_recv() {
+        if let EventKind::BlobSidecar(sse_blob_sidecar) = sidecar_event {
+            sse_blobs.push(sse_blob_sidecar);
+        } else {
+            panic!("`BlobSidecar` event kind expected.");
+        }
+    }
+    assert_eq!(sse_blobs, expected_sse_blobs);
+}
@@ -28,10 +28,15 @@ use std::time::Duration;
 use store::chunked_vector::Chunk;
 use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
 use store::{
+    backup::{self, BackupError, BackupManifest},
     chunked_vector::{chunk_key, Field},
+    era::{self, EraError},
+    garbage_collection::{GcDeletion, GcRun, GC_LOG_MAX_RUNS},
     get_key_for_col,
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
+    verify::Issue,
+    AnchorInfo, BeaconNodeBackend, BlobInfo, DBColumn, DatabaseBackend, Error as StoreError,
+    HotColdDB, KeyValueStore, KeyValueStoreOp, StoreConfig, StoreOp,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -51,7 +56,7 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_generic(db_path, StoreConfig::default(), test_spec::<E>())
 }
 
@@ -59,7 +64,7 @@ fn get_store_generic(
     db_path: &TempDir,
     config: StoreConfig,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let blobs_path = db_path.path().join("blobs_db");
@@ -78,7 +83,7 @@ fn get_store_generic(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -90,7 +95,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -374,6 +379,307 @@ async fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[tokio::test]
+async fn hierarchical_diffs_full_participation() {
+    let num_blocks_produced = E::slots_per_epoch() * 20;
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(
+        &db_path,
+        StoreConfig {
+            slots_per_restore_point: 8,
+            hierarchy_config: Some("3,4,6".parse().unwrap()),
+            ..Default::default()
+        },
+        test_spec::<E>(),
+    );
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    check_finalization(&harness, num_blocks_produced);
+    check_split_slot(&harness, store.clone());
+    check_chain_dump(&harness, num_blocks_produced + 1);
+    check_iterators(&harness);
+
+    let num_diffs = || {
+        store
+            .cold_db
+            .iter_column::<Hash256>(DBColumn::BeaconStateDiff)
+            .count()
+    };
+    assert!(num_diffs() > 0, "some restore points should be diffs");
+
+    // Converting back to full restore points should remove all diffs without affecting states.
+    store.convert_freezer_hierarchy(None).unwrap();
+    assert_eq!(num_diffs(), 0);
+    check_chain_dump(&harness, num_blocks_produced + 1);
+    check_iterators(&harness);
+}
+
+#[tokio::test]
+async fn backup_and_restore() {
+    let num_blocks_produced = E::slots_per_epoch() * 8;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    check_finalization(&harness, num_blocks_produced);
+
+    let backup_dir = db_path.path().join("backup");
+    let manifest = store.backup(&backup_dir).unwrap();
+    let split = store.get_split_info();
+    assert_eq!(manifest.split.slot, split.slot);
+    assert_eq!(manifest.split.state_root, split.state_root);
+    assert_eq!(manifest.schema_version, CURRENT_SCHEMA_VERSION.as_u64());
+    assert!(manifest.cold_entries > 0);
+    assert!(matches!(
+        store.backup(&backup_dir),
+        Err(StoreError::BackupError(BackupError::OutputExists(_)))
+    ));
+
+    // Restore into a new data directory using the other backend.
+    let restore_path = tempdir().unwrap();
+    let config = StoreConfig {
+        backend: DatabaseBackend::Redb,
+        ..StoreConfig::default()
+    };
+    let restore = || {
+        backup::restore::<E>(
+            &backup_dir,
+            &config,
+            &restore_path.path().join("hot_db"),
+            &restore_path.path().join("cold_db"),
+            &restore_path.path().join("blobs_db"),
+        )
+    };
+    assert_eq!(restore().unwrap(), manifest);
+    assert!(matches!(
+        restore(),
+        Err(StoreError::BackupError(BackupError::TargetNotEmpty(_)))
+    ));
+
+    let restored = get_store_generic(&restore_path, config.clone(), test_spec::<E>());
+    assert_eq!(restored.get_split_info().slot, split.slot);
+    for (state_root, slot) in [
+        (harness.chain.genesis_state_root, Slot::new(0)),
+        (split.state_root, split.slot),
+    ] {
+        assert_eq!(
+            restored.get_state(&state_root, Some(slot)).unwrap(),
+            store.get_state(&state_root, Some(slot)).unwrap(),
+        );
+    }
+
+    // Backups from newer software or with an incompatible config are rejected.
+    let future_manifest = BackupManifest {
+        schema_version: CURRENT_SCHEMA_VERSION.as_u64() + 1,
+        ..manifest.clone()
+    };
+    assert!(backup::verify_manifest(&future_manifest, &config).is_err());
+    let hierarchy_config = StoreConfig {
+        hierarchy_config: Some("5,7".parse().unwrap()),
+        ..config
+    };
+    assert!(backup::verify_manifest(&manifest, &hierarchy_config).is_err());
+}
+
+#[tokio::test]
+async fn verify_database() {
+    let num_blocks_produced = E::slots_per_epoch() * 16;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    check_finalization(&harness, num_blocks_produced);
+
+    let head_block_root = harness.head_block_root();
+    let report = store.verify(head_block_root, false).unwrap();
+    assert!(report.is_ok(), "{:?}", report.issues);
+    assert_eq!(report.blocks_checked, num_blocks_produced + 1);
+    assert!(report.restore_points_checked > 0);
+    assert!(report.state_summaries_checked > 0);
+
+    // Orphaned entries are reported, and deleted when repairing.
+    let orphan_root = Hash256::repeat_byte(0xaa);
+    store
+        .hot_db
+        .put_bytes(DBColumn::ExecPayload.into(), orphan_root.as_bytes(), &[0])
+        .unwrap();
+    store
+        .do_atomically_with_block_and_blobs_cache(vec![StoreOp::PutStateTemporaryFlag(orphan_root)])
+        .unwrap();
+    let orphans = vec![
+        Issue::TemporaryState {
+            state_root: orphan_root,
+        },
+        Issue::OrphanedExecutionPayload {
+            block_root: orphan_root,
+        },
+    ];
+    assert_eq!(
+        store.verify(head_block_root, false).unwrap().issues,
+        orphans
+    );
+
+    let report = store.verify(head_block_root, true).unwrap();
+    assert!(report.is_ok(), "{:?}", report.issues);
+    assert_eq!(report.repaired, orphans);
+    assert!(store.verify(head_block_root, false).unwrap().is_ok());
+
+    // Missing canonical blocks can't be repaired.
+    let block_root = harness
+        .chain
+        .block_root_at_slot(Slot::new(1), WhenSlotSkipped::None)
+        .unwrap()
+        .unwrap();
+    store.delete_block(&block_root).unwrap();
+    let report = store.verify(head_block_root, true).unwrap();
+    assert!(report.repaired.is_empty());
+    assert!(report.issues.contains(&Issue::MissingBlock { block_root }));
+}
+
+#[tokio::test]
+async fn export_and_import_era() {
+    let slots_per_historical_root = E::slots_per_historical_root() as u64;
+    let num_blocks_produced = E::slots_per_epoch() * 24;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    check_finalization(&harness, num_blocks_produced);
+
+    // Export every complete era, including states so that pre-Capella eras can be verified.
+    assert_eq!(store.exportable_eras(), Some(1..=2));
+    let era_dir = db_path.path().join("era");
+    std::fs::create_dir(&era_dir).unwrap();
+    let paths = (1..=2)
+        .map(|era| store.export_era(era, &era_dir, true, true).unwrap())
+        .collect::<Vec<_>>();
+    for (era, path) in (1..=2).zip(&paths) {
+        assert_eq!(era::parse_era_filename(path), Some(era));
+    }
+    assert!(matches!(
+        store.export_era(1, &era_dir, true, true),
+        Err(StoreError::EraError(EraError::OutputExists(_)))
+    ));
+    assert!(matches!(
+        store.export_era(3, &era_dir, true, true),
+        Err(StoreError::EraError(EraError::EraUnavailable { era: 3 }))
+    ));
+
+    // Era files are verified against the historical roots of the split state.
+    let split = store.get_split_info();
+    let split_state = store
+        .get_state(&split.state_root, Some(split.slot))
+        .unwrap()
+        .unwrap();
+    let mut file = era::read_era_file::<E>(&paths[1], store.get_chain_spec()).unwrap();
+    assert_eq!(file.blocks.len() as u64, slots_per_historical_root);
+    assert!(file.state.is_some());
+    store.verify_era_file(&file, &split_state, &KZG).unwrap();
+
+    // Blobs with invalid KZG proofs or inclusion proofs are rejected.
+    if let Some(index) = file.blobs.iter().position(|blobs| !blobs.is_empty()) {
+        let mut tampered = file.blobs.clone();
+        Arc::make_mut(&mut tampered[index][0]).blob[0] ^= 1;
+        let original = std::mem::replace(&mut file.blobs, tampered);
+        assert!(matches!(
+            store.verify_era_file(&file, &split_state, &KZG),
+            Err(StoreError::EraError(EraError::InvalidBlobKzgProofs { .. }))
+        ));
+
+        let mut tampered = original.clone();
+        Arc::make_mut(&mut tampered[index][0]).kzg_commitment_inclusion_proof[0] =
+            Hash256::repeat_byte(1);
+        file.blobs = tampered;
+        assert!(matches!(
+            store.verify_era_file(&file, &split_state, &KZG),
+            Err(StoreError::EraError(EraError::InvalidBlobInclusionProof {
+                index: 0,
+                ..
+            }))
+        ));
+        file.blobs = original;
+    }
+
+    file.blocks.pop();
+    assert!(matches!(
+        store.verify_era_file(&file, &split_state, &KZG),
+        Err(StoreError::EraError(EraError::RootMismatch { era: 2, .. }))
+    ));
+
+    // Simulate a checkpoint synced node by deleting the blocks of both eras.
+    let block_roots = harness
+        .chain
+        .forwards_iter_block_roots_until(Slot::new(0), Slot::new(2 * slots_per_historical_root))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    let (oldest_block_parent, _) = block_roots[block_roots.len() - 2];
+    for (block_root, _) in &block_roots[..block_roots.len() - 1] {
+        store.delete_block(block_root).unwrap();
+    }
+    store
+        .compare_and_set_anchor_info_with_write(
+            None,
+            Some(AnchorInfo {
+                anchor_slot: split.slot,
+                oldest_block_slot: Slot::new(2 * slots_per_historical_root),
+                oldest_block_parent,
+                state_upper_limit: Slot::new(0),
+                state_lower_limit: Slot::new(0),
+            }),
+        )
+        .unwrap();
+
+    // Importing the older era alone fails, as it doesn't link to the oldest block.
+    assert!(matches!(
+        store.import_era_files(&paths[..1], &KZG),
+        Err(StoreError::EraError(EraError::MismatchedBlockRoot { .. }))
+    ));
+    assert_eq!(
+        store.import_era_files(&paths, &KZG).unwrap() as u64,
+        2 * slots_per_historical_root
+    );
+    let anchor = store.get_anchor_info().unwrap();
+    assert_eq!(anchor.oldest_block_slot, 0);
+    assert_eq!(anchor.oldest_block_parent, Hash256::zero());
+    for (block_root, slot) in &block_roots {
+        let block = store.get_blinded_block(block_root).unwrap().unwrap();
+        assert_eq!(block.slot(), *slot);
+    }
+
+    // Importing again is a no-op.
+    assert_eq!(store.import_era_files(&paths, &KZG).unwrap(), 0);
+}
+
 #[tokio::test]
 async fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -1357,6 +1663,123 @@ async fn prunes_abandoned_fork_between_two_finalized_checkpoints() {
     assert!(!rig.chain.knows_head(&stray_head));
 }
 
+#[tokio::test]
+async fn sweeps_states_missed_by_abandoned_fork_pruning() {
+    const HONEST_VALIDATOR_COUNT: usize = 32;
+    const ADVERSARIAL_VALIDATOR_COUNT: usize = 16;
+    const VALIDATOR_COUNT: usize = HONEST_VALIDATOR_COUNT + ADVERSARIAL_VALIDATOR_COUNT;
+    let honest_validators: Vec<usize> = (0..HONEST_VALIDATOR_COUNT).collect();
+    let adversarial_validators: Vec<usize> = (HONEST_VALIDATOR_COUNT..VALIDATOR_COUNT).collect();
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let rig = get_harness(store.clone(), VALIDATOR_COUNT);
+    let slots_per_epoch = rig.slots_per_epoch();
+    let (state, state_root) = rig.get_current_state_and_root();
+
+    let canonical_chain_slots: Vec<Slot> = (1..=rig.epoch_start_slot(1)).map(Slot::new).collect();
+    let (_, _, _, mut state) = rig
+        .add_attested_blocks_at_slots(
+            state,
+            state_root,
+            &canonical_chain_slots,
+            &honest_validators,
+        )
+        .await;
+    let canonical_chain_slot: u64 = rig.get_current_slot().into();
+
+    let stray_slots: Vec<Slot> = (canonical_chain_slot + 1..rig.epoch_start_slot(2))
+        .map(Slot::new)
+        .collect();
+    let (current_state, current_state_root) = rig.get_current_state_and_root();
+    let (stray_blocks, _, _, mut stray_state) = rig
+        .add_attested_blocks_at_slots(
+            current_state,
+            current_state_root,
+            &stray_slots,
+            &adversarial_validators,
+        )
+        .await;
+
+    // Store a state from the stray fork which isn't the post-state of any block, and so won't be
+    // found by pruning at finalization.
+    let stray_state_slot = Slot::new(rig.epoch_start_slot(7));
+    complete_state_advance(&mut stray_state, None, stray_state_slot, &rig.spec).unwrap();
+    let stray_state_root = stray_state.update_tree_hash_cache().unwrap();
+    store.put_state(&stray_state_root, &stray_state).unwrap();
+    assert!(rig.hot_state_exists(stray_state_root.into()));
+
+    // Nothing is swept before finalization, as the stray fork is still viable.
+    assert!(rig.chain.sweep_abandoned_forks().unwrap().is_empty());
+    assert!(store.load_gc_log(None).unwrap().is_empty());
+
+    // Trigger finalization.
+    let finalization_slots: Vec<Slot> = ((canonical_chain_slot + 1)
+        ..=(canonical_chain_slot + slots_per_epoch * 5))
+        .map(Slot::new)
+        .collect();
+    let state_root = state.update_tree_hash_cache().unwrap();
+    rig.add_attested_blocks_at_slots(state, state_root, &finalization_slots, &honest_validators)
+        .await;
+
+    // The stray blocks are pruned at finalization, but the stray state isn't.
+    check_no_blocks_exist(&rig, stray_blocks.values());
+    assert!(rig.hot_state_exists(stray_state_root.into()));
+
+    let run = rig.chain.sweep_abandoned_forks().unwrap();
+    assert!(run.deleted_blocks.is_empty());
+    assert_eq!(
+        run.deleted_states,
+        vec![GcDeletion {
+            root: stray_state_root,
+            slot: stray_state_slot,
+        }]
+    );
+    assert!(run.finalized_slot < stray_state_slot);
+    assert!(!rig.hot_state_exists(stray_state_root.into()));
+    assert_eq!(store.load_gc_log(None).unwrap(), vec![run.clone()]);
+
+    // A second sweep finds nothing, and isn't logged.
+    assert!(rig.chain.sweep_abandoned_forks().unwrap().is_empty());
+    assert_eq!(store.load_gc_log(Some(1)).unwrap(), vec![run.clone()]);
+    assert!(store.load_gc_log(Some(0)).unwrap().is_empty());
+
+    // Runs completing in the same millisecond don't overwrite each other.
+    store
+        .do_atomically_with_block_and_blobs_cache(vec![StoreOp::KeyValueOp(run.as_kv_store_op())])
+        .unwrap();
+    assert_eq!(store.load_gc_log(None).unwrap(), vec![run.clone(), run]);
+}
+
+#[test]
+fn gc_log_keeps_most_recent_runs() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let runs = (0..GC_LOG_MAX_RUNS as u64 + 2)
+        .map(|i| GcRun {
+            timestamp_millis: i,
+            split_slot: Slot::new(i),
+            finalized_slot: Slot::new(i),
+            deleted_blocks: vec![],
+            deleted_states: vec![GcDeletion {
+                root: Hash256::from_low_u64_be(i),
+                slot: Slot::new(i + 1),
+            }],
+        })
+        .collect::<Vec<_>>();
+    for run in &runs {
+        let ops = store.gc_log_append_ops(run).unwrap();
+        store
+            .do_atomically_with_block_and_blobs_cache(
+                ops.into_iter().map(StoreOp::KeyValueOp).collect(),
+            )
+            .unwrap();
+    }
+
+    // The two oldest runs have been deleted.
+    assert_eq!(store.load_gc_log(None).unwrap(), runs[2..].to_vec());
+}
+
 #[tokio::test]
 async fn pruning_does_not_touch_abandoned_block_shared_with_canonical_chain() {
     const HONEST_VALIDATOR_COUNT: usize = 32;
@@ -3555,7 +3978,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
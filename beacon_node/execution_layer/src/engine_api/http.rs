@@ -1,4 +1,4 @@
-//! Contains an implementation of `EngineAPI` using the JSON-RPC API via HTTP.
+//! Contains an implementation of `EngineAPI` using the JSON-RPC API via HTTP or IPC.
 
 use super::*;
 use crate::auth::Auth;
@@ -11,6 +11,7 @@ use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashSet;
 use tokio::sync::Mutex;
+use types::VersionedHash;
 
 use std::time::{Duration, Instant};
 
@@ -56,6 +57,9 @@ pub const ENGINE_EXCHANGE_CAPABILITIES_TIMEOUT: Duration = Duration::from_secs(1
 pub const ENGINE_GET_CLIENT_VERSION_V1: &str = "engine_getClientVersionV1";
 pub const ENGINE_GET_CLIENT_VERSION_TIMEOUT: Duration = Duration::from_secs(1);
 
+pub const ENGINE_GET_BLOBS_V1: &str = "engine_getBlobsV1";
+pub const ENGINE_GET_BLOBS_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// This error is returned during a `chainId` call by Geth.
 pub const EIP155_ERROR_STR: &str = "chain not synced beyond EIP-155 replay-protection fork block";
 /// This code is returned by all clients when a method is not supported
@@ -75,6 +79,7 @@ pub static LIGHTHOUSE_CAPABILITIES: &[&str] = &[
     ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1,
     ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1,
     ENGINE_GET_CLIENT_VERSION_V1,
+    ENGINE_GET_BLOBS_V1,
 ];
 
 lazy_static! {
@@ -641,20 +646,24 @@ impl HttpJsonRpc {
             id: json!(STATIC_ID),
         };
 
-        let mut request = self
-            .client
-            .post(self.url.full.clone())
-            .timeout(timeout)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&body);
+        let body: JsonResponseBody = if ipc::is_ipc_url(&self.url) {
+            ipc::send_request(&self.url, &body, timeout).await?
+        } else {
+            let mut request = self
+                .client
+                .post(self.url.full.clone())
+                .timeout(timeout)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&body);
+
+            // Generate and add a jwt token to the header if auth is defined.
+            if let Some(auth) = &self.auth {
+                request = request.bearer_auth(auth.generate_token()?);
+            };
 
-        // Generate and add a jwt token to the header if auth is defined.
-        if let Some(auth) = &self.auth {
-            request = request.bearer_auth(auth.generate_token()?);
+            request.send().await?.error_for_status()?.json().await?
         };
 
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
-
         match (body.result, body.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
             (_, Some(error)) => {
@@ -1049,6 +1058,20 @@ impl HttpJsonRpc {
             .collect())
     }
 
+    pub async fn get_blobs_v1<E: EthSpec>(
+        &self,
+        versioned_hashes: Vec<VersionedHash>,
+    ) -> Result<Vec<Option<BlobAndProofV1<E>>>, Error> {
+        let params = json!([versioned_hashes]);
+
+        self.rpc_request(
+            ENGINE_GET_BLOBS_V1,
+            params,
+            ENGINE_GET_BLOBS_TIMEOUT * self.execution_timeout_multiplier,
+        )
+        .await
+    }
+
     pub async fn exchange_capabilities(&self) -> Result<EngineCapabilities, Error> {
         let params = json!([LIGHTHOUSE_CAPABILITIES]);
 
@@ -1075,6 +1098,7 @@ impl HttpJsonRpc {
             get_payload_v2: capabilities.contains(ENGINE_GET_PAYLOAD_V2),
             get_payload_v3: capabilities.contains(ENGINE_GET_PAYLOAD_V3),
             get_client_version_v1: capabilities.contains(ENGINE_GET_CLIENT_VERSION_V1),
+            get_blobs_v1: capabilities.contains(ENGINE_GET_BLOBS_V1),
         })
     }
 
@@ -1289,7 +1313,7 @@ impl HttpJsonRpc {
 mod test {
     use super::auth::JwtKey;
     use super::*;
-    use crate::test_utils::{MockServer, DEFAULT_JWT_SECRET};
+    use crate::test_utils::{Config, MockExecutionConfig, MockServer, DEFAULT_JWT_SECRET};
     use std::future::Future;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -1650,6 +1674,54 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn get_blobs_v1_request() {
+        Tester::new(true)
+            .assert_request_equals(
+                |client| async move {
+                    let _ = client
+                        .get_blobs_v1::<MainnetEthSpec>(vec![
+                            Hash256::repeat_byte(0),
+                            Hash256::repeat_byte(1),
+                        ])
+                        .await;
+                },
+                json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "method": ENGINE_GET_BLOBS_V1,
+                    "params": [[HASH_00, HASH_01]]
+                }),
+            )
+            .await
+            .with_preloaded_responses(
+                vec![json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "result": [JSON_NULL, JSON_NULL]
+                })],
+                |client| async move {
+                    let response = client
+                        .get_blobs_v1::<MainnetEthSpec>(vec![
+                            Hash256::repeat_byte(0),
+                            Hash256::repeat_byte(1),
+                        ])
+                        .await
+                        .unwrap();
+                    assert_eq!(response, vec![None, None]);
+                },
+            )
+            .await;
+
+        Tester::new(false)
+            .assert_auth_failure(|client| async move {
+                client
+                    .get_blobs_v1::<MainnetEthSpec>(vec![Hash256::repeat_byte(0)])
+                    .await
+            })
+            .await;
+    }
+
     #[tokio::test]
     async fn new_payload_v1_request() {
         Tester::new(true)
@@ -2072,4 +2144,82 @@ mod test {
             )
             .await;
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn ipc_transport() {
+        let ipc_dir = tempfile::tempdir().unwrap();
+        let server = MockServer::<MainnetEthSpec>::new_with_config(
+            &tokio::runtime::Handle::current(),
+            MockExecutionConfig {
+                server_config: Config {
+                    ipc_path: Some(ipc_dir.path().join("engine.ipc")),
+                    ..Config::default()
+                },
+                jwt_key: JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(),
+                ..MockExecutionConfig::default()
+            },
+            None,
+        );
+
+        // No JWT is required over IPC.
+        let ipc_url = SensitiveUrl::parse(&server.ipc_url().unwrap()).unwrap();
+        let client = HttpJsonRpc::new(ipc_url, None).unwrap();
+
+        client.upcheck().await.unwrap();
+        let capabilities = client.exchange_capabilities().await.unwrap();
+        assert!(capabilities.new_payload_v3);
+        assert!(capabilities.forkchoice_updated_v3);
+        assert!(capabilities.get_payload_v3);
+
+        server.push_preloaded_response(json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "id": STATIC_ID,
+            "result": {
+                "payloadStatus": {
+                    "status": "VALID",
+                    "latestValidHash": HASH_00,
+                    "validationError": ""
+                },
+                "payloadId": "0xa247243752eb10b4",
+            }
+        }));
+        let response = client
+            .forkchoice_updated_v1(
+                ForkchoiceState {
+                    head_block_hash: ExecutionBlockHash::repeat_byte(0),
+                    safe_block_hash: ExecutionBlockHash::repeat_byte(0),
+                    finalized_block_hash: ExecutionBlockHash::repeat_byte(1),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.payload_status.status, PayloadStatusV1Status::Valid);
+        assert_eq!(
+            response.payload_id,
+            Some(str_to_payload_id("0xa247243752eb10b4"))
+        );
+
+        // Errors from the engine are returned just as they are over HTTP.
+        let result = client.get_payload_v1::<MainnetEthSpec>([42; 8]).await;
+        assert!(
+            matches!(result, Err(Error::ServerMessage { code, .. }) if code == -38001),
+            "{:?}",
+            result
+        );
+
+        server.set_syncing_response(Ok(true));
+        assert!(matches!(client.upcheck().await, Err(Error::IsSyncing)));
+
+        // Requests fail rather than hang once the socket has gone away.
+        drop(server);
+        let missing_url = SensitiveUrl::parse(&format!(
+            "ipc://{}",
+            ipc_dir.path().join("missing.ipc").display()
+        ))
+        .unwrap();
+        let client = HttpJsonRpc::new(missing_url, None).unwrap();
+        assert!(matches!(client.upcheck().await, Err(Error::Ipc(_))));
+    }
 }
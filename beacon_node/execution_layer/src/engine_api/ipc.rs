@@ -0,0 +1,99 @@
+//! Contains a transport for the JSON-RPC API via an execution engine's IPC socket.
+//!
+//! An IPC endpoint is selected by the `ipc` URL scheme, with the absolute path of the socket as
+//! the URL path (e.g. `ipc:///var/lib/geth/geth.ipc`). Access to the socket is controlled by file
+//! system permissions, so requests sent over IPC do not carry a JWT.
+
+use super::Error;
+use crate::json_structures::{JsonRequestBody, JsonResponseBody};
+use sensitive_url::SensitiveUrl;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const IPC_SCHEME: &str = "ipc";
+
+/// Returns `true` if `url` refers to an IPC socket rather than an HTTP server.
+pub fn is_ipc_url(url: &SensitiveUrl) -> bool {
+    url.full.scheme() == IPC_SCHEME
+}
+
+/// Sends `body` to the IPC socket at `url` and waits for the response, failing if no complete
+/// response has been received before `timeout` has elapsed.
+#[cfg(unix)]
+pub async fn send_request(
+    url: &SensitiveUrl,
+    body: &JsonRequestBody<'_>,
+    timeout: Duration,
+) -> Result<JsonResponseBody, Error> {
+    tokio::time::timeout(timeout, send_request_without_timeout(url, body))
+        .await
+        .map_err(|_| Error::Ipc(format!("request to {} timed out after {:?}", url, timeout)))?
+}
+
+#[cfg(unix)]
+async fn send_request_without_timeout(
+    url: &SensitiveUrl,
+    body: &JsonRequestBody<'_>,
+) -> Result<JsonResponseBody, Error> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    // A host would indicate a relative path such as `ipc://geth.ipc`, which is not supported.
+    if url.full.host_str().map_or(false, |host| !host.is_empty()) {
+        return Err(Error::Ipc(format!(
+            "{} is not an absolute IPC socket path",
+            url.full
+        )));
+    }
+    let path = PathBuf::from(url.full.path());
+
+    // A new connection is used for each request so that concurrent requests (which all share the
+    // same JSON-RPC id) can't receive each other's responses.
+    let mut stream = UnixStream::connect(&path)
+        .await
+        .map_err(|e| Error::Ipc(format!("unable to connect to {}: {}", path.display(), e)))?;
+
+    stream
+        .write_all(&serde_json::to_vec(body)?)
+        .await
+        .map_err(|e| Error::Ipc(format!("unable to send request to {}: {}", url, e)))?;
+
+    // Responses are not length-prefixed or reliably newline-delimited, so keep reading until the
+    // buffer holds a complete JSON object.
+    let mut buf = Vec::new();
+    loop {
+        let bytes_read = stream
+            .read_buf(&mut buf)
+            .await
+            .map_err(|e| Error::Ipc(format!("unable to read response from {}: {}", url, e)))?;
+
+        match serde_json::Deserializer::from_slice(&buf)
+            .into_iter::<JsonResponseBody>()
+            .next()
+        {
+            Some(Ok(response)) => return Ok(response),
+            Some(Err(e)) if !e.is_eof() => return Err(e.into()),
+            Some(Err(_)) | None => (),
+        }
+
+        if bytes_read == 0 {
+            return Err(Error::Ipc(format!(
+                "connection to {} closed before a response was received",
+                url
+            )));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn send_request(
+    url: &SensitiveUrl,
+    _body: &JsonRequestBody<'_>,
+    _timeout: Duration,
+) -> Result<JsonResponseBody, Error> {
+    Err(Error::Ipc(format!(
+        "unable to connect to {}: IPC endpoints are only supported on Unix platforms",
+        url
+    )))
+}
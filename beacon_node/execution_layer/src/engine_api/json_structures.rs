@@ -4,7 +4,7 @@ use strum::EnumString;
 use superstruct::superstruct;
 use types::beacon_block_body::KzgCommitments;
 use types::blob_sidecar::BlobsList;
-use types::{FixedVector, Unsigned};
+use types::{Blob, FixedVector, KzgProof, Unsigned};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -515,6 +515,15 @@ pub struct JsonBlobsBundleV1<E: EthSpec> {
     pub blobs: BlobsList<E>,
 }
 
+/// A blob and its KZG proof, as returned by `engine_getBlobsV1`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec", rename_all = "camelCase")]
+pub struct BlobAndProofV1<E: EthSpec> {
+    #[serde(with = "ssz_types::serde_utils::hex_fixed_vec")]
+    pub blob: Blob<E>,
+    pub proof: KzgProof,
+}
+
 impl<E: EthSpec> From<BlobsBundle<E>> for JsonBlobsBundleV1<E> {
     fn from(blobs_bundle: BlobsBundle<E>) -> Self {
         Self {
@@ -207,6 +207,66 @@ impl<'a, E: EthSpec> TryFrom<ExecutionPayloadRef<'a, E>> for NewPayloadRequest<'
     }
 }
 
+/// An owned copy of a `NewPayloadRequest`.
+///
+/// This allows a request to be sent to an execution engine from a spawned task, after the block
+/// it was created from has been dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedNewPayloadRequest<E: EthSpec> {
+    execution_payload: ExecutionPayload<E>,
+    versioned_hashes: Option<Vec<VersionedHash>>,
+    parent_beacon_block_root: Option<Hash256>,
+}
+
+impl<E: EthSpec> OwnedNewPayloadRequest<E> {
+    /// Returns a `NewPayloadRequest` which borrows from `self`.
+    pub fn as_new_payload_request(&self) -> Result<NewPayloadRequest<'_, E>, BeaconStateError> {
+        let request = match &self.execution_payload {
+            ExecutionPayload::Bellatrix(execution_payload) => {
+                NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix { execution_payload })
+            }
+            ExecutionPayload::Capella(execution_payload) => {
+                NewPayloadRequest::Capella(NewPayloadRequestCapella { execution_payload })
+            }
+            ExecutionPayload::Deneb(execution_payload) => {
+                NewPayloadRequest::Deneb(NewPayloadRequestDeneb {
+                    execution_payload,
+                    versioned_hashes: self
+                        .versioned_hashes
+                        .clone()
+                        .ok_or(BeaconStateError::IncorrectStateVariant)?,
+                    parent_beacon_block_root: self
+                        .parent_beacon_block_root
+                        .ok_or(BeaconStateError::IncorrectStateVariant)?,
+                })
+            }
+            ExecutionPayload::Electra(execution_payload) => {
+                NewPayloadRequest::Electra(NewPayloadRequestElectra {
+                    execution_payload,
+                    versioned_hashes: self
+                        .versioned_hashes
+                        .clone()
+                        .ok_or(BeaconStateError::IncorrectStateVariant)?,
+                    parent_beacon_block_root: self
+                        .parent_beacon_block_root
+                        .ok_or(BeaconStateError::IncorrectStateVariant)?,
+                })
+            }
+        };
+        Ok(request)
+    }
+}
+
+impl<'block, E: EthSpec> From<&NewPayloadRequest<'block, E>> for OwnedNewPayloadRequest<E> {
+    fn from(request: &NewPayloadRequest<'block, E>) -> Self {
+        Self {
+            execution_payload: request.clone().into_execution_payload(),
+            versioned_hashes: request.versioned_hashes().ok().cloned(),
+            parent_beacon_block_root: request.parent_beacon_block_root().ok().copied(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::versioned_hashes::Error as VersionedHashError;
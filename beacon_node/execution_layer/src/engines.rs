@@ -5,6 +5,7 @@ use crate::engine_api::{
     PayloadId,
 };
 use crate::{ClientVersionV1, HttpJsonRpc};
+use eth2::lighthouse::ExecutionEngineState;
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
@@ -57,6 +58,17 @@ impl From<EngineStateInternal> for EngineState {
     }
 }
 
+impl From<EngineStateInternal> for ExecutionEngineState {
+    fn from(state: EngineStateInternal) -> Self {
+        match state {
+            EngineStateInternal::Synced => ExecutionEngineState::Synced,
+            EngineStateInternal::Syncing => ExecutionEngineState::Syncing,
+            EngineStateInternal::Offline => ExecutionEngineState::Offline,
+            EngineStateInternal::AuthFailed => ExecutionEngineState::AuthFailed,
+        }
+    }
+}
+
 /// Wrapper structure that ensures changes to the engine state are correctly reported to watchers.
 struct State {
     /// The actual engine state.
@@ -199,7 +211,7 @@ impl Engine {
         *self.latest_forkchoice_state.write().await = Some(state);
     }
 
-    async fn send_latest_forkchoice_state(&self) {
+    pub async fn send_latest_forkchoice_state(&self) {
         let latest_forkchoice_state = self.get_latest_forkchoice_state().await;
 
         if let Some(forkchoice_state) = latest_forkchoice_state {
@@ -245,6 +257,11 @@ impl Engine {
         EngineState::from(**self.state.read().await) == EngineState::Offline
     }
 
+    /// Returns the last known state of the engine.
+    pub async fn execution_engine_state(&self) -> ExecutionEngineState {
+        (**self.state.read().await).into()
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -15,6 +15,7 @@ pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::ExecutionEngineStatusData;
 use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
 use ethers_core::types::Transaction as EthersTransaction;
@@ -32,6 +33,7 @@ use std::future::Future;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -40,7 +42,7 @@ use tokio::{
     sync::{Mutex, MutexGuard, RwLock},
     time::sleep,
 };
-use tokio_stream::wrappers::WatchStream;
+use tokio_stream::{Stream, StreamExt, StreamMap};
 use tree_hash::TreeHash;
 use types::beacon_block_body::KzgCommitments;
 use types::builder_bid::BuilderBid;
@@ -52,7 +54,7 @@ use types::{
 use types::{
     BeaconStateError, BlindedPayload, ChainSpec, Epoch, ExecPayload, ExecutionPayloadBellatrix,
     ExecutionPayloadCapella, ExecutionPayloadElectra, FullPayload, ProposerPreparationData,
-    PublicKeyBytes, Signature, Slot,
+    PublicKeyBytes, Signature, Slot, VersionedHash,
 };
 
 mod block_hash;
@@ -347,7 +349,11 @@ pub enum FailedCondition {
 type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
 
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
+    /// The configured execution engines, in order of preference.
+    engines: Vec<Arc<Engine>>,
+    /// The index in `engines` of the engine used for all calls other than the mirrored
+    /// `newPayload` calls.
+    primary_engine: AtomicUsize,
     builder: ArcSwapOption<BuilderHttpClient>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
@@ -368,6 +374,10 @@ struct Inner<E: EthSpec> {
 pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
     pub execution_endpoint: Option<SensitiveUrl>,
+    /// Additional engine api endpoints, in order of preference, which take over from the
+    /// `execution_endpoint` if it goes offline. They share its JWT secret.
+    #[serde(default)]
+    pub fallback_execution_endpoints: Vec<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
     pub builder_url: Option<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
@@ -389,8 +399,13 @@ pub struct Config {
     pub execution_timeout_multiplier: Option<u32>,
 }
 
-/// Provides access to one execution engine and provides a neat interface for consumption by the
-/// `BeaconChain`.
+/// Provides access to one or more execution engines and provides a neat interface for consumption
+/// by the `BeaconChain`.
+///
+/// All calls are made to a single *primary* engine, except for `newPayload` and `forkchoiceUpdated`
+/// without payload attributes, which are mirrored to every other online engine so that they are
+/// ready to take over if the primary goes offline. `getPayload` is sent to the engine which issued
+/// the payload ID, even if the primary has changed since.
 #[derive(Clone)]
 pub struct ExecutionLayer<E: EthSpec> {
     inner: Arc<Inner<E>>,
@@ -401,6 +416,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
+            fallback_execution_endpoints,
             builder_url,
             builder_user_agent,
             builder_header_timeout,
@@ -446,16 +462,21 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .map_err(Error::InvalidJWTSecret)
         }?;
 
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
-            debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
-        };
+        let engines = std::iter::once(execution_url)
+            .chain(fallback_execution_endpoints)
+            .map(|execution_url| {
+                let auth = Auth::new(jwt_key.clone(), jwt_id.clone(), jwt_version.clone());
+                debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
+                let api =
+                    HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
+                        .map_err(Error::ApiError)?;
+                Ok(Arc::new(Engine::new(api, executor.clone(), &log)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
 
         let inner = Inner {
-            engine: Arc::new(engine),
+            engines,
+            primary_engine: AtomicUsize::new(0),
             builder: ArcSwapOption::empty(),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
@@ -479,8 +500,64 @@ impl<E: EthSpec> ExecutionLayer<E> {
         Ok(el)
     }
 
+    /// Returns the index of the primary engine, which is used for all calls other than mirrored
+    /// ones.
+    ///
+    /// The primary may change at any time, so calls which must reach the same engine should read
+    /// the index once.
+    fn primary_engine_index(&self) -> usize {
+        self.inner
+            .primary_engine
+            .load(Ordering::Relaxed)
+            .min(self.inner.engines.len() - 1)
+    }
+
+    /// Returns the primary engine.
     fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+        &self.inner.engines[self.primary_engine_index()]
+    }
+
+    /// Returns the online engines other than the engine at `primary_index`.
+    async fn online_secondary_engines(&self, primary_index: usize) -> Vec<Arc<Engine>> {
+        let mut secondary_engines = vec![];
+        for (i, engine) in self.inner.engines.iter().enumerate() {
+            if i != primary_index && !engine.is_offline().await {
+                secondary_engines.push(engine.clone());
+            }
+        }
+        secondary_engines
+    }
+
+    /// Returns the engine to request a payload built on `parent_hash` with `payload_attributes`
+    /// from.
+    ///
+    /// This is the online engine which already issued a payload ID for them, preferring the
+    /// primary, so that `getPayload` reaches the engine which is building the payload even if the
+    /// primary has changed since `forkchoiceUpdated`. Otherwise, it is the primary engine.
+    async fn payload_building_engine(
+        &self,
+        parent_hash: &ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+    ) -> &Arc<Engine> {
+        let primary = self.engine();
+        if primary
+            .get_payload_id(parent_hash, payload_attributes)
+            .await
+            .is_some()
+        {
+            return primary;
+        }
+        for engine in &self.inner.engines {
+            if !engine.is_offline().await
+                && engine
+                    .get_payload_id(parent_hash, payload_attributes)
+                    .await
+                    .is_some()
+            {
+                return engine;
+            }
+        }
+        primary
     }
 
     pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
@@ -560,11 +637,27 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.inner.execution_blocks.lock().await
     }
 
-    /// Gives access to a channel containing if the last engine state is online or not.
+    /// Gives access to a stream indicating whether any engine is online or not, since an online
+    /// engine will be promoted to primary if the current primary goes offline.
     ///
     /// This can be called several times.
-    pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+    pub async fn get_responsiveness_watch(&self) -> impl Stream<Item = EngineState> + Unpin {
+        let mut watches = StreamMap::new();
+        for (i, engine) in self.inner.engines.iter().enumerate() {
+            watches.insert(i, engine.watch_state().await);
+        }
+
+        let mut states = vec![EngineState::Offline; self.inner.engines.len()];
+        watches.map(move |(i, state)| {
+            if let Some(engine_state) = states.get_mut(i) {
+                *engine_state = state;
+            }
+            if states.contains(&EngineState::Online) {
+                EngineState::Online
+            } else {
+                EngineState::Offline
+            }
+        })
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -595,8 +688,23 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.executor().spawn(generate_future(self.clone()), name);
     }
 
-    /// Spawns a routine which attempts to keep the execution engine online.
+    /// Spawns a routine which attempts to keep the execution engines online.
+    ///
+    /// Additionally, a primary engine is re-selected whenever the state of any engine changes.
     pub fn spawn_watchdog_routine<S: SlotClock + 'static>(&self, slot_clock: S) {
+        if self.inner.engines.len() > 1 {
+            for engine in &self.inner.engines {
+                let engine = engine.clone();
+                let state_watcher = |el: ExecutionLayer<E>| async move {
+                    let mut state_watch = engine.watch_state().await;
+                    while state_watch.next().await.is_some() {
+                        el.update_primary_engine().await;
+                    }
+                };
+                self.spawn(state_watcher, "exec_engine_state_watcher");
+            }
+        }
+
         let watchdog = |el: ExecutionLayer<E>| async move {
             // Run one task immediately.
             el.watchdog_task().await;
@@ -616,7 +724,94 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.upcheck().await;
+    }
+
+    /// Selects the primary engine.
+    ///
+    /// The first synced engine in order of preference is selected. If no engine is synced the
+    /// current primary is kept, unless it is offline and another engine is online.
+    async fn update_primary_engine(&self) {
+        let current_index = self.inner.primary_engine.load(Ordering::Relaxed);
+        let mut first_synced = None;
+        let mut first_online = None;
+        let mut current_is_online = false;
+
+        for (i, engine) in self.inner.engines.iter().enumerate() {
+            let is_online = !engine.is_offline().await;
+            metrics::set_gauge_vec(
+                &metrics::EXECUTION_LAYER_ENGINE_ONLINE,
+                &[&engine.api.url.to_string()],
+                is_online as i64,
+            );
+
+            if first_online.is_none() && is_online {
+                first_online = Some(i);
+            }
+            if first_synced.is_none() && engine.is_synced().await {
+                first_synced = Some(i);
+            }
+            if i == current_index {
+                current_is_online = is_online;
+            }
+        }
+
+        let new_index = first_synced
+            .or_else(|| {
+                if current_is_online {
+                    Some(current_index)
+                } else {
+                    first_online
+                }
+            })
+            .unwrap_or(current_index);
+        metrics::set_gauge(&metrics::EXECUTION_LAYER_PRIMARY_ENGINE, new_index as i64);
+
+        if new_index == current_index
+            || self
+                .inner
+                .primary_engine
+                .compare_exchange(
+                    current_index,
+                    new_index,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+        {
+            return;
+        }
+
+        let (Some(previous), Some(primary)) = (
+            self.inner.engines.get(current_index),
+            self.inner.engines.get(new_index),
+        ) else {
+            return;
+        };
+        warn!(
+            self.log(),
+            "Switched primary execution engine";
+            "previous" => %previous.api.url,
+            "primary" => %primary.api.url,
+        );
+        metrics::inc_counter(&metrics::EXECUTION_LAYER_PRIMARY_ENGINE_SWITCHES);
+
+        // Bring the new primary up to date with our head before it is used for block production.
+        primary.send_latest_forkchoice_state().await;
+    }
+
+    /// Returns the last known state of each engine, in order of preference.
+    pub async fn execution_engine_statuses(&self) -> Vec<ExecutionEngineStatusData> {
+        let primary_index = self.inner.primary_engine.load(Ordering::Relaxed);
+        let mut statuses = Vec::with_capacity(self.inner.engines.len());
+        for (i, engine) in self.inner.engines.iter().enumerate() {
+            statuses.push(ExecutionEngineStatusData {
+                endpoint: engine.api.url.to_string(),
+                state: engine.execution_engine_state().await,
+                is_primary: i == primary_index,
+            });
+        }
+        statuses
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -1246,7 +1441,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
             PayloadContentsRefTuple<E>,
         ) -> Option<FullPayloadContents<E>>,
     ) -> Result<GetPayloadResponseType<E>, Error> {
-        self.engine()
+        self.payload_building_engine(&parent_hash, payload_attributes)
+            .await
             .request(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
@@ -1357,8 +1553,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let block_hash = new_payload_request.block_hash();
         let parent_hash = new_payload_request.parent_hash();
 
-        let result = self
-            .engine()
+        let primary_index = self.primary_engine_index();
+        self.mirror_new_payload(primary_index, &new_payload_request)
+            .await;
+
+        let result = self.inner.engines[primary_index]
             .request(|engine| engine.api.new_payload(new_payload_request))
             .await;
 
@@ -1385,9 +1584,91 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
-    /// Update engine sync status.
+    /// Sends `new_payload_request` to every online secondary engine, without waiting for the
+    /// responses.
+    ///
+    /// The responses are only logged, the payload status is always determined by the primary.
+    async fn mirror_new_payload(
+        &self,
+        primary_index: usize,
+        new_payload_request: &NewPayloadRequest<'_, E>,
+    ) {
+        let secondary_engines = self.online_secondary_engines(primary_index).await;
+        if secondary_engines.is_empty() {
+            return;
+        }
+
+        let request = Arc::new(OwnedNewPayloadRequest::from(new_payload_request));
+        let block_hash = new_payload_request.block_hash();
+        for engine in secondary_engines {
+            let request = request.clone();
+            let log = self.log().clone();
+            self.executor().spawn(
+                async move {
+                    let new_payload_request = match request.as_new_payload_request() {
+                        Ok(new_payload_request) => new_payload_request,
+                        Err(e) => {
+                            error!(
+                                log,
+                                "Unable to mirror engine_newPayload";
+                                "error" => ?e,
+                                "block_hash" => ?block_hash,
+                            );
+                            return;
+                        }
+                    };
+                    let result = engine
+                        .request(|engine| engine.api.new_payload(new_payload_request))
+                        .await;
+                    debug!(
+                        log,
+                        "Mirrored engine_newPayload";
+                        "endpoint" => %engine.api.url,
+                        "block_hash" => ?block_hash,
+                        "status" => ?result.as_ref().map(|status| status.status),
+                    );
+                },
+                "mirror_new_payload",
+            );
+        }
+    }
+
+    /// Sends `forkchoice_state` to every online secondary engine without payload attributes,
+    /// without waiting for the responses.
+    ///
+    /// This keeps the secondary engines following the head, so that they are ready to build
+    /// payloads if promoted to primary.
+    async fn mirror_forkchoice_updated(
+        &self,
+        primary_index: usize,
+        forkchoice_state: ForkchoiceState,
+    ) {
+        for engine in self.online_secondary_engines(primary_index).await {
+            let log = self.log().clone();
+            self.executor().spawn(
+                async move {
+                    let result = engine
+                        .request(|engine| engine.api.forkchoice_updated(forkchoice_state, None))
+                        .await;
+                    debug!(
+                        log,
+                        "Mirrored engine_forkchoiceUpdated";
+                        "endpoint" => %engine.api.url,
+                        "head_block_hash" => ?forkchoice_state.head_block_hash,
+                        "status" => ?result.as_ref().map(|response| response.payload_status.status),
+                    );
+                },
+                "mirror_forkchoice_updated",
+            );
+        }
+    }
+
+    /// Update the sync status of all engines and select the primary engine.
     pub async fn upcheck(&self) {
-        self.engine().upcheck().await;
+        for engine in &self.inner.engines {
+            engine.upcheck().await;
+        }
+        self.update_primary_engine().await;
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1501,12 +1782,17 @@ impl<E: EthSpec> ExecutionLayer<E> {
             finalized_block_hash,
         };
 
-        self.engine()
-            .set_latest_forkchoice_state(forkchoice_state)
+        // Every engine keeps track of the latest state, so that it is sent to any engine which
+        // comes back online or is promoted to primary.
+        for engine in &self.inner.engines {
+            engine.set_latest_forkchoice_state(forkchoice_state).await;
+        }
+
+        let primary_index = self.primary_engine_index();
+        self.mirror_forkchoice_updated(primary_index, forkchoice_state)
             .await;
 
-        let result = self
-            .engine()
+        let result = self.inner.engines[primary_index]
             .request(|engine| async move {
                 engine
                     .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
@@ -1799,6 +2085,32 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
+    /// Fetch the blobs with the given `versioned_hashes` from the execution node's mempool.
+    ///
+    /// The response contains an entry for each of the `versioned_hashes`, which is `None` if the
+    /// execution node does not have that blob. Returns an error if the execution node does not
+    /// support `engine_getBlobsV1`.
+    pub async fn get_blobs(
+        &self,
+        versioned_hashes: Vec<VersionedHash>,
+    ) -> Result<Vec<Option<BlobAndProofV1<E>>>, Error> {
+        let capabilities = self.get_engine_capabilities(None).await?;
+        if !capabilities.get_blobs_v1 {
+            return Err(Error::ApiError(ApiError::RequiredMethodUnsupported(
+                http::ENGINE_GET_BLOBS_V1,
+            )));
+        }
+
+        let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_BLOBS);
+        self.engine()
+            .request(
+                |engine: &Engine| async move { engine.api.get_blobs_v1(versioned_hashes).await },
+            )
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError)
+    }
+
     /// Fetch a full payload from the execution node.
     ///
     /// This will fail if the payload is not from the finalized portion of the chain.
@@ -2232,8 +2544,12 @@ fn noop<E: EthSpec>(
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::MockExecutionLayer as GenericMockExecutionLayer;
+    use crate::test_utils::{
+        MockExecutionLayer as GenericMockExecutionLayer, MockServer, DEFAULT_JWT_SECRET,
+    };
+    use eth2::lighthouse::ExecutionEngineState;
     use task_executor::test_utils::TestRuntime;
+    use tempfile::NamedTempFile;
     use types::MainnetEthSpec;
 
     type MockExecutionLayer = GenericMockExecutionLayer<MainnetEthSpec>;
@@ -2356,6 +2672,138 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn fails_over_to_fallback_engine() {
+        let runtime = TestRuntime::default();
+        let executor = runtime.task_executor.clone();
+        let primary = MockServer::<MainnetEthSpec>::unit_testing();
+        let fallback = MockServer::<MainnetEthSpec>::unit_testing();
+
+        let jwt_file = NamedTempFile::new().unwrap();
+        std::fs::write(jwt_file.path(), hex::encode(DEFAULT_JWT_SECRET)).unwrap();
+        let config = Config {
+            execution_endpoint: Some(SensitiveUrl::parse(&primary.url()).unwrap()),
+            fallback_execution_endpoints: vec![SensitiveUrl::parse(&fallback.url()).unwrap()],
+            secret_file: Some(jwt_file.path().into()),
+            ..Default::default()
+        };
+        let el = ExecutionLayer::<MainnetEthSpec>::from_config(
+            config,
+            executor.clone(),
+            executor.log().clone(),
+        )
+        .unwrap();
+
+        let engine_states = |statuses: Vec<ExecutionEngineStatusData>| {
+            statuses
+                .into_iter()
+                .map(|status| (status.state, status.is_primary))
+                .collect::<Vec<_>>()
+        };
+
+        el.upcheck().await;
+        assert_eq!(
+            engine_states(el.execution_engine_statuses().await),
+            vec![
+                (ExecutionEngineState::Synced, true),
+                (ExecutionEngineState::Synced, false)
+            ]
+        );
+
+        // The fallback is promoted when the primary goes offline.
+        primary.set_syncing_response(Err("offline".to_string()));
+        el.upcheck().await;
+        assert_eq!(
+            engine_states(el.execution_engine_statuses().await),
+            vec![
+                (ExecutionEngineState::Offline, false),
+                (ExecutionEngineState::Synced, true)
+            ]
+        );
+
+        // The primary takes over again once it is back online.
+        primary.set_syncing_response(Ok(false));
+        el.upcheck().await;
+        assert_eq!(
+            engine_states(el.execution_engine_statuses().await),
+            vec![
+                (ExecutionEngineState::Synced, true),
+                (ExecutionEngineState::Synced, false)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pins_payload_building_engine() {
+        let runtime = TestRuntime::default();
+        let executor = runtime.task_executor.clone();
+        let primary = MockServer::<MainnetEthSpec>::unit_testing();
+        let fallback = MockServer::<MainnetEthSpec>::unit_testing();
+        for server in [&primary, &fallback] {
+            server
+                .execution_block_generator()
+                .move_to_terminal_block()
+                .unwrap();
+        }
+
+        let jwt_file = NamedTempFile::new().unwrap();
+        std::fs::write(jwt_file.path(), hex::encode(DEFAULT_JWT_SECRET)).unwrap();
+        let config = Config {
+            execution_endpoint: Some(SensitiveUrl::parse(&primary.url()).unwrap()),
+            fallback_execution_endpoints: vec![SensitiveUrl::parse(&fallback.url()).unwrap()],
+            secret_file: Some(jwt_file.path().into()),
+            ..Default::default()
+        };
+        let el = ExecutionLayer::<MainnetEthSpec>::from_config(
+            config,
+            executor.clone(),
+            executor.log().clone(),
+        )
+        .unwrap();
+        el.upcheck().await;
+
+        let head_block = primary
+            .execution_block_generator()
+            .latest_execution_block()
+            .unwrap();
+        let forkchoice_state = ForkchoiceState {
+            head_block_hash: head_block.block_hash,
+            safe_block_hash: ExecutionBlockHash::zero(),
+            finalized_block_hash: ExecutionBlockHash::zero(),
+        };
+        let payload_attributes = PayloadAttributes::new(
+            head_block.timestamp + 1,
+            Hash256::repeat_byte(1),
+            Address::repeat_byte(42),
+            None,
+            None,
+        );
+        el.inner.engines[0]
+            .notify_forkchoice_updated(forkchoice_state, Some(payload_attributes.clone()), el.log())
+            .await
+            .unwrap();
+
+        // The primary which issued the payload ID keeps building the payload after the fallback
+        // is promoted.
+        primary.set_syncing_response(Ok(true));
+        el.upcheck().await;
+        assert_eq!(el.primary_engine_index(), 1);
+        assert!(Arc::ptr_eq(
+            el.payload_building_engine(&head_block.block_hash, &payload_attributes)
+                .await,
+            &el.inner.engines[0]
+        ));
+
+        // Once the engine building the payload goes offline, the new primary is used instead.
+        primary.set_syncing_response(Err("offline".to_string()));
+        el.upcheck().await;
+        assert!(Arc::ptr_eq(
+            el.payload_building_engine(&head_block.block_hash, &payload_attributes)
+                .await,
+            &el.inner.engines[1]
+        ));
+    }
+
     #[tokio::test]
     async fn rejects_unknown_terminal_block_hash() {
         let runtime = TestRuntime::default();
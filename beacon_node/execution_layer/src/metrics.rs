@@ -49,6 +49,10 @@ lazy_static::lazy_static! {
         "execution_layer_get_payload_bodies_by_range_time",
         "Time to fetch a range of payload bodies from the EE"
     );
+    pub static ref EXECUTION_LAYER_GET_BLOBS: Result<Histogram> = try_create_histogram(
+        "execution_layer_get_blobs_time",
+        "Time to fetch blobs from the EE's mempool"
+    );
     pub static ref EXECUTION_LAYER_VERIFY_BLOCK_HASH: Result<Histogram> = try_create_histogram_with_buckets(
         "execution_layer_verify_block_hash_time",
         "Time to verify the execution block hash in Lighthouse, without the EL",
@@ -84,4 +88,17 @@ lazy_static::lazy_static! {
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_ENGINE_ONLINE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "execution_layer_engine_online",
+        "Indicates whether each execution engine is online (1) or offline (0)",
+        &["endpoint"]
+    );
+    pub static ref EXECUTION_LAYER_PRIMARY_ENGINE: Result<IntGauge> = try_create_int_gauge(
+        "execution_layer_primary_engine",
+        "The index of the primary execution engine in the configured list of engines",
+    );
+    pub static ref EXECUTION_LAYER_PRIMARY_ENGINE_SWITCHES: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_primary_engine_switches_total",
+        "Count of times a different execution engine was selected as the primary",
+    );
 }
@@ -3,7 +3,8 @@ use crate::EthersTransaction;
 use crate::{
     engine_api::{
         json_structures::{
-            JsonForkchoiceUpdatedV1Response, JsonPayloadStatusV1, JsonPayloadStatusV1Status,
+            BlobAndProofV1, JsonForkchoiceUpdatedV1Response, JsonPayloadStatusV1,
+            JsonPayloadStatusV1Status,
         },
         ExecutionBlock, PayloadAttributes, PayloadId, PayloadStatusV1, PayloadStatusV1Status,
     },
@@ -451,6 +452,21 @@ impl<E: EthSpec> ExecutionBlockGenerator<E> {
         self.blobs_bundles.get(id).cloned()
     }
 
+    /// Returns the blob with the given `versioned_hash` from any of the payloads built so far,
+    /// simulating a lookup in the execution node's blob mempool.
+    pub fn get_blob_and_proof(&self, versioned_hash: &Hash256) -> Option<BlobAndProofV1<E>> {
+        self.blobs_bundles.values().find_map(|bundle| {
+            let index = bundle
+                .commitments
+                .iter()
+                .position(|commitment| commitment.calculate_versioned_hash() == *versioned_hash)?;
+            Some(BlobAndProofV1 {
+                blob: bundle.blobs.get(index)?.clone(),
+                proof: *bundle.proofs.get(index)?,
+            })
+        })
+    }
+
     pub fn new_payload(&mut self, payload: ExecutionPayload<E>) -> PayloadStatusV1 {
         let Some(parent) = self.blocks.get(&payload.parent_hash()) else {
             return PayloadStatusV1 {
@@ -531,6 +531,18 @@ pub async fn handle_rpc<E: EthSpec>(
         ENGINE_GET_CLIENT_VERSION_V1 => {
             Ok(serde_json::to_value([DEFAULT_CLIENT_VERSION.clone()]).unwrap())
         }
+        ENGINE_GET_BLOBS_V1 => {
+            let versioned_hashes =
+                get_param::<Vec<Hash256>>(params, 0).map_err(|s| (s, BAD_PARAMS_ERROR_CODE))?;
+
+            let execution_block_generator = ctx.execution_block_generator.read();
+            let response = versioned_hashes
+                .iter()
+                .map(|versioned_hash| execution_block_generator.get_blob_and_proof(versioned_hash))
+                .collect::<Vec<_>>();
+
+            Ok(serde_json::to_value(response).unwrap())
+        }
         ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1 => {
             #[derive(Deserialize)]
             #[serde(transparent)]
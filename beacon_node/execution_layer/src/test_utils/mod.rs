@@ -20,6 +20,7 @@ use std::convert::Infallible;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::{runtime, sync::oneshot};
 use types::{EthSpec, ExecutionBlockHash, Uint256};
@@ -52,6 +53,7 @@ pub const DEFAULT_ENGINE_CAPABILITIES: EngineCapabilities = EngineCapabilities {
     get_payload_v2: true,
     get_payload_v3: true,
     get_client_version_v1: true,
+    get_blobs_v1: true,
 };
 
 lazy_static! {
@@ -99,6 +101,7 @@ impl Default for MockExecutionConfig {
 
 pub struct MockServer<E: EthSpec> {
     _shutdown_tx: oneshot::Sender<()>,
+    _ipc_shutdown_tx: Option<oneshot::Sender<()>>,
     listen_socket_addr: SocketAddr,
     last_echo_request: Arc<RwLock<Option<Bytes>>>,
     pub ctx: Arc<Context<E>>,
@@ -184,8 +187,27 @@ impl<E: EthSpec> MockServer<E> {
 
         handle.spawn(server_future);
 
+        // Also serve requests over IPC if a socket path has been configured.
+        let ipc_shutdown_tx = ctx.config.ipc_path.clone().map(|ipc_path| {
+            let (ipc_shutdown_tx, ipc_shutdown_rx) = oneshot::channel();
+            let ipc_shutdown_future = async {
+                let _ = ipc_shutdown_rx.await;
+            };
+
+            let serve_ipc = || serve_ipc(ctx.clone(), ipc_path, ipc_shutdown_future).unwrap();
+            let ipc_server_future = if runtime::Handle::try_current().is_err() {
+                handle.block_on(async { serve_ipc() })
+            } else {
+                serve_ipc()
+            };
+
+            handle.spawn(ipc_server_future);
+            ipc_shutdown_tx
+        });
+
         Self {
             _shutdown_tx: shutdown_tx,
+            _ipc_shutdown_tx: ipc_shutdown_tx,
             listen_socket_addr,
             last_echo_request,
             ctx,
@@ -236,6 +258,15 @@ impl<E: EthSpec> MockServer<E> {
         )
     }
 
+    /// Returns the `ipc://` URL of the IPC socket, if the server was configured with one.
+    pub fn ipc_url(&self) -> Option<String> {
+        self.ctx
+            .config
+            .ipc_path
+            .as_ref()
+            .map(|path| format!("ipc://{}", path.display()))
+    }
+
     pub fn last_echo_request(&self) -> Bytes {
         self.last_echo_request
             .write()
@@ -562,6 +593,8 @@ impl<E: EthSpec> Context<E> {
 pub struct Config {
     pub listen_addr: Ipv4Addr,
     pub listen_port: u16,
+    /// If set, requests are also served over a Unix domain socket at this path.
+    pub ipc_path: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -569,6 +602,7 @@ impl Default for Config {
         Self {
             listen_addr: Ipv4Addr::new(127, 0, 0, 1),
             listen_port: 0,
+            ipc_path: None,
         }
     }
 }
@@ -635,6 +669,46 @@ async fn handle_rejection(err: Rejection) -> Result<impl warp::Reply, Infallible
     Ok(warp::reply::with_status(json, code))
 }
 
+/// Returns the JSON-RPC response to `body`, or `None` if the request has no `id` field.
+///
+/// Responses are taken from `ctx.preloaded_responses` before falling back to `handle_rpc`.
+async fn rpc_response<E: EthSpec>(
+    body: serde_json::Value,
+    ctx: Arc<Context<E>>,
+) -> Option<serde_json::Value> {
+    let id = body.get("id").and_then(serde_json::Value::as_u64)?;
+    let preloaded_response = {
+        let mut preloaded_responses = ctx.preloaded_responses.lock();
+        if !preloaded_responses.is_empty() {
+            Some(preloaded_responses.remove(0))
+        } else {
+            None
+        }
+    };
+
+    let response = if let Some(preloaded_response) = preloaded_response {
+        preloaded_response
+    } else {
+        match handle_rpc(body, ctx).await {
+            Ok(result) => json!({
+                "id": id,
+                "jsonrpc": JSONRPC_VERSION,
+                "result": result
+            }),
+            Err((message, code)) => json!({
+                "id": id,
+                "jsonrpc": JSONRPC_VERSION,
+                "error": {
+                    "code": code,
+                    "message": message
+                }
+            }),
+        }
+    };
+
+    Some(response)
+}
+
 /// Creates a server that will serve requests using information from `ctx`.
 ///
 /// The server will shut down gracefully when the `shutdown` future resolves.
@@ -667,38 +741,9 @@ pub fn serve<E: EthSpec>(
         .and(warp::body::json())
         .and(ctx_filter.clone())
         .and_then(|body: serde_json::Value, ctx: Arc<Context<E>>| async move {
-            let id = body
-                .get("id")
-                .and_then(serde_json::Value::as_u64)
+            let response = rpc_response(body, ctx)
+                .await
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
-            let preloaded_response = {
-                let mut preloaded_responses = ctx.preloaded_responses.lock();
-                if !preloaded_responses.is_empty() {
-                    Some(preloaded_responses.remove(0))
-                } else {
-                    None
-                }
-            };
-
-            let response = if let Some(preloaded_response) = preloaded_response {
-                preloaded_response
-            } else {
-                match handle_rpc(body, ctx).await {
-                    Ok(result) => json!({
-                        "id": id,
-                        "jsonrpc": JSONRPC_VERSION,
-                        "result": result
-                    }),
-                    Err((message, code)) => json!({
-                        "id": id,
-                        "jsonrpc": JSONRPC_VERSION,
-                        "error": {
-                            "code": code,
-                            "message": message
-                        }
-                    }),
-                }
-            };
 
             Ok::<_, warp::reject::Rejection>(
                 warp::http::Response::builder()
@@ -742,3 +787,102 @@ pub fn serve<E: EthSpec>(
 
     Ok((listening_socket, server))
 }
+
+/// Creates a server that will serve requests using information from `ctx` over a Unix domain
+/// socket at `path`. Unlike the HTTP server, no JWT is required.
+///
+/// Each connection may send any number of requests, which are answered in order with one JSON
+/// response per line.
+///
+/// The server will shut down gracefully when the `shutdown` future resolves, removing the socket
+/// file.
+///
+/// ## Errors
+///
+/// Returns an error if the server is unable to bind to `path`.
+#[cfg(unix)]
+pub fn serve_ipc<E: EthSpec>(
+    ctx: Arc<Context<E>>,
+    path: PathBuf,
+    shutdown: impl Future<Output = ()> + Send + Sync + 'static,
+) -> Result<impl Future<Output = ()>, Error> {
+    let listener = tokio::net::UnixListener::bind(&path)
+        .map_err(|e| format!("Unable to bind IPC socket {}: {:?}", path.display(), e))?;
+
+    info!(
+        ctx.log,
+        "Mock IPC server started";
+        "path" => path.display().to_string(),
+    );
+
+    Ok(async move {
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                accepted = listener.accept() => {
+                    if let Ok((stream, _)) = accepted {
+                        tokio::spawn(handle_ipc_connection(stream, ctx.clone()));
+                    }
+                }
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    })
+}
+
+#[cfg(not(unix))]
+pub fn serve_ipc<E: EthSpec>(
+    _ctx: Arc<Context<E>>,
+    path: PathBuf,
+    _shutdown: impl Future<Output = ()> + Send + Sync + 'static,
+) -> Result<std::future::Ready<()>, Error> {
+    Err(Error::Other(format!(
+        "Unable to bind IPC socket {}: IPC is only supported on Unix platforms",
+        path.display()
+    )))
+}
+
+/// Reads JSON-RPC requests from `stream` until it is closed, writing a response for each.
+#[cfg(unix)]
+async fn handle_ipc_connection<E: EthSpec>(
+    mut stream: tokio::net::UnixStream,
+    ctx: Arc<Context<E>>,
+) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut buf = Vec::new();
+    loop {
+        match stream.read_buf(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => (),
+        }
+
+        let mut bodies = vec![];
+        let consumed = {
+            let mut requests =
+                serde_json::Deserializer::from_slice(&buf).into_iter::<serde_json::Value>();
+            loop {
+                match requests.next() {
+                    Some(Ok(body)) => bodies.push(body),
+                    Some(Err(e)) if e.is_eof() => break requests.byte_offset(),
+                    // Malformed requests can't be answered, so drop the connection.
+                    Some(Err(_)) => return,
+                    None => break buf.len(),
+                }
+            }
+        };
+        buf.drain(..consumed);
+
+        for body in bodies {
+            let Some(response) = rpc_response::<E>(body, ctx.clone()).await else {
+                return;
+            };
+            let mut bytes = serde_json::to_vec(&response).expect("response must be valid JSON");
+            bytes.push(b'\n');
+            if stream.write_all(&bytes).await.is_err() {
+                return;
+            }
+        }
+    }
+}
@@ -1,6 +1,13 @@
-use beacon_chain::store::metadata::CURRENT_SCHEMA_VERSION;
+use beacon_chain::store::{
+    backup::{BackupError, BackupManifest},
+    garbage_collection::GcRun,
+    metadata::CURRENT_SCHEMA_VERSION,
+    Error as StoreError,
+};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use eth2::lighthouse::{DatabaseBackupRequest, DatabaseGcQuery, DatabaseInfo};
+use eth2::types::GenericResponse;
+use std::path::{Component, Path};
 use std::sync::Arc;
 
 pub fn info<T: BeaconChainTypes>(
@@ -20,3 +27,53 @@ pub fn info<T: BeaconChainTypes>(
         blob_info,
     })
 }
+
+/// Write a backup of the running node's databases to `request.output_dir` within `backup_dir`,
+/// returning once it is complete.
+///
+/// The output directory must be a relative path which stays within `backup_dir`, so that API
+/// clients can't write to arbitrary locations on the host.
+pub fn backup<T: BeaconChainTypes>(
+    request: DatabaseBackupRequest,
+    backup_dir: &Path,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<GenericResponse<BackupManifest>, warp::Rejection> {
+    let output_dir = &request.output_dir;
+    let is_contained = output_dir.components().next().is_some()
+        && output_dir
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if !is_contained {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "{output_dir:?} must be a relative path within the backup directory"
+        )));
+    }
+
+    chain
+        .store
+        .backup(&backup_dir.join(output_dir))
+        .map(GenericResponse::from)
+        .map_err(|e| match e {
+            StoreError::BackupError(BackupError::OutputExists(_)) => {
+                warp_utils::reject::custom_bad_request(format!("{output_dir:?} already exists"))
+            }
+            e => warp_utils::reject::custom_server_error(format!("backup failed: {e:?}")),
+        })
+}
+
+/// Load the log of blocks and states deleted by the abandoned fork sweeper.
+pub fn gc_log<T: BeaconChainTypes>(
+    query: DatabaseGcQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<GcRun>, warp::Rejection> {
+    chain.store.load_gc_log(query.limit).map_err(|e| {
+        warp_utils::reject::custom_server_error(format!("unable to load gc log: {e:?}"))
+    })
+}
+
+/// Sweep abandoned forks from the database now, returning what was deleted.
+pub fn gc<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>) -> Result<GcRun, warp::Rejection> {
+    chain.sweep_abandoned_forks().map_err(|e| {
+        warp_utils::reject::custom_server_error(format!("abandoned fork sweep failed: {e:?}"))
+    })
+}
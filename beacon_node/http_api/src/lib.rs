@@ -18,6 +18,7 @@ mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod slasher;
 mod standard_block_rewards;
 mod state_id;
 mod sync_committee_rewards;
@@ -47,7 +48,10 @@ use eth2::types::{
     ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use lighthouse_network::rpc::methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+use lighthouse_network::{
+    types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage, ReputationOverride,
+};
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
@@ -65,7 +69,9 @@ use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use sysinfo::{System, SystemExt};
 use system_health::{observe_nat, observe_system_health_bn};
 use task_spawner::{Priority, TaskSpawner};
@@ -79,9 +85,9 @@ use tokio_stream::{
 };
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
+    AttesterSlashing, BeaconStateError, ChainSpec, CommitteeCache, ConfigAndPreset, Epoch, EthSpec,
+    ForkName, ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing,
+    RelativeEpoch, SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
     SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
     SyncCommitteeMessage, SyncContributionData,
 };
@@ -107,6 +113,10 @@ const API_PREFIX: &str = "eth";
 /// finalized head.
 const SYNC_TOLERANCE_EPOCHS: u64 = 8;
 
+/// Name of the directory within the data directory that backups are written beneath, unless
+/// another directory is configured.
+pub const DEFAULT_BACKUP_DIR: &str = "backups";
+
 /// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
 type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 
@@ -145,6 +155,9 @@ pub struct Config {
     pub tls_config: Option<TlsConfig>,
     pub spec_fork_name: Option<ForkName>,
     pub data_dir: PathBuf,
+    /// Directory that backups requested via the API are written beneath. Defaults to
+    /// `DEFAULT_BACKUP_DIR` within the `data_dir`.
+    pub backup_dir: Option<PathBuf>,
     pub sse_capacity_multiplier: usize,
     pub enable_beacon_processor: bool,
     #[serde(with = "eth2::types::serde_status_code")]
@@ -162,6 +175,7 @@ impl Default for Config {
             tls_config: None,
             spec_fork_name: None,
             data_dir: PathBuf::from(DEFAULT_ROOT_DIR),
+            backup_dir: None,
             sse_capacity_multiplier: 1,
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
@@ -383,6 +397,14 @@ pub fn serve<T: BeaconChainTypes>(
     let inner_data_dir = ctx.config.data_dir.clone();
     let data_dir_filter = warp::any().map(move || inner_data_dir.clone());
 
+    // Create a `warp` filter for the directory that backups are written beneath.
+    let inner_backup_dir = ctx
+        .config
+        .backup_dir
+        .clone()
+        .unwrap_or_else(|| ctx.config.data_dir.join(DEFAULT_BACKUP_DIR));
+    let backup_dir_filter = warp::any().map(move || inner_backup_dir.clone());
+
     // Create a `warp` filter that provides access to the beacon chain.
     let inner_ctx = ctx.clone();
     let chain_filter =
@@ -2350,6 +2372,81 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(task_spawner_filter.clone())
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |chain: Arc<BeaconChain<T>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    if query.count == 0 || query.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "count must be between 1 and {MAX_REQUEST_LIGHT_CLIENT_UPDATES}"
+                        )));
+                    }
+
+                    let updates = chain
+                        .get_light_client_updates(query.start_period, query.count)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Unable to obtain LightClientUpdate instances: {e:?}"
+                            ))
+                        })?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => {
+                            // Each update is encoded as the little-endian length of its SSZ
+                            // bytes, followed by its fork digest and then the SSZ bytes.
+                            let mut bytes = vec![];
+                            for update in &updates {
+                                let fork_name = chain
+                                    .spec
+                                    .fork_name_at_slot::<T::EthSpec>(update.attested_header_slot());
+                                let fork_digest = ChainSpec::compute_fork_digest(
+                                    chain.spec.fork_version_for_name(fork_name),
+                                    chain.genesis_validators_root,
+                                );
+                                let update_bytes = update.as_ssz_bytes();
+                                bytes.extend_from_slice(&(update_bytes.len() as u64).to_le_bytes());
+                                bytes.extend_from_slice(&fork_digest);
+                                bytes.extend_from_slice(&update_bytes);
+                            }
+                            Response::builder()
+                                .status(200)
+                                .body(bytes.into())
+                                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                        _ => {
+                            let updates = updates
+                                .into_iter()
+                                .map(|update| ForkVersionedResponse {
+                                    version: Some(chain.spec.fork_name_at_slot::<T::EthSpec>(
+                                        update.attested_header_slot(),
+                                    )),
+                                    metadata: EmptyMetadata {},
+                                    data: update,
+                                })
+                                .collect::<Vec<_>>();
+                            Ok(warp::reply::json(&updates).into_response())
+                        }
+                    }
+                })
+            },
+        );
+
     // GET beacon/light_client/finality_update
     let get_beacon_light_client_finality_update = beacon_light_client_path
         .clone()
@@ -4011,7 +4108,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4029,6 +4126,96 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/peers/reputation
+    let get_lighthouse_peers_reputation = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("reputation"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals
+                        .peers
+                        .read()
+                        .reputations()
+                        .into_iter()
+                        .map(eth2::lighthouse::PeerReputation::from)
+                        .collect::<Vec<_>>())
+                })
+            },
+        );
+
+    // GET lighthouse/peers/reputation/{peer_id}
+    let get_lighthouse_peer_reputation = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("reputation"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |requested_peer_id: String,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    network_globals
+                        .peers
+                        .read()
+                        .reputation(&peer_id)
+                        .map(eth2::lighthouse::PeerReputation::from)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(format!(
+                                "no reputation for peer {}",
+                                peer_id
+                            ))
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/peers/reputation/{peer_id}
+    let post_lighthouse_peer_reputation = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("reputation"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |requested_peer_id: String,
+             reputation_override: eth2::lighthouse::PeerReputationOverride,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    let reputation_override = match reputation_override {
+                        eth2::lighthouse::PeerReputationOverride::Ban {
+                            reason,
+                            duration_secs,
+                        } => ReputationOverride::Ban {
+                            reason,
+                            duration: Duration::from_secs(duration_secs),
+                        },
+                        eth2::lighthouse::PeerReputationOverride::Unban => {
+                            ReputationOverride::Unban
+                        }
+                    };
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::OverridePeerReputation {
+                            peer_id,
+                            reputation_override,
+                        },
+                    )
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -4088,6 +4275,26 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/validator_inclusion/{epoch}
+    let post_lighthouse_validator_inclusion = warp::path("lighthouse")
+        .and(warp::path("validator_inclusion"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |epoch: Epoch,
+             indices: api_types::ValidatorIndexData,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_inclusion::validators_inclusion_data(epoch, &indices.0, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/eth1/syncing
     let get_lighthouse_eth1_syncing = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -4096,28 +4303,40 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let current_slot_opt = chain.slot().ok();
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| async move {
+                let execution_engines = if let Some(el) = &chain.execution_layer {
+                    el.execution_engine_statuses().await
+                } else {
+                    vec![]
+                };
 
-                    chain
-                        .eth1_chain
-                        .as_ref()
-                        .ok_or_else(|| {
-                            warp_utils::reject::custom_not_found(
-                                "Eth1 sync is disabled. See the --eth1 CLI flag.".to_string(),
-                            )
-                        })
-                        .and_then(|eth1| {
-                            eth1.sync_status(chain.genesis_time, current_slot_opt, &chain.spec)
-                                .ok_or_else(|| {
-                                    warp_utils::reject::custom_server_error(
-                                        "Unable to determine Eth1 sync status".to_string(),
-                                    )
-                                })
-                        })
-                        .map(api_types::GenericResponse::from)
-                })
+                task_spawner
+                    .blocking_json_task(Priority::P1, move || {
+                        let current_slot_opt = chain.slot().ok();
+
+                        chain
+                            .eth1_chain
+                            .as_ref()
+                            .ok_or_else(|| {
+                                warp_utils::reject::custom_not_found(
+                                    "Eth1 sync is disabled. See the --eth1 CLI flag.".to_string(),
+                                )
+                            })
+                            .and_then(|eth1| {
+                                eth1.sync_status(chain.genesis_time, current_slot_opt, &chain.spec)
+                                    .ok_or_else(|| {
+                                        warp_utils::reject::custom_server_error(
+                                            "Unable to determine Eth1 sync status".to_string(),
+                                        )
+                                    })
+                            })
+                            .map(|sync_status| eth2::lighthouse::Eth1SyncStatusData {
+                                execution_engines,
+                                ..sync_status
+                            })
+                            .map(api_types::GenericResponse::from)
+                    })
+                    .await
             },
         );
 
@@ -4221,6 +4440,150 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/backup
+    let post_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(backup_dir_filter)
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request: eth2::lighthouse::DatabaseBackupRequest,
+             backup_dir: PathBuf,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    database::backup(request, &backup_dir, chain)
+                })
+            },
+        );
+
+    // GET lighthouse/database/gc
+    let get_lighthouse_database_gc = database_path
+        .and(warp::path("gc"))
+        .and(warp::query::<eth2::lighthouse::DatabaseGcQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::DatabaseGcQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner
+                    .blocking_json_task(Priority::P1, move || database::gc_log(query, chain))
+            },
+        );
+
+    // POST lighthouse/database/gc
+    let post_lighthouse_database_gc = database_path
+        .and(warp::path("gc"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || database::gc(chain))
+            },
+        );
+
+    let slasher_path = warp::path("lighthouse").and(warp::path("slasher"));
+
+    // GET lighthouse/slasher/rescan
+    let get_lighthouse_slasher_rescan = slasher_path
+        .and(warp::path("rescan"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher::rescan_progress(chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/slasher/rescan
+    let post_lighthouse_slasher_rescan = slasher_path
+        .and(warp::path("rescan"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request: eth2::lighthouse::SlasherRescanRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher::rescan(request, chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/slasher/slashings
+    let get_lighthouse_slasher_slashings = slasher_path
+        .and(warp::path("slashings"))
+        .and(warp::query::<eth2::lighthouse::SlasherEpochRangeQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher::slashings(query, chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    let slasher_validator_path =
+        slasher_path
+            .and(warp::path("validators"))
+            .and(warp::path::param::<u64>().or_else(|_| async {
+                Err(warp_utils::reject::custom_bad_request(
+                    "Invalid validator index".to_string(),
+                ))
+            }));
+
+    // GET lighthouse/slasher/validators/{validator_index}/attestations
+    let get_lighthouse_slasher_validator_attestations = slasher_validator_path
+        .clone()
+        .and(warp::path("attestations"))
+        .and(warp::query::<eth2::lighthouse::SlasherEpochRangeQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |validator_index: u64,
+             query,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher::validator_attestations(validator_index, query, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/slasher/validators/{validator_index}/arrays
+    let get_lighthouse_slasher_validator_arrays = slasher_validator_path
+        .clone()
+        .and(warp::path("arrays"))
+        .and(warp::query::<eth2::lighthouse::SlasherEpochRangeQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |validator_index: u64,
+             query,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher::validator_arrays(validator_index, query, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4305,6 +4668,36 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/execution_engine/versions
+    let get_lighthouse_execution_engine_versions = warp::path("lighthouse")
+        .and(warp::path("execution_engine"))
+        .and(warp::path("versions"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let versions = chain
+                        .get_engine_versions()
+                        .await
+                        .map_err(warp_utils::reject::beacon_chain_error)?
+                        .into_iter()
+                        .map(|version| eth2::lighthouse::ExecutionEngineVersion {
+                            code: version.code.to_string(),
+                            name: version.name,
+                            version: version.version,
+                            commit: version.commit.to_string(),
+                        })
+                        .collect::<Vec<_>>();
+                    Ok::<_, warp::reject::Rejection>(
+                        warp::reply::json(&api_types::GenericResponse::from(versions))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4506,6 +4899,8 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_reputation)
+                .uor(get_lighthouse_peer_reputation)
                 .uor(get_lighthouse_proto_array)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
@@ -4514,6 +4909,11 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_staking)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_database_gc)
+                .uor(get_lighthouse_slasher_rescan)
+                .uor(get_lighthouse_slasher_slashings)
+                .uor(get_lighthouse_slasher_validator_attestations)
+                .uor(get_lighthouse_slasher_validator_arrays)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(
@@ -4528,8 +4928,13 @@ pub fn serve<T: BeaconChainTypes>(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_bootstrap),
                 )
+                .uor(
+                    enable(ctx.config.enable_light_client_server)
+                        .and(get_beacon_light_client_updates),
+                )
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
+                .uor(get_lighthouse_execution_engine_versions)
                 .uor(get_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
@@ -4570,7 +4975,12 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_register_validator)
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
+                    .uor(post_lighthouse_validator_inclusion)
+                    .uor(post_lighthouse_peer_reputation)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_database_backup)
+                    .uor(post_lighthouse_database_gc)
+                    .uor(post_lighthouse_slasher_rescan)
                     .uor(post_lighthouse_block_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
@@ -4639,6 +5049,12 @@ fn publish_pubsub_messages<E: EthSpec>(
     publish_network_message(network_tx, NetworkMessage::Publish { messages })
 }
 
+/// Parse a base58 encoded peer id from a request path.
+fn parse_peer_id(peer_id: &str) -> Result<PeerId, warp::Rejection> {
+    PeerId::from_str(peer_id)
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid peer id: {}", e)))
+}
+
 /// Publish a message to the libp2p network.
 fn publish_network_message<E: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<E>>,
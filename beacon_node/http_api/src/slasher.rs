@@ -0,0 +1,150 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    SlasherArrayStatus, SlasherChunkStatus, SlasherEpochRangeQuery, SlasherEpochTargets,
+    SlasherRescanRequest, SlasherRescanStatus, SlasherSlashings,
+};
+use slasher::{ArrayStatus, Error as SlasherError, RescanStats, Slasher};
+use std::sync::Arc;
+use types::IndexedAttestation;
+use warp_utils::reject::{custom_bad_request, custom_not_found, custom_server_error};
+
+fn rescan_status(stats: RescanStats) -> SlasherRescanStatus {
+    SlasherRescanStatus {
+        start_epoch: stats.start_epoch,
+        end_epoch: stats.end_epoch,
+        last_processed_epoch: stats.last_processed_epoch,
+        num_blocks: stats.num_blocks as u64,
+        num_attestations: stats.num_attestations as u64,
+        num_proposer_slashings: stats.num_proposer_slashings as u64,
+        complete: stats.complete,
+        error: stats.error,
+    }
+}
+
+/// Start rescanning historical blocks in the background, returning the initial progress.
+pub fn rescan<T: BeaconChainTypes>(
+    request: SlasherRescanRequest,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<SlasherRescanStatus, warp::Rejection> {
+    let slasher = get_slasher(&chain)?;
+    let end_epoch = match request.end_epoch {
+        Some(end_epoch) => end_epoch,
+        None => chain
+            .epoch()
+            .map_err(|e| custom_server_error(format!("unable to read epoch: {e:?}")))?,
+    };
+
+    chain
+        .spawn_slasher_rescan(request.start_epoch, end_epoch)
+        .map_err(|e| match e {
+            BeaconChainError::SlasherError(SlasherError::RescanInProgress) => {
+                custom_bad_request("a rescan is already in progress".to_string())
+            }
+            e @ (BeaconChainError::SlasherError(SlasherError::RescanOutOfRange { .. })
+            | BeaconChainError::HistoricalBlockError(_)) => {
+                custom_bad_request(format!("invalid rescan range: {e:?}"))
+            }
+            e => custom_server_error(format!("unable to start rescan: {e:?}")),
+        })?;
+
+    slasher
+        .rescan_stats()
+        .map(rescan_status)
+        .ok_or_else(|| custom_server_error("rescan did not start".to_string()))
+}
+
+/// Get the progress of the current or most recent rescan.
+pub fn rescan_progress<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Option<SlasherRescanStatus>, warp::Rejection> {
+    let slasher = get_slasher(&chain)?;
+    Ok(slasher.rescan_stats().map(rescan_status))
+}
+
+fn get_slasher<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<Arc<Slasher<T::EthSpec>>, warp::Rejection> {
+    chain
+        .slasher
+        .clone()
+        .ok_or_else(|| custom_not_found("slasher is not enabled".to_string()))
+}
+
+fn check_epoch_range(query: &SlasherEpochRangeQuery) -> Result<(), warp::Rejection> {
+    if query.start_epoch > query.end_epoch {
+        return Err(custom_bad_request(format!(
+            "start_epoch ({}) must not be greater than end_epoch ({})",
+            query.start_epoch, query.end_epoch
+        )));
+    }
+    Ok(())
+}
+
+fn array_status(status: ArrayStatus) -> SlasherArrayStatus {
+    SlasherArrayStatus {
+        validator_chunk_index: status.validator_chunk_index as u64,
+        current_epoch: status.current_epoch,
+        pruning_horizon: status.pruning_horizon,
+        chunks: status
+            .chunks
+            .into_iter()
+            .map(|chunk| SlasherChunkStatus {
+                chunk_index: chunk.chunk_index as u64,
+                min_chunk_stored: chunk.min_chunk_stored,
+                max_chunk_stored: chunk.max_chunk_stored,
+            })
+            .collect(),
+        targets: status
+            .targets
+            .into_iter()
+            .map(|targets| SlasherEpochTargets {
+                epoch: targets.epoch,
+                min_target: targets.min_target,
+                max_target: targets.max_target,
+            })
+            .collect(),
+    }
+}
+
+/// Get the slashings detected by the slasher for the epochs in `query`.
+pub fn slashings<T: BeaconChainTypes>(
+    query: SlasherEpochRangeQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<SlasherSlashings<T::EthSpec>, warp::Rejection> {
+    let slasher = get_slasher(&chain)?;
+    check_epoch_range(&query)?;
+    let (attester_slashings, proposer_slashings) = slasher
+        .get_detected_slashings(query.start_epoch, query.end_epoch)
+        .map_err(|e| custom_server_error(format!("unable to read slashings: {e:?}")))?;
+    Ok(SlasherSlashings {
+        attester_slashings,
+        proposer_slashings,
+    })
+}
+
+/// Get the attestations recorded by the slasher for `validator_index`, by target epoch.
+pub fn validator_attestations<T: BeaconChainTypes>(
+    validator_index: u64,
+    query: SlasherEpochRangeQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<IndexedAttestation<T::EthSpec>>, warp::Rejection> {
+    let slasher = get_slasher(&chain)?;
+    check_epoch_range(&query)?;
+    slasher
+        .get_validator_attestations(validator_index, query.start_epoch, query.end_epoch)
+        .map_err(|e| custom_server_error(format!("unable to read attestations: {e:?}")))
+}
+
+/// Get the state of the min-max target arrays for `validator_index`.
+pub fn validator_arrays<T: BeaconChainTypes>(
+    validator_index: u64,
+    query: SlasherEpochRangeQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<SlasherArrayStatus, warp::Rejection> {
+    let slasher = get_slasher(&chain)?;
+    check_epoch_range(&query)?;
+    slasher
+        .get_validator_array_status(validator_index, query.start_epoch, query.end_epoch)
+        .map(array_status)
+        .map_err(|e| custom_server_error(format!("unable to read arrays: {e:?}")))
+}
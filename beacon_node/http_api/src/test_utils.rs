@@ -24,6 +24,7 @@ use sensitive_url::SensitiveUrl;
 use slog::Logger;
 use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use store::MemoryStore;
@@ -127,6 +128,11 @@ impl<E: EthSpec> InteractiveTester<E> {
     }
 }
 
+/// The directory that backups requested from test API servers are written beneath.
+pub fn test_backup_dir() -> PathBuf {
+    std::env::temp_dir().join("lighthouse_http_api_test_backups")
+}
+
 pub async fn create_api_server<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     test_runtime: &TestRuntime,
@@ -221,6 +227,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
             enabled: true,
             listen_port: port,
             data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+            backup_dir: Some(test_backup_dir()),
             enable_light_client_server: true,
             ..Config::default()
         },
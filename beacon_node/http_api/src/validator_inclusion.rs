@@ -1,11 +1,13 @@
 use crate::state_id::StateId;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2::{
-    lighthouse::{GlobalValidatorInclusionData, ValidatorInclusionData},
+    lighthouse::{
+        GlobalValidatorInclusionData, IndexedValidatorInclusionData, ValidatorInclusionData,
+    },
     types::ValidatorId,
 };
 use state_processing::per_epoch_processing::{process_epoch, EpochProcessingSummary};
-use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec};
+use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, Validator};
 
 /// Returns the state in the last slot of `epoch`.
 fn end_of_epoch_state<T: BeaconChainTypes>(
@@ -94,7 +96,51 @@ pub fn validator_inclusion_data<T: BeaconChainTypes>(
 
     let summary = get_epoch_processing_summary(&mut state, &chain.spec)?;
 
-    Ok(Some(ValidatorInclusionData {
+    inclusion_data(epoch, validator_index, &validator, &summary).map(Some)
+}
+
+/// Returns information about multiple validators and how they performed during a given epoch.
+///
+/// Unknown validators are omitted from the result. The state is only transitioned once, so this is
+/// far cheaper than calling `validator_inclusion_data` for each validator.
+pub fn validators_inclusion_data<T: BeaconChainTypes>(
+    epoch: Epoch,
+    validator_indices: &[u64],
+    chain: &BeaconChain<T>,
+) -> Result<Vec<IndexedValidatorInclusionData>, warp::Rejection> {
+    let mut state = end_of_epoch_state(epoch, chain)?;
+
+    // Obtain the validators *before* transitioning the state into the next epoch.
+    let validators = validator_indices
+        .iter()
+        .filter_map(|&index| {
+            state
+                .get_validator(index as usize)
+                .ok()
+                .map(|validator| (index, validator.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    let summary = get_epoch_processing_summary(&mut state, &chain.spec)?;
+
+    validators
+        .into_iter()
+        .map(|(index, validator)| {
+            Ok(IndexedValidatorInclusionData {
+                index,
+                data: inclusion_data(epoch, index as usize, &validator, &summary)?,
+            })
+        })
+        .collect()
+}
+
+fn inclusion_data<E: EthSpec>(
+    epoch: Epoch,
+    validator_index: usize,
+    validator: &Validator,
+    summary: &EpochProcessingSummary<E>,
+) -> Result<ValidatorInclusionData, warp::Rejection> {
+    Ok(ValidatorInclusionData {
         is_slashed: validator.slashed,
         is_withdrawable_in_current_epoch: validator.is_withdrawable_at(epoch),
         is_active_unslashed_in_current_epoch: summary
@@ -111,5 +157,5 @@ pub fn validator_inclusion_data<T: BeaconChainTypes>(
         is_previous_epoch_head_attester: summary
             .is_previous_epoch_head_attester(validator_index)
             .map_err(convert_cache_error)?,
-    }))
+    })
 }
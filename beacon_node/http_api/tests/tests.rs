@@ -18,11 +18,11 @@ use execution_layer::test_utils::{
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
 use http_api::{
-    test_utils::{create_api_server, ApiServer},
+    test_utils::{create_api_server, test_backup_dir, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use lighthouse_network::{Enr, EnrExt, PeerId, ReputationOverride};
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
@@ -30,6 +30,7 @@ use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
 use std::convert::TryInto;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::time::Duration;
 use tree_hash::TreeHash;
@@ -1768,6 +1769,36 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_light_client_updates(self) -> Self {
+        let current_period = self
+            .chain
+            .epoch()
+            .unwrap()
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+
+        let result = match self
+            .client
+            .get_beacon_light_client_updates::<E>(0, current_period + 1)
+            .await
+        {
+            Ok(result) => result
+                .unwrap()
+                .into_iter()
+                .map(|res| res.data)
+                .collect::<Vec<_>>(),
+            Err(e) => panic!("query failed incorrectly: {e:?}"),
+        };
+
+        let expected = self
+            .chain
+            .get_light_client_updates(0, current_period + 1)
+            .unwrap();
+        assert_eq!(result, expected);
+
+        self
+    }
+
     pub async fn test_get_beacon_pool_attestations(self) -> Self {
         let result = self
             .client
@@ -5092,6 +5123,34 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_validator_inclusion(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        let validator_count = self.chain.head_snapshot().beacon_state.validators().len() as u64;
+        let indices = vec![0, 1, validator_count];
+
+        let result = self
+            .client
+            .post_lighthouse_validator_inclusion(epoch, &indices)
+            .await
+            .unwrap()
+            .data;
+
+        // The unknown validator is omitted.
+        assert_eq!(result.len(), 2);
+        for (response, index) in result.into_iter().zip(indices) {
+            let expected = self
+                .client
+                .get_lighthouse_validator_inclusion(epoch, ValidatorId::Index(index))
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(response.index, index);
+            assert_eq!(Some(response.data), expected);
+        }
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
         self.client.get_lighthouse_eth1_syncing().await.unwrap();
 
@@ -5149,6 +5208,119 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_backup(self) -> Self {
+        let output_dir = PathBuf::from(format!("backup_{:?}", Hash256::random()));
+        let request = eth2::lighthouse::DatabaseBackupRequest {
+            output_dir: output_dir.clone(),
+        };
+        let manifest = self
+            .client
+            .post_lighthouse_database_backup(&request)
+            .await
+            .unwrap()
+            .data;
+
+        let backup_dir = test_backup_dir().join(&output_dir);
+        assert_eq!(store::backup::load_manifest(&backup_dir).unwrap(), manifest);
+        assert_eq!(manifest.split, self.chain.store.get_split_info());
+
+        // Backups are never written over an existing directory.
+        let error = self
+            .client
+            .post_lighthouse_database_backup(&request)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status().unwrap(), StatusCode::BAD_REQUEST);
+
+        std::fs::remove_dir_all(&backup_dir).unwrap();
+        self
+    }
+
+    pub async fn test_post_lighthouse_database_backup_invalid_path(self) -> Self {
+        // Backups may only be written within the backup directory.
+        for output_dir in [
+            std::env::temp_dir().join("lighthouse_backup"),
+            PathBuf::from("../lighthouse_backup"),
+            PathBuf::from("backups/../../lighthouse_backup"),
+            PathBuf::from(""),
+        ] {
+            let request = eth2::lighthouse::DatabaseBackupRequest { output_dir };
+            let error = self
+                .client
+                .post_lighthouse_database_backup(&request)
+                .await
+                .unwrap_err();
+            assert_eq!(error.status().unwrap(), StatusCode::BAD_REQUEST);
+        }
+        assert!(!std::env::temp_dir().join("lighthouse_backup").exists());
+        self
+    }
+
+    pub async fn test_get_lighthouse_peers_reputation(self) -> Self {
+        let reputations = self.client.get_lighthouse_peers_reputation().await.unwrap();
+        let reputation = self
+            .client
+            .get_lighthouse_peer_reputation(&self.external_peer_id.to_string())
+            .await
+            .unwrap()
+            .expect("external peer should have a reputation");
+
+        assert_eq!(reputation.peer_id, self.external_peer_id.to_string());
+        assert!(reputation.ban.is_none());
+        assert!(reputations.contains(&reputation));
+
+        // Peers that aren't in the peer DB have no reputation.
+        let unknown_peer = self
+            .client
+            .get_lighthouse_peer_reputation(&PeerId::random().to_string())
+            .await
+            .unwrap();
+        assert!(unknown_peer.is_none());
+
+        // Peer ids must be valid.
+        let error = self
+            .client
+            .get_lighthouse_peer_reputation("not_a_peer_id")
+            .await
+            .unwrap_err();
+        assert_eq!(error.status().unwrap(), StatusCode::BAD_REQUEST);
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_peer_reputation(mut self) -> Self {
+        let reputation_override = eth2::lighthouse::PeerReputationOverride::Ban {
+            reason: "manual".to_string(),
+            duration_secs: 60,
+        };
+        self.client
+            .post_lighthouse_peer_reputation(
+                &self.external_peer_id.to_string(),
+                &reputation_override,
+            )
+            .await
+            .unwrap();
+
+        match self.network_rx.network_recv.recv().await {
+            Some(NetworkMessage::OverridePeerReputation {
+                peer_id,
+                reputation_override,
+            }) => {
+                assert_eq!(peer_id, self.external_peer_id);
+                assert_eq!(
+                    reputation_override,
+                    ReputationOverride::Ban {
+                        reason: "manual".to_string(),
+                        duration: Duration::from_secs(60),
+                    }
+                );
+            }
+            other => panic!("unexpected network message: {:?}", other),
+        }
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5876,6 +6048,18 @@ async fn get_light_client_finality_update() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_updates() {
+    let config = ApiTesterConfig {
+        spec: ForkName::Altair.make_genesis_spec(E::default_spec()),
+        ..<_>::default()
+    };
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_updates()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_early() {
     ApiTester::new()
@@ -6461,6 +6645,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_post_lighthouse_validator_inclusion()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
         .test_get_lighthouse_eth1_block_cache()
@@ -6473,7 +6659,15 @@ async fn lighthouse_endpoints() {
         .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_backup()
+        .await
+        .test_post_lighthouse_database_backup_invalid_path()
+        .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_get_lighthouse_peers_reputation()
+        .await
+        .test_post_lighthouse_peer_reputation()
         .await;
 }
 
@@ -117,7 +117,8 @@ pub use libp2p::{core::ConnectedPoint, PeerId, Swarm};
 pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
-    peerdb::client::Client,
+    peerdb::client::{Client, ClientKind},
+    peerdb::reputation::{PeerBan, PeerReputation, ReputationOverride},
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
     ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
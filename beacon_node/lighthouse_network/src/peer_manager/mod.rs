@@ -10,9 +10,10 @@ use delay_map::HashSetDelay;
 use discv5::Enr;
 use libp2p::identify::Info as IdentifyInfo;
 use lru_cache::LRUTimeCache;
+use peerdb::reputation::{PeerReputation, ReputationOverride};
 use peerdb::{BanOperation, BanResult, ScoreUpdateResult};
 use rand::seq::SliceRandom;
-use slog::{debug, error, trace, warn};
+use slog::{debug, error, info, trace, warn};
 use smallvec::SmallVec;
 use std::{
     sync::Arc,
@@ -33,6 +34,7 @@ pub use peerdb::peer_info::{
 };
 use peerdb::score::{PeerAction, ReportSource};
 pub use peerdb::sync_status::{SyncInfo, SyncStatus};
+use std::cmp::Ordering;
 use std::collections::{hash_map::Entry, HashMap};
 use std::net::IpAddr;
 use strum::IntoEnumIterator;
@@ -222,6 +224,84 @@ impl<E: EthSpec> PeerManager<E> {
         self.handle_score_action(peer_id, action, reason);
     }
 
+    /// Applies a manual override to a peer's reputation.
+    pub fn override_reputation(
+        &mut self,
+        peer_id: &PeerId,
+        reputation_override: ReputationOverride,
+    ) {
+        info!(self.log, "Overriding peer reputation"; "peer_id" => %peer_id, "override" => ?reputation_override);
+        let action = {
+            let mut peerdb = self.network_globals.peers.write();
+            match reputation_override {
+                ReputationOverride::Ban { reason, duration } => {
+                    peerdb.ban_peer(peer_id, reason, duration)
+                }
+                ReputationOverride::Unban => peerdb.unban_peer(peer_id),
+            }
+        };
+        if matches!(action, ScoreUpdateResult::Unbanned(_)) {
+            // Also lift any temporary ban, so that the peer can reconnect immediately.
+            self.temporary_banned_peers.raw_remove(peer_id);
+        }
+        self.handle_score_action(peer_id, action, Some(GoodbyeReason::Banned));
+    }
+
+    /// Restores peer reputations persisted by a previous run.
+    ///
+    /// Peers that are still banned are banned at the swarm level, and up to `target_peers` of the
+    /// remaining peers are dialed, preferring those that have been most useful in the past.
+    pub fn restore_reputations(&mut self, reputations: Vec<PeerReputation>) {
+        let num_reputations = reputations.len();
+
+        // Rank the peers that can be dialed by how useful they have been for syncing, and then by
+        // score.
+        let mut dial_candidates = reputations
+            .iter()
+            .filter(|reputation| reputation.ban.is_none())
+            .filter_map(|reputation| {
+                reputation
+                    .enr
+                    .clone()
+                    .map(|enr| (reputation.useful_sync_batches, reputation.score, enr))
+            })
+            .collect::<Vec<_>>();
+        dial_candidates.sort_by(|(batches_a, score_a, _), (batches_b, score_b, _)| {
+            batches_b
+                .cmp(batches_a)
+                .then_with(|| score_b.partial_cmp(score_a).unwrap_or(Ordering::Equal))
+        });
+
+        let banned_peers = self
+            .network_globals
+            .peers
+            .write()
+            .restore_reputations(reputations);
+        let num_banned = banned_peers.len();
+        for (peer_id, banned_ips) in banned_peers {
+            self.events
+                .push(PeerManagerEvent::Banned(peer_id, banned_ips));
+        }
+
+        let mut num_dialed = 0;
+        for (_, _, enr) in dial_candidates {
+            if num_dialed >= self.target_peers {
+                break;
+            }
+            if self.dial_peer(enr) {
+                num_dialed += 1;
+            }
+        }
+
+        debug!(
+            self.log,
+            "Restored peer reputations";
+            "peers" => num_reputations,
+            "banned" => num_banned,
+            "dialing" => num_dialed,
+        );
+    }
+
     /// Upon adjusting a Peer's score, there are times the peer manager must pass messages up to
     /// libp2p. This function handles the conditional logic associated with each score update
     /// result.
@@ -558,6 +638,7 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
@@ -581,6 +662,7 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::MetaData => PeerAction::Fatal,
                     Protocol::Status => PeerAction::Fatal,
                 }
@@ -600,6 +682,7 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
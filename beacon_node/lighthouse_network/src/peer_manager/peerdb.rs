@@ -2,13 +2,14 @@ use crate::discovery::CombinedKey;
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
+use reputation::PeerReputation;
 use score::{PeerAction, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{cmp::Ordering, fmt::Display};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet},
     fmt::Formatter,
 };
 use sync_status::SyncStatus;
@@ -16,6 +17,7 @@ use types::EthSpec;
 
 pub mod client;
 pub mod peer_info;
+pub mod reputation;
 pub mod score;
 pub mod sync_status;
 
@@ -94,6 +96,21 @@ impl<E: EthSpec> PeerDB<E> {
         self.peers.get_mut(peer_id)
     }
 
+    /// Returns a summary of a peer's reputation, if the peer is known and not trusted.
+    pub fn reputation(&self, peer_id: &PeerId) -> Option<PeerReputation> {
+        self.peers
+            .get(peer_id)
+            .and_then(|info| info.reputation(*peer_id))
+    }
+
+    /// Returns the reputations of all known peers, excluding trusted peers.
+    pub fn reputations(&self) -> Vec<PeerReputation> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, info)| info.reputation(*peer_id))
+            .collect()
+    }
+
     /// Returns if the peer is already connected.
     pub fn is_connected(&self, peer_id: &PeerId) -> bool {
         matches!(
@@ -356,6 +373,13 @@ impl<E: EthSpec> PeerDB<E> {
         Some(info.update_sync_status(sync_status))
     }
 
+    /// Records that a peer served a sync batch which was successfully processed.
+    pub fn record_useful_sync_batch(&mut self, peer_id: &PeerId) {
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.record_useful_sync_batch();
+        }
+    }
+
     /// Updates the scores of known peers according to their connection status and the time that
     /// has passed. This function returns a list of peers that have been unbanned.
     /// NOTE: Peer scores cannot be penalized during the update, they can only increase. Therefore
@@ -452,10 +476,12 @@ impl<E: EthSpec> PeerDB<E> {
                 },
             );
 
-            actions.push((
-                *peer_id,
-                Self::handle_score_transition(previous_state, peer_id, info, &self.log),
-            ));
+            let transition =
+                Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+            if matches!(transition, ScoreTransitionResult::Banned) {
+                info.set_ban_reason(Some("gossipsub_score".to_string()));
+            }
+            actions.push((*peer_id, transition));
         }
 
         for (peer_id, action) in actions {
@@ -527,6 +553,9 @@ impl<E: EthSpec> PeerDB<E> {
                 );
                 let result =
                     Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+                if matches!(result, ScoreTransitionResult::Banned) {
+                    info.set_ban_reason(Some(msg.to_string()));
+                }
                 if previous_state == info.score_state() {
                     debug!(
                         self.log,
@@ -576,6 +605,118 @@ impl<E: EthSpec> PeerDB<E> {
         }
     }
 
+    /// Manually bans a peer for `duration`, regardless of its score. The peer's score decays as
+    /// usual once the ban has expired.
+    #[must_use = "Banned peers need to be handled in libp2p"]
+    pub(super) fn ban_peer(
+        &mut self,
+        peer_id: &PeerId,
+        reason: String,
+        duration: Duration,
+    ) -> ScoreUpdateResult {
+        let disable_peer_scoring = self.disable_peer_scoring;
+        let info = self.peers.entry(*peer_id).or_insert_with(|| {
+            if disable_peer_scoring {
+                PeerInfo::trusted_peer_info()
+            } else {
+                PeerInfo::default()
+            }
+        });
+        let previous_state = info.score_state();
+        info.ban_for(reason, duration);
+        match Self::handle_score_transition(previous_state, peer_id, info, &self.log) {
+            ScoreTransitionResult::Banned => self
+                .update_connection_state(peer_id, NewConnectionState::Banned)
+                .into(),
+            // The peer is either trusted or already banned, in which case the ban has been
+            // extended.
+            _ => ScoreUpdateResult::NoAction,
+        }
+    }
+
+    /// Manually lifts any ban on a peer and resets its score.
+    #[must_use = "Unbanned peers need to be reported to libp2p"]
+    pub(super) fn unban_peer(&mut self, peer_id: &PeerId) -> ScoreUpdateResult {
+        let Some(info) = self.peers.get_mut(peer_id) else {
+            return ScoreUpdateResult::NoAction;
+        };
+        let previous_state = info.score_state();
+        info.lift_ban();
+        match Self::handle_score_transition(previous_state, peer_id, info, &self.log) {
+            ScoreTransitionResult::Unbanned => {
+                self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+                let unbanned_ips = self
+                    .peers
+                    .get(peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                ScoreUpdateResult::Unbanned(unbanned_ips)
+            }
+            _ => ScoreUpdateResult::NoAction,
+        }
+    }
+
+    /// Restores the reputations of peers persisted by a previous run. Peers that are already known,
+    /// such as trusted peers, are skipped.
+    ///
+    /// Returns the restored peers that are still banned along with their banned IP addresses,
+    /// which need to be banned at the swarm level.
+    #[must_use = "Banned peers need to be reported to libp2p"]
+    pub(super) fn restore_reputations(
+        &mut self,
+        reputations: Vec<PeerReputation>,
+    ) -> Vec<(PeerId, Vec<IpAddr>)> {
+        // Scores are meaningless if peer scoring is disabled.
+        if self.disable_peer_scoring {
+            return vec![];
+        }
+
+        let now = Instant::now();
+        let mut banned_peers = vec![];
+        for reputation in reputations {
+            let peer_id = reputation.peer_id;
+            let Entry::Vacant(entry) = self.peers.entry(peer_id) else {
+                continue;
+            };
+            let info = entry.insert(PeerInfo::from_reputation(reputation));
+            // Apply any decay that occurred while the node was offline.
+            info.score_update();
+            if info.score_is_banned() {
+                info.set_connection_status(PeerConnectionStatus::Banned { since: now });
+                self.banned_peers_count
+                    .add_banned_peer(info.seen_ip_addresses());
+                banned_peers.push(peer_id);
+            } else {
+                info.set_ban_reason(None);
+                info.set_connection_status(PeerConnectionStatus::Disconnected { since: now });
+                self.disconnected_peers += 1;
+            }
+        }
+
+        // IP bans depend on the number of banned peers per IP, so they can only be determined
+        // once all peers have been restored.
+        let banned_ips = self.banned_peers_count.banned_ips();
+        banned_peers
+            .into_iter()
+            .map(|peer_id| {
+                let ips = self
+                    .peers
+                    .get(&peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| banned_ips.contains(ip))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (peer_id, ips)
+            })
+            .collect()
+    }
+
     /// Update min ttl of a peer.
     // VISIBILITY: Only the peer manager can update the min_ttl
     pub(super) fn update_min_ttl(&mut self, peer_id: &PeerId, min_ttl: Instant) {
@@ -952,6 +1093,7 @@ impl<E: EthSpec> PeerDB<E> {
              * Handles the transition to an unbanned state
              */
             (old_state, NewConnectionState::Unbanned) => {
+                info.set_ban_reason(None);
                 if matches!(info.score_state(), ScoreState::Banned) {
                     error!(self.log, "Unbanning a banned peer"; "peer_id" => %peer_id);
                 }
@@ -1,8 +1,9 @@
 use super::client::Client;
+use super::reputation::{self, PeerBan, PeerReputation, UNKNOWN_BAN_REASON};
 use super::score::{PeerAction, Score, ScoreState};
 use super::sync_status::SyncStatus;
 use crate::discovery::Eth2Enr;
-use crate::{rpc::MetaData, types::Subnet};
+use crate::{rpc::MetaData, types::Subnet, PeerId};
 use discv5::Enr;
 use libp2p::core::multiaddr::{Multiaddr, Protocol};
 use serde::{
@@ -11,7 +12,7 @@ use serde::{
 };
 use std::collections::HashSet;
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime};
 use strum::AsRefStr;
 use types::EthSpec;
 use PeerConnectionStatus::*;
@@ -51,6 +52,10 @@ pub struct PeerInfo<E: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// The reason the peer was banned, if it is banned.
+    ban_reason: Option<String>,
+    /// The number of sync batches served by this peer which were successfully processed.
+    useful_sync_batches: u64,
 }
 
 impl<E: EthSpec> Default for PeerInfo<E> {
@@ -68,6 +73,8 @@ impl<E: EthSpec> Default for PeerInfo<E> {
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            ban_reason: None,
+            useful_sync_batches: 0,
         }
     }
 }
@@ -82,6 +89,56 @@ impl<E: EthSpec> PeerInfo<E> {
         }
     }
 
+    /// Restores a peer's information from a persisted reputation. The peer's connection status is
+    /// left unknown.
+    pub(super) fn from_reputation(reputation: PeerReputation) -> Self {
+        let score = Score::restore(reputation.lighthouse_score, reputation.score_last_updated());
+        let client = Client {
+            kind: reputation.client_kind,
+            ..Default::default()
+        };
+        PeerInfo {
+            score,
+            client,
+            seen_multiaddrs: reputation.seen_multiaddrs.into_iter().collect(),
+            enr: reputation.enr,
+            ban_reason: reputation.ban.map(|ban| ban.reason),
+            useful_sync_batches: reputation.useful_sync_batches,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a summary of the peer's reputation which can be persisted, or `None` for trusted
+    /// peers.
+    pub fn reputation(&self, peer_id: PeerId) -> Option<PeerReputation> {
+        if self.is_trusted {
+            return None;
+        }
+        let recorded_at = SystemTime::now();
+        let ban = self.score_is_banned().then(|| PeerBan {
+            reason: self
+                .ban_reason
+                .clone()
+                .unwrap_or_else(|| UNKNOWN_BAN_REASON.to_string()),
+            expires_at: self
+                .score
+                .last_updated()
+                .map_or(recorded_at, reputation::instant_to_system_time)
+                .max(recorded_at),
+        });
+        Some(PeerReputation {
+            peer_id,
+            score: self.score.score(),
+            lighthouse_score: self.score.lighthouse_score(),
+            ban,
+            client_kind: self.client.kind,
+            useful_sync_batches: self.useful_sync_batches,
+            enr: self.enr.clone(),
+            seen_multiaddrs: self.seen_multiaddrs.iter().cloned().collect(),
+            recorded_at,
+        })
+    }
+
     /// Returns if the peer is subscribed to a given `Subnet` from the metadata attnets/syncnets field.
     pub fn on_subnet_metadata(&self, subnet: &Subnet) -> bool {
         if let Some(meta_data) = &self.meta_data {
@@ -119,6 +176,16 @@ impl<E: EthSpec> PeerInfo<E> {
         &self.sync_status
     }
 
+    /// Returns the reason the peer was banned, if known.
+    pub fn ban_reason(&self) -> Option<&str> {
+        self.ban_reason.as_deref()
+    }
+
+    /// Returns the number of sync batches served by the peer which were successfully processed.
+    pub fn useful_sync_batches(&self) -> u64 {
+        self.useful_sync_batches
+    }
+
     /// Returns the metadata for the peer if currently known.
     pub fn meta_data(&self) -> Option<&MetaData<E>> {
         self.meta_data.as_ref()
@@ -393,6 +460,30 @@ impl<E: EthSpec> PeerInfo<E> {
         }
     }
 
+    /// Bans a non-trusted peer for `duration`, recording the reason.
+    pub(super) fn ban_for(&mut self, reason: String, duration: Duration) {
+        if !self.is_trusted {
+            self.score.ban_for(duration);
+            self.ban_reason = Some(reason);
+        }
+    }
+
+    /// Lifts any ban on the peer and resets its score.
+    pub(super) fn lift_ban(&mut self) {
+        self.score.reset();
+        self.ban_reason = None;
+    }
+
+    /// Sets the reason the peer has been banned.
+    pub(super) fn set_ban_reason(&mut self, reason: Option<String>) {
+        self.ban_reason = reason;
+    }
+
+    /// Records that the peer served a sync batch which was successfully processed.
+    pub(super) fn record_useful_sync_batch(&mut self) {
+        self.useful_sync_batches = self.useful_sync_batches.saturating_add(1);
+    }
+
     /// Updates the gossipsub score with a new score. Optionally ignore the gossipsub score.
     pub(super) fn update_gossipsub_score(&mut self, new_score: f64, ignore: bool) {
         self.score.update_gossipsub_score(new_score, ignore);
@@ -0,0 +1,102 @@
+//! A summary of a peer's reputation which outlives the peer's entry in the `PeerDB`.
+//!
+//! Reputations are persisted when the node shuts down and restored when it starts, so that bans
+//! survive restarts and peers that have been useful in the past are preferred when reconnecting.
+
+use super::client::ClientKind;
+use crate::{Enr, Multiaddr, PeerId};
+use std::time::{Duration, Instant, SystemTime};
+
+/// The reason a peer is banned when the reason is not otherwise known.
+pub const UNKNOWN_BAN_REASON: &str = "bad_score";
+
+/// The parts of a peer's information that are kept across restarts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerReputation {
+    pub peer_id: PeerId,
+    /// The peer's overall score when the reputation was recorded.
+    pub score: f64,
+    /// The lighthouse component of the peer's score. Only this component is restored, since the
+    /// gossipsub component is rebuilt by gossipsub.
+    pub lighthouse_score: f64,
+    /// Present if the peer is banned.
+    pub ban: Option<PeerBan>,
+    pub client_kind: ClientKind,
+    /// The number of sync batches served by the peer which were successfully processed.
+    pub useful_sync_batches: u64,
+    /// The peer's ENR, if known. Required to dial the peer.
+    pub enr: Option<Enr>,
+    /// The addresses the peer has been seen on, used to restore IP bans.
+    pub seen_multiaddrs: Vec<Multiaddr>,
+    /// The time the reputation was recorded.
+    pub recorded_at: SystemTime,
+}
+
+/// The details of a ban.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerBan {
+    pub reason: String,
+    /// The time at which the peer's score starts to decay. The ban is lifted once the score has
+    /// decayed back above the ban threshold.
+    pub expires_at: SystemTime,
+}
+
+impl PeerReputation {
+    /// The time from which time-based score adjustments should resume once the reputation has
+    /// been restored. Scores decay during the time the node was offline.
+    pub(super) fn score_last_updated(&self) -> Instant {
+        let last_updated = self
+            .ban
+            .as_ref()
+            .map_or(self.recorded_at, |ban| ban.expires_at);
+        system_time_to_instant(last_updated)
+    }
+}
+
+/// A manual override of a peer's reputation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReputationOverride {
+    /// Ban the peer for `duration`, after which its score decays as usual.
+    Ban { reason: String, duration: Duration },
+    /// Lift any ban on the peer and reset its score.
+    Unban,
+}
+
+/// Converts an `Instant` to the corresponding `SystemTime`.
+pub(super) fn instant_to_system_time(instant: Instant) -> SystemTime {
+    let now = Instant::now();
+    match instant.checked_duration_since(now) {
+        Some(until) => SystemTime::now() + until,
+        None => SystemTime::now() - now.duration_since(instant),
+    }
+}
+
+/// Converts a `SystemTime` to the corresponding `Instant`, saturating at the current time if the
+/// `Instant` can't be represented.
+fn system_time_to_instant(time: SystemTime) -> Instant {
+    let now = Instant::now();
+    match time.duration_since(SystemTime::now()) {
+        Ok(until) => now + until,
+        Err(e) => now.checked_sub(e.duration()).unwrap_or(now),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_conversion() {
+        let now = Instant::now();
+        let later = now + Duration::from_secs(3600);
+        let earlier = now - Duration::from_secs(60);
+
+        for instant in [later, earlier] {
+            let round_trip = system_time_to_instant(instant_to_system_time(instant));
+            let difference = round_trip
+                .checked_duration_since(instant)
+                .unwrap_or_else(|| instant.duration_since(round_trip));
+            assert!(difference < Duration::from_secs(1));
+        }
+    }
+}
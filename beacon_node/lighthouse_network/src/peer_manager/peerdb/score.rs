@@ -154,6 +154,18 @@ impl Default for RealScore {
 }
 
 impl RealScore {
+    /// Restores a persisted lighthouse score. Time-based adjustments resume from `last_updated`,
+    /// which is in the future if the peer's ban has not yet started to decay.
+    fn restore(lighthouse_score: f64, last_updated: Instant) -> Self {
+        let mut score = RealScore {
+            lighthouse_score: lighthouse_score.clamp(MIN_SCORE, MAX_SCORE),
+            last_updated,
+            ..Default::default()
+        };
+        score.recompute_score();
+        score
+    }
+
     /// Access to the underlying score.
     fn recompute_score(&mut self) {
         self.score = self.lighthouse_score;
@@ -180,6 +192,17 @@ impl RealScore {
         }
     }
 
+    /// Bans the peer for `duration`, after which its score decays as usual.
+    pub fn ban_for(&mut self, duration: Duration) {
+        self.set_lighthouse_score(MIN_SCORE);
+        self.last_updated = Instant::now() + duration;
+    }
+
+    /// Resets the score to its default, lifting any ban.
+    pub fn reset(&mut self) {
+        *self = RealScore::default();
+    }
+
     fn set_lighthouse_score(&mut self, new_score: f64) {
         self.lighthouse_score = new_score;
         self.update_state();
@@ -291,6 +314,8 @@ macro_rules! apply {
 apply!(apply_peer_action, peer_action: PeerAction);
 apply!(update);
 apply!(update_gossipsub_score, new_score: f64, ignore: bool);
+apply!(ban_for, duration: Duration);
+apply!(reset);
 #[cfg(test)]
 apply!(test_add, score: f64);
 #[cfg(test)]
@@ -310,6 +335,28 @@ impl Score {
         Self::Max
     }
 
+    /// Restores a persisted lighthouse score. See `RealScore::restore`.
+    pub(crate) fn restore(lighthouse_score: f64, last_updated: Instant) -> Self {
+        Self::Real(RealScore::restore(lighthouse_score, last_updated))
+    }
+
+    /// The lighthouse component of the score, which excludes the gossipsub score.
+    pub fn lighthouse_score(&self) -> f64 {
+        match self {
+            Self::Max => MAX_SCORE,
+            Self::Real(score) => score.lighthouse_score,
+        }
+    }
+
+    /// The time that time-based adjustments such as score decay were last applied. For a banned
+    /// peer this is in the future until its score starts to decay.
+    pub(crate) fn last_updated(&self) -> Option<Instant> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.last_updated),
+        }
+    }
+
     /// Returns the expected state of the peer given it's score.
     pub(crate) fn state(&self) -> ScoreState {
         match self.score() {
@@ -400,6 +447,36 @@ mod tests {
         assert!(score.score() > MIN_SCORE_BEFORE_BAN);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_restore_ban() {
+        let now = Instant::now();
+
+        // A ban that has not yet started to decay is kept in place.
+        let mut score = RealScore::restore(MIN_SCORE, now + Duration::from_secs(60));
+        assert_eq!(score.score(), MIN_SCORE);
+        score.update_at(now + Duration::from_secs(60));
+        assert_eq!(score.score(), MIN_SCORE);
+        score.update_at(now + Duration::from_secs(61));
+        assert!(score.score() > MIN_SCORE);
+
+        // A ban that expired a long time ago decays away on the next update.
+        let mut score = RealScore::restore(MIN_SCORE, now);
+        score.update_at(now + Duration::from_secs(SCORE_HALFLIFE as u64 * 2));
+        assert!(score.score() > MIN_SCORE_BEFORE_BAN);
+    }
+
+    #[test]
+    fn test_ban_for() {
+        let mut score = Score::default();
+        score.ban_for(Duration::from_secs(60));
+        assert_eq!(score.state(), ScoreState::Banned);
+        assert!(score.last_updated().unwrap() > Instant::now());
+
+        score.reset();
+        assert_eq!(score.state(), ScoreState::Healthy);
+    }
+
     #[test]
     fn test_very_negative_gossipsub_score() {
         let mut score = Score::default();
@@ -4,7 +4,7 @@ use std::{
     time::Duration,
 };
 
-use super::{rate_limiter::Quota, Protocol};
+use super::{methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES, rate_limiter::Quota, Protocol};
 
 use serde::{Deserialize, Serialize};
 
@@ -94,6 +94,7 @@ pub struct RateLimiterConfig {
     pub(super) light_client_bootstrap_quota: Quota,
     pub(super) light_client_optimistic_update_quota: Quota,
     pub(super) light_client_finality_update_quota: Quota,
+    pub(super) light_client_updates_by_range_quota: Quota,
 }
 
 impl RateLimiterConfig {
@@ -108,6 +109,8 @@ impl RateLimiterConfig {
     pub const DEFAULT_LIGHT_CLIENT_BOOTSTRAP_QUOTA: Quota = Quota::one_every(10);
     pub const DEFAULT_LIGHT_CLIENT_OPTIMISTIC_UPDATE_QUOTA: Quota = Quota::one_every(10);
     pub const DEFAULT_LIGHT_CLIENT_FINALITY_UPDATE_QUOTA: Quota = Quota::one_every(10);
+    pub const DEFAULT_LIGHT_CLIENT_UPDATES_BY_RANGE_QUOTA: Quota =
+        Quota::n_every(MAX_REQUEST_LIGHT_CLIENT_UPDATES, 10);
 }
 
 impl Default for RateLimiterConfig {
@@ -125,6 +128,7 @@ impl Default for RateLimiterConfig {
             light_client_optimistic_update_quota:
                 Self::DEFAULT_LIGHT_CLIENT_OPTIMISTIC_UPDATE_QUOTA,
             light_client_finality_update_quota: Self::DEFAULT_LIGHT_CLIENT_FINALITY_UPDATE_QUOTA,
+            light_client_updates_by_range_quota: Self::DEFAULT_LIGHT_CLIENT_UPDATES_BY_RANGE_QUOTA,
         }
     }
 }
@@ -150,6 +154,10 @@ impl Debug for RateLimiterConfig {
             .field("blocks_by_root", fmt_q!(&self.blocks_by_root_quota))
             .field("blobs_by_range", fmt_q!(&self.blobs_by_range_quota))
             .field("blobs_by_root", fmt_q!(&self.blobs_by_root_quota))
+            .field(
+                "light_client_updates_by_range",
+                fmt_q!(&self.light_client_updates_by_range_quota),
+            )
             .finish()
     }
 }
@@ -173,6 +181,7 @@ impl FromStr for RateLimiterConfig {
         let mut light_client_bootstrap_quota = None;
         let mut light_client_optimistic_update_quota = None;
         let mut light_client_finality_update_quota = None;
+        let mut light_client_updates_by_range_quota = None;
 
         for proto_def in s.split(';') {
             let ProtocolQuota { protocol, quota } = proto_def.parse()?;
@@ -197,6 +206,10 @@ impl FromStr for RateLimiterConfig {
                     light_client_finality_update_quota =
                         light_client_finality_update_quota.or(quota)
                 }
+                Protocol::LightClientUpdatesByRange => {
+                    light_client_updates_by_range_quota =
+                        light_client_updates_by_range_quota.or(quota)
+                }
             }
         }
         Ok(RateLimiterConfig {
@@ -217,6 +230,8 @@ impl FromStr for RateLimiterConfig {
                 .unwrap_or(Self::DEFAULT_LIGHT_CLIENT_OPTIMISTIC_UPDATE_QUOTA),
             light_client_finality_update_quota: light_client_finality_update_quota
                 .unwrap_or(Self::DEFAULT_LIGHT_CLIENT_FINALITY_UPDATE_QUOTA),
+            light_client_updates_by_range_quota: light_client_updates_by_range_quota
+                .unwrap_or(Self::DEFAULT_LIGHT_CLIENT_UPDATES_BY_RANGE_QUOTA),
         })
     }
 }
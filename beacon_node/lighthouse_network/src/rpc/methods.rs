@@ -15,14 +15,17 @@ use superstruct::superstruct;
 use types::blob_sidecar::BlobIdentifier;
 use types::{
     blob_sidecar::BlobSidecar, ChainSpec, Epoch, EthSpec, Hash256, LightClientBootstrap,
-    LightClientFinalityUpdate, LightClientOptimisticUpdate, RuntimeVariableList, SignedBeaconBlock,
-    Slot,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, RuntimeVariableList,
+    SignedBeaconBlock, Slot,
 };
 
 /// Maximum length of error message.
 pub type MaxErrorLen = U256;
 pub const MAX_ERROR_LEN: u64 = 256;
 
+/// Maximum number of light client updates served in response to a single request.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
 /// Wrapper over SSZ List to represent error message in rpc responses.
 #[derive(Debug, Clone)]
 pub struct ErrorType(pub VariableList<u8, MaxErrorLen>);
@@ -397,6 +400,9 @@ pub enum RPCResponse<E: EthSpec> {
     /// A response to a get LIGHT_CLIENT_FINALITY_UPDATE request.
     LightClientFinalityUpdate(Arc<LightClientFinalityUpdate<E>>),
 
+    /// A response to a get LIGHT_CLIENT_UPDATES_BY_RANGE request.
+    LightClientUpdatesByRange(Arc<LightClientUpdate<E>>),
+
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Arc<BlobSidecar<E>>),
 
@@ -421,6 +427,9 @@ pub enum ResponseTermination {
 
     /// Blobs by root stream termination.
     BlobsByRoot,
+
+    /// Light client updates by range stream termination.
+    LightClientUpdatesByRange,
 }
 
 /// The structured response containing a result/code indicating success or failure
@@ -442,6 +451,22 @@ pub struct LightClientBootstrapRequest {
     pub root: Hash256,
 }
 
+/// Request the best light client updates of a range of sync committee periods.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+pub struct LightClientUpdatesByRangeRequest {
+    /// The first sync committee period to request an update for.
+    pub start_period: u64,
+
+    /// The number of sync committee periods from the start period.
+    pub count: u64,
+}
+
+impl LightClientUpdatesByRangeRequest {
+    pub fn max_requested(&self) -> u64 {
+        std::cmp::min(self.count, MAX_REQUEST_LIGHT_CLIENT_UPDATES)
+    }
+}
+
 /// The code assigned to an erroneous `RPCResponse`.
 #[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
@@ -516,6 +541,7 @@ impl<E: EthSpec> RPCResponse<E> {
             RPCResponse::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
             RPCResponse::LightClientOptimisticUpdate(_) => Protocol::LightClientOptimisticUpdate,
             RPCResponse::LightClientFinalityUpdate(_) => Protocol::LightClientFinalityUpdate,
+            RPCResponse::LightClientUpdatesByRange(_) => Protocol::LightClientUpdatesByRange,
         }
     }
 }
@@ -575,6 +601,13 @@ impl<E: EthSpec> std::fmt::Display for RPCResponse<E> {
                     update.signature_slot()
                 )
             }
+            RPCResponse::LightClientUpdatesByRange(update) => {
+                write!(
+                    f,
+                    "LightClientUpdatesByRange Slot: {}",
+                    update.signature_slot()
+                )
+            }
         }
     }
 }
@@ -648,6 +681,16 @@ impl std::fmt::Display for BlobsByRangeRequest {
     }
 }
 
+impl std::fmt::Display for LightClientUpdatesByRangeRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Request: LightClientUpdatesByRange: Start Period: {}, Count: {}",
+            self.start_period, self.count
+        )
+    }
+}
+
 impl slog::KV for StatusMessage {
     fn serialize(
         &self,
@@ -103,6 +103,8 @@ pub struct RPCRateLimiter {
     lc_optimistic_update_rl: Limiter<PeerId>,
     /// LightClientFinalityUpdate rate limiter.
     lc_finality_update_rl: Limiter<PeerId>,
+    /// LightClientUpdatesByRange rate limiter.
+    lc_updates_by_range_rl: Limiter<PeerId>,
 }
 
 /// Error type for non conformant requests
@@ -139,6 +141,8 @@ pub struct RPCRateLimiterBuilder {
     lc_optimistic_update_quota: Option<Quota>,
     /// Quota for the LightClientOptimisticUpdate protocol.
     lc_finality_update_quota: Option<Quota>,
+    /// Quota for the LightClientUpdatesByRange protocol.
+    lc_updates_by_range_quota: Option<Quota>,
 }
 
 impl RPCRateLimiterBuilder {
@@ -157,6 +161,7 @@ impl RPCRateLimiterBuilder {
             Protocol::LightClientBootstrap => self.lcbootstrap_quota = q,
             Protocol::LightClientOptimisticUpdate => self.lc_optimistic_update_quota = q,
             Protocol::LightClientFinalityUpdate => self.lc_finality_update_quota = q,
+            Protocol::LightClientUpdatesByRange => self.lc_updates_by_range_quota = q,
         }
         self
     }
@@ -182,6 +187,9 @@ impl RPCRateLimiterBuilder {
         let lc_finality_update_quota = self
             .lc_finality_update_quota
             .ok_or("LightClientFinalityUpdate quota not specified")?;
+        let lc_updates_by_range_quota = self
+            .lc_updates_by_range_quota
+            .ok_or("LightClientUpdatesByRange quota not specified")?;
 
         let blbrange_quota = self
             .blbrange_quota
@@ -203,6 +211,7 @@ impl RPCRateLimiterBuilder {
         let lc_bootstrap_rl = Limiter::from_quota(lc_bootstrap_quota)?;
         let lc_optimistic_update_rl = Limiter::from_quota(lc_optimistic_update_quota)?;
         let lc_finality_update_rl = Limiter::from_quota(lc_finality_update_quota)?;
+        let lc_updates_by_range_rl = Limiter::from_quota(lc_updates_by_range_quota)?;
 
         // check for peers to prune every 30 seconds, starting in 30 seconds
         let prune_every = tokio::time::Duration::from_secs(30);
@@ -221,6 +230,7 @@ impl RPCRateLimiterBuilder {
             lc_bootstrap_rl,
             lc_optimistic_update_rl,
             lc_finality_update_rl,
+            lc_updates_by_range_rl,
             init_time: Instant::now(),
         })
     }
@@ -265,6 +275,7 @@ impl RPCRateLimiter {
             light_client_bootstrap_quota,
             light_client_optimistic_update_quota,
             light_client_finality_update_quota,
+            light_client_updates_by_range_quota,
         } = config;
 
         Self::builder()
@@ -285,6 +296,10 @@ impl RPCRateLimiter {
                 Protocol::LightClientFinalityUpdate,
                 light_client_finality_update_quota,
             )
+            .set_quota(
+                Protocol::LightClientUpdatesByRange,
+                light_client_updates_by_range_quota,
+            )
             .build()
     }
 
@@ -315,6 +330,7 @@ impl RPCRateLimiter {
             Protocol::LightClientBootstrap => &mut self.lc_bootstrap_rl,
             Protocol::LightClientOptimisticUpdate => &mut self.lc_optimistic_update_rl,
             Protocol::LightClientFinalityUpdate => &mut self.lc_finality_update_rl,
+            Protocol::LightClientUpdatesByRange => &mut self.lc_updates_by_range_rl,
         };
         check(limiter)
     }
@@ -3,15 +3,16 @@ use std::sync::Arc;
 use libp2p::swarm::ConnectionId;
 use types::{
     BlobSidecar, EthSpec, LightClientBootstrap, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, SignedBeaconBlock,
+    LightClientOptimisticUpdate, LightClientUpdate, SignedBeaconBlock,
 };
 
 use crate::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
 use crate::rpc::{
     methods::{
         BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
-        OldBlocksByRangeRequest, OldBlocksByRangeRequestV1, OldBlocksByRangeRequestV2,
-        RPCCodedResponse, RPCResponse, ResponseTermination, StatusMessage,
+        LightClientUpdatesByRangeRequest, OldBlocksByRangeRequest, OldBlocksByRangeRequestV1,
+        OldBlocksByRangeRequestV2, RPCCodedResponse, RPCResponse, ResponseTermination,
+        StatusMessage,
     },
     OutboundRequest, SubstreamId,
 };
@@ -47,6 +48,8 @@ pub enum Request {
     LightClientOptimisticUpdate,
     // light client finality update request
     LightClientFinalityUpdate,
+    // light client updates by range request
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
     /// A request blobs root request.
     BlobsByRoot(BlobsByRootRequest),
 }
@@ -73,7 +76,8 @@ impl<E: EthSpec> std::convert::From<Request> for OutboundRequest<E> {
             },
             Request::LightClientBootstrap(_)
             | Request::LightClientOptimisticUpdate
-            | Request::LightClientFinalityUpdate => {
+            | Request::LightClientFinalityUpdate
+            | Request::LightClientUpdatesByRange(_) => {
                 unreachable!("Lighthouse never makes an outbound light client request")
             }
             Request::BlobsByRange(r) => OutboundRequest::BlobsByRange(r),
@@ -107,6 +111,9 @@ pub enum Response<E: EthSpec> {
     LightClientOptimisticUpdate(Arc<LightClientOptimisticUpdate<E>>),
     /// A response to a LightClientFinalityUpdate request.
     LightClientFinalityUpdate(Arc<LightClientFinalityUpdate<E>>),
+    /// A response to a LightClientUpdatesByRange request. A None response signals the end of the
+    /// batch.
+    LightClientUpdatesByRange(Option<Arc<LightClientUpdate<E>>>),
 }
 
 impl<E: EthSpec> std::convert::From<Response<E>> for RPCCodedResponse<E> {
@@ -138,6 +145,12 @@ impl<E: EthSpec> std::convert::From<Response<E>> for RPCCodedResponse<E> {
             Response::LightClientFinalityUpdate(f) => {
                 RPCCodedResponse::Success(RPCResponse::LightClientFinalityUpdate(f))
             }
+            Response::LightClientUpdatesByRange(r) => match r {
+                Some(u) => RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(u)),
+                None => RPCCodedResponse::StreamTermination(
+                    ResponseTermination::LightClientUpdatesByRange,
+                ),
+            },
         }
     }
 }
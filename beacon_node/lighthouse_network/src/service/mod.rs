@@ -1195,6 +1195,10 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                 &metrics::TOTAL_RPC_REQUESTS,
                 &["light_client_finality_update"],
             ),
+            Request::LightClientUpdatesByRange(_) => metrics::inc_counter_vec(
+                &metrics::TOTAL_RPC_REQUESTS,
+                &["light_client_updates_by_range"],
+            ),
             Request::BlocksByRange { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_range"])
             }
@@ -1553,6 +1557,14 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                         );
                         Some(event)
                     }
+                    InboundRequest::LightClientUpdatesByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::LightClientUpdatesByRange(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             HandlerEvent::Ok(RPCReceived::Response(id, resp)) => {
@@ -1600,6 +1612,11 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                         peer_id,
                         Response::LightClientFinalityUpdate(update),
                     ),
+                    RPCResponse::LightClientUpdatesByRange(update) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::LightClientUpdatesByRange(Some(update)),
+                    ),
                 }
             }
             HandlerEvent::Ok(RPCReceived::EndOfStream(id, termination)) => {
@@ -1608,6 +1625,9 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
                     ResponseTermination::BlobsByRange => Response::BlobsByRange(None),
                     ResponseTermination::BlobsByRoot => Response::BlobsByRoot(None),
+                    ResponseTermination::LightClientUpdatesByRange => {
+                        Response::LightClientUpdatesByRange(None)
+                    }
                 };
                 self.build_response(id, peer_id, response)
             }
@@ -8,6 +8,7 @@ mod metrics;
 mod nat;
 mod network_beacon_processor;
 mod persisted_dht;
+mod persisted_reputation;
 mod router;
 mod status;
 mod subnet_service;
@@ -6,6 +6,7 @@ use crate::{
 };
 use beacon_chain::blob_verification::{GossipBlobError, GossipVerifiedBlob};
 use beacon_chain::block_verification_types::AsBlock;
+use beacon_chain::fetch_blobs::{fetch_and_process_engine_blobs, FetchEngineBlobError};
 use beacon_chain::store::Error;
 use beacon_chain::{
     attestation_verification::{self, Error as AttnError, VerifiedAttestation},
@@ -18,7 +19,9 @@ use beacon_chain::{
     AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
     GossipVerifiedBlock, NotifyExecutionLayer,
 };
-use lighthouse_network::{Client, MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource};
+use lighthouse_network::{
+    Client, MessageAcceptance, MessageId, PeerAction, PeerId, PubsubMessage, ReportSource,
+};
 use operation_pool::ReceivedPreCapella;
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
@@ -1174,6 +1177,21 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         let block = verified_block.block.block_cloned();
         let block_root = verified_block.block_root;
 
+        // Fetch any of the block's blobs that are already in the EL's mempool while the block is
+        // being processed, rather than waiting for them all to arrive on gossip.
+        if block.num_expected_blobs() > 0 {
+            let processor = self.clone();
+            let block = block.clone();
+            self.executor.spawn(
+                async move {
+                    processor
+                        .fetch_engine_blobs_and_publish(block, block_root)
+                        .await
+                },
+                "fetch_engine_blobs",
+            );
+        }
+
         // TODO(block source)
 
         let result = self
@@ -1309,6 +1327,61 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         });
     }
 
+    /// Fetches any of the blobs for `block` that are already in the EL's mempool and processes
+    /// them, publishing those that haven't been seen on gossip yet.
+    async fn fetch_engine_blobs_and_publish(
+        self: &Arc<Self>,
+        block: Arc<SignedBeaconBlock<T::EthSpec>>,
+        block_root: Hash256,
+    ) {
+        let processor = self.clone();
+        let publish_fn = move |blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>| {
+            processor.send_network_message(NetworkMessage::Publish {
+                messages: blobs
+                    .into_iter()
+                    .map(|blob| PubsubMessage::BlobSidecar(Box::new((blob.index, blob))))
+                    .collect(),
+            })
+        };
+
+        match fetch_and_process_engine_blobs(&self.chain, block_root, block, publish_fn).await {
+            Ok(Some(AvailabilityProcessingStatus::Imported(block_root))) => {
+                // Note: Reusing block imported metric here
+                metrics::inc_counter(&metrics::BEACON_PROCESSOR_GOSSIP_BLOCK_IMPORTED_TOTAL);
+                debug!(
+                    self.log,
+                    "Blobs fetched from the EL, imported fully available block";
+                    "block_root" => %block_root
+                );
+                self.chain.recompute_head_at_current_slot().await;
+            }
+            Ok(Some(AvailabilityProcessingStatus::MissingComponents(slot, block_root))) => {
+                trace!(
+                    self.log,
+                    "Processed blobs fetched from the EL, waiting for other components";
+                    "slot" => %slot,
+                    "block_root" => %block_root,
+                );
+            }
+            Ok(None) => {}
+            Err(FetchEngineBlobError::BlobProcessingError(BlockError::BlockIsAlreadyKnown(_))) => {
+                debug!(
+                    self.log,
+                    "Ignoring blobs fetched from the EL for imported block";
+                    "block_root" => %block_root,
+                );
+            }
+            Err(e) => {
+                warn!(
+                    self.log,
+                    "Unable to fetch blobs from the EL";
+                    "block_root" => %block_root,
+                    "error" => ?e,
+                );
+            }
+        }
+    }
+
     pub fn process_gossip_voluntary_exit(
         self: &Arc<Self>,
         message_id: MessageId,
@@ -10,7 +10,10 @@ use beacon_processor::{
 };
 use lighthouse_network::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
 use lighthouse_network::{
-    rpc::{BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest, StatusMessage},
+    rpc::{
+        BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
+        LightClientUpdatesByRangeRequest, StatusMessage,
+    },
     Client, MessageId, NetworkGlobals, PeerId, PeerRequestId,
 };
 use slog::{debug, Logger};
@@ -620,6 +623,23 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to process a `LightClientUpdatesByRange` request from the RPC network.
+    pub fn send_light_client_updates_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: LightClientUpdatesByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            move || processor.handle_light_client_updates_by_range(peer_id, request_id, request);
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: true,
+            work: Work::LightClientUpdatesByRangeRequest(Box::new(process_fn)),
+        })
+    }
+
     /// Send a message to `sync_tx`.
     ///
     /// Creates a log if there is an internal error.
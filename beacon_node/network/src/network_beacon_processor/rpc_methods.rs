@@ -394,6 +394,70 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         );
     }
 
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    pub fn handle_light_client_updates_by_range(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: LightClientUpdatesByRangeRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            request_id,
+            self.handle_light_client_updates_by_range_inner(peer_id, request_id, req),
+            Response::LightClientUpdatesByRange,
+        );
+    }
+
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    fn handle_light_client_updates_by_range_inner(
+        &self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: LightClientUpdatesByRangeRequest,
+    ) -> Result<(), (RPCResponseErrorCode, &'static str)> {
+        debug!(self.log, "Received LightClientUpdatesByRange Request";
+            "peer_id" => %peer_id,
+            "count" => req.count,
+            "start_period" => req.start_period,
+        );
+
+        let updates = self
+            .chain
+            .get_light_client_updates(req.start_period, req.max_requested())
+            .map_err(|e| {
+                error!(self.log, "Error getting light client updates";
+                    "request" => ?req,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                (
+                    RPCResponseErrorCode::ServerError,
+                    "Failed fetching light client updates",
+                )
+            })?;
+
+        let updates_sent = updates.len();
+        for update in updates {
+            self.send_network_message(NetworkMessage::SendResponse {
+                peer_id,
+                response: Response::LightClientUpdatesByRange(Some(Arc::new(update))),
+                id: request_id,
+            });
+        }
+
+        debug!(
+            self.log,
+            "LightClientUpdatesByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_period" => req.start_period,
+            "requested" => req.count,
+            "returned" => updates_sent
+        );
+
+        Ok(())
+    }
+
     /// Handle a `BlocksByRange` request from the peer.
     pub async fn handle_blocks_by_range_request(
         self: Arc<Self>,
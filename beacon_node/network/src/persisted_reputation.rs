@@ -0,0 +1,214 @@
+use lighthouse_network::{ClientKind, Enr, Multiaddr, PeerBan, PeerId, PeerReputation};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeerReputations`. All zero because
+/// `PersistedPeerReputations` has its own column.
+pub const PEER_REPUTATION_DB_KEY: Hash256 = Hash256::zero();
+
+/// Loads the peer reputations persisted by a previous run. Reputations that can't be decoded are
+/// skipped.
+pub fn load_peer_reputations<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Vec<PeerReputation> {
+    match store.get_item(&PEER_REPUTATION_DB_KEY) {
+        Ok(Some(p)) => {
+            let p: PersistedPeerReputations = p;
+            p.reputations
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the peer reputations to `store`, replacing any previously persisted
+/// reputations.
+pub fn persist_peer_reputations<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    reputations: Vec<PeerReputation>,
+) -> Result<(), store::Error> {
+    store.put_item(
+        &PEER_REPUTATION_DB_KEY,
+        &PersistedPeerReputations { reputations },
+    )
+}
+
+/// Wrapper around the peer reputations for persistence to disk.
+pub struct PersistedPeerReputations {
+    pub reputations: Vec<PeerReputation>,
+}
+
+impl StoreItem for PersistedPeerReputations {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerReputation
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.reputations
+            .iter()
+            .map(SszPeerReputation::from)
+            .collect::<Vec<_>>()
+            .as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let reputations = Vec::<SszPeerReputation>::from_ssz_bytes(bytes)?
+            .into_iter()
+            .filter_map(SszPeerReputation::into_reputation)
+            .collect();
+        Ok(PersistedPeerReputations { reputations })
+    }
+}
+
+/// The on-disk representation of a `PeerReputation`.
+#[derive(Encode, Decode)]
+struct SszPeerReputation {
+    peer_id: Vec<u8>,
+    /// The bits of the `f64` score, since SSZ has no floating point types.
+    score: u64,
+    /// The bits of the `f64` lighthouse score.
+    lighthouse_score: u64,
+    ban: Option<SszPeerBan>,
+    client_kind: Vec<u8>,
+    useful_sync_batches: u64,
+    /// The RLP encoded ENR, empty if unknown.
+    enr: Vec<u8>,
+    seen_multiaddrs: Vec<Vec<u8>>,
+    /// Seconds since the UNIX epoch.
+    recorded_at: u64,
+}
+
+#[derive(Encode, Decode)]
+struct SszPeerBan {
+    reason: Vec<u8>,
+    /// Seconds since the UNIX epoch.
+    expires_at: u64,
+}
+
+impl From<&PeerReputation> for SszPeerReputation {
+    fn from(reputation: &PeerReputation) -> Self {
+        SszPeerReputation {
+            peer_id: reputation.peer_id.to_bytes(),
+            score: reputation.score.to_bits(),
+            lighthouse_score: reputation.lighthouse_score.to_bits(),
+            ban: reputation.ban.as_ref().map(|ban| SszPeerBan {
+                reason: ban.reason.as_bytes().to_vec(),
+                expires_at: to_unix_secs(ban.expires_at),
+            }),
+            client_kind: reputation.client_kind.as_ref().as_bytes().to_vec(),
+            useful_sync_batches: reputation.useful_sync_batches,
+            enr: reputation
+                .enr
+                .as_ref()
+                .map(|enr| rlp::encode(enr).to_vec())
+                .unwrap_or_default(),
+            seen_multiaddrs: reputation
+                .seen_multiaddrs
+                .iter()
+                .map(|multiaddr| multiaddr.to_vec())
+                .collect(),
+            recorded_at: to_unix_secs(reputation.recorded_at),
+        }
+    }
+}
+
+impl SszPeerReputation {
+    /// Converts to a `PeerReputation`, returning `None` if the peer id can't be decoded. Any other
+    /// fields that can't be decoded are dropped.
+    fn into_reputation(self) -> Option<PeerReputation> {
+        let peer_id = PeerId::from_bytes(&self.peer_id).ok()?;
+        let client_kind = std::str::from_utf8(&self.client_kind)
+            .ok()
+            .and_then(|kind| ClientKind::from_str(kind).ok())
+            .unwrap_or(ClientKind::Unknown);
+        let enr = (!self.enr.is_empty())
+            .then(|| rlp::decode::<Enr>(&self.enr).ok())
+            .flatten();
+        let seen_multiaddrs = self
+            .seen_multiaddrs
+            .into_iter()
+            .filter_map(|bytes| Multiaddr::try_from(bytes).ok())
+            .collect();
+
+        Some(PeerReputation {
+            peer_id,
+            score: f64::from_bits(self.score),
+            lighthouse_score: f64::from_bits(self.lighthouse_score),
+            ban: self.ban.map(|ban| PeerBan {
+                reason: String::from_utf8_lossy(&ban.reason).into_owned(),
+                expires_at: from_unix_secs(ban.expires_at),
+            }),
+            client_kind,
+            useful_sync_batches: self.useful_sync_batches,
+            enr,
+            seen_multiaddrs,
+            recorded_at: from_unix_secs(self.recorded_at),
+        })
+    }
+}
+
+fn to_unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn from_unix_secs(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::MemoryStore;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peer_reputations() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+
+        let enr = Enr::from_str("enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8").unwrap();
+        let recorded_at = from_unix_secs(1_700_000_000);
+        let reputations = vec![
+            PeerReputation {
+                peer_id: PeerId::random(),
+                score: 5.5,
+                lighthouse_score: -1.25,
+                ban: None,
+                client_kind: ClientKind::Teku,
+                useful_sync_batches: 42,
+                enr: Some(enr),
+                seen_multiaddrs: vec!["/ip4/127.0.0.1/tcp/9000".parse().unwrap()],
+                recorded_at,
+            },
+            PeerReputation {
+                peer_id: PeerId::random(),
+                score: -100.0,
+                lighthouse_score: -100.0,
+                ban: Some(PeerBan {
+                    reason: "goodbye_peer".to_string(),
+                    expires_at: recorded_at + Duration::from_secs(3600),
+                }),
+                client_kind: ClientKind::Unknown,
+                useful_sync_batches: 0,
+                enr: None,
+                seen_multiaddrs: vec![],
+                recorded_at,
+            },
+        ];
+
+        persist_peer_reputations(store.clone(), reputations.clone()).unwrap();
+        assert_eq!(load_peer_reputations(store), reputations);
+    }
+}
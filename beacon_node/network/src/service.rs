@@ -2,6 +2,7 @@ use super::sync::manager::RequestId as SyncId;
 use crate::nat;
 use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_reputation::{load_peer_reputations, persist_peer_reputations};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -20,7 +21,8 @@ use lighthouse_network::types::GossipKind;
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
     rpc::{GoodbyeReason, RPCResponseErrorCode},
-    Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request, Response, Subnet,
+    Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, ReputationOverride, Request,
+    Response, Subnet,
 };
 use lighthouse_network::{
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
@@ -108,6 +110,11 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Manually override the reputation of a peer.
+    OverridePeerReputation {
+        peer_id: PeerId,
+        reputation_override: ReputationOverride,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -299,6 +306,16 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the reputations of known peers, so that bans survive restarts and useful peers
+        // are reconnected to.
+        let reputations =
+            load_peer_reputations::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
+        debug!(
+            network_log,
+            "Restoring peer reputations"; "peers" => reputations.len()
+        );
+        libp2p.peer_manager_mut().restore_reputations(reputations);
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -680,6 +697,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::OverridePeerReputation {
+                peer_id,
+                reputation_override,
+            } => self
+                .libp2p
+                .peer_manager_mut()
+                .override_reputation(&peer_id, reputation_override),
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -996,6 +1020,23 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
                 "Saved DHT state";
             ),
         }
+
+        let reputations = self.network_globals.peers.read().reputations();
+        debug!(
+            self.log,
+            "Persisting peer reputations to store";
+            "Number of peers" => reputations.len(),
+        );
+        if let Err(e) = persist_peer_reputations::<T::EthSpec, T::HotStore, T::ColdStore>(
+            self.store.clone(),
+            reputations,
+        ) {
+            error!(
+                self.log,
+                "Failed to persist peer reputations on drop";
+                "error" => ?e
+            );
+        }
         info!(self.log, "Network service shutdown");
     }
 }
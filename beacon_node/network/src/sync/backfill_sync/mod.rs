@@ -627,6 +627,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                 // If the processed batch was not empty, we can validate previous unvalidated
                 // blocks.
                 if *was_non_empty {
+                    network.record_useful_batch(&peer);
                     self.advance_chain(network, batch_id);
                 }
 
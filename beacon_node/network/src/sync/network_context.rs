@@ -231,6 +231,15 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             .unwrap_or_default()
     }
 
+    /// Records that a peer served a batch which was successfully processed, so that the peer is
+    /// preferred when reconnecting after a restart.
+    pub fn record_useful_batch(&self, peer_id: &PeerId) {
+        self.network_globals()
+            .peers
+            .write()
+            .record_useful_sync_batch(peer_id);
+    }
+
     pub fn status_peers<C: ToStatusMessage>(&self, chain: &C, peers: impl Iterator<Item = PeerId>) {
         let status_message = chain.status_message();
         for peer_id in peers {
@@ -478,6 +478,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 batch.processing_completed(BatchProcessingResult::Success)?;
 
                 if *was_non_empty {
+                    network.record_useful_batch(&peer);
                     // If the processed batch was not empty, we can validate previous unvalidated
                     // blocks.
                     self.advance_chain(network, batch_id);
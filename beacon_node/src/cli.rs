@@ -488,6 +488,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-backup-dir")
+                .long("http-backup-dir")
+                .requires("enable_http")
+                .value_name("DIR")
+                .help("Directory that database backups requested via the HTTP API are written \
+                       beneath. Requests may only name a relative path within this directory. \
+                       Defaults to the `backups` directory within the data directory.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-spec-fork")
                 .long("http-spec-fork")
@@ -728,6 +739,51 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help("Store freezer DB restore points as hierarchical state diffs. Takes a \
+                       comma-separated list of strictly increasing exponents, each of which \
+                       defines a layer of states stored every 2^exponent slots. Restore points \
+                       are stored at the smallest layer and full states only at the largest. \
+                       Cannot be changed after initialization without running \
+                       `lighthouse db migrate`. [example: 5,9,11,13,16,18,21]")
+                .action(ArgAction::Set)
+                .conflicts_with("slots-per-restore-point")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node. Cannot be changed \
+                       after initialization without copying the database with \
+                       `lighthouse db copy-backend`.")
+                .action(ArgAction::Set)
+                .value_parser(store::DatabaseBackend::VARIANTS.to_vec())
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("db-compression-level")
+                .long("db-compression-level")
+                .value_name("LEVEL")
+                .help("Compress newly written database values with zstd at the given level. \
+                       Values that are already stored are readable regardless of this setting, \
+                       so compression can be enabled or disabled at any time.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("db-compression-columns")
+                .long("db-compression-columns")
+                .value_name("TAGS")
+                .help("Comma-separated list of 3-byte database column tags whose values should \
+                       be compressed. [default: ste,blk,exp]")
+                .action(ArgAction::Set)
+                .requires("db-compression-level")
+                .display_order(0)
+        )
         .arg(
             Arg::new("epochs-per-migration")
                 .long("epochs-per-migration")
@@ -739,6 +795,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("epochs-per-fork-sweep")
+                .long("epochs-per-fork-sweep")
+                .value_name("N")
+                .help("The number of epochs to wait between sweeps of the hot DB for blocks and \
+                       states from abandoned forks, which are deleted. Deletions are recorded in a \
+                       log available from the HTTP API. Set to 0 to disable")
+                .default_value("8")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("block-cache-size")
                 .long("block-cache-size")
@@ -776,7 +843,11 @@ pub fn cli_app() -> Command {
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
                        JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       deposit cache. Additional comma-separated endpoints are used as \
+                       fallbacks, in order, if the first endpoint goes offline. All \
+                       endpoints must share the same JWT secret. An execution node on the \
+                       same host may instead be reached over its IPC socket using an ipc:// \
+                       URL, e.g. ipc:///var/lib/geth/geth.ipc, in which case no JWT is sent.")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -1115,6 +1186,18 @@ pub fn cli_app() -> Command {
                 .requires("slasher")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slasher-migrate-from")
+                .long("slasher-migrate-from")
+                .value_name("DATABASE")
+                .help("Copy the slasher's history from the database of another backend when \
+                       the database for --slasher-backend is empty. Both databases must be in \
+                       the slasher directory.")
+                .action(ArgAction::Set)
+                .value_parser(slasher::DatabaseBackend::VARIANTS.to_vec())
+                .requires("slasher")
+                .display_order(0)
+        )
         .arg(
             Arg::new("wss-checkpoint")
                 .long("wss-checkpoint")
@@ -1248,6 +1331,16 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("disable-publish-engine-blobs")
+                .long("disable-publish-engine-blobs")
+                .help("Do not publish blobs fetched from the execution node's mempool on gossip. \
+                       By default, blobs for a gossip block that are fetched from the execution \
+                       node are published if they have not already been seen on gossip.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-proposer-reorgs")
                 .long("disable-proposer-reorgs")
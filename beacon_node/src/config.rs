@@ -27,6 +27,8 @@ use std::num::NonZeroU16;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
+use store::compression::{CompressionConfig, DEFAULT_COMPRESSED_COLUMNS};
+use store::hdiff::HierarchyConfig;
 use types::graffiti::GraffitiString;
 use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
 
@@ -129,6 +131,9 @@ pub fn get_config<E: EthSpec>(
             client_config.http_api.allow_origin = Some(allow_origin.to_string());
         }
 
+        client_config.http_api.backup_dir =
+            clap_utils::parse_optional(cli_args, "http-backup-dir")?;
+
         if cli_args.get_one::<String>("http-spec-fork").is_some() {
             warn!(
                 log,
@@ -287,12 +292,21 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // Parse the execution endpoints. The first endpoint is the primary, the others are used
+        // as fallbacks in the order they are supplied.
+        let mut execution_endpoints = endpoints
+            .split(',')
+            .map(SensitiveUrl::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("--execution-endpoint contains an invalid value {:?}", e))?
+            .into_iter();
+        let execution_endpoint = execution_endpoints
+            .next()
+            .ok_or("Must provide at least one value to --execution-endpoint")?;
+        el_config.fallback_execution_endpoints = execution_endpoints.collect();
 
         // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
-        // file_path or directly as string.
+        // file_path or directly as string. The same JWT secret is used for all endpoints.
 
         let secret_file: PathBuf;
         // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
@@ -405,6 +419,13 @@ pub fn get_config<E: EthSpec>(
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(cli_args)?;
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    client_config.store.hierarchy_config = get_hierarchy_config(cli_args)?;
+
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+
+    client_config.store.compression = get_compression_config(cli_args)?;
 
     if let Some(block_cache_size) = cli_args.get_one::<String>("block-cache-size") {
         client_config.store.block_cache_size = block_cache_size
@@ -442,6 +463,12 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.epochs_per_migration = epochs_per_migration;
     }
 
+    if let Some(epochs_per_fork_sweep) =
+        clap_utils::parse_optional(cli_args, "epochs-per-fork-sweep")?
+    {
+        client_config.chain.epochs_per_fork_sweep = epochs_per_fork_sweep;
+    }
+
     if let Some(prune_blobs) = clap_utils::parse_optional(cli_args, "prune-blobs")? {
         client_config.store.prune_blobs = prune_blobs;
     }
@@ -710,6 +737,8 @@ pub fn get_config<E: EthSpec>(
             slasher_config.backend = backend;
         }
 
+        slasher_config.migrate_from = clap_utils::parse_optional(cli_args, "slasher-migrate-from")?;
+
         client_config.slasher = Some(slasher_config);
     }
 
@@ -846,6 +875,8 @@ pub fn get_config<E: EthSpec>(
     client_config.chain.optimistic_finalized_sync =
         !cli_args.get_flag("disable-optimistic-finalized-sync");
 
+    client_config.chain.publish_engine_blobs = !cli_args.get_flag("disable-publish-engine-blobs");
+
     if cli_args.get_flag("genesis-backfill") {
         client_config.chain.genesis_backfill = true;
     }
@@ -1478,7 +1509,10 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
 pub fn get_slots_per_restore_point<E: EthSpec>(
     cli_args: &ArgMatches,
 ) -> Result<(u64, bool), String> {
-    if let Some(slots_per_restore_point) =
+    if let Some(hierarchy_config) = get_hierarchy_config(cli_args)? {
+        // Restore points are stored at the smallest layer of the hierarchy.
+        Ok((hierarchy_config.restore_point_interval(), true))
+    } else if let Some(slots_per_restore_point) =
         clap_utils::parse_optional(cli_args, "slots-per-restore-point")?
     {
         Ok((slots_per_restore_point, true))
@@ -1491,6 +1525,23 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
     }
 }
 
+/// Get the hierarchical state diff configuration to use for the freezer database, if any.
+pub fn get_hierarchy_config(cli_args: &ArgMatches) -> Result<Option<HierarchyConfig>, String> {
+    clap_utils::parse_optional(cli_args, "hierarchy-exponents")
+}
+
+/// Get the configuration for compressing database values, if compression is enabled.
+pub fn get_compression_config(cli_args: &ArgMatches) -> Result<Option<CompressionConfig>, String> {
+    let Some(level) = clap_utils::parse_optional(cli_args, "db-compression-level")? else {
+        return Ok(None);
+    };
+    let columns = match cli_args.get_one::<String>("db-compression-columns") {
+        Some(columns) => CompressionConfig::parse_columns(columns)?,
+        None => DEFAULT_COMPRESSED_COLUMNS.to_vec(),
+    };
+    Ok(Some(CompressionConfig { level, columns }))
+}
+
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
 ///
 /// If there is more than one value, log a warning. If there are no values, return an error.
@@ -0,0 +1,333 @@
+//! Runtime selection between the on-disk key-value store implementations.
+use crate::compression::{self, CompressionConfig};
+use crate::config::{DatabaseBackend, StoreConfig, StoreConfigError};
+use crate::redb_store::REDB_DATA_FILENAME;
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, Key, KeyValueStore, KeyValueStoreOp,
+    LevelDB, RawEntryIter, RawKeyIter, Redb,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+/// File that LevelDB creates in every database directory.
+const LEVELDB_MARKER_FILENAME: &str = "CURRENT";
+
+/// The on-disk database used by the beacon node.
+///
+/// Values are transparently compressed according to the `CompressionConfig`, and transparently
+/// decompressed in every column that has ever been compressed, regardless of it.
+pub struct BeaconNodeBackend<E: EthSpec> {
+    db: RawBackend<E>,
+    compression: Option<CompressionConfig>,
+    /// The columns that may contain compressed values.
+    compressed_columns: Vec<DBColumn>,
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` using the backend and compression from `config`, creating a new
+    /// database if one does not already exist.
+    pub fn open(config: &StoreConfig, path: &Path) -> Result<Self, Error> {
+        let db = RawBackend::open(config.backend, path)?;
+
+        // Record newly compressed columns before any compressed value is written to them.
+        let mut compressed_columns = compression::load_compressed_columns(&db)?;
+        if let Some(compression) = &config.compression {
+            let num_columns = compressed_columns.len();
+            for column in &compression.columns {
+                if !compressed_columns.contains(column) {
+                    compressed_columns.push(*column);
+                }
+            }
+            if compressed_columns.len() > num_columns {
+                compression::store_compressed_columns(&db, &compressed_columns)?;
+            }
+        }
+
+        Ok(Self {
+            db,
+            compression: config.compression.clone(),
+            compressed_columns,
+        })
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        self.db.backend()
+    }
+
+    /// Access the underlying database, which reads and writes values exactly as they're stored.
+    pub fn raw(&self) -> &RawBackend<E> {
+        &self.db
+    }
+
+    fn compress_for_column(&self, column: &str, value: &[u8]) -> Result<Vec<u8>, Error> {
+        match &self.compression {
+            Some(compression) if compression.compresses_column(column) => {
+                compression.compress(value)
+            }
+            _ => Ok(value.to_vec()),
+        }
+    }
+
+    /// Return `true` if the column with the given name may contain compressed values.
+    fn may_be_compressed(&self, column: &str) -> bool {
+        self.compressed_columns
+            .iter()
+            .any(|col| col.as_str() == column)
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let value = self.db.get_bytes(column, key)?;
+        if self.may_be_compressed(column) {
+            value.map(compression::decompress).transpose()
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        let value = self.compress_for_column(column, value)?;
+        self.db.put_bytes(column, key, &value)
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        let value = self.compress_for_column(column, value)?;
+        self.db.put_bytes_sync(column, key, &value)
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        self.db.sync()
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        self.db.key_exists(column, key)
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        self.db.key_delete(column, key)
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let Some(compression) = &self.compression else {
+            return self.db.do_atomically(batch);
+        };
+
+        let batch = batch
+            .into_iter()
+            .map(|op| match op {
+                KeyValueStoreOp::PutKeyValue(key, value) if compression.compresses_key(&key) => Ok(
+                    KeyValueStoreOp::PutKeyValue(key, compression.compress(&value)?),
+                ),
+                op => Ok(op),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.db.do_atomically(batch)
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.db.begin_rw_transaction()
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        self.db.compact_column(column)
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        self.db.compact()
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        let iter = self.db.iter_column_from(column, from);
+        if !self.may_be_compressed(column.as_str()) {
+            return iter;
+        }
+        Box::new(iter.map(|res| -> Result<_, Error> {
+            let (key, value) = res?;
+            Ok((key, compression::decompress(value)?))
+        }))
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let iter = self.db.iter_raw_entries(column, prefix);
+        if !self.may_be_compressed(column.as_str()) {
+            return iter;
+        }
+        Box::new(iter.map(|res| -> Result<_, Error> {
+            let (key, value) = res?;
+            Ok((key, compression::decompress(value)?))
+        }))
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        self.db.iter_raw_keys(column, prefix)
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        self.db.iter_column_keys(column)
+    }
+
+    /// Values are returned as stored, i.e. possibly compressed.
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        self.db.with_snapshot(f)
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
+
+/// An on-disk database using whichever backend was configured, storing values verbatim.
+pub enum RawBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    Redb(Redb<E>),
+}
+
+impl<E: EthSpec> RawBackend<E> {
+    /// Open a database at `path` using `backend`, creating a new database if one does not already
+    /// exist.
+    ///
+    /// Returns an error if a database created by a different backend already exists at `path`.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = detect_backend(path) {
+            if on_disk != backend {
+                return Err(StoreConfigError::MismatchedBackend {
+                    config: backend,
+                    on_disk,
+                }
+                .into());
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            DatabaseBackend::Redb => Redb::open(path).map(Self::Redb),
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::LevelDb(_) => DatabaseBackend::LevelDb,
+            Self::Redb(_) => DatabaseBackend::Redb,
+        }
+    }
+}
+
+/// Determine which backend created the database at `path`, if any.
+pub fn detect_backend(path: &Path) -> Option<DatabaseBackend> {
+    if path.join(REDB_DATA_FILENAME).exists() {
+        Some(DatabaseBackend::Redb)
+    } else if path.join(LEVELDB_MARKER_FILENAME).exists() {
+        Some(DatabaseBackend::LevelDb)
+    } else {
+        None
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for RawBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            Self::LevelDb(db) => db.get_bytes(column, key),
+            Self::Redb(db) => db.get_bytes(column, key),
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes(column, key, value),
+            Self::Redb(db) => db.put_bytes(column, key, value),
+        }
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes_sync(column, key, value),
+            Self::Redb(db) => db.put_bytes_sync(column, key, value),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.sync(),
+            Self::Redb(db) => db.sync(),
+        }
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            Self::LevelDb(db) => db.key_exists(column, key),
+            Self::Redb(db) => db.key_exists(column, key),
+        }
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.key_delete(column, key),
+            Self::Redb(db) => db.key_delete(column, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.do_atomically(batch),
+            Self::Redb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        match self {
+            Self::LevelDb(db) => db.begin_rw_transaction(),
+            Self::Redb(db) => db.begin_rw_transaction(),
+        }
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.compact_column(column),
+            Self::Redb(db) => db.compact_column(column),
+        }
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.compact(),
+            Self::Redb(db) => db.compact(),
+        }
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        match self {
+            Self::LevelDb(db) => db.iter_column_from(column, from),
+            Self::Redb(db) => db.iter_column_from(column, from),
+        }
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        match self {
+            Self::LevelDb(db) => db.iter_raw_entries(column, prefix),
+            Self::Redb(db) => db.iter_raw_entries(column, prefix),
+        }
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        match self {
+            Self::LevelDb(db) => db.iter_raw_keys(column, prefix),
+            Self::Redb(db) => db.iter_raw_keys(column, prefix),
+        }
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        match self {
+            Self::LevelDb(db) => db.iter_column_keys(column),
+            Self::Redb(db) => db.iter_column_keys(column),
+        }
+    }
+
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        match self {
+            Self::LevelDb(db) => db.with_snapshot(f),
+            Self::Redb(db) => db.with_snapshot(f),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for RawBackend<E> {}
@@ -0,0 +1,508 @@
+//! Point-in-time backups of the hot, freezer and blobs databases.
+//!
+//! A backup is a directory containing a JSON manifest and one zstd-compressed file of entries per
+//! database. Entries are copied exactly as they are stored (keys including their column prefix),
+//! so a backup can be restored into either database backend.
+//!
+//! The databases are snapshotted in turn while the split is locked, hot DB first. Because the
+//! freezer is always written *before* the split is advanced in the hot DB, the freezer snapshot
+//! contains at least the data required by the split in the hot snapshot, which is the same
+//! guarantee that the database provides after a crash.
+use crate::backend::{detect_backend, RawBackend};
+use crate::compression;
+use crate::config::{OnDiskStoreConfig, StoreConfig};
+use crate::hot_cold_store::{HotColdDB, Split};
+use crate::metadata::{
+    SchemaVersion, ANCHOR_INFO_KEY, BLOB_INFO_KEY, COMPRESSED_COLUMNS_KEY, CONFIG_KEY,
+    CURRENT_SCHEMA_VERSION, SCHEMA_VERSION_KEY, SPLIT_KEY,
+};
+use crate::{
+    get_key_for_col, AnchorInfo, BlobInfo, DBColumn, Error, ItemStore, KeyValueStore,
+    KeyValueStoreOp, RawEntryIter, StoreItem,
+};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use types::{EthSpec, Hash256};
+
+/// Name of the manifest file within a backup directory.
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// Backups are mostly made up of already compressed states and blocks, so we favour speed.
+const BACKUP_COMPRESSION_LEVEL: i32 = 1;
+
+/// Number of entries to write per batch when restoring a backup.
+const RESTORE_BATCH_SIZE: usize = 1024;
+
+#[derive(Debug)]
+pub enum BackupError {
+    Io(io::Error),
+    InvalidManifest(serde_json::Error),
+    /// The file or directory to write the backup to already exists.
+    OutputExists(PathBuf),
+    /// A database already exists where the backup would be restored.
+    TargetNotEmpty(PathBuf),
+    /// The hot database is missing a value that every initialized database has.
+    MissingMetadata(&'static str),
+    /// The backup was made by a newer version of Lighthouse.
+    UnsupportedSchemaVersion {
+        backup: SchemaVersion,
+        current: SchemaVersion,
+    },
+    /// A database file contains a different number of entries to the manifest.
+    EntryCountMismatch {
+        database: BackupDatabase,
+        expected: u64,
+        actual: u64,
+    },
+    /// The metadata in the hot database file doesn't match the manifest.
+    ManifestMismatch {
+        manifest: Box<BackupManifest>,
+        restored: Box<BackupManifest>,
+    },
+    /// A database file ended part way through an entry.
+    Truncated(BackupDatabase),
+}
+
+impl From<io::Error> for BackupError {
+    fn from(e: io::Error) -> Self {
+        BackupError::Io(e)
+    }
+}
+
+/// The databases included in a backup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupDatabase {
+    Hot,
+    Cold,
+    Blobs,
+}
+
+impl BackupDatabase {
+    pub fn filename(self) -> &'static str {
+        match self {
+            BackupDatabase::Hot => "hot.zst",
+            BackupDatabase::Cold => "freezer.zst",
+            BackupDatabase::Blobs => "blobs.zst",
+        }
+    }
+}
+
+/// Description of a backup, written alongside the database files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub schema_version: u64,
+    pub config: OnDiskStoreConfig,
+    pub split: Split,
+    pub anchor: Option<AnchorInfo>,
+    pub blob_info: Option<BlobInfo>,
+    pub hot_entries: u64,
+    pub cold_entries: u64,
+    pub blobs_entries: u64,
+}
+
+impl BackupManifest {
+    fn entries(&self, database: BackupDatabase) -> u64 {
+        match database {
+            BackupDatabase::Hot => self.hot_entries,
+            BackupDatabase::Cold => self.cold_entries,
+            BackupDatabase::Blobs => self.blobs_entries,
+        }
+    }
+}
+
+/// Read the manifest of the backup in `backup_dir`.
+pub fn load_manifest(backup_dir: &Path) -> Result<BackupManifest, Error> {
+    let file = File::open(backup_dir.join(MANIFEST_FILENAME)).map_err(BackupError::Io)?;
+    let manifest =
+        serde_json::from_reader(BufReader::new(file)).map_err(BackupError::InvalidManifest)?;
+    Ok(manifest)
+}
+
+/// Collects the metadata stored in the hot DB as its entries are copied.
+///
+/// Values are decoded once all entries have been observed, because whether they need to be
+/// decompressed depends on the record of compressed columns, which sorts after them.
+#[derive(Default)]
+struct MetadataCollector {
+    schema_version: Option<Vec<u8>>,
+    config: Option<Vec<u8>>,
+    split: Option<Vec<u8>>,
+    anchor: Option<Vec<u8>>,
+    blob_info: Option<Vec<u8>>,
+    compressed_columns: Option<Vec<u8>>,
+}
+
+impl MetadataCollector {
+    fn observe(&mut self, column_key: &[u8], value: &[u8]) {
+        let meta_key = |key: Hash256| get_key_for_col(DBColumn::BeaconMeta.into(), key.as_bytes());
+        if !column_key.starts_with(DBColumn::BeaconMeta.as_bytes()) {
+            return;
+        }
+
+        let field = if column_key == meta_key(SCHEMA_VERSION_KEY) {
+            &mut self.schema_version
+        } else if column_key == meta_key(CONFIG_KEY) {
+            &mut self.config
+        } else if column_key == meta_key(SPLIT_KEY) {
+            &mut self.split
+        } else if column_key == meta_key(ANCHOR_INFO_KEY) {
+            &mut self.anchor
+        } else if column_key == meta_key(BLOB_INFO_KEY) {
+            &mut self.blob_info
+        } else if column_key == meta_key(COMPRESSED_COLUMNS_KEY) {
+            &mut self.compressed_columns
+        } else {
+            return;
+        };
+        *field = Some(value.to_vec());
+    }
+
+    fn into_manifest(self, [hot, cold, blobs]: [u64; 3]) -> Result<BackupManifest, Error> {
+        // Metadata is never compressed by default, but may be if configured.
+        let compressed = self
+            .compressed_columns
+            .as_deref()
+            .map(compression::parse_compressed_columns)
+            .transpose()?
+            .is_some_and(|columns| columns.contains(&DBColumn::BeaconMeta));
+
+        Ok(BackupManifest {
+            schema_version: decode_metadata::<SchemaVersion>(self.schema_version, compressed)?
+                .ok_or(BackupError::MissingMetadata("schema version"))?
+                .as_u64(),
+            config: decode_metadata(self.config, compressed)?
+                .ok_or(BackupError::MissingMetadata("config"))?,
+            split: decode_metadata(self.split, compressed)?
+                .ok_or(BackupError::MissingMetadata("split"))?,
+            anchor: decode_metadata(self.anchor, compressed)?,
+            blob_info: decode_metadata(self.blob_info, compressed)?,
+            hot_entries: hot,
+            cold_entries: cold,
+            blobs_entries: blobs,
+        })
+    }
+}
+
+/// Decode a metadata value, decompressing it if the `BeaconMeta` column is compressed.
+fn decode_metadata<T: StoreItem>(
+    value: Option<Vec<u8>>,
+    compressed: bool,
+) -> Result<Option<T>, Error> {
+    value
+        .map(|value| {
+            let value = if compressed {
+                compression::decompress(value)?
+            } else {
+                value
+            };
+            T::from_store_bytes(&value)
+        })
+        .transpose()
+}
+
+fn write_u64(writer: &mut impl Write, n: u64) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+/// Write every entry of `entries` to a new database file, returning the number of entries.
+fn write_database_file(
+    path: &Path,
+    entries: RawEntryIter,
+    mut metadata: Option<&mut MetadataCollector>,
+) -> Result<u64, Error> {
+    let file = File::create(path).map_err(BackupError::Io)?;
+    let mut encoder = zstd::Encoder::new(BufWriter::new(file), BACKUP_COMPRESSION_LEVEL)
+        .map_err(BackupError::Io)?;
+    encoder.include_checksum(true).map_err(BackupError::Io)?;
+
+    let mut count = 0;
+    for res in entries {
+        let (key, value) = res?;
+        if let Some(metadata) = metadata.as_mut() {
+            metadata.observe(&key, &value);
+        }
+        write_u64(&mut encoder, key.len() as u64).map_err(BackupError::Io)?;
+        encoder.write_all(&key).map_err(BackupError::Io)?;
+        write_u64(&mut encoder, value.len() as u64).map_err(BackupError::Io)?;
+        encoder.write_all(&value).map_err(BackupError::Io)?;
+        count += 1;
+    }
+
+    let file = encoder
+        .finish()
+        .and_then(|writer| writer.into_inner().map_err(|e| e.into_error()))
+        .map_err(BackupError::Io)?;
+    file.sync_all().map_err(BackupError::Io)?;
+    Ok(count)
+}
+
+/// Iterator over the entries of a database file written by `write_database_file`.
+struct DatabaseFileReader<R: Read> {
+    reader: R,
+    database: BackupDatabase,
+}
+
+impl<R: Read> DatabaseFileReader<R> {
+    /// Read a length-prefixed byte string, or `None` at the end of the file.
+    fn read_bytes(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        let mut len = [0; 8];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(BackupError::Io(e).into()),
+        }
+        // Don't trust the length prefix for the allocation, as the file may be corrupt.
+        let len = u64::from_le_bytes(len);
+        let mut bytes = vec![];
+        (&mut self.reader)
+            .take(len)
+            .read_to_end(&mut bytes)
+            .map_err(BackupError::Io)?;
+        if bytes.len() as u64 != len {
+            return Err(BackupError::Truncated(self.database).into());
+        }
+        Ok(Some(bytes))
+    }
+}
+
+impl<R: Read> Iterator for DatabaseFileReader<R> {
+    type Item = Result<(Vec<u8>, Vec<u8>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_bytes() {
+            Ok(Some(key)) => match self.read_bytes() {
+                Ok(Some(value)) => Some(Ok((key, value))),
+                Ok(None) => Some(Err(BackupError::Truncated(self.database).into())),
+                Err(e) => Some(Err(e)),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Write a consistent point-in-time backup of the hot, freezer and blobs databases to a new
+    /// directory at `backup_dir`.
+    ///
+    /// The backup is written to a temporary directory alongside `backup_dir` and only moved into
+    /// place once complete, so an interrupted backup is never mistaken for a valid one.
+    pub fn backup(&self, backup_dir: &Path) -> Result<BackupManifest, Error> {
+        if backup_dir.exists() {
+            return Err(BackupError::OutputExists(backup_dir.to_path_buf()).into());
+        }
+        let mut partial_dir = backup_dir.as_os_str().to_owned();
+        partial_dir.push(".partial");
+        let partial_dir = PathBuf::from(partial_dir);
+        if partial_dir.exists() {
+            return Err(BackupError::OutputExists(partial_dir).into());
+        }
+        fs::create_dir_all(&partial_dir).map_err(BackupError::Io)?;
+
+        // Hold the split lock while the snapshots are created so that a freezer migration can't
+        // complete in between them. The lock is released before any entries are copied.
+        let split_guard = self.split.read_recursive();
+        let manifest = self.hot_db.with_snapshot(|hot| {
+            self.cold_db.with_snapshot(|cold| {
+                self.blobs_db.with_snapshot(|blobs| {
+                    drop(split_guard);
+                    write_backup(&partial_dir, hot, cold, blobs)
+                })
+            })
+        })?;
+
+        fs::rename(&partial_dir, backup_dir).map_err(BackupError::Io)?;
+        Ok(manifest)
+    }
+}
+
+fn write_backup(
+    dir: &Path,
+    hot: RawEntryIter,
+    cold: RawEntryIter,
+    blobs: RawEntryIter,
+) -> Result<BackupManifest, Error> {
+    let mut metadata = MetadataCollector::default();
+    let hot_entries = write_database_file(
+        &dir.join(BackupDatabase::Hot.filename()),
+        hot,
+        Some(&mut metadata),
+    )?;
+    let cold_entries = write_database_file(&dir.join(BackupDatabase::Cold.filename()), cold, None)?;
+    let blobs_entries =
+        write_database_file(&dir.join(BackupDatabase::Blobs.filename()), blobs, None)?;
+    let manifest = metadata.into_manifest([hot_entries, cold_entries, blobs_entries])?;
+
+    let file = File::create(dir.join(MANIFEST_FILENAME)).map_err(BackupError::Io)?;
+    serde_json::to_writer_pretty(&file, &manifest).map_err(BackupError::InvalidManifest)?;
+    file.sync_all().map_err(BackupError::Io)?;
+    Ok(manifest)
+}
+
+/// Check that the backup described by `manifest` can be restored and used with `config`.
+///
+/// If the slots-per-restore-point wasn't set explicitly it is taken from the backup.
+pub fn verify_manifest(manifest: &BackupManifest, config: &StoreConfig) -> Result<(), Error> {
+    let backup_version = SchemaVersion(manifest.schema_version);
+    if backup_version > CURRENT_SCHEMA_VERSION {
+        return Err(BackupError::UnsupportedSchemaVersion {
+            backup: backup_version,
+            current: CURRENT_SCHEMA_VERSION,
+        }
+        .into());
+    }
+
+    let mut config = config.clone();
+    if !config.slots_per_restore_point_set_explicitly {
+        config.slots_per_restore_point = manifest.config.slots_per_restore_point;
+    }
+    config.check_compatibility(&restored_config(manifest, &config))?;
+    Ok(())
+}
+
+/// The on-disk config of a restored database, which uses the backend it was restored into.
+fn restored_config(manifest: &BackupManifest, config: &StoreConfig) -> OnDiskStoreConfig {
+    OnDiskStoreConfig {
+        backend: config.backend,
+        ..manifest.config.clone()
+    }
+}
+
+/// Restore the backup in `backup_dir` into new databases at the given paths, using the backend
+/// from `config`.
+///
+/// The manifest is verified with `verify_manifest` before anything is written, and the restored
+/// databases are checked against it afterwards.
+pub fn restore<E: EthSpec>(
+    backup_dir: &Path,
+    config: &StoreConfig,
+    hot_path: &Path,
+    cold_path: &Path,
+    blobs_path: &Path,
+) -> Result<BackupManifest, Error> {
+    let manifest = load_manifest(backup_dir)?;
+    verify_manifest(&manifest, config)?;
+
+    let targets = [
+        (BackupDatabase::Hot, hot_path),
+        (BackupDatabase::Cold, cold_path),
+        (BackupDatabase::Blobs, blobs_path),
+    ];
+    for (_, path) in targets {
+        if detect_backend(path).is_some() {
+            return Err(BackupError::TargetNotEmpty(path.to_path_buf()).into());
+        }
+    }
+
+    let mut metadata = MetadataCollector::default();
+    let mut counts = [0; 3];
+    for (i, (database, path)) in targets.into_iter().enumerate() {
+        let db = RawBackend::<E>::open(config.backend, path)?;
+        let file = File::open(backup_dir.join(database.filename())).map_err(BackupError::Io)?;
+        let reader = DatabaseFileReader {
+            reader: zstd::Decoder::new(file).map_err(BackupError::Io)?,
+            database,
+        };
+
+        let mut batch = Vec::with_capacity(RESTORE_BATCH_SIZE);
+        for res in reader {
+            let (key, value) = res?;
+            if database == BackupDatabase::Hot {
+                metadata.observe(&key, &value);
+            }
+            batch.push(KeyValueStoreOp::PutKeyValue(key, value));
+            counts[i] += 1;
+
+            if batch.len() >= RESTORE_BATCH_SIZE {
+                db.do_atomically(std::mem::take(&mut batch))?;
+            }
+        }
+        db.do_atomically(batch)?;
+
+        if counts[i] != manifest.entries(database) {
+            return Err(BackupError::EntryCountMismatch {
+                database,
+                expected: manifest.entries(database),
+                actual: counts[i],
+            }
+            .into());
+        }
+
+        // The restored database may use a different backend to the one that was backed up.
+        if database == BackupDatabase::Hot {
+            db.put_sync(&CONFIG_KEY, &restored_config(&manifest, config))?;
+        }
+        db.sync()?;
+    }
+
+    let restored = metadata.into_manifest(counts)?;
+    if restored != manifest {
+        return Err(BackupError::ManifestMismatch {
+            manifest: Box::new(manifest),
+            restored: Box::new(restored),
+        }
+        .into());
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_entries(file: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Error> {
+        DatabaseFileReader {
+            reader: file,
+            database: BackupDatabase::Hot,
+        }
+        .collect()
+    }
+
+    fn entry(key: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut file = vec![];
+        write_u64(&mut file, key.len() as u64).unwrap();
+        file.extend_from_slice(key);
+        write_u64(&mut file, value.len() as u64).unwrap();
+        file.extend_from_slice(value);
+        file
+    }
+
+    #[test]
+    fn read_entries_roundtrip() {
+        let mut file = entry(b"key1", b"value1");
+        file.extend(entry(b"key2", b""));
+        assert_eq!(
+            read_entries(&file).unwrap(),
+            vec![
+                (b"key1".to_vec(), b"value1".to_vec()),
+                (b"key2".to_vec(), vec![])
+            ]
+        );
+    }
+
+    #[test]
+    fn corrupt_length_prefix() {
+        let mut file = entry(b"key", b"value");
+        // Corrupt the length of the value.
+        file[11..19].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            read_entries(&file),
+            Err(Error::BackupError(BackupError::Truncated(
+                BackupDatabase::Hot
+            )))
+        ));
+    }
+
+    #[test]
+    fn truncated_entry() {
+        let file = entry(b"key", b"value");
+        assert!(matches!(
+            read_entries(&file[..file.len() - 1]),
+            Err(Error::BackupError(BackupError::Truncated(
+                BackupDatabase::Hot
+            )))
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+//! Transparent compression of database values.
+//!
+//! Compressed values are written as a single header byte followed by a zstd frame. Every value
+//! written to a compressed column carries the header, even if compression doesn't make it any
+//! smaller.
+//!
+//! The columns that have ever been compressed are recorded in the database itself, and values are
+//! only decompressed when they belong to one of those columns *and* start with both the header
+//! byte and the zstd magic number. Values in other columns are always returned as-is, so
+//! decompression doesn't depend on the current configuration, and compression can be enabled or
+//! disabled at any time.
+use crate::metadata::COMPRESSED_COLUMNS_KEY;
+use crate::{DBColumn, Error, KeyValueStore};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use types::EthSpec;
+
+/// First byte of every compressed value.
+pub const COMPRESSED_VALUE_HEADER: u8 = 0xc5;
+
+/// Magic number at the start of every zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Columns compressed by default, which hold the largest values.
+pub const DEFAULT_COMPRESSED_COLUMNS: [DBColumn; 3] = [
+    DBColumn::BeaconState,
+    DBColumn::BeaconBlock,
+    DBColumn::ExecPayload,
+];
+
+/// Configuration for compressing values written to the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// The zstd compression level.
+    pub level: i32,
+    /// The columns whose values should be compressed.
+    pub columns: Vec<DBColumn>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_COMPRESSION_LEVEL,
+            columns: DEFAULT_COMPRESSED_COLUMNS.to_vec(),
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Parse a comma-separated list of column tags, e.g. `ste,blk`.
+    pub fn parse_columns(s: &str) -> Result<Vec<DBColumn>, String> {
+        s.split(',')
+            .map(|tag| {
+                DBColumn::from_str(tag.trim())
+                    .map_err(|_| format!("invalid database column: {:?}", tag))
+            })
+            .collect()
+    }
+
+    /// Return `true` if values in the column with the given name should be compressed.
+    pub fn compresses_column(&self, column: &str) -> bool {
+        self.columns.iter().any(|col| col.as_str() == column)
+    }
+
+    /// Return `true` if the value with the given raw key (column prefix and key) should be
+    /// compressed.
+    pub fn compresses_key(&self, column_key: &[u8]) -> bool {
+        self.columns
+            .iter()
+            .any(|col| column_key.starts_with(col.as_bytes()))
+    }
+
+    /// Compress `value`, prefixing it with the header byte.
+    pub fn compress(&self, value: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut compressed = Vec::with_capacity(value.len() / 2 + 1);
+        compressed.push(COMPRESSED_VALUE_HEADER);
+        zstd::stream::copy_encode(value, &mut compressed, self.level)
+            .map_err(Error::CompressionError)?;
+        Ok(compressed)
+    }
+}
+
+/// Return `true` if `value` was written by `CompressionConfig::compress`.
+pub fn is_compressed(value: &[u8]) -> bool {
+    value.first() == Some(&COMPRESSED_VALUE_HEADER)
+        && value.get(1..1 + ZSTD_MAGIC.len()) == Some(&ZSTD_MAGIC[..])
+}
+
+/// Decompress `value` if it is compressed, or return it unmodified otherwise.
+///
+/// Only values read from a column returned by `load_compressed_columns` should be passed to this
+/// function, as values in other columns may start with the header by coincidence.
+pub fn decompress(value: Vec<u8>) -> Result<Vec<u8>, Error> {
+    if is_compressed(&value) {
+        zstd::decode_all(&value[1..]).map_err(Error::CompressionError)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Load the columns of `db` that compressed values have ever been written to.
+pub fn load_compressed_columns<E: EthSpec>(
+    db: &impl KeyValueStore<E>,
+) -> Result<Vec<DBColumn>, Error> {
+    let Some(bytes) = db.get_bytes(
+        DBColumn::BeaconMeta.into(),
+        COMPRESSED_COLUMNS_KEY.as_bytes(),
+    )?
+    else {
+        return Ok(vec![]);
+    };
+    parse_compressed_columns(&bytes)
+}
+
+/// Parse the record of compressed columns stored under `COMPRESSED_COLUMNS_KEY`.
+pub fn parse_compressed_columns(bytes: &[u8]) -> Result<Vec<DBColumn>, Error> {
+    let tags =
+        std::str::from_utf8(bytes).map_err(|e| Error::InvalidCompressedColumns(e.to_string()))?;
+    if tags.is_empty() {
+        return Ok(vec![]);
+    }
+    CompressionConfig::parse_columns(tags).map_err(Error::InvalidCompressedColumns)
+}
+
+/// Record that compressed values may have been written to each of `columns` in `db`.
+///
+/// The record is stored uncompressed, and must be written before any compressed value.
+pub fn store_compressed_columns<E: EthSpec>(
+    db: &impl KeyValueStore<E>,
+    columns: &[DBColumn],
+) -> Result<(), Error> {
+    let tags = columns
+        .iter()
+        .map(|col| col.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    db.put_bytes_sync(
+        DBColumn::BeaconMeta.into(),
+        COMPRESSED_COLUMNS_KEY.as_bytes(),
+        tags.as_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let config = CompressionConfig::default();
+        let value = vec![42; 1024];
+
+        let compressed = config.compress(&value).unwrap();
+        assert!(is_compressed(&compressed));
+        assert!(compressed.len() < value.len());
+        assert_eq!(decompress(compressed).unwrap(), value);
+    }
+
+    #[test]
+    fn incompressible_value_has_header() {
+        let config = CompressionConfig::default();
+        let value = vec![1, 2, 3];
+
+        let stored = config.compress(&value).unwrap();
+        assert!(is_compressed(&stored));
+        assert!(stored.len() > value.len());
+        assert_eq!(decompress(stored).unwrap(), value);
+    }
+
+    #[test]
+    fn uncompressed_value_with_header_byte() {
+        let value = vec![COMPRESSED_VALUE_HEADER, 0, 0, 0, 0, 0];
+        assert!(!is_compressed(&value));
+        assert_eq!(decompress(value.clone()).unwrap(), value);
+    }
+
+    #[test]
+    fn parse_columns() {
+        assert_eq!(
+            CompressionConfig::parse_columns("ste, blk").unwrap(),
+            vec![DBColumn::BeaconState, DBColumn::BeaconBlock]
+        );
+        assert!(CompressionConfig::parse_columns("ste,xyz").is_err());
+    }
+
+    #[test]
+    fn compresses_key() {
+        let config = CompressionConfig::default();
+        assert!(config.compresses_column("ste"));
+        assert!(!config.compresses_column("bma"));
+        assert!(config.compresses_key(b"blk0123"));
+        assert!(!config.compresses_key(b"blb0123"));
+    }
+}
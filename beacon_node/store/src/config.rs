@@ -1,8 +1,11 @@
+use crate::compression::CompressionConfig;
+use crate::hdiff::HierarchyConfig;
 use crate::{DBColumn, Error, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::num::NonZeroUsize;
+use strum::{EnumString, EnumVariantNames, IntoStaticStr};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, MinimalEthSpec};
 
@@ -40,17 +43,71 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// Layers of hierarchical state diffs to use in the freezer database.
+    ///
+    /// If `None`, every restore point is stored as a full state.
+    pub hierarchy_config: Option<HierarchyConfig>,
+    /// Key-value store implementation used for the hot, cold and blobs databases.
+    pub backend: DatabaseBackend,
+    /// Compression to apply to newly written values, if any.
+    ///
+    /// Compressed values are readable regardless of this setting.
+    pub compression: Option<CompressionConfig>,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
-#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
 pub struct OnDiskStoreConfig {
     pub slots_per_restore_point: u64,
+    pub hierarchy_config: Option<HierarchyConfig>,
+    pub backend: DatabaseBackend,
+}
+
+/// On-disk key-value store implementations supported by the beacon node.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+#[ssz(enum_behaviour = "tag")]
+pub enum DatabaseBackend {
+    #[default]
+    LevelDb,
+    Redb,
+}
+
+impl DatabaseBackend {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedSlotsPerRestorePoint {
+        config: u64,
+        on_disk: u64,
+    },
+    MismatchedHierarchyConfig {
+        config: Option<HierarchyConfig>,
+        on_disk: Option<HierarchyConfig>,
+    },
+    MismatchedBackend {
+        config: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
 }
 
 impl Default for StoreConfig {
@@ -68,6 +125,9 @@ impl Default for StoreConfig {
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            hierarchy_config: None,
+            backend: DatabaseBackend::default(),
+            compression: None,
         }
     }
 }
@@ -76,6 +136,8 @@ impl StoreConfig {
     pub fn as_disk_config(&self) -> OnDiskStoreConfig {
         OnDiskStoreConfig {
             slots_per_restore_point: self.slots_per_restore_point,
+            hierarchy_config: self.hierarchy_config.clone(),
+            backend: self.backend,
         }
     }
 
@@ -89,6 +151,18 @@ impl StoreConfig {
                 on_disk: on_disk_config.slots_per_restore_point,
             });
         }
+        if self.hierarchy_config != on_disk_config.hierarchy_config {
+            return Err(StoreConfigError::MismatchedHierarchyConfig {
+                config: self.hierarchy_config.clone(),
+                on_disk: on_disk_config.hierarchy_config.clone(),
+            });
+        }
+        if self.backend != on_disk_config.backend {
+            return Err(StoreConfigError::MismatchedBackend {
+                config: self.backend,
+                on_disk: on_disk_config.backend,
+            });
+        }
         Ok(())
     }
 }
@@ -103,6 +177,53 @@ impl StoreItem for OnDiskStoreConfig {
     }
 
     fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        // Configs written prior to the introduction of hierarchical diffs consist of a lone `u64`,
+        // and were always written by LevelDB.
+        if bytes.len() == <u64 as Decode>::ssz_fixed_len() {
+            return Ok(Self {
+                slots_per_restore_point: u64::from_ssz_bytes(bytes)?,
+                hierarchy_config: None,
+                backend: DatabaseBackend::LevelDb,
+            });
+        }
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn on_disk_config_roundtrip() {
+        let config = OnDiskStoreConfig {
+            slots_per_restore_point: 32,
+            hierarchy_config: Some(HierarchyConfig::default()),
+            backend: DatabaseBackend::Redb,
+        };
+        let bytes = config.as_store_bytes();
+        assert_eq!(OnDiskStoreConfig::from_store_bytes(&bytes).unwrap(), config);
+    }
+
+    #[test]
+    fn on_disk_config_legacy_decode() {
+        let bytes = 2048u64.as_ssz_bytes();
+        assert_eq!(
+            OnDiskStoreConfig::from_store_bytes(&bytes).unwrap(),
+            OnDiskStoreConfig {
+                slots_per_restore_point: 2048,
+                hierarchy_config: None,
+                backend: DatabaseBackend::LevelDb,
+            }
+        );
+    }
+
+    #[test]
+    fn database_backend_names() {
+        assert_eq!(DatabaseBackend::LevelDb.as_str(), "leveldb");
+        assert_eq!(
+            "redb".parse::<DatabaseBackend>().unwrap(),
+            DatabaseBackend::Redb
+        );
+    }
+}
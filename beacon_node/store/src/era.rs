@@ -0,0 +1,686 @@
+//! Export and import of finalized chain history as era files.
+//!
+//! Each era file holds the blocks of one `SLOTS_PER_HISTORICAL_ROOT` period in the e2store format
+//! described at https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md. Era `N`
+//! contains the blocks from slot `(N - 1) * SLOTS_PER_HISTORICAL_ROOT` up to (but excluding) slot
+//! `N * SLOTS_PER_HISTORICAL_ROOT`, and optionally the state at the latter slot.
+//!
+//! Every entry is compressed using the snappy framing format, which includes a checksum of its
+//! contents. Imported files are verified against the `historical_roots` or `historical_summaries`
+//! of the split state, and blobs against the commitments in their blocks, so they needn't come
+//! from a trusted source.
+//!
+//! Two entry types are Lighthouse extensions to the format, which other implementations ignore:
+//! blinded blocks, which are written when the execution payload of a block has been pruned, and
+//! the blobs for a block.
+use crate::chunked_vector::BlockRoots;
+use crate::hot_cold_store::HotColdDB;
+use crate::{AnchorInfo, BlobInfo, ChunkWriter, DatabaseBlock, Error, ItemStore, KeyValueStore};
+use kzg::Kzg;
+use slog::{debug, info};
+use ssz::{Decode, Encode};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use tree_hash::TreeHash;
+use types::{
+    BeaconState, BlobSidecarList, ChainSpec, EthSpec, FixedVector, Hash256, HistoricalSummary,
+    SignedBeaconBlock, SignedBlindedBeaconBlock, Slot,
+};
+
+/// File extension of era files.
+pub const ERA_FILE_EXTENSION: &str = "era";
+
+/// Length of the header of every e2store entry: type, length and reserved bytes.
+const HEADER_LENGTH: u64 = 8;
+
+const VERSION: [u8; 2] = [0x65, 0x32];
+const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+/// Lighthouse extension: a `SignedBlindedBeaconBlock`.
+const COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK: [u8; 2] = [0x01, 0x4c];
+/// Lighthouse extension: the `BlobSidecarList` of a single block.
+const COMPRESSED_BLOB_SIDECAR_LIST: [u8; 2] = [0x03, 0x4c];
+
+#[derive(Debug)]
+pub enum EraError {
+    Io(io::Error),
+    /// An era file is malformed.
+    InvalidFile {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The file to write the era to already exists.
+    OutputExists(PathBuf),
+    /// The blocks of the era are not all stored in the freezer database.
+    EraUnavailable {
+        era: u64,
+    },
+    /// The split state doesn't include a historical root for the era.
+    UnknownEra {
+        era: u64,
+    },
+    /// Eras prior to Capella can only be verified using the state at the end of the era.
+    MissingEraState {
+        era: u64,
+    },
+    /// The contents of an era file don't match the historical root of the era.
+    RootMismatch {
+        era: u64,
+        expected: Hash256,
+        computed: Hash256,
+    },
+    /// The blobs in an era file don't match the commitments in their block.
+    BlobsMismatch {
+        block_root: Hash256,
+    },
+    /// The commitment inclusion proof of a blob in an era file is invalid.
+    InvalidBlobInclusionProof {
+        block_root: Hash256,
+        index: u64,
+    },
+    /// The KZG proofs of the blobs in an era file are invalid.
+    InvalidBlobKzgProofs {
+        block_root: Hash256,
+        error: kzg::Error,
+    },
+    /// The blocks in an era file don't end at the parent of the oldest stored block. The era
+    /// files should be imported from newest to oldest without gaps.
+    MismatchedBlockRoot {
+        block_root: Hash256,
+        expected_block_root: Hash256,
+    },
+}
+
+impl From<io::Error> for EraError {
+    fn from(e: io::Error) -> Self {
+        EraError::Io(e)
+    }
+}
+
+/// The contents of an era file, with blocks in slot-ascending order.
+pub struct EraFile<E: EthSpec> {
+    pub era: u64,
+    pub blocks: Vec<(Hash256, SignedBlindedBeaconBlock<E>)>,
+    pub state: Option<BeaconState<E>>,
+    pub blobs: Vec<BlobSidecarList<E>>,
+}
+
+/// Verify the KZG proofs of the blobs of a single block in one batch.
+fn verify_blob_kzg_proofs<E: EthSpec>(
+    kzg: &Kzg,
+    blobs: &BlobSidecarList<E>,
+) -> Result<(), kzg::Error> {
+    let kzg_blobs = blobs
+        .iter()
+        .map(|blob| kzg::Blob::from_bytes(blob.blob.as_ref()).map_err(Into::into))
+        .collect::<Result<Vec<_>, kzg::Error>>()?;
+    let commitments = blobs
+        .iter()
+        .map(|blob| blob.kzg_commitment)
+        .collect::<Vec<_>>();
+    let proofs = blobs.iter().map(|blob| blob.kzg_proof).collect::<Vec<_>>();
+    kzg.verify_blob_kzg_proof_batch(&kzg_blobs, &commitments, &proofs)
+}
+
+/// Return the slots of the blocks contained in `era`.
+pub fn era_block_slots<E: EthSpec>(era: u64) -> (Slot, Slot) {
+    let slots_per_historical_root = E::slots_per_historical_root() as u64;
+    (
+        Slot::new(era.saturating_sub(1) * slots_per_historical_root),
+        Slot::new(era * slots_per_historical_root),
+    )
+}
+
+/// Return the historical root for the blocks of `era` from `state`, if it has one.
+///
+/// `HistoricalSummary` has the same tree hash root as the `HistoricalBatch` used prior to
+/// Capella, so roots from either list can be compared to `HistoricalSummary::tree_hash_root`.
+fn era_root<E: EthSpec>(state: &BeaconState<E>, era: u64) -> Option<Hash256> {
+    let period = era.checked_sub(1)? as usize;
+    let historical_roots = state.historical_roots();
+    if let Some(root) = historical_roots.get(period) {
+        return Some(*root);
+    }
+    state
+        .historical_summaries()
+        .ok()?
+        .get(period - historical_roots.len())
+        .map(|summary| summary.tree_hash_root())
+}
+
+/// Return the filename for `era`, e.g. `mainnet-01234-a1b2c3d4.era`.
+pub fn era_filename(spec: &ChainSpec, era: u64, era_root: Hash256) -> String {
+    let short_root = era_root.as_bytes()[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}-{era:05}-{short_root}.{ERA_FILE_EXTENSION}",
+        spec.config_name.as_deref().unwrap_or("custom"),
+    )
+}
+
+/// Parse the era number from an era filename.
+pub fn parse_era_filename(path: &Path) -> Option<u64> {
+    if path.extension()? != ERA_FILE_EXTENSION {
+        return None;
+    }
+    let mut parts = path.file_stem()?.to_str()?.rsplit('-');
+    let _short_root = parts.next()?;
+    parts.next()?.parse().ok()
+}
+
+/// Writer of e2store entries, which tracks the offset of each entry.
+struct E2StoreWriter<W: Write> {
+    writer: W,
+    offset: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Write an entry, returning its offset.
+    fn write_entry(&mut self, entry_type: [u8; 2], data: &[u8]) -> Result<u64, EraError> {
+        let offset = self.offset;
+        self.writer.write_all(&entry_type)?;
+        self.writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(data)?;
+        self.offset += HEADER_LENGTH + data.len() as u64;
+        Ok(offset)
+    }
+
+    fn write_compressed(&mut self, entry_type: [u8; 2], ssz: &[u8]) -> Result<u64, EraError> {
+        let mut encoder = snap::write::FrameEncoder::new(vec![]);
+        encoder.write_all(ssz)?;
+        let data = encoder.into_inner().map_err(|e| e.into_error())?;
+        self.write_entry(entry_type, &data)
+    }
+
+    /// Write a slot index of the entries at `offsets`, which are relative to the start of the
+    /// index (and so negative). Empty slots have an offset of 0.
+    fn write_slot_index(
+        &mut self,
+        starting_slot: Slot,
+        offsets: &[Option<u64>],
+    ) -> Result<u64, EraError> {
+        let index_offset = self.offset as i64;
+        let mut data = Vec::with_capacity(8 * (offsets.len() + 2));
+        data.extend_from_slice(&(starting_slot.as_u64() as i64).to_le_bytes());
+        for offset in offsets {
+            let relative_offset = offset.map_or(0, |offset| offset as i64 - index_offset);
+            data.extend_from_slice(&relative_offset.to_le_bytes());
+        }
+        data.extend_from_slice(&(offsets.len() as i64).to_le_bytes());
+        self.write_entry(SLOT_INDEX, &data)
+    }
+}
+
+/// Reader of e2store entries.
+struct E2StoreReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Read the next entry, or `None` at the end of the file.
+    fn next_entry(&mut self) -> Result<Option<([u8; 2], Vec<u8>)>, EraError> {
+        let mut header = [0; HEADER_LENGTH as usize];
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        let entry_type = [header[0], header[1]];
+        let length = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        let mut data = vec![0; length as usize];
+        self.reader.read_exact(&mut data)?;
+        Ok(Some((entry_type, data)))
+    }
+}
+
+/// Decompress the data of an entry, verifying its checksums.
+fn decompress(data: &[u8]) -> Result<Vec<u8>, EraError> {
+    let mut decompressed = vec![];
+    snap::read::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Read an era file, checking that it contains the blocks of a single era.
+pub fn read_era_file<E: EthSpec>(path: &Path, spec: &ChainSpec) -> Result<EraFile<E>, Error> {
+    let invalid = |reason: String| EraError::InvalidFile {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let era = parse_era_filename(path).ok_or_else(|| invalid("invalid filename".into()))?;
+    let (start_slot, end_slot) = era_block_slots::<E>(era);
+
+    let mut reader = E2StoreReader {
+        reader: BufReader::new(File::open(path).map_err(EraError::Io)?),
+    };
+    match reader.next_entry()? {
+        Some((VERSION, _)) => (),
+        _ => return Err(invalid("missing version entry".into()).into()),
+    }
+
+    let mut file = EraFile {
+        era,
+        blocks: vec![],
+        state: None,
+        blobs: vec![],
+    };
+    while let Some((entry_type, data)) = reader.next_entry()? {
+        let block = match entry_type {
+            COMPRESSED_SIGNED_BEACON_BLOCK => {
+                SignedBeaconBlock::<E>::from_ssz_bytes(&decompress(&data)?, spec)?
+                    .clone_as_blinded()
+            }
+            COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK => {
+                SignedBlindedBeaconBlock::<E>::from_ssz_bytes(&decompress(&data)?, spec)?
+            }
+            COMPRESSED_BEACON_STATE => {
+                let state = BeaconState::from_ssz_bytes(&decompress(&data)?, spec)?;
+                if state.slot() != end_slot {
+                    return Err(invalid(format!("state has slot {}", state.slot())).into());
+                }
+                file.state = Some(state);
+                continue;
+            }
+            COMPRESSED_BLOB_SIDECAR_LIST => {
+                file.blobs
+                    .push(BlobSidecarList::from_ssz_bytes(&decompress(&data)?)?);
+                continue;
+            }
+            // Unknown entries must be skipped, and indices aren't needed as the file is read
+            // sequentially.
+            _ => continue,
+        };
+
+        let prev_slot = file.blocks.last().map(|(_, block)| block.slot());
+        if block.slot() < start_slot
+            || block.slot() >= end_slot
+            || prev_slot.map_or(false, |prev_slot| block.slot() <= prev_slot)
+        {
+            return Err(invalid(format!("unexpected block at slot {}", block.slot())).into());
+        }
+        file.blocks.push((block.canonical_root(), block));
+    }
+    Ok(file)
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Return the eras whose blocks are all stored in the freezer database.
+    pub fn exportable_eras(&self) -> Option<RangeInclusive<u64>> {
+        let slots_per_historical_root = E::slots_per_historical_root() as u64;
+        let oldest_block_slot = self.get_oldest_block_slot().as_u64();
+        let first_era = oldest_block_slot.div_ceil(slots_per_historical_root) + 1;
+        let last_era = self.get_split_slot().as_u64() / slots_per_historical_root;
+        (first_era <= last_era).then_some(first_era..=last_era)
+    }
+
+    /// Load the split state, whose historical roots are used to name and verify era files.
+    fn load_split_state(&self) -> Result<BeaconState<E>, Error> {
+        let split = self.get_split_info();
+        self.get_state(&split.state_root, Some(split.slot))?
+            .ok_or(Error::BlockNotFound(split.block_root))
+    }
+
+    /// Write the blocks of `era` to a new era file in `output_dir`, returning its path.
+    ///
+    /// Blobs and the state at the end of the era are included if requested and available.
+    pub fn export_era(
+        &self,
+        era: u64,
+        output_dir: &Path,
+        include_blobs: bool,
+        include_state: bool,
+    ) -> Result<PathBuf, Error> {
+        if !self
+            .exportable_eras()
+            .map_or(false, |eras| eras.contains(&era))
+        {
+            return Err(EraError::EraUnavailable { era }.into());
+        }
+        let (start_slot, end_slot) = era_block_slots::<E>(era);
+        let split_state = self.load_split_state()?;
+        let root = era_root(&split_state, era).ok_or(EraError::UnknownEra { era })?;
+        drop(split_state);
+
+        let path = output_dir.join(era_filename(&self.spec, era, root));
+        if path.exists() {
+            return Err(EraError::OutputExists(path).into());
+        }
+        let mut partial_path = path.as_os_str().to_owned();
+        partial_path.push(".partial");
+        let partial_path = PathBuf::from(partial_path);
+
+        let file = File::create(&partial_path).map_err(EraError::Io)?;
+        let mut writer = E2StoreWriter {
+            writer: BufWriter::new(file),
+            offset: 0,
+        };
+        writer.write_entry(VERSION, &[])?;
+
+        // The root before the start of the era is needed to identify a skipped first slot.
+        let iter_start_slot = if start_slot > self.get_oldest_block_slot() {
+            start_slot - 1
+        } else {
+            start_slot
+        };
+        let mut prev_block_root = self
+            .get_anchor_info()
+            .filter(|anchor| anchor.oldest_block_slot == start_slot)
+            .map_or(Hash256::zero(), |anchor| anchor.oldest_block_parent);
+
+        let mut block_offsets = vec![None; (end_slot - start_slot).as_usize()];
+        let mut blobs = vec![];
+        let mut num_slots = 0;
+        for res in self.forwards_block_roots_iterator_until(
+            iter_start_slot,
+            end_slot - 1,
+            || Err(Error::NoContinuationData),
+            &self.spec,
+        )? {
+            let (block_root, slot) = res?;
+            num_slots += 1;
+            if slot < start_slot || block_root == prev_block_root {
+                prev_block_root = block_root;
+                continue;
+            }
+            prev_block_root = block_root;
+
+            let offset = match self
+                .try_get_full_block(&block_root)?
+                .ok_or(Error::BlockNotFound(block_root))?
+            {
+                DatabaseBlock::Full(block) => writer
+                    .write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())?,
+                DatabaseBlock::Blinded(block) => writer.write_compressed(
+                    COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK,
+                    &block.as_ssz_bytes(),
+                )?,
+            };
+            block_offsets[(slot - start_slot).as_usize()] = Some(offset);
+
+            if include_blobs {
+                blobs.extend(self.get_blobs(&block_root)?);
+            }
+        }
+        // The frozen block roots iterator stops early if any are missing.
+        if num_slots != (end_slot - iter_start_slot).as_u64() {
+            return Err(EraError::EraUnavailable { era }.into());
+        }
+
+        let state = if !include_state {
+            None
+        } else if end_slot < self.get_split_slot() {
+            self.load_cold_state_by_slot(end_slot)?
+        } else {
+            self.load_split_state().map(Some)?
+        };
+        let state_offset = state
+            .map(|state| writer.write_compressed(COMPRESSED_BEACON_STATE, &state.as_ssz_bytes()))
+            .transpose()?;
+
+        for blobs in blobs {
+            writer.write_compressed(COMPRESSED_BLOB_SIDECAR_LIST, &blobs.as_ssz_bytes())?;
+        }
+
+        writer.write_slot_index(start_slot, &block_offsets)?;
+        if let Some(state_offset) = state_offset {
+            writer.write_slot_index(end_slot, &[Some(state_offset)])?;
+        }
+
+        let file = writer
+            .writer
+            .into_inner()
+            .map_err(|e| EraError::Io(e.into_error()))?;
+        file.sync_all().map_err(EraError::Io)?;
+        fs::rename(&partial_path, &path).map_err(EraError::Io)?;
+
+        debug!(
+            self.log,
+            "Exported era";
+            "era" => era,
+            "blocks" => block_offsets.iter().flatten().count(),
+            "state" => state_offset.is_some(),
+        );
+        Ok(path)
+    }
+
+    /// Check the contents of an era file against the historical roots of the split state.
+    ///
+    /// Blobs are checked against the commitments of their blocks, including their inclusion and
+    /// KZG proofs.
+    pub fn verify_era_file(
+        &self,
+        file: &EraFile<E>,
+        split_state: &BeaconState<E>,
+        kzg: &Kzg,
+    ) -> Result<(), Error> {
+        let era = file.era;
+        let (start_slot, end_slot) = era_block_slots::<E>(era);
+        let expected = era_root(split_state, era).ok_or(EraError::UnknownEra { era })?;
+
+        // Reconstruct the `block_roots` of the state at the end of the era. Slots prior to the
+        // first block hold the root of its parent.
+        let mut blocks = file.blocks.iter().peekable();
+        let mut prev_block_root = file
+            .blocks
+            .first()
+            .map_or(Hash256::zero(), |(_, block)| block.parent_root());
+        let mut block_roots = Vec::with_capacity((end_slot - start_slot).as_usize());
+        for slot in start_slot.as_u64()..end_slot.as_u64() {
+            if let Some((block_root, _)) = blocks.next_if(|(_, block)| block.slot() == slot) {
+                prev_block_root = *block_root;
+            }
+            block_roots.push(prev_block_root);
+        }
+        let block_summary_root =
+            FixedVector::<Hash256, E::SlotsPerHistoricalRoot>::from(block_roots).tree_hash_root();
+
+        // Without the state, the block roots can only be compared to a `HistoricalSummary`.
+        let summary = match &file.state {
+            Some(state) => HistoricalSummary::new(state),
+            None => {
+                let historical_roots = split_state.historical_roots();
+                let summary = split_state
+                    .historical_summaries()
+                    .ok()
+                    .and_then(|summaries| {
+                        summaries.get(((era - 1) as usize).checked_sub(historical_roots.len())?)
+                    })
+                    .ok_or(EraError::MissingEraState { era })?;
+                *summary
+            }
+        };
+        let computed = summary.tree_hash_root();
+        if computed != expected {
+            return Err(EraError::RootMismatch {
+                era,
+                expected,
+                computed,
+            }
+            .into());
+        }
+        if block_summary_root != summary.block_summary_root() {
+            return Err(EraError::RootMismatch {
+                era,
+                expected: summary.block_summary_root(),
+                computed: block_summary_root,
+            }
+            .into());
+        }
+
+        let block_commitments = file
+            .blocks
+            .iter()
+            .map(|(block_root, block)| {
+                let commitments = block
+                    .message()
+                    .body()
+                    .blob_kzg_commitments()
+                    .map(|commitments| commitments.to_vec())
+                    .unwrap_or_default();
+                (*block_root, commitments)
+            })
+            .collect::<HashMap<_, _>>();
+        for blobs in &file.blobs {
+            let Some(block_root) = blobs.first().map(|blob| blob.block_root()) else {
+                continue;
+            };
+            let matches = block_commitments
+                .get(&block_root)
+                .map_or(false, |commitments| {
+                    blobs.len() == commitments.len()
+                        && blobs.iter().zip(commitments).enumerate().all(
+                            |(index, (blob, commitment))| {
+                                blob.index == index as u64 && blob.kzg_commitment == *commitment
+                            },
+                        )
+                });
+            if !matches {
+                return Err(EraError::BlobsMismatch { block_root }.into());
+            }
+            if let Some(blob) = blobs
+                .iter()
+                .find(|blob| !blob.verify_blob_sidecar_inclusion_proof())
+            {
+                return Err(EraError::InvalidBlobInclusionProof {
+                    block_root,
+                    index: blob.index,
+                }
+                .into());
+            }
+            verify_blob_kzg_proofs(kzg, blobs)
+                .map_err(|error| EraError::InvalidBlobKzgProofs { block_root, error })?;
+        }
+        Ok(())
+    }
+
+    /// Import the blocks (and blobs) of a verified era file that are older than the oldest
+    /// stored block, in the same way as backfill sync.
+    ///
+    /// Return the number of blocks imported.
+    pub fn import_era_file(&self, file: EraFile<E>) -> Result<usize, Error> {
+        let Some(anchor_info) = self.get_anchor_info() else {
+            return Ok(0);
+        };
+        let blob_info = self.get_blob_info();
+
+        let mut blobs = file
+            .blobs
+            .into_iter()
+            .filter_map(|blobs| Some((blobs.first()?.block_root(), blobs)))
+            .collect::<HashMap<_, _>>();
+        let blocks = file
+            .blocks
+            .into_iter()
+            .filter(|(_, block)| block.slot() < anchor_info.oldest_block_slot)
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(0);
+        }
+
+        let mut expected_block_root = anchor_info.oldest_block_parent;
+        let mut prev_block_slot = anchor_info.oldest_block_slot;
+        let mut chunk_writer =
+            ChunkWriter::<BlockRoots, _, _>::new(&self.cold_db, prev_block_slot.as_usize())?;
+        let mut new_oldest_blob_slot = blob_info.oldest_blob_slot;
+
+        let mut blob_batch = vec![];
+        let mut cold_batch = vec![];
+        let mut hot_batch = Vec::with_capacity(blocks.len());
+        let num_blocks = blocks.len();
+
+        for (block_root, block) in blocks.into_iter().rev() {
+            if block_root != expected_block_root {
+                return Err(EraError::MismatchedBlockRoot {
+                    block_root,
+                    expected_block_root,
+                }
+                .into());
+            }
+
+            self.blinded_block_as_kv_store_ops(&block_root, &block, &mut hot_batch);
+            if let Some(blobs) = blobs.remove(&block_root) {
+                new_oldest_blob_slot = Some(block.slot());
+                self.blobs_as_kv_store_ops(&block_root, blobs, &mut blob_batch);
+            }
+
+            // Store block roots, including at all skip slots in the freezer DB.
+            for slot in (block.slot().as_usize()..prev_block_slot.as_usize()).rev() {
+                chunk_writer.set(slot, block_root, &mut cold_batch)?;
+            }
+
+            prev_block_slot = block.slot();
+            expected_block_root = block.message().parent_root();
+        }
+        chunk_writer.write(&mut cold_batch)?;
+
+        // Write the blocks before the block roots that point to them.
+        self.blobs_db.do_atomically(blob_batch)?;
+        self.hot_db.do_atomically(hot_batch)?;
+        self.cold_db.do_atomically(cold_batch)?;
+
+        let mut anchor_and_blob_batch = Vec::with_capacity(2);
+        if new_oldest_blob_slot != blob_info.oldest_blob_slot {
+            let new_blob_info = BlobInfo {
+                oldest_blob_slot: new_oldest_blob_slot,
+                ..blob_info.clone()
+            };
+            anchor_and_blob_batch.push(self.compare_and_set_blob_info(blob_info, new_blob_info)?);
+        }
+        let new_anchor = AnchorInfo {
+            oldest_block_slot: prev_block_slot,
+            oldest_block_parent: expected_block_root,
+            ..anchor_info.clone()
+        };
+        anchor_and_blob_batch
+            .push(self.compare_and_set_anchor_info(Some(anchor_info), Some(new_anchor))?);
+        self.hot_db.do_atomically(anchor_and_blob_batch)?;
+
+        info!(
+            self.log,
+            "Imported era";
+            "era" => file.era,
+            "blocks" => num_blocks,
+            "oldest_block_slot" => prev_block_slot,
+        );
+        Ok(num_blocks)
+    }
+
+    /// Verify and import the era files at `paths`, newest first, returning the number of blocks
+    /// imported.
+    ///
+    /// Files that only contain blocks which are already stored are skipped.
+    pub fn import_era_files(&self, paths: &[PathBuf], kzg: &Kzg) -> Result<usize, Error> {
+        let mut eras = paths
+            .iter()
+            .map(|path| {
+                let era = parse_era_filename(path).ok_or_else(|| EraError::InvalidFile {
+                    path: path.clone(),
+                    reason: "invalid filename".into(),
+                })?;
+                Ok((era, path))
+            })
+            .collect::<Result<Vec<_>, EraError>>()?;
+        eras.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let split_state = self.load_split_state()?;
+        let mut num_blocks = 0;
+        for (era, path) in eras {
+            let oldest_block_slot = self.get_oldest_block_slot();
+            if era_block_slots::<E>(era).0 >= oldest_block_slot {
+                debug!(self.log, "Skipping era already in database"; "era" => era);
+                continue;
+            }
+
+            let file = read_era_file::<E>(path, &self.spec)?;
+            self.verify_era_file(&file, &split_state, kzg)?;
+            num_blocks += self.import_era_file(file)?;
+        }
+        Ok(num_blocks)
+    }
+}
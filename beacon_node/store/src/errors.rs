@@ -1,5 +1,8 @@
+use crate::backup::BackupError;
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::era::EraError;
+use crate::hdiff;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -22,6 +25,13 @@ pub enum Error {
     NoContinuationData,
     SplitPointModified(Slot, Slot),
     ConfigError(StoreConfigError),
+    HDiffError(hdiff::Error),
+    /// Failed to compress or decompress a database value.
+    CompressionError(std::io::Error),
+    /// The record of compressed columns stored in the database is invalid.
+    InvalidCompressedColumns(String),
+    BackupError(BackupError),
+    EraError(EraError),
     SchemaMigrationError(String),
     /// The store's `anchor_info` was mutated concurrently, the latest modification wasn't applied.
     AnchorInfoConcurrentMutation,
@@ -109,6 +119,24 @@ impl From<StoreConfigError> for Error {
     }
 }
 
+impl From<hdiff::Error> for Error {
+    fn from(e: hdiff::Error) -> Error {
+        Error::HDiffError(e)
+    }
+}
+
+impl From<BackupError> for Error {
+    fn from(e: BackupError) -> Error {
+        Error::BackupError(e)
+    }
+}
+
+impl From<EraError> for Error {
+    fn from(e: EraError) -> Error {
+        Error::EraError(e)
+    }
+}
+
 impl From<BlockReplayError> for Error {
     fn from(e: BlockReplayError) -> Error {
         Error::BlockReplayError(e)
@@ -1,10 +1,66 @@
 //! Garbage collection process that runs at start-up to clean up the database.
+//!
+//! Also contains the persistent log of blocks and states deleted by the abandoned fork sweeper,
+//! which runs in the background while the node is running.
 use crate::hot_cold_store::HotColdDB;
-use crate::{Error, LevelDB, StoreOp};
+use crate::{
+    get_key_for_col, BeaconNodeBackend, DBColumn, Error, HotStateSummary, ItemStore, KeyValueStore,
+    KeyValueStoreOp, StoreOp,
+};
+use serde::{Deserialize, Serialize};
 use slog::debug;
-use types::EthSpec;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::sync::atomic::{AtomicU64, Ordering};
+use types::{EthSpec, Hash256, Slot};
 
-impl<E> HotColdDB<E, LevelDB<E>, LevelDB<E>>
+/// The maximum number of runs kept in the garbage collection log.
+pub const GC_LOG_MAX_RUNS: usize = 256;
+
+/// Distinguishes the log keys of runs which completed in the same millisecond.
+static GC_RUN_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// A block or state deleted by the garbage collector.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct GcDeletion {
+    pub root: Hash256,
+    pub slot: Slot,
+}
+
+/// A record of a single run of the garbage collector which deleted at least one item.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct GcRun {
+    /// Time at which the run completed, in milliseconds since the UNIX epoch.
+    pub timestamp_millis: u64,
+    /// The split slot of the database at the start of the run.
+    pub split_slot: Slot,
+    /// The slot of the finalized block at the start of the run.
+    ///
+    /// Only items at later slots are considered for deletion.
+    pub finalized_slot: Slot,
+    pub deleted_blocks: Vec<GcDeletion>,
+    pub deleted_states: Vec<GcDeletion>,
+}
+
+impl GcRun {
+    pub fn is_empty(&self) -> bool {
+        self.deleted_blocks.is_empty() && self.deleted_states.is_empty()
+    }
+
+    /// Return a database operation which appends this run to the log.
+    ///
+    /// The run is keyed by its timestamp followed by a sequence number, so that runs are ordered
+    /// by time and never overwrite each other.
+    pub fn as_kv_store_op(&self) -> KeyValueStoreOp {
+        let sequence = GC_RUN_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let mut log_key = self.timestamp_millis.to_be_bytes().to_vec();
+        log_key.extend_from_slice(&sequence.to_be_bytes());
+        let key = get_key_for_col(DBColumn::GarbageCollectionLog.into(), &log_key);
+        KeyValueStoreOp::PutKeyValue(key, self.as_ssz_bytes())
+    }
+}
+
+impl<E> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>
 where
     E: EthSpec,
 {
@@ -37,3 +93,70 @@ where
         Ok(())
     }
 }
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    /// Load the summaries of all non-temporary hot states with slots greater than `min_slot`.
+    pub fn load_hot_state_summaries_after(
+        &self,
+        min_slot: Slot,
+    ) -> Result<Vec<(Hash256, HotStateSummary)>, Error> {
+        let mut summaries = vec![];
+        for res in self
+            .hot_db
+            .iter_column::<Hash256>(DBColumn::BeaconStateSummary)
+        {
+            let (state_root, summary_bytes) = res?;
+            let summary = HotStateSummary::from_ssz_bytes(&summary_bytes)?;
+            if summary.slot > min_slot && self.load_state_temporary_flag(&state_root)?.is_none() {
+                summaries.push((state_root, summary));
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Return database operations which append `run` to the garbage collection log.
+    ///
+    /// The oldest runs are deleted so that at most `GC_LOG_MAX_RUNS` runs are kept.
+    pub fn gc_log_append_ops(&self, run: &GcRun) -> Result<Vec<KeyValueStoreOp>, Error> {
+        let keys = self
+            .hot_db
+            .iter_column_keys::<Vec<u8>>(DBColumn::GarbageCollectionLog)
+            .collect::<Result<Vec<_>, Error>>()?;
+        let excess_runs = (keys.len() + 1).saturating_sub(GC_LOG_MAX_RUNS);
+        let mut ops = keys
+            .iter()
+            .take(excess_runs)
+            .map(|key| {
+                KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::GarbageCollectionLog.into(),
+                    key,
+                ))
+            })
+            .collect::<Vec<_>>();
+        ops.push(run.as_kv_store_op());
+        Ok(ops)
+    }
+
+    /// Load the garbage collection log, oldest run first.
+    ///
+    /// If `limit` is set, only that many of the most recent runs are returned.
+    pub fn load_gc_log(&self, limit: Option<usize>) -> Result<Vec<GcRun>, Error> {
+        let mut runs = self
+            .hot_db
+            .iter_column::<Vec<u8>>(DBColumn::GarbageCollectionLog)
+            .map(|res| {
+                let (_, bytes) = res?;
+                Ok(GcRun::from_ssz_bytes(&bytes)?)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if let Some(limit) = limit {
+            runs.drain(..runs.len().saturating_sub(limit));
+        }
+        Ok(runs)
+    }
+}
@@ -0,0 +1,429 @@
+//! Hierarchical state diffs for the freezer database.
+//!
+//! When a `HierarchyConfig` is set, restore points in the freezer are no longer all stored as full
+//! states. Instead, the restore point grid is divided into layers of increasing size, and only the
+//! restore points aligned with the largest layer are stored as full *snapshots*. Every other
+//! restore point is stored as an `HDiff` relative to the closest restore point of the next layer
+//! up, which in turn may be a diff relative to a larger layer, and so on until a snapshot is
+//! reached.
+//!
+//! The bulk of a modern `BeaconState` is its validator registry and balances, so those are the
+//! fields that get diffed. The validator list is diffed entry-by-entry (validators rarely
+//! change), while the balances are XOR-ed against the base and compressed, which exploits the
+//! fact that most balances only change in their low-order bytes. The remainder of the state is
+//! stored compressed in its entirety.
+use crate::{get_key_for_col, DBColumn, KeyValueStoreOp, PartialBeaconState};
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fmt;
+use std::str::FromStr;
+use types::{milhouse, BeaconState, ChainSpec, EthSpec, Hash256, List, Slot, Unsigned, Validator};
+
+/// Compression level used for the compressed portions of a diff.
+///
+/// Diffs are written once per restore point during finalization, so we favour speed.
+const HDIFF_COMPRESSION_LEVEL: i32 = 1;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The exponents of a hierarchy are empty, not strictly increasing, or too large.
+    InvalidHierarchy,
+    Compression(std::io::Error),
+    Decompression(std::io::Error),
+    /// The number of bytes in a balances diff is not a multiple of 8.
+    InvalidBalancesLength,
+    /// The target state has fewer validators than the base state.
+    ValidatorRegistryShrunk {
+        base: usize,
+        target: usize,
+    },
+    /// A validator diff refers to an index that is neither present nor next in line.
+    InvalidValidatorIndex {
+        index: u64,
+        len: usize,
+    },
+    Milhouse(milhouse::Error),
+}
+
+impl From<milhouse::Error> for Error {
+    fn from(e: milhouse::Error) -> Self {
+        Error::Milhouse(e)
+    }
+}
+
+/// Configuration for the layers of the state diff hierarchy.
+///
+/// Each exponent `e` defines a layer containing every `2^e`-th slot. The smallest exponent
+/// determines the restore point frequency, and the largest determines the snapshot frequency.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct HierarchyConfig {
+    pub exponents: Vec<u8>,
+}
+
+impl Default for HierarchyConfig {
+    fn default() -> Self {
+        HierarchyConfig {
+            exponents: vec![5, 9, 11, 13, 16, 18, 21],
+        }
+    }
+}
+
+/// How a state at a given slot is stored in the freezer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageStrategy {
+    /// Store the full state.
+    Snapshot,
+    /// Store a diff relative to the state at the given slot.
+    DiffFrom(Slot),
+    /// Don't store the state, and reconstruct it by replaying blocks from the given slot.
+    ReplayFrom(Slot),
+}
+
+impl HierarchyConfig {
+    /// Check that the exponents are non-empty, strictly increasing and representable as `u64`
+    /// slot counts.
+    pub fn validate(&self) -> Result<(), Error> {
+        let Some(&largest) = self.exponents.last() else {
+            return Err(Error::InvalidHierarchy);
+        };
+        if largest >= 64 || self.exponents.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(Error::InvalidHierarchy);
+        }
+        Ok(())
+    }
+
+    /// The number of slots between states in each layer, from the smallest layer to the largest.
+    pub fn moduli(&self) -> Vec<u64> {
+        self.exponents.iter().map(|exp| 1 << exp).collect()
+    }
+
+    /// The number of slots between restore points, i.e. the size of the smallest layer.
+    pub fn restore_point_interval(&self) -> u64 {
+        self.exponents.first().map_or(1, |exp| 1 << exp)
+    }
+
+    /// Determine how the state at `slot` should be stored.
+    pub fn storage_strategy(&self, slot: Slot) -> StorageStrategy {
+        let moduli = self.moduli();
+        let Some(&snapshot_modulus) = moduli.last() else {
+            return StorageStrategy::Snapshot;
+        };
+
+        if slot % snapshot_modulus == 0 {
+            return StorageStrategy::Snapshot;
+        }
+
+        // Find the largest layer that `slot` belongs to, and diff against the previous state of
+        // the layer above it.
+        for pair in moduli.windows(2).rev() {
+            let (layer, parent_layer) = (pair[0], pair[1]);
+            if slot % layer == 0 {
+                return StorageStrategy::DiffFrom(slot / parent_layer * parent_layer);
+            }
+        }
+
+        let smallest_layer = moduli[0];
+        StorageStrategy::ReplayFrom(slot / smallest_layer * smallest_layer)
+    }
+}
+
+impl FromStr for HierarchyConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let exponents = s
+            .split(',')
+            .map(|exp| {
+                exp.trim()
+                    .parse::<u8>()
+                    .map_err(|e| format!("invalid hierarchy exponent {exp:?}: {e:?}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let config = HierarchyConfig { exponents };
+        config
+            .validate()
+            .map_err(|_| "hierarchy exponents must be strictly increasing and < 64".to_string())?;
+        Ok(config)
+    }
+}
+
+impl fmt::Display for HierarchyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let exponents = self
+            .exponents
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", exponents.join(","))
+    }
+}
+
+/// A single changed or appended entry of the validator registry.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct ValidatorDiffEntry {
+    pub index: u64,
+    pub validator: Validator,
+}
+
+/// Diff between two states' validator registries, in increasing order of index.
+#[derive(Debug, Clone, PartialEq, Default, Encode, Decode)]
+pub struct ValidatorsDiff {
+    pub changes: Vec<ValidatorDiffEntry>,
+}
+
+impl ValidatorsDiff {
+    pub fn compute(
+        base: &List<Validator, impl Unsigned>,
+        target: &List<Validator, impl Unsigned>,
+    ) -> Result<Self, Error> {
+        if target.len() < base.len() {
+            return Err(Error::ValidatorRegistryShrunk {
+                base: base.len(),
+                target: target.len(),
+            });
+        }
+
+        let changes = target
+            .iter()
+            .enumerate()
+            .filter(|(index, validator)| base.get(*index) != Some(*validator))
+            .map(|(index, validator)| ValidatorDiffEntry {
+                index: index as u64,
+                validator: validator.clone(),
+            })
+            .collect();
+
+        Ok(Self { changes })
+    }
+
+    pub fn apply(&self, validators: &mut List<Validator, impl Unsigned>) -> Result<(), Error> {
+        for entry in &self.changes {
+            let len = validators.len();
+            let index = entry.index as usize;
+            if let Some(validator) = validators.get_mut(index) {
+                *validator = entry.validator.clone();
+            } else if index == len {
+                validators.push(entry.validator.clone())?;
+            } else {
+                return Err(Error::InvalidValidatorIndex {
+                    index: entry.index,
+                    len,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Diff between two lists of balances.
+///
+/// Each target balance is XOR-ed with the base balance at the same index (or zero for new
+/// validators), and the result is compressed.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct BalancesDiff {
+    pub bytes: Vec<u8>,
+}
+
+impl BalancesDiff {
+    pub fn compute(
+        base: &List<u64, impl Unsigned>,
+        target: &List<u64, impl Unsigned>,
+    ) -> Result<Self, Error> {
+        let mut xor_bytes = Vec::with_capacity(target.len() * 8);
+        for (index, balance) in target.iter().enumerate() {
+            let base_balance = base.get(index).copied().unwrap_or(0);
+            xor_bytes.extend_from_slice(&(balance ^ base_balance).to_le_bytes());
+        }
+        let bytes = zstd::encode_all(xor_bytes.as_slice(), HDIFF_COMPRESSION_LEVEL)
+            .map_err(Error::Compression)?;
+        Ok(Self { bytes })
+    }
+
+    pub fn apply<N: Unsigned>(&self, base: &List<u64, N>) -> Result<List<u64, N>, Error> {
+        let xor_bytes = zstd::decode_all(self.bytes.as_slice()).map_err(Error::Decompression)?;
+        if xor_bytes.len() % 8 != 0 {
+            return Err(Error::InvalidBalancesLength);
+        }
+
+        let balances = xor_bytes
+            .chunks_exact(8)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let mut xor = [0; 8];
+                xor.copy_from_slice(chunk);
+                u64::from_le_bytes(xor) ^ base.get(index).copied().unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(List::new(balances)?)
+    }
+}
+
+/// Hierarchical diff between a state and the state at `base_slot`.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct HDiff {
+    /// The slot of the state that this diff must be applied to.
+    pub base_slot: Slot,
+    /// Compressed SSZ bytes of the `PartialBeaconState`, with empty validators and balances.
+    state_bytes: Vec<u8>,
+    validators: ValidatorsDiff,
+    balances: BalancesDiff,
+}
+
+impl HDiff {
+    pub fn compute<E: EthSpec>(
+        base: &BeaconState<E>,
+        target: &BeaconState<E>,
+    ) -> Result<Self, Error> {
+        let mut partial_state = PartialBeaconState::from_state_forgetful(target);
+        *partial_state.validators_mut() = List::empty();
+        *partial_state.balances_mut() = List::empty();
+        let state_bytes = zstd::encode_all(
+            partial_state.as_ssz_bytes().as_slice(),
+            HDIFF_COMPRESSION_LEVEL,
+        )
+        .map_err(Error::Compression)?;
+
+        Ok(Self {
+            base_slot: base.slot(),
+            state_bytes,
+            validators: ValidatorsDiff::compute(base.validators(), target.validators())?,
+            balances: BalancesDiff::compute(base.balances(), target.balances())?,
+        })
+    }
+
+    /// Decompress the partial state stored in this diff.
+    ///
+    /// The returned state has empty `validators` and `balances`, which should be filled in by
+    /// `apply_registry` once the vector fields have been loaded and it has been converted to a
+    /// full `BeaconState`.
+    pub fn partial_state<E: EthSpec>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<PartialBeaconState<E>, crate::Error> {
+        let bytes = zstd::decode_all(self.state_bytes.as_slice()).map_err(Error::Decompression)?;
+        Ok(PartialBeaconState::from_ssz_bytes(&bytes, spec)?)
+    }
+
+    /// Set the validators and balances of `target` by applying this diff to `base`.
+    pub fn apply_registry<E: EthSpec>(
+        &self,
+        base: &BeaconState<E>,
+        target: &mut BeaconState<E>,
+    ) -> Result<(), Error> {
+        let mut validators = base.validators().clone();
+        self.validators.apply(&mut validators)?;
+        *target.validators_mut() = validators;
+        *target.balances_mut() = self.balances.apply(base.balances())?;
+        Ok(())
+    }
+
+    /// Prepare the diff for storage in the KV database, keyed by the target state root.
+    pub fn as_kv_store_op(&self, state_root: Hash256) -> KeyValueStoreOp {
+        let db_key = get_key_for_col(DBColumn::BeaconStateDiff.into(), state_root.as_bytes());
+        KeyValueStoreOp::PutKeyValue(db_key, self.as_ssz_bytes())
+    }
+
+    pub fn from_store_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::typenum::U1099511627776;
+
+    type Limit = U1099511627776;
+
+    fn config(exponents: &[u8]) -> HierarchyConfig {
+        HierarchyConfig {
+            exponents: exponents.to_vec(),
+        }
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        HierarchyConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn invalid_configs() {
+        assert!(config(&[]).validate().is_err());
+        assert!(config(&[5, 5]).validate().is_err());
+        assert!(config(&[9, 5]).validate().is_err());
+        assert!(config(&[5, 64]).validate().is_err());
+        assert!("5,x".parse::<HierarchyConfig>().is_err());
+    }
+
+    #[test]
+    fn parse_and_display_roundtrip() {
+        let config = "5, 9,11".parse::<HierarchyConfig>().unwrap();
+        assert_eq!(config.exponents, vec![5, 9, 11]);
+        assert_eq!(config.to_string(), "5,9,11");
+    }
+
+    #[test]
+    fn storage_strategies() {
+        // Layers of 8, 32 and 128 slots.
+        let config = config(&[3, 5, 7]);
+        let strategy = |slot| config.storage_strategy(Slot::new(slot));
+
+        assert_eq!(strategy(0), StorageStrategy::Snapshot);
+        assert_eq!(strategy(256), StorageStrategy::Snapshot);
+        assert_eq!(strategy(160), StorageStrategy::DiffFrom(Slot::new(128)));
+        assert_eq!(strategy(168), StorageStrategy::DiffFrom(Slot::new(160)));
+        assert_eq!(strategy(8), StorageStrategy::DiffFrom(Slot::new(0)));
+        assert_eq!(strategy(170), StorageStrategy::ReplayFrom(Slot::new(168)));
+        assert_eq!(strategy(7), StorageStrategy::ReplayFrom(Slot::new(0)));
+    }
+
+    #[test]
+    fn single_layer_only_snapshots() {
+        let config = config(&[5]);
+        assert_eq!(
+            config.storage_strategy(Slot::new(64)),
+            StorageStrategy::Snapshot
+        );
+        assert_eq!(
+            config.storage_strategy(Slot::new(65)),
+            StorageStrategy::ReplayFrom(Slot::new(64))
+        );
+    }
+
+    #[test]
+    fn balances_diff_roundtrip() {
+        let base = List::<u64, Limit>::new(vec![32_000_000_000, 31_999_999_999, 0]).unwrap();
+        let target =
+            List::<u64, Limit>::new(vec![32_000_012_345, 31_000_000_000, 1, 32_000_000_000])
+                .unwrap();
+
+        let diff = BalancesDiff::compute(&base, &target).unwrap();
+        assert_eq!(diff.apply(&base).unwrap(), target);
+    }
+
+    #[test]
+    fn validators_diff_roundtrip() {
+        let validator = |effective_balance| Validator {
+            effective_balance,
+            ..Validator::default()
+        };
+        let base = List::<Validator, Limit>::new(vec![validator(1), validator(2)]).unwrap();
+        let target =
+            List::<Validator, Limit>::new(vec![validator(1), validator(3), validator(4)]).unwrap();
+
+        let diff = ValidatorsDiff::compute(&base, &target).unwrap();
+        assert_eq!(diff.changes.len(), 2);
+
+        let mut validators = base.clone();
+        diff.apply(&mut validators).unwrap();
+        assert_eq!(validators, target);
+
+        assert!(matches!(
+            ValidatorsDiff::compute(&target, &base),
+            Err(Error::ValidatorRegistryShrunk { .. })
+        ));
+    }
+}
@@ -6,10 +6,10 @@ use crate::config::{
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::hdiff::{HDiff, HierarchyConfig, StorageStrategy};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
@@ -19,11 +19,10 @@ use crate::metadata::{
 use crate::metrics;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_key_for_col, BeaconNodeBackend, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -154,6 +153,10 @@ pub enum HotColdDBError {
         slots_per_historical_root: u64,
         slots_per_epoch: u64,
     },
+    InvalidHierarchyConfig {
+        hierarchy_config: HierarchyConfig,
+        slots_per_restore_point: u64,
+    },
     ZeroEpochsPerBlobPrune,
     BlobPruneLogicError,
     RestorePointBlockHashError(BeaconStateError),
@@ -196,7 +199,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
@@ -211,14 +214,18 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         log: Logger,
     ) -> Result<Arc<Self>, Error> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        Self::verify_hierarchy_config(
+            config.slots_per_restore_point,
+            config.hierarchy_config.as_ref(),
+        )?;
 
         let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
             blob_info: RwLock::new(BlobInfo::default()),
-            cold_db: LevelDB::open(cold_path)?,
-            blobs_db: LevelDB::open(blobs_db_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: BeaconNodeBackend::open(&config, cold_path)?,
+            blobs_db: BeaconNodeBackend::open(&config, blobs_db_path)?,
+            hot_db: BeaconNodeBackend::open(&config, hot_path)?,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
@@ -334,30 +341,15 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
         Ok(db)
     }
+}
 
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconStateTemporary)
     }
-}
 
-impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
     pub fn update_finalized_state(
         &self,
         state_root: Hash256,
@@ -1255,6 +1247,23 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         state_root: &Hash256,
         state: &BeaconState<E>,
         ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        self.store_cold_state_with_hierarchy(
+            state_root,
+            state,
+            self.config.hierarchy_config.as_ref(),
+            ops,
+        )
+    }
+
+    /// Store a pre-finalization state in the freezer database, using `hierarchy_config` to decide
+    /// whether a restore point is stored as a full state or as a diff.
+    fn store_cold_state_with_hierarchy(
+        &self,
+        state_root: &Hash256,
+        state: &BeaconState<E>,
+        hierarchy_config: Option<&HierarchyConfig>,
+        ops: &mut Vec<KeyValueStoreOp>,
     ) -> Result<(), Error> {
         ops.push(ColdStateSummary { slot: state.slot() }.as_kv_store_op(*state_root));
 
@@ -1269,10 +1278,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             "state_root" => format!("{:?}", state_root)
         );
 
-        // 1. Convert to PartialBeaconState and store that in the DB.
-        let partial_state = PartialBeaconState::from_state_forgetful(state);
-        let op = partial_state.as_kv_store_op(*state_root);
-        ops.push(op);
+        // 1. Store a diff against the base state if the hierarchy calls for one and the base is
+        // available. Otherwise convert to PartialBeaconState and store that in the DB.
+        let hdiff = match hierarchy_config.map(|config| config.storage_strategy(state.slot())) {
+            Some(StorageStrategy::DiffFrom(base_slot)) => self
+                .load_hdiff_base(base_slot, state)?
+                .map(|base_state| HDiff::compute(&base_state, state))
+                .transpose()?,
+            _ => None,
+        };
+        if let Some(hdiff) = hdiff {
+            ops.push(hdiff.as_kv_store_op(*state_root));
+        } else {
+            let partial_state = PartialBeaconState::from_state_forgetful(state);
+            ops.push(partial_state.as_kv_store_op(*state_root));
+        }
 
         // 2. Store updated vector entries.
         // Block roots need to be written here as well as by the `ChunkWriter` in `migrate_db`
@@ -1325,24 +1345,82 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load a restore point state by its `state_root`.
-    fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
+    ///
+    /// Restore points stored as diffs are reconstructed by recursively loading their base state.
+    pub(crate) fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
+        if let Some(hdiff_bytes) = self
+            .cold_db
+            .get_bytes(DBColumn::BeaconStateDiff.into(), state_root.as_bytes())?
+        {
+            let hdiff = HDiff::from_store_bytes(&hdiff_bytes)?;
+            let base_state = self.load_restore_point_by_index(
+                hdiff.base_slot.as_u64() / self.config.slots_per_restore_point,
+            )?;
+
+            let mut state = self.fill_in_partial_state(hdiff.partial_state(&self.spec)?)?;
+            hdiff.apply_registry(&base_state, &mut state)?;
+            state.apply_pending_mutations()?;
+            return Ok(state);
+        }
+
         let partial_state_bytes = self
             .cold_db
             .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
             .ok_or(HotColdDBError::MissingRestorePoint(*state_root))?;
-        let mut partial_state: PartialBeaconState<E> =
+        let partial_state: PartialBeaconState<E> =
             PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
 
-        // Fill in the fields of the partial state.
+        let mut state = self.fill_in_partial_state(partial_state)?;
+        state.apply_pending_mutations()?;
+        Ok(state)
+    }
+
+    /// Load the vector fields of a partial state from the freezer and convert it to a full state.
+    fn fill_in_partial_state(
+        &self,
+        mut partial_state: PartialBeaconState<E>,
+    ) -> Result<BeaconState<E>, Error> {
         partial_state.load_block_roots(&self.cold_db, &self.spec)?;
         partial_state.load_state_roots(&self.cold_db, &self.spec)?;
         partial_state.load_historical_roots(&self.cold_db, &self.spec)?;
         partial_state.load_randao_mixes(&self.cold_db, &self.spec)?;
         partial_state.load_historical_summaries(&self.cold_db, &self.spec)?;
 
-        let mut state: BeaconState<E> = partial_state.try_into()?;
-        state.apply_pending_mutations()?;
-        Ok(state)
+        partial_state.try_into()
+    }
+
+    /// Load the state at `base_slot` for use as the base of a diff for `state`.
+    ///
+    /// The base is usually a restore point in the freezer. During migration it may instead be a
+    /// finalized state that is yet to be frozen, in which case it is loaded from the hot database
+    /// as long as it will be retained as a restore point.
+    ///
+    /// Return `None` if the base state is unavailable, e.g. due to a gap in the historic state
+    /// database after checkpoint sync. The caller should store a full state instead.
+    fn load_hdiff_base(
+        &self,
+        base_slot: Slot,
+        state: &BeaconState<E>,
+    ) -> Result<Option<BeaconState<E>>, Error> {
+        let restore_point_key =
+            Self::restore_point_key(base_slot.as_u64() / self.config.slots_per_restore_point);
+        if let Some(RestorePointHash { state_root }) = self.cold_db.get(&restore_point_key)? {
+            return self.load_restore_point(&state_root).map(Some);
+        }
+
+        let retained = self
+            .get_anchor_info()
+            .map_or(true, |anchor| base_slot >= anchor.state_upper_limit);
+        if base_slot < self.get_split_slot() || !retained {
+            return Ok(None);
+        }
+
+        match state.get_state_root(base_slot) {
+            Ok(base_state_root) => Ok(self
+                .load_hot_state(base_state_root)?
+                .map(|(base_state, _)| base_state)),
+            Err(_) => Ok(None),
+        }
     }
 
     /// Load a restore point state by its `restore_point_index`.
@@ -1552,6 +1630,67 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Fetch the best light client update of `sync_committee_period` from the store.
+    pub fn get_light_client_update(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdate<E>>, Error> {
+        match self.hot_db.get_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+        )? {
+            Some(bytes) => {
+                // The update is prefixed by its attested slot, which determines its fork.
+                if bytes.len() < 8 {
+                    return Err(Error::InvalidBytes);
+                }
+                let (slot_bytes, update_bytes) = bytes.split_at(8);
+                let attested_slot = Slot::from_ssz_bytes(slot_bytes)?;
+                let fork_name = self.spec.fork_name_at_slot::<E>(attested_slot);
+                Ok(Some(LightClientUpdate::from_ssz_bytes(
+                    update_bytes,
+                    fork_name,
+                )?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the best light client updates of up to `count` consecutive sync committee periods,
+    /// starting at `start_period`.
+    ///
+    /// The updates stop at the first period for which no update is stored.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<E>>, Error> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            match self.get_light_client_update(period)? {
+                Some(update) => updates.push(update),
+                None => break,
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Store `update` as the best light client update of `sync_committee_period`, replacing any
+    /// update already stored for that period.
+    pub fn put_light_client_update(
+        &self,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<E>,
+    ) -> Result<(), Error> {
+        let mut bytes = update.attested_header_slot().as_ssz_bytes();
+        bytes.extend_from_slice(&update.as_ssz_bytes());
+        self.hot_db.put_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+            &bytes,
+        )
+    }
+
     /// Get a reference to the `ChainSpec` used by the database.
     pub fn get_chain_spec(&self) -> &ChainSpec {
         &self.spec
@@ -1876,7 +2015,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load the state root of a restore point.
-    fn load_restore_point_hash(&self, restore_point_index: u64) -> Result<Hash256, Error> {
+    pub(crate) fn load_restore_point_hash(
+        &self,
+        restore_point_index: u64,
+    ) -> Result<Hash256, Error> {
         let key = Self::restore_point_key(restore_point_index);
         self.cold_db
             .get(&key)?
@@ -1931,6 +2073,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// Verify that a parsed config is valid.
     fn verify_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        Self::verify_hierarchy_config(
+            config.slots_per_restore_point,
+            config.hierarchy_config.as_ref(),
+        )?;
         Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)
     }
 
@@ -1964,6 +2110,28 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Check that the hierarchy config (if any) is valid and aligned with the restore points.
+    ///
+    /// The smallest layer of the hierarchy must coincide with the restore point grid, so that
+    /// every snapshot and diff base is also a restore point.
+    fn verify_hierarchy_config(
+        slots_per_restore_point: u64,
+        hierarchy_config: Option<&HierarchyConfig>,
+    ) -> Result<(), HotColdDBError> {
+        match hierarchy_config {
+            Some(hierarchy_config)
+                if hierarchy_config.validate().is_err()
+                    || hierarchy_config.restore_point_interval() != slots_per_restore_point =>
+            {
+                Err(HotColdDBError::InvalidHierarchyConfig {
+                    hierarchy_config: hierarchy_config.clone(),
+                    slots_per_restore_point,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     // Check that epochs_per_blob_prune is at least 1 epoch to avoid attempting to prune the same
     // epochs over and over again.
     fn verify_epochs_per_blob_prune(epochs_per_blob_prune: u64) -> Result<(), HotColdDBError> {
@@ -2439,6 +2607,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
         let columns = [
             DBColumn::BeaconState,
+            DBColumn::BeaconStateDiff,
             DBColumn::BeaconStateSummary,
             DBColumn::BeaconRestorePoint,
             DBColumn::BeaconStateRoots,
@@ -2486,6 +2655,87 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Re-encode every restore point in the freezer database according to `hierarchy_config`.
+    ///
+    /// Passing `None` converts all diffs back into full states. Restore points are rewritten in
+    /// ascending slot order, so that the base of each new diff has already been converted by the
+    /// time it is needed. Loading restore points doesn't depend on the configured hierarchy, so
+    /// this is safe to interrupt and re-run.
+    ///
+    /// The in-memory config of this store is *not* updated, so the database should be re-opened
+    /// after conversion.
+    pub fn convert_freezer_hierarchy(
+        &self,
+        hierarchy_config: Option<HierarchyConfig>,
+    ) -> Result<(), Error> {
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        Self::verify_hierarchy_config(slots_per_restore_point, hierarchy_config.as_ref())?;
+
+        // Restore point keys are big-endian indices, so the column is iterated in slot order.
+        let restore_points = self
+            .cold_db
+            .iter_column::<Hash256>(DBColumn::BeaconRestorePoint)
+            .map(|res| {
+                let (_, bytes) = res?;
+                RestorePointHash::from_store_bytes(&bytes).map(|r| r.state_root)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        info!(
+            self.log,
+            "Converting freezer states";
+            "num_restore_points" => restore_points.len(),
+            "hierarchy" => hierarchy_config.as_ref().map_or("none".to_string(), |h| h.to_string()),
+        );
+
+        for (i, state_root) in restore_points.iter().enumerate() {
+            let state = self.load_restore_point(state_root)?;
+
+            // Delete both representations before storing the new one, so that a stale diff can't
+            // shadow a new full state (or vice versa).
+            let mut ops = vec![
+                KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconState.into(),
+                    state_root.as_bytes(),
+                )),
+                KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconStateDiff.into(),
+                    state_root.as_bytes(),
+                )),
+            ];
+            self.store_cold_state_with_hierarchy(
+                state_root,
+                &state,
+                hierarchy_config.as_ref(),
+                &mut ops,
+            )?;
+            self.cold_db.do_atomically(ops)?;
+
+            if (i + 1) % 256 == 0 {
+                info!(
+                    self.log,
+                    "Freezer conversion in progress";
+                    "slot" => state.slot(),
+                    "remaining" => restore_points.len() - i - 1,
+                );
+            }
+        }
+
+        self.hot_db.put(
+            &CONFIG_KEY,
+            &OnDiskStoreConfig {
+                slots_per_restore_point,
+                hierarchy_config,
+            },
+        )?;
+
+        // Reclaim the space used by the previous representation.
+        self.cold_db.compact()?;
+
+        info!(self.log, "Freezer conversion complete");
+        Ok(())
+    }
+
     /// Prune states from the hot database which are prior to the split.
     ///
     /// This routine is important for cleaning up advanced states which are stored in the database
@@ -2751,7 +3001,7 @@ fn no_state_root_iter() -> Option<std::iter::Empty<Result<(Hash256, Slot), Error
 pub struct HotStateSummary {
     pub slot: Slot,
     pub latest_block_root: Hash256,
-    epoch_boundary_state_root: Hash256,
+    pub(crate) epoch_boundary_state_root: Hash256,
 }
 
 impl StoreItem for HotStateSummary {
@@ -7,6 +7,7 @@ use leveldb::database::Database;
 use leveldb::error::Error as LevelDBError;
 use leveldb::iterator::{Iterable, KeyIterator, LevelDBIterator};
 use leveldb::options::{Options, ReadOptions, WriteOptions};
+use leveldb::snapshots::Snapshots;
 use parking_lot::Mutex;
 use std::marker::PhantomData;
 use std::path::Path;
@@ -234,6 +235,16 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        let snapshot = self.db.snapshot();
+        let iter = snapshot.iter(self.read_options());
+        iter.seek_to_first();
+
+        f(Box::new(iter.map(|(bytes_key, value)| {
+            Ok((bytes_key.into_vec(), value))
+        })))
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
@@ -282,6 +293,10 @@ impl BytesKey {
     pub fn from_vec(key: Vec<u8>) -> Self {
         Self { key }
     }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.key
+    }
 }
 
 impl From<LevelDBError> for Error {
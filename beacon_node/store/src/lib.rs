@@ -2,19 +2,24 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or redb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
 //! tests for implementation examples.
+pub mod backend;
+pub mod backup;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
+pub mod compression;
 pub mod config;
 pub mod consensus_context;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
-mod garbage_collection;
+pub mod garbage_collection;
+pub mod hdiff;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -23,25 +28,30 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+mod redb_store;
 pub mod state_cache;
+pub mod verify;
 
 pub mod iter;
 
+pub use self::backend::{BeaconNodeBackend, RawBackend};
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
 pub use self::consensus_context::OnDiskConsensusContext;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+pub use self::redb_store::Redb;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
 pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoEnumIterator, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a, K> = Box<dyn Iterator<Item = Result<(K, Vec<u8>), Error>> + 'a>;
@@ -115,6 +125,19 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
 
     /// Iterate through all keys in a particular column.
     fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K>;
+
+    /// Call `f` with an iterator over every entry in the database as of a single point in time.
+    ///
+    /// Keys include their column prefix, and values are returned exactly as they are stored.
+    /// The default implementation iterates over each column in turn, and is only consistent if
+    /// the database isn't modified concurrently.
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        f(Box::new(DBColumn::iter().flat_map(move |column| {
+            self.iter_raw_entries(column, &[]).map(move |res| {
+                res.map(|(key, value)| (get_key_for_col(column.into(), &key), value))
+            })
+        })))
+    }
 }
 
 pub trait Key: Sized + 'static {
@@ -214,7 +237,9 @@ pub enum StoreOp<'a, E: EthSpec> {
 }
 
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr, EnumString, EnumIter, Serialize, Deserialize,
+)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
@@ -233,6 +258,9 @@ pub enum DBColumn {
     /// and then made non-temporary by the deletion of their state root from this column.
     #[strum(serialize = "bst")]
     BeaconStateTemporary,
+    /// For hierarchical diffs of frozen states, keyed by state root.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
     /// Execution payloads for blocks more recent than the finalized checkpoint.
     #[strum(serialize = "exp")]
     ExecPayload,
@@ -267,6 +295,15 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For the log of blocks and states deleted by the abandoned fork sweeper.
+    #[strum(serialize = "bgc")]
+    GarbageCollectionLog,
+    /// For the best light client update of each sync committee period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
+    /// For the reputations of known peers, persisted across restarts.
+    #[strum(serialize = "rep")]
+    PeerReputation,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -296,6 +333,7 @@ impl DBColumn {
             | Self::BeaconBlob
             | Self::BeaconStateSummary
             | Self::BeaconStateTemporary
+            | Self::BeaconStateDiff
             | Self::ExecPayload
             | Self::BeaconChain
             | Self::OpPool
@@ -304,12 +342,15 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
+            | Self::PeerReputation
             | Self::OptimisticTransitionBlock => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
             | Self::BeaconHistoricalSummaries
-            | Self::BeaconRandaoMixes => 8,
+            | Self::BeaconRandaoMixes
+            | Self::LightClientUpdate => 8,
+            Self::GarbageCollectionLog => 16,
         }
     }
 }
@@ -389,6 +430,169 @@ mod tests {
         test_impl(store);
     }
 
+    #[test]
+    fn redb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = Redb::open(path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[test]
+    fn redb_iter_column() {
+        let dir = tempdir().unwrap();
+        let store = Redb::<MinimalEthSpec>::open(dir.path()).unwrap();
+        let keys = (0..4).map(Hash256::from_low_u64_be).collect::<Vec<_>>();
+
+        for key in &keys {
+            store.put(key, &StorableThing { a: 1, b: 2 }).unwrap();
+        }
+        // Entries in neighbouring columns must not be returned.
+        store
+            .put_bytes(DBColumn::BeaconMeta.into(), keys[0].as_bytes(), &[0])
+            .unwrap();
+
+        let iterated = store
+            .iter_column_keys::<Hash256>(DBColumn::BeaconBlock)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(iterated, keys);
+
+        let from_two = store
+            .iter_column_from::<Hash256>(DBColumn::BeaconBlock, keys[2].as_bytes())
+            .count();
+        assert_eq!(from_two, 2);
+    }
+
+    #[test]
+    fn backend_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        drop(RawBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).unwrap());
+
+        assert!(matches!(
+            RawBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, path),
+            Err(Error::ConfigError(
+                config::StoreConfigError::MismatchedBackend { .. }
+            ))
+        ));
+        RawBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).unwrap();
+    }
+
+    #[test]
+    fn compressed_backend() {
+        let dir = tempdir().unwrap();
+        let config = StoreConfig {
+            compression: Some(compression::CompressionConfig::default()),
+            ..StoreConfig::default()
+        };
+        let store = BeaconNodeBackend::<MinimalEthSpec>::open(&config, dir.path()).unwrap();
+        let key = Hash256::random();
+        let value = vec![7; 4096];
+
+        store
+            .put_bytes(DBColumn::BeaconBlock.into(), key.as_bytes(), &value)
+            .unwrap();
+        store
+            .do_atomically(vec![KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(DBColumn::BeaconMeta.into(), key.as_bytes()),
+                value.clone(),
+            )])
+            .unwrap();
+
+        // Only the configured columns are compressed on disk.
+        let raw_block = store
+            .raw()
+            .get_bytes(DBColumn::BeaconBlock.into(), key.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert!(compression::is_compressed(&raw_block));
+        let raw_meta = store
+            .raw()
+            .get_bytes(DBColumn::BeaconMeta.into(), key.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(raw_meta, value);
+
+        assert_eq!(
+            store
+                .get_bytes(DBColumn::BeaconBlock.into(), key.as_bytes())
+                .unwrap(),
+            Some(value.clone())
+        );
+        let (_, iterated) = store
+            .iter_column::<Hash256>(DBColumn::BeaconBlock)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(iterated, value);
+
+        // Compressed values remain readable after compression is disabled.
+        drop(store);
+        let store =
+            BeaconNodeBackend::<MinimalEthSpec>::open(&StoreConfig::default(), dir.path()).unwrap();
+        assert_eq!(
+            store
+                .get_bytes(DBColumn::BeaconBlock.into(), key.as_bytes())
+                .unwrap(),
+            Some(value)
+        );
+    }
+
+    #[test]
+    fn uncompressed_column_read_as_is() {
+        let dir = tempdir().unwrap();
+        let config = StoreConfig {
+            compression: Some(compression::CompressionConfig::default()),
+            ..StoreConfig::default()
+        };
+        let store = BeaconNodeBackend::<MinimalEthSpec>::open(&config, dir.path()).unwrap();
+        let key = Hash256::random();
+
+        // A value that looks compressed, in a column that has never been compressed.
+        let value = compression::CompressionConfig::default()
+            .compress(&[7; 4096])
+            .unwrap();
+        store
+            .raw()
+            .put_bytes(DBColumn::BeaconBlob.into(), key.as_bytes(), &value)
+            .unwrap();
+
+        assert_eq!(
+            store
+                .get_bytes(DBColumn::BeaconBlob.into(), key.as_bytes())
+                .unwrap(),
+            Some(value.clone())
+        );
+        let (_, iterated) = store
+            .iter_column::<Hash256>(DBColumn::BeaconBlob)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(iterated, value);
+    }
+
+    #[test]
+    fn compressed_columns_persisted() {
+        let dir = tempdir().unwrap();
+        let config = StoreConfig {
+            compression: Some(compression::CompressionConfig {
+                columns: vec![DBColumn::BeaconState],
+                ..compression::CompressionConfig::default()
+            }),
+            ..StoreConfig::default()
+        };
+        drop(BeaconNodeBackend::<MinimalEthSpec>::open(&config, dir.path()).unwrap());
+
+        let store =
+            BeaconNodeBackend::<MinimalEthSpec>::open(&StoreConfig::default(), dir.path()).unwrap();
+        assert_eq!(
+            compression::load_compressed_columns(store.raw()).unwrap(),
+            vec![DBColumn::BeaconState]
+        );
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
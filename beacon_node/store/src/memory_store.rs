@@ -1,6 +1,6 @@
 use crate::{
     get_key_for_col, leveldb_store::BytesKey, ColumnIter, ColumnKeyIter, DBColumn, Error,
-    ItemStore, Key, KeyValueStore, KeyValueStoreOp,
+    ItemStore, Key, KeyValueStore, KeyValueStoreOp, RawEntryIter,
 };
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use std::collections::BTreeMap;
@@ -111,6 +111,15 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
     fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
         Ok(())
     }
+
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        let snapshot = self.db.read().clone();
+        f(Box::new(
+            snapshot
+                .into_iter()
+                .map(|(key, value)| Ok((key.into_vec(), value))),
+        ))
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for MemoryStore<E> {}
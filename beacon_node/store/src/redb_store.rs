@@ -0,0 +1,276 @@
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use crate::{
+    get_key_for_col, metrics, ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, Key,
+    KeyValueStore, KeyValueStoreOp, RawEntryIter, RawKeyIter,
+};
+use parking_lot::{Mutex, MutexGuard};
+use redb::{Durability, TableDefinition};
+use std::marker::PhantomData;
+use std::path::Path;
+use types::EthSpec;
+
+/// Name of the redb file within the database directory.
+pub const REDB_DATA_FILENAME: &str = "database.redb";
+
+/// All columns share a single table, with keys prefixed by the column name as in LevelDB.
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("lighthouse");
+
+/// A wrapped redb database.
+pub struct Redb<E: EthSpec> {
+    db: redb::Database,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory at `path`, creating a new database if one does not
+    /// already exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create database directory {path:?}: {e:?}"),
+        })?;
+        let db = redb::Database::create(path.join(REDB_DATA_FILENAME))?;
+
+        // Create the table up front so that read transactions on an empty database succeed.
+        let tx = db.begin_write()?;
+        tx.open_table(TABLE)?;
+        tx.commit()?;
+
+        Ok(Self {
+            db,
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as u64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(durability);
+        tx.open_table(TABLE)?.insert(column_key.as_slice(), val)?;
+        tx.commit()?;
+
+        metrics::stop_timer(timer);
+        Ok(())
+    }
+
+    /// Iterate over all entries with keys starting with `prefix`, beginning at `start_key`.
+    ///
+    /// Keys are returned in full, including the column prefix.
+    fn iter_prefix(&self, start_key: Vec<u8>, prefix: Vec<u8>) -> RawEntryIter {
+        let range = self.db.begin_read().map_err(Error::from).and_then(|tx| {
+            let table = tx.open_table(TABLE)?;
+            Ok(table.range::<&[u8]>(start_key.as_slice()..)?)
+        });
+        let range = match range {
+            Ok(range) => range,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+
+        Box::new(
+            range
+                .map(|res| -> Result<_, Error> {
+                    let (key, value) = res?;
+                    Ok((key.value().to_vec(), value.value().to_vec()))
+                })
+                .take_while(move |res| {
+                    res.as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(&prefix))
+                }),
+        )
+    }
+
+    /// Compact the entire database file.
+    ///
+    /// Compaction requires exclusive access to the database, so it is only performed offline, by
+    /// `lighthouse db compact`.
+    pub fn compact_file(mut self) -> Result<(), Error> {
+        self.db.compact()?;
+        Ok(())
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    /// Commit an empty transaction with immediate durability, which persists all prior
+    /// transactions committed with eventual durability.
+    fn sync(&self) -> Result<(), Error> {
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(Durability::Immediate);
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let tx = self.db.begin_read()?;
+        let table = tx.open_table(TABLE)?;
+        let value = table
+            .get(column_key.as_slice())?
+            .map(|bytes| bytes.value().to_vec());
+
+        if let Some(bytes) = &value {
+            metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as u64);
+            metrics::stop_timer(timer);
+        }
+        Ok(value)
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        let tx = self.db.begin_read()?;
+        let table = tx.open_table(TABLE)?;
+        let exists = table.get(column_key.as_slice())?.is_some();
+        Ok(exists)
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(Durability::Eventual);
+        tx.open_table(TABLE)?.remove(column_key.as_slice())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(Durability::Eventual);
+        {
+            let mut table = tx.open_table(TABLE)?;
+            for op in ops_batch {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        table.insert(key.as_slice(), value.as_slice())?;
+                    }
+
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        table.remove(key.as_slice())?;
+                    }
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// redb can only compact the whole file, which blocks all other transactions, so this is a
+    /// no-op. See `compact_file`.
+    fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        let start_key = get_key_for_col(column.into(), from);
+
+        Box::new(
+            self.iter_prefix(start_key, column.as_bytes().to_vec())
+                .map(move |res| {
+                    let (key, value) = res?;
+                    let bytes_key = BytesKey::from_vec(key);
+                    let key = bytes_key.remove_column_variable(column).ok_or_else(|| {
+                        HotColdDBError::IterationError {
+                            unexpected_key: bytes_key.clone(),
+                        }
+                    })?;
+                    Ok((K::from_bytes(key)?, value))
+                }),
+        )
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+
+        Box::new(
+            self.iter_prefix(start_key.clone(), start_key)
+                .map(move |res| {
+                    let (key, value) = res?;
+                    Ok((key[column.as_bytes().len()..].to_vec(), value))
+                }),
+        )
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        Box::new(
+            self.iter_raw_entries(column, prefix)
+                .map(|res| res.map(|(key, _)| key)),
+        )
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        Box::new(self.iter_column(column).map(|res| res.map(|(key, _)| key)))
+    }
+
+    /// All reads within a single redb transaction see the same snapshot of the database.
+    fn with_snapshot<T>(&self, f: impl FnOnce(RawEntryIter) -> T) -> T {
+        f(self.iter_prefix(vec![], vec![]))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+macro_rules! impl_from_redb_error {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(e: $error) -> Error {
+                    Error::DBError {
+                        message: format!("{:?}", e),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_redb_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError,
+    redb::CompactionError
+);
@@ -0,0 +1,659 @@
+//! Offline verification of the integrity of the database.
+//!
+//! The canonical chain is walked from the oldest stored block to the head, checking each block
+//! against the frozen `block_roots` and `state_roots` vectors and the blobs stored for it. The
+//! restore points and state summaries are then checked independently of the chain.
+//!
+//! Problems are collected into a `VerificationReport` rather than returned as errors, so that a
+//! single run finds every problem. Errors are only returned if the database can't be read at all.
+//!
+//! Orphaned entries, i.e. those that nothing refers to, can be deleted by enabling repair. Other
+//! problems require the affected data to be re-synced.
+use crate::chunked_vector::{chunk_key, BlockRoots, Chunk, Field, StateRoots};
+use crate::hot_cold_store::{HotColdDB, HotColdDBError, Split};
+use crate::{
+    get_key_for_col, DBColumn, Error, HotStateSummary, ItemStore, KeyValueStore, KeyValueStoreOp,
+    StoreOp,
+};
+use serde::{Deserialize, Serialize};
+use slog::{debug, info};
+use ssz::Decode;
+use std::cmp::{max, min};
+use std::marker::PhantomData;
+use types::{EthSpec, Hash256, SignedBlindedBeaconBlock, Slot};
+
+/// A problem found while verifying the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Issue {
+    /// The frozen `block_roots` vector has no value for a slot after the oldest block.
+    MissingBlockRoot { slot: Slot },
+    /// The frozen `state_roots` vector has no value for a slot with a stored state.
+    MissingStateRoot { slot: Slot },
+    /// A canonical block is missing.
+    MissingBlock { block_root: Hash256 },
+    /// A canonical block couldn't be loaded or decoded.
+    InvalidBlock { block_root: Hash256, error: String },
+    /// A block is stored under a key that isn't its root.
+    BlockRootMismatch {
+        block_root: Hash256,
+        computed_root: Hash256,
+    },
+    /// A block's slot doesn't match the slot it has in the `block_roots` vector.
+    BlockSlotMismatch {
+        block_root: Hash256,
+        expected_slot: Slot,
+        block_slot: Slot,
+    },
+    /// A block's parent isn't the previous block in the `block_roots` vector.
+    ParentMismatch {
+        block_root: Hash256,
+        expected_parent_root: Hash256,
+        parent_root: Hash256,
+    },
+    /// The frozen `block_roots` vector doesn't end at the split block.
+    SplitNotLinked {
+        split_block_root: Hash256,
+        frozen_block_root: Hash256,
+    },
+    /// The chain walked back from the head passed the split without reaching the split block.
+    HeadNotDescendedFromSplit { block_root: Hash256, slot: Slot },
+    /// A block's state root doesn't match the state root in the `state_roots` vector.
+    StateRootMismatch {
+        block_root: Hash256,
+        slot: Slot,
+        expected_state_root: Hash256,
+        block_state_root: Hash256,
+    },
+    /// A frozen state root has no summary, or a summary with the wrong slot.
+    ColdStateSummaryMismatch {
+        state_root: Hash256,
+        slot: Slot,
+        summary_slot: Option<Slot>,
+    },
+    /// The post-state of an unfinalized canonical block has no summary.
+    MissingStateSummary {
+        block_root: Hash256,
+        state_root: Hash256,
+    },
+    /// A restore point is missing from the freezer database.
+    MissingRestorePoint { slot: Slot },
+    /// A restore point couldn't be loaded.
+    InvalidRestorePoint {
+        slot: Slot,
+        state_root: Hash256,
+        error: String,
+    },
+    /// A restore point state doesn't hash to its key.
+    RestorePointRootMismatch {
+        slot: Slot,
+        state_root: Hash256,
+        computed_root: Hash256,
+    },
+    /// Blobs are missing for a block with KZG commitments.
+    MissingBlobs { block_root: Hash256 },
+    /// A block's blobs couldn't be loaded or decoded.
+    InvalidBlobs { block_root: Hash256, error: String },
+    /// A blob doesn't match the KZG commitment at its index in the block.
+    BlobCommitmentMismatch { block_root: Hash256, index: u64 },
+    /// A hot state summary refers to a missing epoch boundary state.
+    MissingEpochBoundaryState {
+        state_root: Hash256,
+        epoch_boundary_state_root: Hash256,
+    },
+    /// A hot state summary couldn't be decoded.
+    InvalidStateSummary { state_root: Hash256, error: String },
+    /// A hot state summary from before the split that should have been pruned.
+    StaleStateSummary { state_root: Hash256, slot: Slot },
+    /// A hot state summary whose latest block is missing.
+    OrphanedStateSummary {
+        state_root: Hash256,
+        latest_block_root: Hash256,
+    },
+    /// A hot state without a summary.
+    OrphanedState { state_root: Hash256 },
+    /// A state left over from an incomplete block import.
+    TemporaryState { state_root: Hash256 },
+    /// Blobs for a block that isn't stored.
+    OrphanedBlobs { block_root: Hash256 },
+    /// An execution payload for a block that isn't stored.
+    OrphanedExecutionPayload { block_root: Hash256 },
+}
+
+impl Issue {
+    /// Append the operations that repair this issue to `ops`, returning `false` if it can't be
+    /// repaired.
+    ///
+    /// Only orphaned entries can be repaired, by deleting them.
+    fn repair<E: EthSpec>(&self, ops: &mut Vec<StoreOp<E>>) -> bool {
+        match *self {
+            Issue::InvalidStateSummary { state_root, .. }
+            | Issue::StaleStateSummary { state_root, .. }
+            | Issue::OrphanedStateSummary { state_root, .. } => {
+                ops.push(StoreOp::DeleteState(state_root, None));
+            }
+            Issue::OrphanedState { state_root } => {
+                let key = get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
+                ops.push(StoreOp::KeyValueOp(KeyValueStoreOp::DeleteKey(key)));
+            }
+            Issue::TemporaryState { state_root } => {
+                ops.push(StoreOp::DeleteState(state_root, None));
+                ops.push(StoreOp::DeleteStateTemporaryFlag(state_root));
+            }
+            Issue::OrphanedBlobs { block_root } => {
+                ops.push(StoreOp::DeleteBlobs(block_root));
+            }
+            Issue::OrphanedExecutionPayload { block_root } => {
+                ops.push(StoreOp::DeleteExecutionPayload(block_root));
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The result of verifying the database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub split: Split,
+    pub head_block_root: Hash256,
+    pub oldest_block_slot: Slot,
+    pub blocks_checked: u64,
+    pub blobs_checked: u64,
+    pub restore_points_checked: u64,
+    pub state_summaries_checked: u64,
+    /// Problems that remain in the database.
+    pub issues: Vec<Issue>,
+    /// Problems that were repaired.
+    pub repaired: Vec<Issue>,
+}
+
+impl VerificationReport {
+    /// Return `true` if no problems remain in the database.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Reader for single values of a frozen `block_roots` or `state_roots` vector, which caches the
+/// most recently loaded chunk.
+struct FrozenRoots<E: EthSpec, F: Field<E, Value = Hash256>> {
+    chunk: Option<(usize, Chunk<Hash256>)>,
+    _phantom: PhantomData<(E, F)>,
+}
+
+impl<E: EthSpec, F: Field<E, Value = Hash256>> FrozenRoots<E, F> {
+    fn new() -> Self {
+        Self {
+            chunk: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Return the root for `slot`, or `None` if it isn't stored.
+    fn get(
+        &mut self,
+        cold_db: &impl KeyValueStore<E>,
+        slot: Slot,
+    ) -> Result<Option<Hash256>, Error> {
+        let cindex = F::chunk_index(slot.as_usize());
+        if self.chunk.as_ref().map_or(true, |(i, _)| *i != cindex) {
+            let chunk = Chunk::load(cold_db, F::column(), &chunk_key(cindex))?;
+            self.chunk = Some((cindex, chunk.unwrap_or_default()));
+        }
+        Ok(self
+            .chunk
+            .as_ref()
+            .and_then(|(_, chunk)| chunk.values.get(slot.as_usize() % F::chunk_size()))
+            .copied()
+            .filter(|root| !root.is_zero()))
+    }
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Verify the integrity of the database, with the canonical chain ending at
+    /// `head_block_root`.
+    ///
+    /// If `repair` is `true` then orphaned entries are deleted, and listed as repaired in the
+    /// report.
+    pub fn verify(
+        &self,
+        head_block_root: Hash256,
+        repair: bool,
+    ) -> Result<VerificationReport, Error> {
+        let split = self.get_split_info();
+        let mut report = VerificationReport {
+            split,
+            head_block_root,
+            oldest_block_slot: self.get_oldest_block_slot(),
+            ..VerificationReport::default()
+        };
+
+        info!(
+            self.log,
+            "Verifying frozen chain";
+            "from_slot" => report.oldest_block_slot,
+            "to_slot" => split.slot,
+        );
+        let frozen_block_root = self.verify_frozen_chain(&split, &mut report)?;
+
+        info!(self.log, "Verifying unfinalized chain"; "head_block_root" => ?head_block_root);
+        let split_block = self.verify_hot_chain(head_block_root, &split, &mut report)?;
+        if let (Some(frozen_block_root), Some(split_block)) = (frozen_block_root, split_block) {
+            let expected_frozen_block_root = if split_block.slot() < split.slot {
+                split.block_root
+            } else {
+                split_block.parent_root()
+            };
+            if frozen_block_root != expected_frozen_block_root {
+                report.issues.push(Issue::SplitNotLinked {
+                    split_block_root: split.block_root,
+                    frozen_block_root,
+                });
+            }
+        }
+
+        info!(self.log, "Verifying restore points");
+        self.verify_restore_points(&split, &mut report)?;
+
+        info!(self.log, "Verifying orphaned entries");
+        self.verify_hot_state_summaries(&split, &mut report)?;
+        self.verify_orphans(&mut report)?;
+
+        if repair {
+            self.repair(&mut report)?;
+        }
+        Ok(report)
+    }
+
+    /// Check every block between the oldest block and the split using the frozen vectors.
+    ///
+    /// Return the block root at the slot prior to the split, if any.
+    fn verify_frozen_chain(
+        &self,
+        split: &Split,
+        report: &mut VerificationReport,
+    ) -> Result<Option<Hash256>, Error> {
+        if report.oldest_block_slot >= split.slot {
+            return Ok(None);
+        }
+
+        // State roots are only stored for slots with frozen states, prior to the latest restore
+        // point.
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let (lower_limit, upper_limit) = self.get_historic_state_limits();
+        let latest_restore_point_slot =
+            (split.slot - 1) / slots_per_restore_point * slots_per_restore_point;
+        let has_state_root = |slot: Slot| {
+            slot < latest_restore_point_slot && (slot < lower_limit || slot >= upper_limit)
+        };
+
+        let oldest_blob_slot = self.get_blob_info().oldest_blob_slot;
+        let mut block_roots = FrozenRoots::<E, BlockRoots>::new();
+        let mut state_roots = FrozenRoots::<E, StateRoots>::new();
+        let mut prev_block_root = self
+            .get_anchor_info()
+            .map_or(Hash256::zero(), |anchor| anchor.oldest_block_parent);
+        let mut prev_block = None;
+
+        for slot in report.oldest_block_slot.as_u64()..split.slot.as_u64() {
+            let slot = Slot::new(slot);
+            if slot % (64 * slots_per_restore_point) == 0 {
+                debug!(self.log, "Verifying frozen chain"; "slot" => slot);
+            }
+
+            let Some(block_root) = block_roots.get(&self.cold_db, slot)? else {
+                report.issues.push(Issue::MissingBlockRoot { slot });
+                continue;
+            };
+
+            if block_root != prev_block_root {
+                prev_block = self.verify_block(block_root, report);
+                if let Some(block) = &prev_block {
+                    if block.slot() != slot {
+                        report.issues.push(Issue::BlockSlotMismatch {
+                            block_root,
+                            expected_slot: slot,
+                            block_slot: block.slot(),
+                        });
+                    }
+                    if block.parent_root() != prev_block_root {
+                        report.issues.push(Issue::ParentMismatch {
+                            block_root,
+                            expected_parent_root: prev_block_root,
+                            parent_root: block.parent_root(),
+                        });
+                    }
+                    self.verify_blobs(block_root, block, oldest_blob_slot, report);
+                }
+                prev_block_root = block_root;
+            }
+
+            if !has_state_root(slot) {
+                continue;
+            }
+            let Some(state_root) = state_roots.get(&self.cold_db, slot)? else {
+                report.issues.push(Issue::MissingStateRoot { slot });
+                continue;
+            };
+            // The block's state root only applies at the block's own slot.
+            if let Some(block) = prev_block.as_ref().filter(|block| block.slot() == slot) {
+                if block.state_root() != state_root {
+                    report.issues.push(Issue::StateRootMismatch {
+                        block_root,
+                        slot,
+                        expected_state_root: state_root,
+                        block_state_root: block.state_root(),
+                    });
+                }
+            }
+
+            report.state_summaries_checked += 1;
+            let summary_slot = self.load_cold_state_slot(&state_root).ok().flatten();
+            if summary_slot != Some(slot) {
+                report.issues.push(Issue::ColdStateSummaryMismatch {
+                    state_root,
+                    slot,
+                    summary_slot,
+                });
+            }
+        }
+
+        Ok(Some(prev_block_root))
+    }
+
+    /// Walk back from the head to the split block, checking each block and its state summary.
+    ///
+    /// Return the split block if it was reached.
+    fn verify_hot_chain(
+        &self,
+        head_block_root: Hash256,
+        split: &Split,
+        report: &mut VerificationReport,
+    ) -> Result<Option<SignedBlindedBeaconBlock<E>>, Error> {
+        let oldest_blob_slot = self.get_blob_info().oldest_blob_slot;
+        let mut block_root = head_block_root;
+
+        loop {
+            let Some(block) = self.verify_block(block_root, report) else {
+                return Ok(None);
+            };
+            self.verify_blobs(block_root, &block, oldest_blob_slot, report);
+
+            // Summaries for the states of blocks prior to the split are pruned.
+            if block.slot() >= split.slot {
+                report.state_summaries_checked += 1;
+                let summary = self
+                    .load_hot_state_summary(&block.state_root())
+                    .ok()
+                    .flatten();
+                if summary.map_or(true, |summary| {
+                    summary.slot != block.slot() || summary.latest_block_root != block_root
+                }) {
+                    report.issues.push(Issue::MissingStateSummary {
+                        block_root,
+                        state_root: block.state_root(),
+                    });
+                }
+            }
+
+            if block_root == split.block_root {
+                return Ok(Some(block));
+            }
+            if block.slot() <= split.slot {
+                report.issues.push(Issue::HeadNotDescendedFromSplit {
+                    block_root,
+                    slot: block.slot(),
+                });
+                return Ok(None);
+            }
+            block_root = block.parent_root();
+        }
+    }
+
+    /// Load and check a single block, returning it if it's valid.
+    fn verify_block(
+        &self,
+        block_root: Hash256,
+        report: &mut VerificationReport,
+    ) -> Option<SignedBlindedBeaconBlock<E>> {
+        report.blocks_checked += 1;
+        let block = match self.get_blinded_block(&block_root) {
+            Ok(Some(block)) => block,
+            Ok(None) => {
+                report.issues.push(Issue::MissingBlock { block_root });
+                return None;
+            }
+            Err(e) => {
+                report.issues.push(Issue::InvalidBlock {
+                    block_root,
+                    error: format!("{e:?}"),
+                });
+                return None;
+            }
+        };
+
+        let computed_root = block.canonical_root();
+        if computed_root != block_root {
+            report.issues.push(Issue::BlockRootMismatch {
+                block_root,
+                computed_root,
+            });
+            return None;
+        }
+        Some(block)
+    }
+
+    /// Check that the blobs for a block match its KZG commitments, if they haven't been pruned.
+    fn verify_blobs(
+        &self,
+        block_root: Hash256,
+        block: &SignedBlindedBeaconBlock<E>,
+        oldest_blob_slot: Option<Slot>,
+        report: &mut VerificationReport,
+    ) {
+        let Ok(commitments) = block.message().body().blob_kzg_commitments() else {
+            return;
+        };
+        if commitments.is_empty() || oldest_blob_slot.map_or(true, |slot| block.slot() < slot) {
+            return;
+        }
+
+        let blobs = match self.get_blobs(&block_root) {
+            Ok(Some(blobs)) => blobs,
+            Ok(None) => {
+                report.issues.push(Issue::MissingBlobs { block_root });
+                return;
+            }
+            Err(e) => {
+                report.issues.push(Issue::InvalidBlobs {
+                    block_root,
+                    error: format!("{e:?}"),
+                });
+                return;
+            }
+        };
+
+        report.blobs_checked += blobs.len() as u64;
+        for index in 0..max(blobs.len(), commitments.len()) {
+            let matches = match (blobs.get(index), commitments.get(index)) {
+                (Some(blob), Some(commitment)) => {
+                    blob.index == index as u64 && blob.kzg_commitment == *commitment
+                }
+                _ => false,
+            };
+            if !matches {
+                report.issues.push(Issue::BlobCommitmentMismatch {
+                    block_root,
+                    index: index as u64,
+                });
+            }
+        }
+    }
+
+    /// Check that every restore point that should exist hashes to its state root.
+    fn verify_restore_points(
+        &self,
+        split: &Split,
+        report: &mut VerificationReport,
+    ) -> Result<(), Error> {
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let (lower_limit, upper_limit) = self.get_historic_state_limits();
+
+        let mut slot = Slot::new(0);
+        while slot < split.slot {
+            // Skip the gap in the historic states left by checkpoint sync.
+            if slot > lower_limit && slot < upper_limit {
+                slot = min(upper_limit, split.slot);
+                continue;
+            }
+
+            report.restore_points_checked += 1;
+            let restore_point_index = slot.as_u64() / slots_per_restore_point;
+            let state_root = match self.load_restore_point_hash(restore_point_index) {
+                Ok(state_root) => state_root,
+                Err(Error::HotColdDBError(HotColdDBError::MissingRestorePointHash(_))) => {
+                    report.issues.push(Issue::MissingRestorePoint { slot });
+                    slot += slots_per_restore_point;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            match self.load_restore_point(&state_root) {
+                Ok(state) => {
+                    let computed_root = state.canonical_root();
+                    if state.slot() != slot || computed_root != state_root {
+                        report.issues.push(Issue::RestorePointRootMismatch {
+                            slot,
+                            state_root,
+                            computed_root,
+                        });
+                    }
+                }
+                Err(e) => report.issues.push(Issue::InvalidRestorePoint {
+                    slot,
+                    state_root,
+                    error: format!("{e:?}"),
+                }),
+            }
+            slot += slots_per_restore_point;
+        }
+        Ok(())
+    }
+
+    /// Check every summary in the hot database for consistency with the split and the blocks.
+    fn verify_hot_state_summaries(
+        &self,
+        split: &Split,
+        report: &mut VerificationReport,
+    ) -> Result<(), Error> {
+        for res in self
+            .hot_db
+            .iter_column::<Hash256>(DBColumn::BeaconStateSummary)
+        {
+            let (state_root, summary_bytes) = res?;
+            report.state_summaries_checked += 1;
+
+            let summary = match HotStateSummary::from_ssz_bytes(&summary_bytes) {
+                Ok(summary) => summary,
+                Err(e) => {
+                    report.issues.push(Issue::InvalidStateSummary {
+                        state_root,
+                        error: format!("{e:?}"),
+                    });
+                    continue;
+                }
+            };
+
+            // Mirror the conditions used when pruning old states.
+            let non_canonical = summary.slot == split.slot
+                && state_root != split.state_root
+                && !split.state_root.is_zero();
+            if summary.slot < split.slot || non_canonical {
+                report.issues.push(Issue::StaleStateSummary {
+                    state_root,
+                    slot: summary.slot,
+                });
+            } else if !self.block_exists(&summary.latest_block_root)? {
+                report.issues.push(Issue::OrphanedStateSummary {
+                    state_root,
+                    latest_block_root: summary.latest_block_root,
+                });
+            } else if !self.hot_db.key_exists(
+                DBColumn::BeaconStateSummary.into(),
+                summary.epoch_boundary_state_root.as_bytes(),
+            )? {
+                report.issues.push(Issue::MissingEpochBoundaryState {
+                    state_root,
+                    epoch_boundary_state_root: summary.epoch_boundary_state_root,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Find hot states, blobs and payloads that nothing refers to.
+    fn verify_orphans(&self, report: &mut VerificationReport) -> Result<(), Error> {
+        for state_root in self.iter_temporary_state_roots() {
+            let state_root = state_root?;
+            report.issues.push(Issue::TemporaryState { state_root });
+        }
+
+        for state_root in self
+            .hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconState)
+        {
+            let state_root = state_root?;
+            if !self
+                .hot_db
+                .key_exists(DBColumn::BeaconStateSummary.into(), state_root.as_bytes())?
+            {
+                report.issues.push(Issue::OrphanedState { state_root });
+            }
+        }
+
+        for block_root in self
+            .blobs_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconBlob)
+        {
+            let block_root = block_root?;
+            if !self.block_exists(&block_root)? {
+                report.issues.push(Issue::OrphanedBlobs { block_root });
+            }
+        }
+
+        for block_root in self
+            .hot_db
+            .iter_column_keys::<Hash256>(DBColumn::ExecPayload)
+        {
+            let block_root = block_root?;
+            if !self.block_exists(&block_root)? {
+                report
+                    .issues
+                    .push(Issue::OrphanedExecutionPayload { block_root });
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete every orphaned entry in the report, moving the issues to `repaired`.
+    fn repair(&self, report: &mut VerificationReport) -> Result<(), Error> {
+        let mut ops: Vec<StoreOp<E>> = vec![];
+        let (repaired, issues): (Vec<_>, Vec<_>) = std::mem::take(&mut report.issues)
+            .into_iter()
+            .partition(|issue| issue.repair(&mut ops));
+        report.issues = issues;
+        report.repaired = repaired;
+
+        if !ops.is_empty() {
+            info!(
+                self.log,
+                "Deleting orphaned database entries";
+                "count" => report.repaired.len(),
+            );
+            self.do_atomically_with_block_and_blobs_cache(ops)?;
+        }
+        Ok(())
+    }
+}
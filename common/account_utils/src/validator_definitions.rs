@@ -15,7 +15,10 @@ use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
-use types::{graffiti::GraffitiString, Address, PublicKey};
+use types::{
+    graffiti::{GraffitiString, GraffitiTemplate},
+    Address, Epoch, PublicKey,
+};
 use validator_dir::VOTING_KEYSTORE_FILE;
 
 /// The file name for the serialized `ValidatorDefinitions` struct.
@@ -80,6 +83,59 @@ pub struct Web3SignerDefinition {
     /// An empty password will be used if this is omitted.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_identity_password: Option<String>,
+
+    /// Token sent in the `Authorization` header, e.g. the API token of a Lighthouse VC which is
+    /// acting as a remote signer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_token: Option<String>,
+}
+
+/// A peer validator client holding one of the key shares of a threshold validator.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdPeerDefinition {
+    /// The URL of the peer's HTTP API.
+    pub url: String,
+    /// Path to a file containing the API token for the peer's HTTP API.
+    pub api_token_path: PathBuf,
+    /// The index of the key share held by the peer.
+    pub share_index: u64,
+    /// The public key of the key share held by the peer.
+    pub share_public_key: PublicKey,
+    /// Specifies a request timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdDefinition {
+    /// The number of key shares required to produce a signature.
+    pub threshold: usize,
+    /// The index of the key share held by this validator client.
+    pub share_index: u64,
+    /// Path to an EIP-2335 keystore containing the key share held by this validator client.
+    pub share_keystore_path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_keystore_password_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_keystore_password: Option<ZeroizeString>,
+    /// The validator clients holding the other key shares.
+    pub peers: Vec<ThresholdPeerDefinition>,
+}
+
+/// A condition which causes the validator client to publish a voluntary exit for a validator once
+/// it is met.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExitCondition {
+    /// Exit once the current epoch reaches `epoch`.
+    AtEpoch { epoch: Epoch },
+    /// Exit once the validator's balance drops below `balance_gwei`.
+    BalanceBelow {
+        #[serde(with = "serde_utils::quoted_u64")]
+        balance_gwei: u64,
+    },
+    /// Exit once the validator has 0x01 withdrawal credentials.
+    Eth1WithdrawalCredentials,
 }
 
 /// Defines how the validator client should attempt to sign messages for this validator.
@@ -100,6 +156,10 @@ pub enum SigningDefinition {
     /// https://github.com/ConsenSys/web3signer
     #[serde(rename = "web3signer")]
     Web3Signer(Web3SignerDefinition),
+    /// A validator whose key is split into shares held by several validator clients, a threshold
+    /// of which must sign each message.
+    #[serde(rename = "threshold")]
+    Threshold(ThresholdDefinition),
 }
 
 impl SigningDefinition {
@@ -121,7 +181,7 @@ impl SigningDefinition {
                 .map(Option::Some)
                 .map_err(Error::UnableToReadKeystorePassword),
             SigningDefinition::LocalKeystore { .. } => Err(Error::KeystoreWithoutPassword),
-            SigningDefinition::Web3Signer(_) => Ok(None),
+            SigningDefinition::Web3Signer(_) | SigningDefinition::Threshold(_) => Ok(None),
         }
     }
 
@@ -147,6 +207,10 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graffiti: Option<GraffitiString>,
+    /// Takes precedence over `graffiti` if set.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graffiti_template: Option<GraffitiTemplate>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub suggested_fee_recipient: Option<Address>,
@@ -162,6 +226,14 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
+    /// A voluntary exit which will be published once its condition is met.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_exit: Option<ExitCondition>,
+    /// Overrides `--enable-doppelganger-protection` for this validator.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doppelganger: Option<bool>,
     #[serde(default)]
     pub description: String,
     #[serde(flatten)]
@@ -202,11 +274,14 @@ impl ValidatorDefinition {
             voting_public_key,
             description: keystore.description().unwrap_or("").to_string(),
             graffiti,
+            graffiti_template: None,
             suggested_fee_recipient,
             gas_limit,
             builder_proposals,
             builder_boost_factor,
             prefer_builder_proposals,
+            pending_exit: None,
+            doppelganger: None,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path,
@@ -285,6 +360,8 @@ impl ValidatorDefinitions {
                 } => Some(voting_keystore_path),
                 // A Web3Signer validator does not use a local keystore file.
                 SigningDefinition::Web3Signer { .. } => None,
+                // A threshold validator's key share should not be loaded as a validator itself.
+                SigningDefinition::Threshold(threshold) => Some(&threshold.share_keystore_path),
             })
             .collect();
 
@@ -352,11 +429,14 @@ impl ValidatorDefinitions {
                     voting_public_key,
                     description: keystore.description().unwrap_or("").to_string(),
                     graffiti: None,
+                    graffiti_template: None,
                     suggested_fee_recipient: None,
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    pending_exit: None,
+                    doppelganger: None,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
@@ -687,4 +767,112 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn threshold_checks() {
+        let threshold = r#"---
+        description: ""
+        enabled: true
+        type: threshold
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        threshold: 2
+        share_index: 1
+        share_keystore_path: "share.json"
+        share_keystore_password_path: "share.pass"
+        peers:
+          - url: "http://localhost:5063"
+            api_token_path: "api-token.txt"
+            share_index: 2
+            share_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+
+        let def: ValidatorDefinition = serde_yaml::from_str(threshold).unwrap();
+        let SigningDefinition::Threshold(threshold) = def.signing_definition else {
+            panic!("expected a threshold signing definition");
+        };
+        assert_eq!(threshold.threshold, 2);
+        assert_eq!(threshold.share_index, 1);
+        assert_eq!(threshold.share_keystore_path, PathBuf::from("share.json"));
+        assert!(threshold.share_keystore_password.is_none());
+        assert_eq!(threshold.peers.len(), 1);
+        assert_eq!(threshold.peers[0].share_index, 2);
+        assert!(threshold.peers[0].request_timeout_ms.is_none());
+    }
+
+    #[test]
+    fn pending_exit_checks() {
+        let no_pending_exit = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_pending_exit).unwrap();
+        assert!(def.pending_exit.is_none());
+
+        let invalid_pending_exit = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        pending_exit:
+          type: at_slot
+          slot: "100"
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: Result<ValidatorDefinition, _> = serde_yaml::from_str(invalid_pending_exit);
+        assert!(def.is_err());
+
+        let at_epoch = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        pending_exit:
+          type: at_epoch
+          epoch: "1000"
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(at_epoch).unwrap();
+        assert_eq!(
+            def.pending_exit,
+            Some(ExitCondition::AtEpoch {
+                epoch: Epoch::new(1000)
+            })
+        );
+
+        let balance_below = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        pending_exit:
+          type: balance_below
+          balance_gwei: "31000000000"
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(balance_below).unwrap();
+        assert_eq!(
+            def.pending_exit,
+            Some(ExitCondition::BalanceBelow {
+                balance_gwei: 31_000_000_000
+            })
+        );
+
+        let eth1_withdrawal_credentials = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        pending_exit:
+          type: eth1_withdrawal_credentials
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(eth1_withdrawal_credentials).unwrap();
+        assert_eq!(
+            def.pending_exit,
+            Some(ExitCondition::Eth1WithdrawalCredentials)
+        );
+    }
 }
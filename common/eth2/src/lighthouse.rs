@@ -9,7 +9,8 @@ mod sync_committee_rewards;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        AttesterSlashing, DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock,
+        GenericResponse, IndexedAttestation, ProposerSlashing, ValidatorId, ValidatorIndexDataRef,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
@@ -17,7 +18,10 @@ use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
-use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
+use std::path::PathBuf;
+use store::{
+    backup::BackupManifest, garbage_collection::GcRun, AnchorInfo, BlobInfo, Split, StoreConfig,
+};
 
 pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
@@ -47,6 +51,66 @@ pub struct Peer<E: EthSpec> {
     pub peer_info: PeerInfo<E>,
 }
 
+/// A peer's reputation, as returned by the `lighthouse/peers/reputation` endpoints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerReputation {
+    pub peer_id: String,
+    /// The peer's overall score.
+    pub score: f64,
+    /// The component of the score that is kept across restarts.
+    pub lighthouse_score: f64,
+    /// Present if the peer is banned.
+    pub ban: Option<PeerBan>,
+    pub client: String,
+    /// The number of sync batches served by the peer which were successfully processed.
+    pub useful_sync_batches: u64,
+    pub enr: Option<String>,
+    pub seen_multiaddrs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerBan {
+    pub reason: String,
+    /// Seconds since the UNIX epoch at which the peer's score starts to decay. The ban is lifted
+    /// once the score has decayed back above the ban threshold.
+    pub expires_at: u64,
+}
+
+impl From<lighthouse_network::PeerReputation> for PeerReputation {
+    fn from(reputation: lighthouse_network::PeerReputation) -> Self {
+        Self {
+            peer_id: reputation.peer_id.to_string(),
+            score: reputation.score,
+            lighthouse_score: reputation.lighthouse_score,
+            ban: reputation.ban.map(|ban| PeerBan {
+                reason: ban.reason,
+                expires_at: ban
+                    .expires_at
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs()),
+            }),
+            client: reputation.client_kind.as_ref().to_string(),
+            useful_sync_batches: reputation.useful_sync_batches,
+            enr: reputation.enr.map(|enr| enr.to_base64()),
+            seen_multiaddrs: reputation
+                .seen_multiaddrs
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// A manual override of a peer's reputation, sent to `POST lighthouse/peers/reputation/{peer_id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PeerReputationOverride {
+    /// Ban the peer for `duration_secs`, after which its score decays as usual.
+    Ban { reason: String, duration_secs: u64 },
+    /// Lift any ban on the peer and reset its score.
+    Unban,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -88,6 +152,15 @@ pub struct ValidatorInclusionData {
     pub is_previous_epoch_head_attester: bool,
 }
 
+/// The `ValidatorInclusionData` of the validator with the given `index`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedValidatorInclusionData {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    #[serde(flatten)]
+    pub data: ValidatorInclusionData,
+}
+
 #[cfg(target_os = "linux")]
 use {
     psutil::cpu::os::linux::CpuTimesExt, psutil::memory::os::linux::VirtualMemoryExt,
@@ -307,6 +380,29 @@ pub struct Eth1SyncStatusData {
     pub voting_target_timestamp: u64,
     pub eth1_node_sync_status_percentage: f64,
     pub lighthouse_is_cached_and_ready: bool,
+    /// The execution engines connected to the beacon node, in order of preference.
+    #[serde(default)]
+    pub execution_engines: Vec<ExecutionEngineStatusData>,
+}
+
+/// The state of an execution engine, as last observed by the beacon node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionEngineState {
+    Synced,
+    Syncing,
+    Offline,
+    AuthFailed,
+}
+
+/// The status of one of the execution engines connected to the beacon node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionEngineStatusData {
+    /// The engine API endpoint, with any credentials redacted.
+    pub endpoint: String,
+    pub state: ExecutionEngineState,
+    /// `true` if this engine is currently used for `forkchoiceUpdated` and `getPayload`.
+    pub is_primary: bool,
 }
 
 /// A fully parsed eth1 deposit contract log.
@@ -365,6 +461,92 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseBackupRequest {
+    /// Directory to write the backup to, relative to the beacon node's backup directory. Must not
+    /// already exist.
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseGcQuery {
+    /// Only return this many of the most recent runs.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlasherRescanRequest {
+    pub start_epoch: Epoch,
+    /// The last epoch to rescan, defaulting to the current epoch.
+    pub end_epoch: Option<Epoch>,
+}
+
+/// Progress of a slasher rescan of historical blocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherRescanStatus {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub last_processed_epoch: Option<Epoch>,
+    pub num_blocks: u64,
+    pub num_attestations: u64,
+    pub num_proposer_slashings: u64,
+    pub complete: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlasherEpochRangeQuery {
+    /// Lower epoch limit (inclusive).
+    pub start_epoch: Epoch,
+    /// Upper epoch limit (inclusive).
+    pub end_epoch: Epoch,
+}
+
+/// Slashings detected by the slasher, along with the conflicting messages which prove them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct SlasherSlashings<E: EthSpec> {
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+}
+
+/// The state of the slasher's min-max target arrays for a single validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherArrayStatus {
+    pub validator_chunk_index: u64,
+    /// The latest epoch that the validator's arrays have been updated for.
+    pub current_epoch: Option<Epoch>,
+    /// The oldest epoch still covered by the validator's arrays.
+    pub pruning_horizon: Option<Epoch>,
+    pub chunks: Vec<SlasherChunkStatus>,
+    pub targets: Vec<SlasherEpochTargets>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherChunkStatus {
+    pub chunk_index: u64,
+    pub min_chunk_stored: bool,
+    pub max_chunk_stored: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherEpochTargets {
+    pub epoch: Epoch,
+    pub min_target: Option<Epoch>,
+    pub max_target: Option<Epoch>,
+}
+
+/// The version of the execution engine connected to the beacon node, as reported by
+/// `engine_getClientVersionV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionEngineVersion {
+    /// The two-letter client code, e.g. `GE` for Geth.
+    pub code: String,
+    pub name: String,
+    pub version: String,
+    pub commit: String,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -393,11 +575,62 @@ impl BeaconNodeHttpClient {
     /*
      * Note:
      *
-     * The `lighthouse/peers` endpoints do not have functions here. We are yet to implement
+     * The `lighthouse/peers` and `lighthouse/peers/connected` endpoints do not have functions
+     * here. We are yet to implement
      * `Deserialize` on the `PeerInfo` struct since it contains use of `Instant`. This could be
      * fairly simply achieved, if desired.
      */
 
+    /// `GET lighthouse/peers/reputation`
+    pub async fn get_lighthouse_peers_reputation(&self) -> Result<Vec<PeerReputation>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("reputation");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/peers/reputation/{peer_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_peer_reputation(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerReputation>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("reputation")
+            .push(peer_id);
+
+        self.get_opt(path).await
+    }
+
+    /// `POST lighthouse/peers/reputation/{peer_id}`
+    pub async fn post_lighthouse_peer_reputation(
+        &self,
+        peer_id: &str,
+        reputation_override: &PeerReputationOverride,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("reputation")
+            .push(peer_id);
+
+        self.post(path, reputation_override).await
+    }
+
     /// `GET lighthouse/proto_array`
     pub async fn get_lighthouse_proto_array(&self) -> Result<GenericResponse<ProtoArray>, Error> {
         let mut path = self.server.full.clone();
@@ -445,6 +678,24 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `POST lighthouse/validator_inclusion/{epoch}`
+    pub async fn post_lighthouse_validator_inclusion(
+        &self,
+        epoch: Epoch,
+        indices: &[u64],
+    ) -> Result<GenericResponse<Vec<IndexedValidatorInclusionData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_inclusion")
+            .push(&epoch.to_string());
+
+        self.post_with_response(path, &ValidatorIndexDataRef(indices))
+            .await
+    }
+
     /// `GET lighthouse/eth1/syncing`
     pub async fn get_lighthouse_eth1_syncing(
         &self,
@@ -528,10 +779,175 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/backup`
+    pub async fn post_lighthouse_database_backup(
+        &self,
+        request: &DatabaseBackupRequest,
+    ) -> Result<GenericResponse<BackupManifest>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/database/gc?limit`
+    pub async fn get_lighthouse_database_gc(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<GcRun>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("gc");
+
+        if let Some(limit) = limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/database/gc`
+    pub async fn post_lighthouse_database_gc(&self) -> Result<GcRun, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("gc");
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `POST lighthouse/slasher/rescan`
+    pub async fn post_lighthouse_slasher_rescan(
+        &self,
+        request: &SlasherRescanRequest,
+    ) -> Result<GenericResponse<SlasherRescanStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("rescan");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/slasher/rescan`
+    pub async fn get_lighthouse_slasher_rescan(
+        &self,
+    ) -> Result<GenericResponse<Option<SlasherRescanStatus>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("rescan");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/slashings?start_epoch,end_epoch`
+    pub async fn get_lighthouse_slasher_slashings<E: EthSpec>(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<SlasherSlashings<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("slashings");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/validators/{validator_index}/attestations?start_epoch,end_epoch`
+    pub async fn get_lighthouse_slasher_validator_attestations<E: EthSpec>(
+        &self,
+        validator_index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<IndexedAttestation<E>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("validators")
+            .push(&validator_index.to_string())
+            .push("attestations");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/validators/{validator_index}/arrays?start_epoch,end_epoch`
+    pub async fn get_lighthouse_slasher_validator_arrays(
+        &self,
+        validator_index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<SlasherArrayStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("validators")
+            .push(&validator_index.to_string())
+            .push("arrays");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
 
+    /// `GET lighthouse/execution_engine/versions`
+    pub async fn get_lighthouse_execution_engine_versions(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExecutionEngineVersion>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_engine")
+            .push("versions");
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/block_rewards?start_slot,end_slot
     pub async fn get_lighthouse_analysis_block_rewards(
         &self,
@@ -375,6 +375,21 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/beacon/health`
+    pub async fn get_lighthouse_beacon_health(
+        &self,
+    ) -> Result<GenericResponse<Vec<BeaconNodeHealthData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("health");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/spec`
     pub async fn get_lighthouse_spec<T: Serialize + DeserializeOwned>(
         &self,
@@ -482,6 +497,22 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `POST lighthouse/validators/signing_method`
+    pub async fn post_lighthouse_validators_signing_method(
+        &self,
+        req: &ConvertSigningMethodRequest,
+    ) -> Result<ConvertSigningMethodResponse, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("signing_method");
+
+        self.post_with_unsigned_response(path, req).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
@@ -575,6 +606,38 @@ impl ValidatorClientHttpClient {
         Ok(url)
     }
 
+    fn make_graffiti_template_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&pubkey.to_string())
+            .push("graffiti_template");
+        Ok(url)
+    }
+
+    fn make_pending_exit_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&pubkey.to_string())
+            .push("pending_exit");
+        Ok(url)
+    }
+
+    fn make_slashing_protection_url(&self, endpoint: &str) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slashing_protection")
+            .push(endpoint);
+        Ok(url)
+    }
+
     fn make_gas_limit_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
@@ -723,6 +786,129 @@ impl ValidatorClientHttpClient {
         self.post(path, &()).await
     }
 
+    /// `POST lighthouse/validators/{pubkey}/threshold_share`
+    pub async fn post_lighthouse_validators_threshold_share<E: EthSpec>(
+        &self,
+        pubkey: &PublicKeyBytes,
+        message: &ThresholdSignableMessage<E>,
+    ) -> Result<GenericResponse<ThresholdSignatureShare>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&pubkey.to_string())
+            .push("threshold_share");
+
+        self.post(path, message).await
+    }
+
+    /// `GET lighthouse/validators/pending_exits`
+    pub async fn get_lighthouse_validators_pending_exits(
+        &self,
+    ) -> Result<GenericResponse<Vec<PendingExitData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("pending_exits");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validators/doppelganger`
+    pub async fn get_lighthouse_validators_doppelganger(
+        &self,
+    ) -> Result<GenericResponse<Vec<DoppelgangerData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("doppelganger");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/validators/{pubkey}/pending_exit`
+    pub async fn post_lighthouse_validators_pending_exit(
+        &self,
+        pubkey: &PublicKeyBytes,
+        condition: ExitCondition,
+    ) -> Result<(), Error> {
+        let url = self.make_pending_exit_url(pubkey)?;
+        let request = SetPendingExitRequest { condition };
+        self.post(url, &request).await
+    }
+
+    /// `DELETE lighthouse/validators/{pubkey}/pending_exit`
+    pub async fn delete_lighthouse_validators_pending_exit(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<(), Error> {
+        let url = self.make_pending_exit_url(pubkey)?;
+        self.delete(url).await
+    }
+
+    /// `GET lighthouse/slashing_protection/standby`
+    pub async fn get_lighthouse_slashing_protection_standby(
+        &self,
+    ) -> Result<GenericResponse<SlashingProtectionStandbyData>, Error> {
+        let url = self.make_slashing_protection_url("standby")?;
+        self.get(url).await
+    }
+
+    /// `POST lighthouse/slashing_protection/replicate`
+    pub async fn post_lighthouse_slashing_protection_replicate(
+        &self,
+        interchange: &Interchange,
+    ) -> Result<(), Error> {
+        let url = self.make_slashing_protection_url("replicate")?;
+        self.post(url, interchange).await
+    }
+
+    /// `POST lighthouse/slashing_protection/promote`
+    pub async fn post_lighthouse_slashing_protection_promote(
+        &self,
+        request: &SlashingProtectionPromoteRequest,
+    ) -> Result<(), Error> {
+        let url = self.make_slashing_protection_url("promote")?;
+        self.post(url, request).await
+    }
+
+    /// `GET lighthouse/validators/{pubkey}/graffiti_template`
+    pub async fn get_lighthouse_validators_graffiti_template(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GenericResponse<GraffitiTemplateData>, Error> {
+        let url = self.make_graffiti_template_url(pubkey)?;
+        self.get(url).await
+    }
+
+    /// `POST lighthouse/validators/{pubkey}/graffiti_template`
+    pub async fn post_lighthouse_validators_graffiti_template(
+        &self,
+        pubkey: &PublicKeyBytes,
+        graffiti_template: GraffitiTemplate,
+    ) -> Result<(), Error> {
+        let url = self.make_graffiti_template_url(pubkey)?;
+        let request = SetGraffitiTemplateRequest { graffiti_template };
+        self.post(url, &request).await
+    }
+
+    /// `DELETE lighthouse/validators/{pubkey}/graffiti_template`
+    pub async fn delete_lighthouse_validators_graffiti_template(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<(), Error> {
+        let url = self.make_graffiti_template_url(pubkey)?;
+        self.delete(url).await
+    }
+
     /// `GET /eth/v1/validator/{pubkey}/graffiti`
     pub async fn get_graffiti(
         &self,
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 pub use crate::lighthouse::Health;
 pub use crate::lighthouse_vc::std_types::*;
 pub use crate::types::{GenericResponse, VersionData};
+pub use account_utils::validator_definitions::ExitCondition;
 pub use types::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -197,3 +198,177 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraffitiTemplateData {
+    pub pubkey: PublicKeyBytes,
+    pub graffiti_template: Option<GraffitiTemplate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetGraffitiTemplateRequest {
+    pub graffiti_template: GraffitiTemplate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingExitData {
+    pub pubkey: PublicKeyBytes,
+    pub condition: ExitCondition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetPendingExitRequest {
+    pub condition: ExitCondition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoppelgangerProtectionStatus {
+    /// Doppelganger protection does not apply to the validator.
+    Disabled,
+    /// The validator is waiting for doppelganger detection to complete.
+    Detecting,
+    /// Doppelganger detection completed without finding a doppelganger.
+    Complete,
+    /// A doppelganger was detected, the validator will not sign.
+    DoppelgangerDetected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoppelgangerData {
+    pub pubkey: PublicKeyBytes,
+    pub status: DoppelgangerProtectionStatus,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub remaining_epochs: u64,
+    /// The first epoch in which the validator is expected to sign, if it is still detecting.
+    pub signing_epoch: Option<Epoch>,
+}
+
+/// A message to be signed with a key share of a threshold validator.
+///
+/// Blocks are represented by their header, which has the same signing root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    bound = "E: EthSpec",
+    tag = "type",
+    content = "message",
+    rename_all = "snake_case"
+)]
+pub enum ThresholdSignableMessage<E: EthSpec> {
+    RandaoReveal {
+        epoch: Epoch,
+    },
+    BlockHeader(BeaconBlockHeader),
+    Attestation(AttestationData),
+    AggregateAndProof(AggregateAndProof<E>),
+    SelectionProof {
+        slot: Slot,
+    },
+    SyncSelectionProof(SyncAggregatorSelectionData),
+    SyncCommitteeMessage {
+        beacon_block_root: Hash256,
+        slot: Slot,
+    },
+    ContributionAndProof(ContributionAndProof<E>),
+    ValidatorRegistration(ValidatorRegistrationData),
+    VoluntaryExit(VoluntaryExit),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdSignatureShare {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub share_index: u64,
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlashingProtectionStandbyData {
+    pub standby: bool,
+    /// The epoch in which the last replication from a primary arrived.
+    pub last_replication_epoch: Option<Epoch>,
+    /// The UNIX timestamp in seconds at which the last replication from a primary arrived.
+    pub last_replication_time: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlashingProtectionPromoteRequest {
+    /// Promote the standby even if it hasn't received a replication recently.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BeaconNodeStatus {
+    Synced,
+    NotSynced,
+    Offline,
+    Incompatible,
+    Uninitialized,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthData {
+    /// The position of the beacon node in the `--beacon-nodes` list.
+    pub index: usize,
+    pub endpoint: String,
+    pub status: BeaconNodeStatus,
+    pub sync_distance: Option<Slot>,
+    pub is_optimistic: Option<bool>,
+    pub el_offline: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub error_rate: f64,
+    /// The health score of the beacon node, lower is healthier.
+    pub score: u64,
+}
+
+/// The signing method to convert a validator to.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SigningMethodConversion {
+    LocalKeystore {
+        keystore: Keystore,
+        password: ZeroizeString,
+    },
+    Web3Signer {
+        url: String,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        root_certificate_path: Option<PathBuf>,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_timeout_ms: Option<u64>,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_identity_path: Option<PathBuf>,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_identity_password: Option<String>,
+    },
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SingleConvertSigningMethodRequest {
+    pub pubkey: PublicKeyBytes,
+    pub signing_method: SigningMethodConversion,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConvertSigningMethodRequest {
+    pub validators: Vec<SingleConvertSigningMethodRequest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvertSigningMethodStatus {
+    Converted,
+    /// The validator already uses the requested signing method.
+    Unchanged,
+    NotFound,
+    Error,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConvertSigningMethodResponse {
+    pub data: Vec<Status<ConvertSigningMethodStatus>>,
+}
@@ -540,6 +540,12 @@ pub struct SyncSubcommittee {
     pub indices: Vec<u64>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct HeadersQuery {
     pub slot: Option<Slot>,
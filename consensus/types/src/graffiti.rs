@@ -1,6 +1,6 @@
 use crate::{
     test_utils::{RngCore, TestRandom},
-    Hash256,
+    Epoch, Hash256,
 };
 use regex::bytes::Regex;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
@@ -95,6 +95,196 @@ impl From<GraffitiString> for Graffiti {
     }
 }
 
+/// A graffiti template, which is rendered into a `Graffiti` when a block is proposed.
+///
+/// Templates may contain the following placeholders:
+///
+/// - `{validator_index}`: the index of the proposer.
+/// - `{epoch}`: the epoch of the proposal.
+/// - `{el_client}`: the two-letter client code of the execution client, e.g. `GE`.
+/// - `{cl_version}`: the version of the consensus client, e.g. `v5.3.0`.
+/// - `{rotate:first|second|third}`: one of the listed messages, rotating every epoch.
+///
+/// Braces may be escaped as `{{` and `}}`. The rendered graffiti is truncated to
+/// `GRAFFITI_BYTES_LEN` bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraffitiTemplate {
+    segments: Vec<TemplateSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TemplateSegment {
+    Literal(String),
+    ValidatorIndex,
+    Epoch,
+    ElClient,
+    ClVersion,
+    Rotate(Vec<String>),
+}
+
+/// The values of the placeholders in a `GraffitiTemplate`.
+///
+/// Placeholders with unknown values are rendered as empty strings.
+#[derive(Debug, Clone, Copy)]
+pub struct GraffitiTemplateContext<'a> {
+    pub validator_index: Option<u64>,
+    pub epoch: Epoch,
+    pub el_client: Option<&'a str>,
+    pub cl_version: &'a str,
+}
+
+impl GraffitiTemplate {
+    /// Returns `true` if the template contains any placeholders.
+    pub fn has_placeholders(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| !matches!(segment, TemplateSegment::Literal(_)))
+    }
+
+    /// Returns `true` if the template contains the `{el_client}` placeholder.
+    pub fn uses_el_client(&self) -> bool {
+        self.segments.contains(&TemplateSegment::ElClient)
+    }
+
+    pub fn render(&self, context: &GraffitiTemplateContext) -> Graffiti {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                TemplateSegment::Literal(literal) => rendered.push_str(literal),
+                TemplateSegment::ValidatorIndex => {
+                    if let Some(validator_index) = context.validator_index {
+                        rendered.push_str(&validator_index.to_string());
+                    }
+                }
+                TemplateSegment::Epoch => rendered.push_str(&context.epoch.to_string()),
+                TemplateSegment::ElClient => rendered.push_str(context.el_client.unwrap_or("")),
+                TemplateSegment::ClVersion => rendered.push_str(context.cl_version),
+                TemplateSegment::Rotate(messages) => {
+                    let index = context.epoch.as_u64() % messages.len() as u64;
+                    if let Some(message) = messages.get(index as usize) {
+                        rendered.push_str(message);
+                    }
+                }
+            }
+        }
+
+        // Truncate to the last character boundary that fits.
+        let mut len = std::cmp::min(rendered.len(), GRAFFITI_BYTES_LEN);
+        while !rendered.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut graffiti = [0; GRAFFITI_BYTES_LEN];
+        graffiti[..len].copy_from_slice(&rendered.as_bytes()[..len]);
+        graffiti.into()
+    }
+}
+
+impl From<GraffitiString> for GraffitiTemplate {
+    fn from(graffiti: GraffitiString) -> Self {
+        Self {
+            segments: vec![TemplateSegment::Literal(graffiti.0)],
+        }
+    }
+}
+
+impl FromStr for GraffitiTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => {
+                                return Err(format!("Unterminated placeholder in {:?}", s))
+                            }
+                            Some(c) => placeholder.push(c),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        segments.push(TemplateSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(parse_placeholder(&placeholder)?);
+                }
+                '}' => return Err(format!("Unmatched }} in {:?}", s)),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(TemplateSegment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+}
+
+fn parse_placeholder(placeholder: &str) -> Result<TemplateSegment, String> {
+    match placeholder {
+        "validator_index" => Ok(TemplateSegment::ValidatorIndex),
+        "epoch" => Ok(TemplateSegment::Epoch),
+        "el_client" => Ok(TemplateSegment::ElClient),
+        "cl_version" => Ok(TemplateSegment::ClVersion),
+        _ => match placeholder.strip_prefix("rotate:") {
+            Some(messages) => Ok(TemplateSegment::Rotate(
+                messages.split('|').map(String::from).collect(),
+            )),
+            None => Err(format!("Unknown placeholder {{{}}}", placeholder)),
+        },
+    }
+}
+
+impl fmt::Display for GraffitiTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                TemplateSegment::Literal(literal) => {
+                    write!(f, "{}", literal.replace('{', "{{").replace('}', "}}"))?
+                }
+                TemplateSegment::ValidatorIndex => write!(f, "{{validator_index}}")?,
+                TemplateSegment::Epoch => write!(f, "{{epoch}}")?,
+                TemplateSegment::ElClient => write!(f, "{{el_client}}")?,
+                TemplateSegment::ClVersion => write!(f, "{{cl_version}}")?,
+                TemplateSegment::Rotate(messages) => {
+                    write!(f, "{{rotate:{}}}", messages.join("|"))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for GraffitiTemplate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GraffitiTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = serde::Deserialize::deserialize(deserializer)?;
+        GraffitiTemplate::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 pub mod serde_graffiti {
     use super::*;
 
@@ -183,3 +373,72 @@ impl TestRandom for Graffiti {
         Self::from(Hash256::random_for_test(rng).to_fixed_bytes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, context: &GraffitiTemplateContext) -> String {
+        GraffitiTemplate::from_str(template)
+            .unwrap()
+            .render(context)
+            .as_utf8_lossy()
+    }
+
+    #[test]
+    fn graffiti_template_placeholders() {
+        let context = GraffitiTemplateContext {
+            validator_index: Some(42),
+            epoch: Epoch::new(7),
+            el_client: Some("GE"),
+            cl_version: "v5.3.0",
+        };
+        assert_eq!(
+            render("{el_client}LH{cl_version} #{validator_index}", &context),
+            "GELHv5.3.0 #42"
+        );
+        assert_eq!(render("e{epoch} {rotate:a|b|c}", &context), "e7 b");
+        assert_eq!(render("{{literal}}", &context), "{literal}");
+
+        let unknown = GraffitiTemplateContext {
+            validator_index: None,
+            el_client: None,
+            ..context
+        };
+        assert_eq!(render("{el_client}#{validator_index}", &unknown), "#");
+    }
+
+    #[test]
+    fn graffiti_template_truncation() {
+        let context = GraffitiTemplateContext {
+            validator_index: None,
+            epoch: Epoch::new(0),
+            el_client: None,
+            cl_version: "",
+        };
+        let template = format!("{}é{{epoch}}", "a".repeat(GRAFFITI_BYTES_LEN - 1));
+        // The two-byte character doesn't fit and is dropped entirely.
+        assert_eq!(
+            render(&template, &context),
+            "a".repeat(GRAFFITI_BYTES_LEN - 1)
+        );
+    }
+
+    #[test]
+    fn graffiti_template_parsing() {
+        for template in ["plain", "{{", "{epoch}-{rotate:x|y}", "{el_client}}}"] {
+            let parsed = GraffitiTemplate::from_str(template).unwrap();
+            assert_eq!(parsed.to_string(), template);
+        }
+        assert!(!GraffitiTemplate::from_str("plain")
+            .unwrap()
+            .has_placeholders());
+        assert!(GraffitiTemplate::from_str("{el_client}")
+            .unwrap()
+            .uses_el_client());
+
+        for invalid in ["{", "}", "{unknown}", "{epoch", "{ep{och}"] {
+            assert!(GraffitiTemplate::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
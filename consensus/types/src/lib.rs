@@ -165,7 +165,7 @@ pub use crate::fork_context::ForkContext;
 pub use crate::fork_data::ForkData;
 pub use crate::fork_name::{ForkName, InconsistentFork};
 pub use crate::fork_versioned_response::{ForkVersionDeserialize, ForkVersionedResponse};
-pub use crate::graffiti::{Graffiti, GRAFFITI_BYTES_LEN};
+pub use crate::graffiti::{Graffiti, GraffitiTemplate, GRAFFITI_BYTES_LEN};
 pub use crate::historical_batch::HistoricalBatch;
 pub use crate::indexed_attestation::IndexedAttestation;
 pub use crate::light_client_bootstrap::{
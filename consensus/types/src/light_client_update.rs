@@ -1,21 +1,20 @@
 use super::{EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
 use crate::{
-    beacon_state, test_utils::TestRandom, BeaconBlock, BeaconBlockHeader, BeaconState, ChainSpec,
-    ForkName, ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
-    LightClientHeaderDeneb, SignedBeaconBlock,
+    beacon_state, test_utils::TestRandom, ChainSpec, ForkName, ForkVersionDeserialize,
+    LightClientHeader, LightClientHeaderAltair, LightClientHeaderCapella, LightClientHeaderDeneb,
+    SignedBeaconBlock,
 };
 use derivative::Derivative;
-use safe_arith::ArithError;
+use safe_arith::{ArithError, SafeArith};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use ssz::Decode;
+use ssz::{Decode, Encode};
 use ssz_derive::Decode;
 use ssz_derive::Encode;
 use ssz_types::typenum::{U4, U5, U6};
 use std::sync::Arc;
 use superstruct::superstruct;
 use test_random_derive::TestRandom;
-use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 pub const FINALIZED_ROOT_INDEX: usize = 105;
@@ -147,44 +146,35 @@ impl<E: EthSpec> ForkVersionDeserialize for LightClientUpdate<E> {
 }
 
 impl<E: EthSpec> LightClientUpdate<E> {
+    /// Create an update signed by `sync_aggregate` in `block_slot`, attesting to `attested_block`.
+    ///
+    /// The sync committee and the proofs are taken from the post-state of `attested_block`, and
+    /// `finalized_block` must be the block of its finalized checkpoint.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        beacon_state: BeaconState<E>,
-        block: BeaconBlock<E>,
-        attested_state: &mut BeaconState<E>,
+        sync_aggregate: &SyncAggregate<E>,
+        block_slot: Slot,
+        next_sync_committee: Arc<SyncCommittee<E>>,
+        next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+        finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
         attested_block: &SignedBeaconBlock<E>,
         finalized_block: &SignedBeaconBlock<E>,
         chain_spec: &ChainSpec,
     ) -> Result<Self, Error> {
-        let sync_aggregate = block.body().sync_aggregate()?;
         if sync_aggregate.num_set_bits() < chain_spec.min_sync_committee_participants as usize {
             return Err(Error::NotEnoughSyncCommitteeParticipants);
         }
 
-        let signature_period = block.epoch().sync_committee_period(chain_spec)?;
-        // Compute and validate attested header.
-        let mut attested_header = attested_state.latest_block_header().clone();
-        attested_header.state_root = attested_state.tree_hash_root();
-        let attested_period = attested_header
-            .slot
+        let signature_period = block_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let attested_period = attested_block
+            .slot()
             .epoch(E::slots_per_epoch())
             .sync_committee_period(chain_spec)?;
         if attested_period != signature_period {
             return Err(Error::MismatchingPeriods);
         }
-        // Build finalized header from finalized block
-        let finalized_header = BeaconBlockHeader {
-            slot: finalized_block.slot(),
-            proposer_index: finalized_block.message().proposer_index(),
-            parent_root: finalized_block.parent_root(),
-            state_root: finalized_block.state_root(),
-            body_root: finalized_block.message().body_root(),
-        };
-        if finalized_header.tree_hash_root() != beacon_state.finalized_checkpoint().root {
-            return Err(Error::InvalidFinalizedBlock);
-        }
-        let next_sync_committee_branch =
-            attested_state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?;
-        let finality_branch = attested_state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?;
 
         let light_client_update = match attested_block
             .fork_name(chain_spec)
@@ -198,12 +188,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderAltair::block_to_light_client_header(finalized_block)?;
                 Self::Altair(LightClientUpdateAltair {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot: block_slot,
                 })
             }
             ForkName::Capella => {
@@ -213,12 +203,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderCapella::block_to_light_client_header(finalized_block)?;
                 Self::Capella(LightClientUpdateCapella {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot: block_slot,
                 })
             }
             ForkName::Deneb | ForkName::Electra => {
@@ -228,12 +218,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderDeneb::block_to_light_client_header(finalized_block)?;
                 Self::Deneb(LightClientUpdateDeneb {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot: block_slot,
                 })
             }
         };
@@ -241,6 +231,110 @@ impl<E: EthSpec> LightClientUpdate<E> {
         Ok(light_client_update)
     }
 
+    pub fn map_with_fork_name<F, R>(&self, func: F) -> R
+    where
+        F: Fn(ForkName) -> R,
+    {
+        match self {
+            Self::Altair(_) => func(ForkName::Altair),
+            Self::Capella(_) => func(ForkName::Capella),
+            Self::Deneb(_) => func(ForkName::Deneb),
+        }
+    }
+
+    pub fn attested_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.attested_header.beacon.slot
+        })
+    }
+
+    pub fn finalized_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.finalized_header.beacon.slot
+        })
+    }
+
+    /// Returns `true` if the update proves the sync committee of the next period.
+    pub fn is_sync_committee_update(&self) -> bool {
+        self.next_sync_committee_branch()
+            .iter()
+            .any(|root| !root.is_zero())
+    }
+
+    /// Returns `true` if the update proves a finalized header.
+    pub fn is_finality_update(&self) -> bool {
+        self.finality_branch().iter().any(|root| !root.is_zero())
+    }
+
+    /// Returns `true` if `new` is a better update than `self` for the same sync committee period.
+    ///
+    /// ref: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#is_better_update
+    pub fn is_better_light_client_update(
+        &self,
+        new: &Self,
+        chain_spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        // Compare supermajority (> 2/3) sync committee participation.
+        let max_active_participants = new.sync_aggregate().sync_committee_bits.len();
+        let new_active_participants = new.sync_aggregate().num_set_bits();
+        let prev_active_participants = self.sync_aggregate().num_set_bits();
+
+        let new_has_supermajority =
+            new_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+        let prev_has_supermajority =
+            prev_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+        if new_has_supermajority != prev_has_supermajority {
+            return Ok(new_has_supermajority);
+        }
+        if !new_has_supermajority && new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Compare presence of relevant sync committee.
+        let new_has_relevant_sync_committee = new.is_sync_committee_update()
+            && sync_committee_period_at_slot::<E>(new.attested_header_slot(), chain_spec)?
+                == sync_committee_period_at_slot::<E>(*new.signature_slot(), chain_spec)?;
+        let prev_has_relevant_sync_committee = self.is_sync_committee_update()
+            && sync_committee_period_at_slot::<E>(self.attested_header_slot(), chain_spec)?
+                == sync_committee_period_at_slot::<E>(*self.signature_slot(), chain_spec)?;
+        if new_has_relevant_sync_committee != prev_has_relevant_sync_committee {
+            return Ok(new_has_relevant_sync_committee);
+        }
+
+        // Compare indication of any finality.
+        let new_has_finality = new.is_finality_update();
+        let prev_has_finality = self.is_finality_update();
+        if new_has_finality != prev_has_finality {
+            return Ok(new_has_finality);
+        }
+
+        // Compare sync committee finality.
+        if new_has_finality {
+            let new_has_sync_committee_finality =
+                sync_committee_period_at_slot::<E>(new.finalized_header_slot(), chain_spec)?
+                    == sync_committee_period_at_slot::<E>(new.attested_header_slot(), chain_spec)?;
+            let prev_has_sync_committee_finality =
+                sync_committee_period_at_slot::<E>(self.finalized_header_slot(), chain_spec)?
+                    == sync_committee_period_at_slot::<E>(self.attested_header_slot(), chain_spec)?;
+            if new_has_sync_committee_finality != prev_has_sync_committee_finality {
+                return Ok(new_has_sync_committee_finality);
+            }
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond supermajority.
+        if new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best).
+        if new.attested_header_slot() != self.attested_header_slot() {
+            return Ok(new.attested_header_slot() < self.attested_header_slot());
+        }
+        Ok(new.signature_slot() < self.signature_slot())
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
@@ -259,16 +353,95 @@ impl<E: EthSpec> LightClientUpdate<E> {
 
         Ok(update)
     }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
+        match fork_name {
+            ForkName::Base => 0,
+            ForkName::Altair
+            | ForkName::Bellatrix
+            | ForkName::Capella
+            | ForkName::Deneb
+            | ForkName::Electra => {
+                <LightClientUpdateAltair<E> as Encode>::ssz_fixed_len()
+                    + 2 * LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
+            }
+        }
+    }
+}
+
+fn sync_committee_period_at_slot<E: EthSpec>(
+    slot: Slot,
+    chain_spec: &ChainSpec,
+) -> Result<u64, ArithError> {
+    slot.epoch(E::slots_per_epoch())
+        .sync_committee_period(chain_spec)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
     use crate::MainnetEthSpec;
     use ssz_types::typenum::Unsigned;
+    use ssz_types::BitVector;
 
     ssz_tests!(LightClientUpdateDeneb<MainnetEthSpec>);
 
+    fn update_with_participants(
+        rng: &mut XorShiftRng,
+        participants: usize,
+        attested_slot: Slot,
+    ) -> LightClientUpdate<MainnetEthSpec> {
+        let mut update = LightClientUpdateAltair::<MainnetEthSpec>::random_for_test(rng);
+        update.attested_header.beacon.slot = attested_slot;
+        update.finalized_header.beacon.slot = Slot::new(0);
+        update.signature_slot = attested_slot + 1;
+        update.sync_aggregate.sync_committee_bits = BitVector::new();
+        for i in 0..participants {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(i, true)
+                .unwrap();
+        }
+        LightClientUpdate::Altair(update)
+    }
+
+    #[test]
+    fn better_update_prefers_supermajority() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let spec = MainnetEthSpec::default_spec();
+
+        let without_supermajority = update_with_participants(rng, 300, Slot::new(10));
+        let with_supermajority = update_with_participants(rng, 400, Slot::new(10));
+
+        assert!(without_supermajority
+            .is_better_light_client_update(&with_supermajority, &spec)
+            .unwrap());
+        assert!(!with_supermajority
+            .is_better_light_client_update(&without_supermajority, &spec)
+            .unwrap());
+    }
+
+    #[test]
+    fn better_update_tiebreakers() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let spec = MainnetEthSpec::default_spec();
+
+        // More participation beyond the supermajority wins.
+        let lower = update_with_participants(rng, 400, Slot::new(10));
+        let higher = update_with_participants(rng, 500, Slot::new(10));
+        assert!(lower.is_better_light_client_update(&higher, &spec).unwrap());
+        assert!(!higher.is_better_light_client_update(&lower, &spec).unwrap());
+
+        // With equal participation, older data wins.
+        let older = update_with_participants(rng, 400, Slot::new(10));
+        let newer = update_with_participants(rng, 400, Slot::new(20));
+        assert!(newer.is_better_light_client_update(&older, &spec).unwrap());
+        assert!(!older.is_better_light_client_update(&newer, &spec).unwrap());
+    }
+
     #[test]
     fn finalized_root_params() {
         assert!(2usize.pow(FINALIZED_ROOT_PROOF_LEN as u32) <= FINALIZED_ROOT_INDEX);
@@ -25,6 +25,10 @@ pub trait TSecretKey<SignaturePoint, PublicKeyPoint>: Sized {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Evaluates a random polynomial of degree `threshold - 1` with `self` as its constant term
+    /// at each of `1..=share_count`.
+    fn split_shares(&self, threshold: usize, share_count: usize) -> Result<Vec<Self>, Error>;
 }
 
 #[derive(Clone)]
@@ -71,6 +75,43 @@ where
         self.point.serialize()
     }
 
+    /// Splits `self` into `share_count` key shares, any `threshold` of which can produce a
+    /// signature by `self` (see `GenericSignature::combine_threshold_shares`).
+    ///
+    /// Each share is returned with its index, starting from 1.
+    ///
+    /// ## Note
+    ///
+    /// The shares are sensitive cryptographic material. Fewer than `threshold` of them reveal
+    /// nothing about `self`.
+    pub fn split_threshold_shares(
+        &self,
+        threshold: usize,
+        share_count: usize,
+    ) -> Result<Vec<(u64, Self)>, Error> {
+        if threshold == 0 || threshold > share_count {
+            return Err(Error::InvalidThreshold {
+                threshold,
+                share_count,
+            });
+        }
+
+        Ok(self
+            .point
+            .split_shares(threshold, share_count)?
+            .into_iter()
+            .zip(1..)
+            .map(|(point, index)| {
+                let share = Self {
+                    point,
+                    _phantom_signature: PhantomData,
+                    _phantom_public_key: PhantomData,
+                };
+                (index, share)
+            })
+            .collect())
+    }
+
     /// Deserialize `self` from compressed bytes.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         if bytes.len() != SECRET_KEY_BYTES_LEN {
@@ -6,6 +6,7 @@ use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_utils::hex::encode as hex_encode;
 use ssz::{Decode, Encode};
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
@@ -36,6 +37,10 @@ pub trait TSignature<GenericPublicKey>: Sized + Clone {
 
     /// Returns `true` if `self` is a signature across `msg` by `pubkey`.
     fn verify(&self, pubkey: &GenericPublicKey, msg: Hash256) -> bool;
+
+    /// Combines signature shares from distinct, non-zero key share indices via Lagrange
+    /// interpolation at zero.
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS signature that is generic across:
@@ -115,6 +120,33 @@ where
         }
     }
 
+    /// Combines the signatures produced by the key shares of a threshold key into a signature by
+    /// the threshold key itself.
+    ///
+    /// Each signature is paired with the index of the key share that produced it (see
+    /// `GenericSecretKey::split_threshold_shares`). The result is only valid if there are at least
+    /// as many shares as the threshold and they all sign the same message.
+    pub fn combine_threshold_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let mut indices = HashSet::with_capacity(shares.len());
+        let mut points = Vec::with_capacity(shares.len());
+        for (index, share) in shares {
+            if *index == 0 || !indices.insert(*index) {
+                return Err(Error::InvalidThresholdShareIndex(*index));
+            }
+            let point = share
+                .point()
+                .ok_or(Error::InvalidThresholdSignatureShares)?;
+            points.push((*index, point));
+        }
+        if points.is_empty() {
+            return Err(Error::InvalidThresholdSignatureShares);
+        }
+
+        let point = Sig::combine_shares(&points)?;
+        let is_infinity = point.serialize() == INFINITY_SIGNATURE;
+        Ok(Self::from_point(point, is_infinity))
+    }
+
     /// Deserialize `self` from compressed bytes.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         let point = if bytes == &NONE_SIGNATURE[..] {
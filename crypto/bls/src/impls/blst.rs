@@ -2,13 +2,14 @@ use crate::{
     generic_aggregate_public_key::TAggregatePublicKey,
     generic_aggregate_signature::TAggregateSignature,
     generic_public_key::{GenericPublicKey, TPublicKey, PUBLIC_KEY_BYTES_LEN},
-    generic_secret_key::TSecretKey,
+    generic_secret_key::{TSecretKey, SECRET_KEY_BYTES_LEN},
     generic_signature::{TSignature, SIGNATURE_BYTES_LEN},
     Error, Hash256, ZeroizeHash, INFINITY_SIGNATURE,
 };
 pub use blst::min_pk as blst_core;
-use blst::{blst_scalar, BLST_ERROR};
+use blst::{blst_fr, blst_p2, blst_p2_affine, blst_scalar, BLST_ERROR};
 use rand::Rng;
+use zeroize::Zeroize;
 
 pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 pub const RAND_BITS: usize = 64;
@@ -179,6 +180,76 @@ impl TSignature<blst_core::PublicKey> for blst_core::Signature {
         // Check Signature inside function for subgroup
         self.verify(true, msg.as_bytes(), DST, &[], pubkey, false) == BLST_ERROR::BLST_SUCCESS
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let indices = shares.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+
+        let mut sum = blst_p2::default();
+        for (index, share) in shares {
+            let coefficient = lagrange_coefficient_at_zero(*index, &indices);
+            let mut scalar = blst_scalar::default();
+            let mut affine = blst_p2_affine::default();
+            let mut point = blst_p2::default();
+            let mut term = blst_p2::default();
+
+            // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+            //
+            // https://github.com/sigp/lighthouse/issues/1720
+            unsafe {
+                let err = blst::blst_p2_uncompress(&mut affine, share.to_bytes().as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                blst::blst_p2_from_affine(&mut point, &affine);
+                blst::blst_scalar_from_fr(&mut scalar, &coefficient);
+                blst::blst_p2_mult(&mut term, &point, scalar.b.as_ptr(), 255);
+                let previous = sum;
+                blst::blst_p2_add_or_double(&mut sum, &previous, &term);
+            }
+        }
+
+        let mut bytes = [0; SIGNATURE_BYTES_LEN];
+        unsafe {
+            blst::blst_p2_compress(bytes.as_mut_ptr(), &sum);
+        }
+        Self::from_bytes(&bytes).map_err(Into::into)
+    }
+}
+
+/// Returns the scalar field element equal to `value`.
+fn fr_from_u64(value: u64) -> blst_fr {
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_fr_from_uint64(&mut fr, [value, 0, 0, 0].as_ptr());
+    }
+    fr
+}
+
+/// Returns the Lagrange basis polynomial for `index` over the points in `indices`, evaluated at
+/// zero.
+fn lagrange_coefficient_at_zero(index: u64, indices: &[u64]) -> blst_fr {
+    let x_i = fr_from_u64(index);
+    let mut numerator = fr_from_u64(1);
+    let mut denominator = fr_from_u64(1);
+    for &other in indices.iter().filter(|other| **other != index) {
+        let x_j = fr_from_u64(other);
+        let mut difference = blst_fr::default();
+        unsafe {
+            let previous = numerator;
+            blst::blst_fr_mul(&mut numerator, &previous, &x_j);
+            blst::blst_fr_sub(&mut difference, &x_j, &x_i);
+            let previous = denominator;
+            blst::blst_fr_mul(&mut denominator, &previous, &difference);
+        }
+    }
+
+    let mut inverse = blst_fr::default();
+    let mut coefficient = blst_fr::default();
+    unsafe {
+        blst::blst_fr_inverse(&mut inverse, &denominator);
+        blst::blst_fr_mul(&mut coefficient, &numerator, &inverse);
+    }
+    coefficient
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -278,4 +349,49 @@ impl TSecretKey<blst_core::Signature, blst_core::PublicKey> for blst_core::Secre
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn split_shares(&self, threshold: usize, share_count: usize) -> Result<Vec<Self>, Error> {
+        let to_fr = |secret_key: &Self| {
+            let mut bytes = secret_key.to_bytes();
+            let mut scalar = blst_scalar::default();
+            let mut fr = blst_fr::default();
+            unsafe {
+                blst::blst_scalar_from_bendian(&mut scalar, bytes.as_ptr());
+                blst::blst_fr_from_scalar(&mut fr, &scalar);
+            }
+            bytes.zeroize();
+            fr
+        };
+
+        // The polynomial coefficients, starting with the constant term.
+        let coefficients = std::iter::once(to_fr(self))
+            .chain((1..threshold).map(|_| to_fr(&Self::random())))
+            .collect::<Vec<_>>();
+
+        (1..=share_count as u64)
+            .map(|index| {
+                let x = fr_from_u64(index);
+
+                // Evaluate the polynomial at `x` using Horner's method.
+                let mut y = blst_fr::default();
+                for coefficient in coefficients.iter().rev() {
+                    let mut product = blst_fr::default();
+                    unsafe {
+                        blst::blst_fr_mul(&mut product, &y, &x);
+                        blst::blst_fr_add(&mut y, &product, coefficient);
+                    }
+                }
+
+                let mut scalar = blst_scalar::default();
+                let mut bytes = [0; SECRET_KEY_BYTES_LEN];
+                unsafe {
+                    blst::blst_scalar_from_fr(&mut scalar, &y);
+                    blst::blst_bendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+                }
+                let share = Self::from_bytes(&bytes).map_err(Into::into);
+                bytes.zeroize();
+                share
+            })
+            .collect()
+    }
 }
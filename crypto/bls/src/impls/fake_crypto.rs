@@ -105,6 +105,10 @@ impl TSignature<PublicKey> for Signature {
     fn verify(&self, _pubkey: &PublicKey, _msg: Hash256) -> bool {
         true
     }
+
+    fn combine_shares(_shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        Ok(Self::infinity())
+    }
 }
 
 impl PartialEq for Signature {
@@ -211,4 +215,8 @@ impl TSecretKey<Signature, PublicKey> for SecretKey {
         sk.0[..].copy_from_slice(&bytes[0..SECRET_KEY_BYTES_LEN]);
         Ok(sk)
     }
+
+    fn split_shares(&self, _threshold: usize, share_count: usize) -> Result<Vec<Self>, Error> {
+        Ok(vec![self.clone(); share_count])
+    }
 }
@@ -57,6 +57,15 @@ pub enum Error {
     InvalidInfinityPublicKey,
     /// The secret key is all zero bytes, which is invalid.
     InvalidZeroSecretKey,
+    /// The threshold is zero or exceeds the number of key shares.
+    InvalidThreshold {
+        threshold: usize,
+        share_count: usize,
+    },
+    /// A key share index is zero or is duplicated.
+    InvalidThresholdShareIndex(u64),
+    /// No signature shares were provided, or one of them was empty.
+    InvalidThresholdSignatureShares,
 }
 
 #[cfg(feature = "supranational")]
@@ -161,6 +161,80 @@ macro_rules! test_suite {
                 .assert_verify(false)
         }
 
+        #[test]
+        fn threshold_signature_from_any_threshold_shares() {
+            let secret = secret_from_u64(42);
+            let pubkey = secret.public_key();
+            let msg = Hash256::from_low_u64_be(42);
+
+            let key_shares = secret.split_threshold_shares(3, 5).unwrap();
+            assert_eq!(
+                key_shares
+                    .iter()
+                    .map(|(index, _)| *index)
+                    .collect::<Vec<_>>(),
+                vec![1, 2, 3, 4, 5]
+            );
+            let signature_shares = key_shares
+                .iter()
+                .map(|(index, share)| (*index, share.sign(msg)))
+                .collect::<Vec<_>>();
+
+            for subset in [[0, 1, 2], [0, 2, 4], [4, 1, 3], [2, 3, 4]] {
+                let shares = subset
+                    .iter()
+                    .map(|i| (signature_shares[*i].0, &signature_shares[*i].1))
+                    .collect::<Vec<_>>();
+                let signature = Signature::combine_threshold_shares(&shares).unwrap();
+                assert!(signature.verify(&pubkey, msg));
+                assert_eq!(signature, secret.sign(msg));
+            }
+
+            // All of the shares can be combined too, but fewer than the threshold cannot.
+            let shares = signature_shares
+                .iter()
+                .map(|(index, share)| (*index, share))
+                .collect::<Vec<_>>();
+            assert!(Signature::combine_threshold_shares(&shares)
+                .unwrap()
+                .verify(&pubkey, msg));
+            assert!(!Signature::combine_threshold_shares(&shares[..2])
+                .unwrap()
+                .verify(&pubkey, msg));
+        }
+
+        #[test]
+        fn threshold_signature_invalid_shares() {
+            let secret = secret_from_u64(42);
+            let msg = Hash256::from_low_u64_be(42);
+            let signature = secret.sign(msg);
+
+            assert!(matches!(
+                secret.split_threshold_shares(0, 3),
+                Err(bls::Error::InvalidThreshold { .. })
+            ));
+            assert!(matches!(
+                secret.split_threshold_shares(4, 3),
+                Err(bls::Error::InvalidThreshold { .. })
+            ));
+            assert_eq!(
+                Signature::combine_threshold_shares(&[]),
+                Err(bls::Error::InvalidThresholdSignatureShares)
+            );
+            assert_eq!(
+                Signature::combine_threshold_shares(&[(0, &signature)]),
+                Err(bls::Error::InvalidThresholdShareIndex(0))
+            );
+            assert_eq!(
+                Signature::combine_threshold_shares(&[(1, &signature), (1, &signature)]),
+                Err(bls::Error::InvalidThresholdShareIndex(1))
+            );
+            assert_eq!(
+                Signature::combine_threshold_shares(&[(1, &Signature::empty())]),
+                Err(bls::Error::InvalidThresholdSignatureShares)
+            );
+        }
+
         /// A helper struct for composing tests via the builder pattern.
         struct AggregateSignatureTester {
             sig: AggregateSignature,
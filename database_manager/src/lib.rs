@@ -1,22 +1,33 @@
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
-    slot_clock::SystemTimeSlotClock,
+    slot_clock::SystemTimeSlotClock, BeaconChain, ResetPayloadStatuses,
+};
+use beacon_node::{
+    get_compression_config, get_data_dir, get_hierarchy_config, get_slots_per_restore_point,
+    ClientConfig,
 };
-use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::{get_color_style, FLAG_HEADER};
 use environment::{Environment, RuntimeContext};
+use kzg::{Kzg, TrustedSetup};
 use slog::{info, warn, Logger};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
+    backend::detect_backend,
+    backup, compression,
+    config::OnDiskStoreConfig,
+    era::{self, EraError},
     errors::Error,
-    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    get_key_for_col,
+    hdiff::HierarchyConfig,
+    metadata::{SchemaVersion, CONFIG_KEY, CURRENT_SCHEMA_VERSION},
+    BeaconNodeBackend, DBColumn, DatabaseBackend, HotColdDB, ItemStore, KeyValueStore,
+    KeyValueStoreOp, RawBackend,
 };
-use strum::{EnumString, EnumVariantNames, VariantNames};
+use strum::{EnumString, EnumVariantNames, IntoEnumIterator, VariantNames};
 use types::{BeaconState, EthSpec, Slot};
 
 pub const CMD: &str = "database_manager";
@@ -31,14 +42,34 @@ pub fn version_cli_app() -> Command {
 pub fn migrate_cli_app() -> Command {
     Command::new("migrate")
         .styles(get_color_style())
-        .about("Migrate the database to a specific schema version")
+        .about("Migrate the database to a specific schema version or freezer layout")
         .arg(
             Arg::new("to")
                 .long("to")
                 .value_name("VERSION")
                 .help("Schema version to migrate to")
                 .action(ArgAction::Set)
-                .required(true),
+                .required_unless_present_any(["to-hierarchy", "to-restore-points"]),
+        )
+        .arg(
+            Arg::new("to-hierarchy")
+                .long("to-hierarchy")
+                .value_name("EXPONENTS")
+                .help(
+                    "Re-encode the freezer database using hierarchical state diffs with the \
+                     given comma-separated exponents. The smallest exponent must match the \
+                     existing restore point interval.",
+                )
+                .action(ArgAction::Set)
+                .conflicts_with_all(["to", "to-restore-points"]),
+        )
+        .arg(
+            Arg::new("to-restore-points")
+                .long("to-restore-points")
+                .help("Re-encode all hierarchical state diffs in the freezer as full states")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .conflicts_with_all(["to", "to-hierarchy"]),
         )
 }
 
@@ -50,9 +81,11 @@ pub fn inspect_cli_app() -> Command {
             Arg::new("column")
                 .long("column")
                 .value_name("TAG")
-                .help("3-byte column ID (see `DBColumn`)")
+                .help(
+                    "3-byte column ID (see `DBColumn`). Required unless the output is \
+                     `compression`, which inspects every column by default.",
+                )
                 .action(ArgAction::Set)
-                .required(true)
                 .display_order(0),
         )
         .arg(
@@ -114,7 +147,10 @@ pub fn compact_cli_app() -> Command {
             Arg::new("column")
                 .long("column")
                 .value_name("TAG")
-                .help("3-byte column ID (see `DBColumn`)")
+                .help(
+                    "3-byte column ID (see `DBColumn`). Ignored by redb, which always compacts \
+                    the whole database",
+                )
                 .action(ArgAction::Set)
                 .required(true)
                 .display_order(0),
@@ -171,6 +207,169 @@ pub fn prune_states_app() -> Command {
         .about("Prune all beacon states from the freezer database")
 }
 
+pub fn copy_backend_app() -> Command {
+    Command::new("copy-backend")
+        .styles(get_color_style())
+        .about("Copy the database into a new directory using a different backend")
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("BACKEND")
+                .help("Database backend to copy the database into")
+                .value_parser(DatabaseBackend::VARIANTS.to_vec())
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help(
+                    "Beacon node data directory to write the copy to. The hot, freezer and blobs \
+                     databases are created within it using their default names.",
+                )
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+}
+
+pub fn backup_app() -> Command {
+    Command::new("backup")
+        .styles(get_color_style())
+        .about(
+            "Write a point-in-time backup of the hot, freezer and blobs databases. The beacon \
+             node must be stopped; use the `/lighthouse/database/backup` HTTP API endpoint to \
+             back up a running node.",
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory to write the backup to. Must not already exist.")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+}
+
+pub fn restore_app() -> Command {
+    Command::new("restore")
+        .styles(get_color_style())
+        .about(
+            "Restore a backup into the configured data directory, which must not contain a \
+             database",
+        )
+        .arg(
+            Arg::new("backup-dir")
+                .long("backup-dir")
+                .value_name("DIR")
+                .help("Directory containing the backup to restore")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+}
+
+pub fn verify_app() -> Command {
+    Command::new("verify")
+        .styles(get_color_style())
+        .about(
+            "Verify the integrity of the database by walking the canonical chain and checking \
+             every block, blob, restore point and state summary. Prints a JSON report.",
+        )
+        .arg(
+            Arg::new("repair")
+                .long("repair")
+                .help("Delete orphaned entries found during verification")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FILE")
+                .help("Write the report to FILE instead of stdout")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+pub fn export_era_app() -> Command {
+    Command::new("export-era")
+        .styles(get_color_style())
+        .about(
+            "Export finalized blocks from the freezer database to era files, one per \
+             SLOTS_PER_HISTORICAL_ROOT slots. Existing era files are not overwritten.",
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory to write the era files to")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("start-era")
+                .long("start-era")
+                .value_name("ERA")
+                .help("First era to export. Defaults to the oldest era stored in the database.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("end-era")
+                .long("end-era")
+                .value_name("ERA")
+                .help("Last era to export. Defaults to the newest finalized era.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("blobs")
+                .long("blobs")
+                .help("Include blobs in the era files")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("states")
+                .long("states")
+                .help(
+                    "Include the state at the end of each era. This is required to import eras \
+                     prior to Capella, and requires the states to be stored in the freezer \
+                     database.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+}
+
+pub fn import_era_app() -> Command {
+    Command::new("import-era")
+        .styles(get_color_style())
+        .about(
+            "Import blocks older than the oldest stored block from era files, verifying them \
+             against the historical roots of the finalized state. This fills in the same \
+             history as backfill sync.",
+        )
+        .arg(
+            Arg::new("era-dir")
+                .long("era-dir")
+                .value_name("DIR")
+                .help("Directory containing the era files to import")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+}
+
 pub fn cli_app() -> Command {
     Command::new(CMD)
         .display_order(0)
@@ -198,6 +397,50 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0),
         )
+        .arg(
+            Arg::new("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help(
+                    "Exponents of the hierarchical state diffs used by the freezer database. \
+                       Must match the value the database was initialized with.",
+                )
+                .action(ArgAction::Set)
+                .conflicts_with("slots-per-restore-point")
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node.")
+                .value_parser(DatabaseBackend::VARIANTS.to_vec())
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("db-compression-level")
+                .long("db-compression-level")
+                .value_name("LEVEL")
+                .help(
+                    "Compress database values written by this command with zstd at the given \
+                     level.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("db-compression-columns")
+                .long("db-compression-columns")
+                .value_name("TAGS")
+                .help(
+                    "Comma-separated list of 3-byte database column tags whose values should \
+                     be compressed. [default: ste,blk,exp]",
+                )
+                .action(ArgAction::Set)
+                .requires("db-compression-level")
+                .display_order(0),
+        )
         .arg(
             Arg::new("freezer-dir")
                 .long("freezer-dir")
@@ -233,6 +476,12 @@ pub fn cli_app() -> Command {
         .subcommand(prune_payloads_app())
         .subcommand(prune_blobs_app())
         .subcommand(prune_states_app())
+        .subcommand(copy_backend_app())
+        .subcommand(backup_app())
+        .subcommand(restore_app())
+        .subcommand(verify_app())
+        .subcommand(export_era_app())
+        .subcommand(import_era_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -254,6 +503,12 @@ fn parse_client_config<E: EthSpec>(
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(cli_args)?;
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    client_config.store.hierarchy_config = get_hierarchy_config(cli_args)?;
+
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+    client_config.store.compression = get_compression_config(cli_args)?;
 
     if let Some(blob_prune_margin_epochs) =
         clap_utils::parse_optional(cli_args, "blob-prune-margin-epochs")?
@@ -275,7 +530,7 @@ pub fn display_db_version<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -311,10 +566,13 @@ pub enum InspectTarget {
     Values,
     #[strum(serialize = "gaps")]
     Gaps,
+    #[strum(serialize = "compression")]
+    Compression,
 }
 
 pub struct InspectConfig {
-    column: DBColumn,
+    /// The column to inspect. Only optional for `InspectTarget::Compression`.
+    column: Option<DBColumn>,
     target: InspectTarget,
     skip: Option<usize>,
     limit: Option<usize>,
@@ -325,8 +583,11 @@ pub struct InspectConfig {
 }
 
 fn parse_inspect_config(cli_args: &ArgMatches) -> Result<InspectConfig, String> {
-    let column = clap_utils::parse_required(cli_args, "column")?;
+    let column = clap_utils::parse_optional(cli_args, "column")?;
     let target = clap_utils::parse_required(cli_args, "output")?;
+    if column.is_none() && target != InspectTarget::Compression {
+        return Err("--column is required for this output".into());
+    }
     let skip = clap_utils::parse_optional(cli_args, "skip")?;
     let limit = clap_utils::parse_optional(cli_args, "limit")?;
     let freezer = cli_args.get_flag("freezer");
@@ -357,16 +618,28 @@ pub fn inspect_db<E: EthSpec>(
     let mut num_keys = 0;
 
     let sub_db = if inspect_config.freezer {
-        LevelDB::<E>::open(&cold_path).map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
+        RawBackend::<E>::open(client_config.store.backend, &cold_path)
+            .map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
     } else if inspect_config.blobs_db {
-        LevelDB::<E>::open(&blobs_path).map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
+        RawBackend::<E>::open(client_config.store.backend, &blobs_path)
+            .map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
     } else {
-        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
+        RawBackend::<E>::open(client_config.store.backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
     let skip = inspect_config.skip.unwrap_or(0);
     let limit = inspect_config.limit.unwrap_or(usize::MAX);
 
+    let column = match (inspect_config.target, inspect_config.column) {
+        (InspectTarget::Compression, column) => {
+            let columns = column.map_or_else(|| DBColumn::iter().collect(), |col| vec![col]);
+            return inspect_compression(&sub_db, &columns, skip, limit);
+        }
+        (_, Some(column)) => column,
+        (_, None) => return Err("No column to inspect".into()),
+    };
+
     let mut prev_key = 0;
     let mut found_gaps = false;
 
@@ -377,12 +650,13 @@ pub fn inspect_db<E: EthSpec>(
             .map_err(|e| format!("Unable to create import directory: {:?}", e))?;
     }
 
-    for res in sub_db
-        .iter_column::<Vec<u8>>(inspect_config.column)
-        .skip(skip)
-        .take(limit)
-    {
+    let compressed_columns = compression::load_compressed_columns(&sub_db)
+        .map_err(|e| format!("Unable to load compressed columns: {e:?}"))?;
+    let decompress = compressed_columns.contains(&column);
+
+    for res in sub_db.iter_column::<Vec<u8>>(column).skip(skip).take(limit) {
         let (key, value) = res.map_err(|e| format!("{:?}", e))?;
+        total += value.len();
 
         match inspect_config.target {
             InspectTarget::ValueSizes => {
@@ -405,13 +679,16 @@ pub fn inspect_db<E: EthSpec>(
                 }
                 prev_key = numeric_key;
             }
-            InspectTarget::ValueTotal => (),
+            InspectTarget::ValueTotal | InspectTarget::Compression => (),
             InspectTarget::Values => {
-                let file_path = base_path.join(format!(
-                    "{}_{}.ssz",
-                    inspect_config.column.as_str(),
-                    hex::encode(&key)
-                ));
+                let file_path =
+                    base_path.join(format!("{}_{}.ssz", column.as_str(), hex::encode(&key)));
+                let value = if decompress {
+                    compression::decompress(value)
+                        .map_err(|e| format!("Unable to decompress value: {e:?}"))?
+                } else {
+                    value
+                };
 
                 let write_result = fs::OpenOptions::new()
                     .create(true)
@@ -430,7 +707,6 @@ pub fn inspect_db<E: EthSpec>(
                 }
             }
         }
-        total += value.len();
         num_keys += 1;
     }
 
@@ -444,6 +720,61 @@ pub fn inspect_db<E: EthSpec>(
     Ok(())
 }
 
+/// Print the compression ratio of each of `columns`.
+fn inspect_compression<E: EthSpec>(
+    sub_db: &RawBackend<E>,
+    columns: &[DBColumn],
+    skip: usize,
+    limit: usize,
+) -> Result<(), String> {
+    let compressed_columns = compression::load_compressed_columns(sub_db)
+        .map_err(|e| format!("Unable to load compressed columns: {e:?}"))?;
+
+    for &column in columns {
+        let may_be_compressed = compressed_columns.contains(&column);
+        let mut num_keys = 0;
+        let mut num_compressed = 0;
+        let mut stored = 0;
+        let mut uncompressed = 0;
+
+        for res in sub_db.iter_raw_entries(column, &[]).skip(skip).take(limit) {
+            let (_, value) = res.map_err(|e| format!("{:?}", e))?;
+            num_keys += 1;
+            stored += value.len();
+
+            if may_be_compressed && compression::is_compressed(&value) {
+                num_compressed += 1;
+                uncompressed += compression::decompress(value)
+                    .map_err(|e| format!("Unable to decompress value: {e:?}"))?
+                    .len();
+            } else {
+                uncompressed += value.len();
+            }
+        }
+
+        // Skip empty columns unless they were explicitly requested.
+        if num_keys == 0 && columns.len() > 1 {
+            continue;
+        }
+
+        let ratio = if stored == 0 {
+            1.0
+        } else {
+            uncompressed as f64 / stored as f64
+        };
+        println!(
+            "{}: {} keys ({} compressed), {} bytes stored, {} bytes uncompressed, ratio {:.2}",
+            column.as_str(),
+            num_keys,
+            num_compressed,
+            stored,
+            uncompressed,
+            ratio,
+        );
+    }
+    Ok(())
+}
+
 pub struct CompactConfig {
     column: DBColumn,
     freezer: bool,
@@ -472,30 +803,67 @@ pub fn compact_db<E: EthSpec>(
     let column = compact_config.column;
 
     let (sub_db, db_name) = if compact_config.freezer {
-        (LevelDB::<E>::open(&cold_path)?, "freezer_db")
+        (
+            RawBackend::<E>::open(client_config.store.backend, &cold_path)?,
+            "freezer_db",
+        )
     } else if compact_config.blobs_db {
-        (LevelDB::<E>::open(&blobs_path)?, "blobs_db")
+        (
+            RawBackend::<E>::open(client_config.store.backend, &blobs_path)?,
+            "blobs_db",
+        )
     } else {
-        (LevelDB::<E>::open(&hot_path)?, "hot_db")
+        (
+            RawBackend::<E>::open(client_config.store.backend, &hot_path)?,
+            "hot_db",
+        )
     };
-    info!(
-        log,
-        "Compacting database";
-        "db" => db_name,
-        "column" => ?column
-    );
-    sub_db.compact_column(column)?;
+    match sub_db {
+        // redb can only compact the whole file.
+        RawBackend::Redb(db) => {
+            info!(
+                log,
+                "Compacting database";
+                "db" => db_name,
+            );
+            db.compact_file()?;
+        }
+        RawBackend::LevelDb(db) => {
+            info!(
+                log,
+                "Compacting database";
+                "db" => db_name,
+                "column" => ?column
+            );
+            db.compact_column(column)?;
+        }
+    }
     Ok(())
 }
 
-pub struct MigrateConfig {
-    to: SchemaVersion,
+pub enum MigrateConfig {
+    /// Migrate to a different schema version.
+    Schema { to: SchemaVersion },
+    /// Re-encode the freezer database with a different diff hierarchy (or none at all).
+    Hierarchy {
+        hierarchy_config: Option<HierarchyConfig>,
+    },
 }
 
 fn parse_migrate_config(cli_args: &ArgMatches) -> Result<MigrateConfig, String> {
-    let to = SchemaVersion(clap_utils::parse_required(cli_args, "to")?);
+    if let Some(hierarchy_config) = clap_utils::parse_optional(cli_args, "to-hierarchy")? {
+        return Ok(MigrateConfig::Hierarchy {
+            hierarchy_config: Some(hierarchy_config),
+        });
+    }
+    if cli_args.get_flag("to-restore-points") {
+        return Ok(MigrateConfig::Hierarchy {
+            hierarchy_config: None,
+        });
+    }
 
-    Ok(MigrateConfig { to })
+    let to = SchemaVersion(clap_utils::parse_required(cli_args, "to")?);
+    Ok(MigrateConfig::Schema { to })
 }
 
 pub fn migrate_db<E: EthSpec>(
@@ -504,14 +872,20 @@ pub fn migrate_db<E: EthSpec>(
     runtime_context: &RuntimeContext<E>,
     log: Logger,
 ) -> Result<(), Error> {
+    let to = match migrate_config {
+        MigrateConfig::Schema { to } => to,
+        MigrateConfig::Hierarchy { hierarchy_config } => {
+            return migrate_freezer_hierarchy(hierarchy_config, client_config, runtime_context, log)
+        }
+    };
+
     let spec = &runtime_context.eth2_config.spec;
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut from = CURRENT_SCHEMA_VERSION;
-    let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -541,6 +915,40 @@ pub fn migrate_db<E: EthSpec>(
     )
 }
 
+fn migrate_freezer_hierarchy<E: EthSpec>(
+    hierarchy_config: Option<HierarchyConfig>,
+    mut client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    // Open the database with the layout it currently has on disk, regardless of the CLI flags.
+    let on_disk_config = {
+        let hot_db = BeaconNodeBackend::<E>::open(&client_config.store, &hot_path)?;
+        hot_db.get::<OnDiskStoreConfig>(&CONFIG_KEY)?
+    };
+    if let Some(on_disk_config) = on_disk_config {
+        client_config.store.slots_per_restore_point = on_disk_config.slots_per_restore_point;
+        client_config.store.hierarchy_config = on_disk_config.hierarchy_config;
+    }
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log,
+    )?;
+
+    db.convert_freezer_hierarchy(hierarchy_config)
+}
+
 pub fn prune_payloads<E: EthSpec>(
     client_config: ClientConfig,
     runtime_context: &RuntimeContext<E>,
@@ -551,7 +959,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -577,7 +985,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -613,7 +1021,7 @@ pub fn prune_states<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -675,6 +1083,471 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+pub struct CopyBackendConfig {
+    to: DatabaseBackend,
+    output_dir: PathBuf,
+}
+
+fn parse_copy_backend_config(cli_args: &ArgMatches) -> Result<CopyBackendConfig, String> {
+    let to = clap_utils::parse_required(cli_args, "to")?;
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    Ok(CopyBackendConfig { to, output_dir })
+}
+
+/// Number of key-value pairs to write per transaction when copying a database.
+const COPY_BATCH_SIZE: usize = 1024;
+
+/// Copy the hot, freezer and blobs databases into `output_dir` using a different backend.
+///
+/// The copy is made key-by-key, so the source can use any backend and the copy is byte-for-byte
+/// identical apart from the backend recorded in the on-disk store config.
+pub fn copy_backend<E: EthSpec>(
+    copy_config: CopyBackendConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), String> {
+    let from = client_config.store.backend;
+    let to = copy_config.to;
+
+    let mut target_config = client_config.clone();
+    target_config.set_data_dir(copy_config.output_dir);
+    target_config.freezer_db_path = None;
+    target_config.blobs_db_path = None;
+
+    let databases = [
+        (
+            "hot_db",
+            client_config.get_db_path(),
+            target_config.get_db_path(),
+        ),
+        (
+            "freezer_db",
+            client_config.get_freezer_db_path(),
+            target_config.get_freezer_db_path(),
+        ),
+        (
+            "blobs_db",
+            client_config.get_blobs_db_path(),
+            target_config.get_blobs_db_path(),
+        ),
+    ];
+
+    // Check all paths before copying anything.
+    for (db_name, source_path, target_path) in &databases {
+        if detect_backend(source_path) != Some(from) {
+            return Err(format!(
+                "No {} database found for {db_name} at {source_path:?}",
+                from.as_str()
+            ));
+        }
+        if let Some(existing) = detect_backend(target_path) {
+            return Err(format!(
+                "Refusing to overwrite existing {} database at {target_path:?}",
+                existing.as_str()
+            ));
+        }
+    }
+
+    for (db_name, source_path, target_path) in &databases {
+        info!(
+            log,
+            "Copying database";
+            "db" => db_name,
+            "from" => from.as_str(),
+            "to" => to.as_str(),
+            "target" => ?target_path,
+        );
+        let source = RawBackend::<E>::open(from, source_path)
+            .map_err(|e| format!("Unable to open {db_name}: {e:?}"))?;
+        let target = RawBackend::<E>::open(to, target_path)
+            .map_err(|e| format!("Unable to create {db_name} copy: {e:?}"))?;
+        let num_keys = copy_database(&source, &target)
+            .map_err(|e| format!("Error copying {db_name}: {e:?}"))?;
+        info!(log, "Database copied"; "db" => db_name, "num_keys" => num_keys);
+    }
+
+    // Record the new backend in the copy, so that it can be opened.
+    let target_hot_db = RawBackend::<E>::open(to, &target_config.get_db_path())
+        .map_err(|e| format!("Unable to open hot DB copy: {e:?}"))?;
+    if let Some(mut on_disk_config) = target_hot_db
+        .get::<OnDiskStoreConfig>(&CONFIG_KEY)
+        .map_err(|e| format!("Unable to read store config: {e:?}"))?
+    {
+        on_disk_config.backend = to;
+        target_hot_db
+            .put(&CONFIG_KEY, &on_disk_config)
+            .map_err(|e| format!("Unable to write store config: {e:?}"))?;
+    }
+    target_hot_db
+        .sync()
+        .map_err(|e| format!("Unable to sync hot DB copy: {e:?}"))?;
+
+    info!(
+        log,
+        "Copy complete";
+        "hint" => format!("run the beacon node with `--beacon-node-backend {}`", to.as_str()),
+    );
+    Ok(())
+}
+
+/// Copy every column of `source` into `target`, returning the number of keys copied.
+fn copy_database<E: EthSpec>(
+    source: &RawBackend<E>,
+    target: &RawBackend<E>,
+) -> Result<usize, Error> {
+    let mut num_keys = 0;
+    for column in DBColumn::iter() {
+        let mut ops = vec![];
+        for res in source.iter_raw_entries(column, &[]) {
+            let (key, value) = res?;
+            ops.push(KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(column.into(), &key),
+                value,
+            ));
+            num_keys += 1;
+
+            if ops.len() >= COPY_BATCH_SIZE {
+                target.do_atomically(std::mem::take(&mut ops))?;
+            }
+        }
+        target.do_atomically(ops)?;
+    }
+    target.sync()?;
+    Ok(num_keys)
+}
+
+pub struct BackupConfig {
+    output_dir: PathBuf,
+}
+
+fn parse_backup_config(cli_args: &ArgMatches) -> Result<BackupConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    Ok(BackupConfig { output_dir })
+}
+
+pub fn backup_db<E: EthSpec>(
+    backup_config: BackupConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    info!(log, "Writing database backup"; "output_dir" => ?backup_config.output_dir);
+    let manifest = db.backup(&backup_config.output_dir)?;
+    info!(
+        log,
+        "Database backup complete";
+        "split_slot" => manifest.split.slot,
+        "hot_entries" => manifest.hot_entries,
+        "cold_entries" => manifest.cold_entries,
+        "blobs_entries" => manifest.blobs_entries,
+    );
+    Ok(())
+}
+
+pub struct RestoreConfig {
+    backup_dir: PathBuf,
+}
+
+fn parse_restore_config(cli_args: &ArgMatches) -> Result<RestoreConfig, String> {
+    let backup_dir = clap_utils::parse_required(cli_args, "backup-dir")?;
+    Ok(RestoreConfig { backup_dir })
+}
+
+pub fn restore_db<E: EthSpec>(
+    restore_config: RestoreConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), Error> {
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let manifest = backup::load_manifest(&restore_config.backup_dir)?;
+    info!(
+        log,
+        "Restoring database backup";
+        "schema_version" => manifest.schema_version,
+        "split_slot" => manifest.split.slot,
+        "backend" => client_config.store.backend.as_str(),
+    );
+
+    backup::restore::<E>(
+        &restore_config.backup_dir,
+        &client_config.store,
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+    )?;
+
+    if manifest.schema_version < CURRENT_SCHEMA_VERSION.as_u64() {
+        warn!(
+            log,
+            "Restored database uses an older schema";
+            "info" => "the database will be migrated when the beacon node starts",
+            "schema_version" => manifest.schema_version,
+        );
+    }
+    info!(log, "Database restore complete"; "datadir" => ?hot_path);
+    Ok(())
+}
+
+pub struct VerifyConfig {
+    repair: bool,
+    output: Option<PathBuf>,
+}
+
+fn parse_verify_config(cli_args: &ArgMatches) -> Result<VerifyConfig, String> {
+    let repair = cli_args.get_flag("repair");
+    let output = clap_utils::parse_optional(cli_args, "output")?;
+    Ok(VerifyConfig { repair, output })
+}
+
+pub fn verify_db<E: EthSpec>(
+    verify_config: VerifyConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    // Use the head from the persisted fork choice, as the beacon node would on start-up.
+    let fork_choice = BeaconChain::<
+        Witness<
+            SystemTimeSlotClock,
+            CachingEth1Backend<E>,
+            E,
+            BeaconNodeBackend<E>,
+            BeaconNodeBackend<E>,
+        >,
+    >::load_fork_choice(
+        db.clone(),
+        ResetPayloadStatuses::OnlyWithInvalidPayload,
+        spec,
+        &log,
+    )
+    .map_err(|e| format!("Unable to load fork choice: {e:?}"))?
+    .ok_or("Fork choice is missing from the database")?;
+    let head_block_root = fork_choice.get_forkchoice_update_parameters().head_root;
+
+    info!(
+        log,
+        "Verifying database";
+        "head_block_root" => ?head_block_root,
+        "repair" => verify_config.repair,
+    );
+    let report = db
+        .verify(head_block_root, verify_config.repair)
+        .map_err(|e| format!("Unable to verify database: {e:?}"))?;
+
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Unable to serialize report: {e:?}"))?;
+    match &verify_config.output {
+        Some(path) => fs::write(path, json)
+            .map_err(|e| format!("Unable to write report to {path:?}: {e:?}"))?,
+        None => println!("{json}"),
+    }
+
+    if !report.repaired.is_empty() {
+        info!(log, "Repaired database"; "repaired" => report.repaired.len());
+    }
+    if report.is_ok() {
+        info!(
+            log,
+            "Database verification complete";
+            "blocks_checked" => report.blocks_checked,
+            "blobs_checked" => report.blobs_checked,
+            "restore_points_checked" => report.restore_points_checked,
+            "state_summaries_checked" => report.state_summaries_checked,
+        );
+        Ok(())
+    } else {
+        Err(format!(
+            "Database verification found {} issues",
+            report.issues.len()
+        ))
+    }
+}
+
+pub struct ExportEraConfig {
+    output_dir: PathBuf,
+    start_era: Option<u64>,
+    end_era: Option<u64>,
+    blobs: bool,
+    states: bool,
+}
+
+fn parse_export_era_config(cli_args: &ArgMatches) -> Result<ExportEraConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    let start_era = clap_utils::parse_optional(cli_args, "start-era")?;
+    let end_era = clap_utils::parse_optional(cli_args, "end-era")?;
+    let blobs = cli_args.get_flag("blobs");
+    let states = cli_args.get_flag("states");
+    Ok(ExportEraConfig {
+        output_dir,
+        start_era,
+        end_era,
+        blobs,
+        states,
+    })
+}
+
+pub fn export_era<E: EthSpec>(
+    export_config: ExportEraConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    let Some(exportable_eras) = db.exportable_eras() else {
+        warn!(log, "No complete eras in the freezer database");
+        return Ok(());
+    };
+    let start_era = export_config
+        .start_era
+        .unwrap_or(*exportable_eras.start())
+        .max(*exportable_eras.start());
+    let end_era = export_config
+        .end_era
+        .unwrap_or(*exportable_eras.end())
+        .min(*exportable_eras.end());
+
+    fs::create_dir_all(&export_config.output_dir).map_err(EraError::Io)?;
+    info!(
+        log,
+        "Exporting eras";
+        "start_era" => start_era,
+        "end_era" => end_era,
+        "output_dir" => ?export_config.output_dir,
+    );
+    for era in start_era..=end_era {
+        match db.export_era(
+            era,
+            &export_config.output_dir,
+            export_config.blobs,
+            export_config.states,
+        ) {
+            Ok(path) => info!(log, "Exported era"; "era" => era, "path" => ?path),
+            Err(Error::EraError(EraError::OutputExists(path))) => {
+                info!(log, "Era file already exists"; "era" => era, "path" => ?path)
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+pub struct ImportEraConfig {
+    era_dir: PathBuf,
+}
+
+fn parse_import_era_config(cli_args: &ArgMatches) -> Result<ImportEraConfig, String> {
+    let era_dir = clap_utils::parse_required(cli_args, "era-dir")?;
+    Ok(ImportEraConfig { era_dir })
+}
+
+/// Load the KZG trusted setup of the network, which is required to verify blobs.
+fn load_kzg<E: EthSpec>(runtime_context: &RuntimeContext<E>) -> Result<Kzg, String> {
+    let trusted_setup_bytes = runtime_context
+        .eth2_network_config
+        .as_ref()
+        .and_then(|config| config.kzg_trusted_setup.as_ref())
+        .ok_or("No trusted setup available to verify blobs")?;
+    let trusted_setup: TrustedSetup = serde_json::from_slice(trusted_setup_bytes)
+        .map_err(|e| format!("Unable to read trusted setup file: {}", e))?;
+    Kzg::new_from_trusted_setup(trusted_setup)
+        .map_err(|e| format!("Unable to load trusted setup: {:?}", e))
+}
+
+pub fn import_era<E: EthSpec>(
+    import_config: ImportEraConfig,
+    client_config: ClientConfig,
+    kzg: &Kzg,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    let paths = fs::read_dir(&import_config.era_dir)
+        .map_err(EraError::Io)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(EraError::Io)?
+        .into_iter()
+        .filter(|path| era::parse_era_filename(path).is_some())
+        .collect::<Vec<_>>();
+
+    info!(
+        log,
+        "Importing era files";
+        "count" => paths.len(),
+        "oldest_block_slot" => db.get_oldest_block_slot(),
+    );
+    let num_blocks = db.import_era_files(&paths, kzg)?;
+    info!(
+        log,
+        "Era import complete";
+        "blocks_imported" => num_blocks,
+        "oldest_block_slot" => db.get_oldest_block_slot(),
+    );
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(cli_args: &ArgMatches, env: Environment<E>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -726,6 +1599,31 @@ pub fn run<E: EthSpec>(cli_args: &ArgMatches, env: Environment<E>) -> Result<(),
 
             prune_states(client_config, prune_config, genesis_state, &context, log)
         }
+        Some(("copy-backend", cli_args)) => {
+            let copy_config = parse_copy_backend_config(cli_args)?;
+            copy_backend::<E>(copy_config, client_config, log)
+        }
+        Some(("backup", cli_args)) => {
+            let backup_config = parse_backup_config(cli_args)?;
+            backup_db(backup_config, client_config, &context, log).map_err(format_err)
+        }
+        Some(("restore", cli_args)) => {
+            let restore_config = parse_restore_config(cli_args)?;
+            restore_db::<E>(restore_config, client_config, log).map_err(format_err)
+        }
+        Some(("verify", cli_args)) => {
+            let verify_config = parse_verify_config(cli_args)?;
+            verify_db(verify_config, client_config, &context, log)
+        }
+        Some(("export-era", cli_args)) => {
+            let export_config = parse_export_era_config(cli_args)?;
+            export_era(export_config, client_config, &context, log).map_err(format_err)
+        }
+        Some(("import-era", cli_args)) => {
+            let import_config = parse_import_era_config(cli_args)?;
+            let kzg = load_kzg(&context)?;
+            import_era(import_config, client_config, &kzg, &context, log).map_err(format_err)
+        }
         _ => Err("Unknown subcommand, for help `lighthouse database_manager --help`".into()),
     }
 }
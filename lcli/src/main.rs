@@ -514,6 +514,15 @@ fn main() {
                         .default_value("8551")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("ipc-path")
+                        .long("ipc-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("If set, the server will also serve the engine API over a Unix \
+                            domain socket at this path. No JWT is required over IPC.")
+                        .display_order(0)
+                )
                 .arg(
                     Arg::new("all-payloads-valid")
                         .long("all-payloads-valid")
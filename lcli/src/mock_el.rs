@@ -15,6 +15,7 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
     let jwt_path: PathBuf = parse_required(matches, "jwt-output-path")?;
     let listen_addr: Ipv4Addr = parse_required(matches, "listen-address")?;
     let listen_port: u16 = parse_required(matches, "listen-port")?;
+    let ipc_path: Option<PathBuf> = parse_optional(matches, "ipc-path")?;
     let all_payloads_valid: bool = parse_required(matches, "all-payloads-valid")?;
     let shanghai_time = parse_required(matches, "shanghai-time")?;
     let cancun_time = parse_optional(matches, "cancun-time")?;
@@ -29,6 +30,7 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
         server_config: Config {
             listen_addr,
             listen_port,
+            ipc_path,
         },
         jwt_key,
         terminal_difficulty: spec.terminal_total_difficulty,
@@ -491,11 +491,14 @@ fn validator_import_launchpad() {
         enabled: false,
         description: "".into(),
         graffiti: None,
+        graffiti_template: None,
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        pending_exit: None,
+        doppelganger: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -615,11 +618,14 @@ fn validator_import_launchpad_no_password_then_add_password() {
         enabled: true,
         description: "".into(),
         graffiti: None,
+        graffiti_template: None,
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        pending_exit: None,
+        doppelganger: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -643,11 +649,14 @@ fn validator_import_launchpad_no_password_then_add_password() {
         enabled: true,
         description: "".into(),
         graffiti: None,
+        graffiti_template: None,
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        pending_exit: None,
+        doppelganger: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
@@ -747,11 +756,14 @@ fn validator_import_launchpad_password_file() {
         description: "".into(),
         voting_public_key: keystore.public_key().unwrap(),
         graffiti: None,
+        graffiti_template: None,
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        pending_exit: None,
+        doppelganger: None,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
@@ -439,8 +439,7 @@ fn eth1_cache_follow_distance_manual() {
 fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
+    // the first provided endpoint is the primary, the rest are fallbacks.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -475,6 +474,10 @@ fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
                 config.execution_endpoint.as_ref().unwrap().clone(),
                 SensitiveUrl::parse(&urls[0]).unwrap()
             );
+            assert_eq!(
+                config.fallback_execution_endpoints,
+                vec![SensitiveUrl::parse(&urls[1]).unwrap()]
+            );
             // Only the first secret file should be used.
             assert_eq!(
                 config.secret_file.as_ref().unwrap().clone(),
@@ -483,6 +486,23 @@ fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
         });
 }
 #[test]
+fn execution_endpoint_ipc_flag() {
+    use sensitive_url::SensitiveUrl;
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwt_file = dir.path().join("jwt-file");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("ipc:///var/lib/geth/geth.ipc"))
+        .flag("execution-jwt", jwt_file.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(
+                config.execution_endpoint.as_ref().unwrap().clone(),
+                SensitiveUrl::parse("ipc:///var/lib/geth/geth.ipc").unwrap()
+            );
+        });
+}
+#[test]
 fn run_execution_jwt_secret_key_is_persisted() {
     let jwt_secret_key = "0x3cbc11b0d8fa16f3344eacfd6ff6430b9d30734450e8adcf5400f88d327dcb33";
     CommandLineTest::new()
@@ -1645,6 +1665,28 @@ fn http_allow_origin_all_flag() {
         .with_config(|config| assert_eq!(config.http_api.allow_origin, Some("*".to_string())));
 }
 
+#[test]
+fn http_backup_dir_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.backup_dir, None));
+}
+
+#[test]
+fn http_backup_dir_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-backup-dir", Some("/var/backups/lighthouse"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.backup_dir,
+                Some(PathBuf::from("/var/backups/lighthouse"))
+            )
+        });
+}
+
 #[test]
 fn http_allow_sync_stalled_flag() {
     CommandLineTest::new()
@@ -1878,6 +1920,82 @@ fn slots_per_restore_point_update_prev_default() {
         })
 }
 
+#[test]
+fn hierarchy_exponents_flag() {
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,7,11"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.slots_per_restore_point, 32);
+            assert_eq!(
+                config.store.hierarchy_config.as_ref().unwrap().exponents,
+                vec![5, 7, 11]
+            );
+        });
+}
+
+#[test]
+fn hierarchy_exponents_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hierarchy_config, None));
+}
+
+#[test]
+fn beacon_node_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.backend,
+                beacon_node::beacon_chain::store::DatabaseBackend::LevelDb
+            )
+        });
+}
+#[test]
+fn beacon_node_backend_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.backend,
+                beacon_node::beacon_chain::store::DatabaseBackend::Redb
+            )
+        });
+}
+#[test]
+fn db_compression_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.compression, None));
+}
+#[test]
+fn db_compression_level_flag() {
+    use beacon_node::beacon_chain::store::compression::DEFAULT_COMPRESSED_COLUMNS;
+    CommandLineTest::new()
+        .flag("db-compression-level", Some("5"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let compression = config.store.compression.as_ref().unwrap();
+            assert_eq!(compression.level, 5);
+            assert_eq!(compression.columns, DEFAULT_COMPRESSED_COLUMNS.to_vec());
+        });
+}
+#[test]
+fn db_compression_columns_flag() {
+    use beacon_node::beacon_chain::store::DBColumn;
+    CommandLineTest::new()
+        .flag("db-compression-level", Some("1"))
+        .flag("db-compression-columns", Some("ste,bsd"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compression.as_ref().unwrap().columns,
+                vec![DBColumn::BeaconState, DBColumn::BeaconStateDiff]
+            )
+        });
+}
 #[test]
 fn block_cache_size_flag() {
     CommandLineTest::new()
@@ -2020,6 +2138,24 @@ fn epochs_per_migration_override() {
         .run_with_zero_port()
         .with_config(|config| assert_eq!(config.chain.epochs_per_migration, 128));
 }
+#[test]
+fn epochs_per_fork_sweep_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.epochs_per_fork_sweep,
+                beacon_node::beacon_chain::abandoned_fork_sweeper::DEFAULT_EPOCHS_PER_FORK_SWEEP
+            )
+        });
+}
+#[test]
+fn epochs_per_fork_sweep_override() {
+    CommandLineTest::new()
+        .flag("epochs-per-fork-sweep", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.epochs_per_fork_sweep, 0));
+}
 
 // Tests for Slasher flags.
 // Using `--slasher-max-db-size` to work around https://github.com/sigp/lighthouse/issues/2342
@@ -2257,6 +2393,22 @@ fn slasher_backend_override_to_default() {
         });
 }
 
+#[test]
+fn slasher_migrate_from_flag() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-max-db-size", Some("1"))
+        .flag("slasher-migrate-from", Some("lmdb"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config.slasher.as_ref().unwrap();
+            assert_eq!(
+                slasher_config.migrate_from,
+                Some(slasher::DatabaseBackend::Lmdb)
+            );
+        });
+}
+
 #[test]
 fn malloc_tuning_flag() {
     CommandLineTest::new()
@@ -2579,6 +2731,21 @@ fn disable_optimistic_finalized_sync() {
         });
 }
 
+#[test]
+fn publish_engine_blobs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.publish_engine_blobs));
+}
+
+#[test]
+fn disable_publish_engine_blobs() {
+    CommandLineTest::new()
+        .flag("disable-publish-engine-blobs", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.publish_engine_blobs));
+}
+
 #[test]
 fn invalid_gossip_verified_blocks_path_default() {
     CommandLineTest::new()
@@ -1,4 +1,7 @@
-use validator_client::{config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, Config};
+use validator_client::{
+    config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, AttestationConsensus, Config,
+    DisagreementAction,
+};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
@@ -343,6 +346,84 @@ fn http_store_keystore_passwords_in_secrets_dir_present() {
 
 // Tests for Metrics flags.
 #[test]
+fn remote_signer_server_flags() {
+    let addr = "127.0.0.99".parse::<IpAddr>().unwrap();
+    CommandLineTest::new()
+        .flag("remote-signer-server", None)
+        .flag("remote-signer-server-address", Some("127.0.0.99"))
+        .flag("remote-signer-server-port", Some("9090"))
+        .run()
+        .with_config(|config| {
+            assert!(config.remote_signer_server.enabled);
+            assert_eq!(config.remote_signer_server.listen_addr, addr);
+            assert_eq!(config.remote_signer_server.listen_port, 9090);
+            assert!(!config.remote_signer_server.allow_unprotected_messages);
+        });
+}
+#[test]
+fn remote_signer_server_disabled_by_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.remote_signer_server.enabled));
+}
+#[test]
+fn remote_signer_server_allow_unprotected_flag() {
+    CommandLineTest::new()
+        .flag("remote-signer-server", None)
+        .flag("remote-signer-server-allow-unprotected", None)
+        .run()
+        .with_config(|config| assert!(config.remote_signer_server.allow_unprotected_messages));
+}
+#[test]
+fn slashing_protection_standby_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("slashing-protection-standby", None)
+        .run()
+        .with_config(|config| assert!(config.slashing_protection_standby));
+}
+#[test]
+fn slashing_protection_standby_disabled_by_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(!config.slashing_protection_standby);
+        assert!(config.slashing_protection_replication.is_none());
+    });
+}
+#[test]
+fn slashing_protection_replication_flags() {
+    CommandLineTest::new()
+        .flag(
+            "slashing-protection-replication-url",
+            Some("http://standby:5062"),
+        )
+        .flag(
+            "slashing-protection-replication-token",
+            Some("/tmp/api-token.txt"),
+        )
+        .run()
+        .with_config(|config| {
+            let replication = config.slashing_protection_replication.as_ref().unwrap();
+            assert_eq!(
+                replication.standby_url.full.as_str(),
+                "http://standby:5062/"
+            );
+            assert_eq!(
+                replication.standby_api_token_path,
+                PathBuf::from("/tmp/api-token.txt")
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn slashing_protection_replication_url_without_token() {
+    CommandLineTest::new()
+        .flag(
+            "slashing-protection-replication-url",
+            Some("http://standby:5062"),
+        )
+        .run();
+}
+#[test]
 fn metrics_flag() {
     CommandLineTest::new()
         .flag("metrics", None)
@@ -423,6 +504,28 @@ fn no_doppelganger_protection_flag() {
         .with_config(|config| assert!(!config.enable_doppelganger_protection));
 }
 #[test]
+fn doppelganger_lookback_epochs_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.doppelganger_lookback_epochs, 0));
+}
+#[test]
+fn doppelganger_lookback_epochs_flag() {
+    CommandLineTest::new()
+        .flag("enable-doppelganger-protection", None)
+        .flag("doppelganger-lookback-epochs", Some("2"))
+        .run()
+        .with_config(|config| assert_eq!(config.doppelganger_lookback_epochs, 2));
+}
+#[test]
+#[should_panic]
+fn doppelganger_lookback_epochs_too_large() {
+    CommandLineTest::new()
+        .flag("enable-doppelganger-protection", None)
+        .flag("doppelganger-lookback-epochs", Some("17"))
+        .run();
+}
+#[test]
 fn produce_block_v3_flag() {
     CommandLineTest::new()
         .flag("produce-block-v3", None)
@@ -592,6 +695,60 @@ fn wrong_broadcast_flag() {
         });
 }
 
+#[test]
+fn attestation_consensus_disabled_by_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.attestation_consensus, None));
+}
+#[test]
+fn attestation_consensus_flags() {
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://localhost:1001,http://localhost:1002,http://localhost:1003"),
+        )
+        .flag("attestation-consensus-quorum", Some("2"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.attestation_consensus,
+                Some(AttestationConsensus {
+                    quorum: 2,
+                    on_disagreement: DisagreementAction::Majority,
+                })
+            );
+        });
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://localhost:1001,http://localhost:1002"),
+        )
+        .flag("attestation-consensus-quorum", Some("2"))
+        .flag("attestation-consensus-fallback", Some("skip"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.attestation_consensus,
+                Some(AttestationConsensus {
+                    quorum: 2,
+                    on_disagreement: DisagreementAction::Skip,
+                })
+            );
+        });
+}
+#[test]
+#[should_panic(expected = "attestation-consensus-quorum must be between 1")]
+fn attestation_consensus_quorum_above_beacon_nodes() {
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://localhost:1001,http://localhost:1002"),
+        )
+        .flag("attestation-consensus-quorum", Some("3"))
+        .run();
+}
+
 #[test]
 fn disable_latency_measurement_service() {
     CommandLineTest::new()
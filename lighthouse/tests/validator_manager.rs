@@ -8,9 +8,13 @@ use std::str::FromStr;
 use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
+    broadcast_signed::BroadcastConfig,
+    convert_signing_method::{ConversionTarget, ConvertConfig},
     create_validators::CreateConfig,
+    export_signing_requests::{ExportConfig, SigningRequestType},
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
+    sign_offline::SignOfflineConfig,
 };
 
 const EXAMPLE_ETH1_ADDRESS: &str = "0x00000000219ab540356cBB839Cbe05303d7705Fa";
@@ -105,6 +109,30 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ExportConfig> {
+    fn export_signing_requests() -> Self {
+        Self::default().flag("export-signing-requests", None)
+    }
+}
+
+impl CommandLineTest<SignOfflineConfig> {
+    fn sign_offline() -> Self {
+        Self::default().flag("sign-offline", None)
+    }
+}
+
+impl CommandLineTest<BroadcastConfig> {
+    fn broadcast_signed() -> Self {
+        Self::default().flag("broadcast-signed", None)
+    }
+}
+
+impl CommandLineTest<ConvertConfig> {
+    fn convert_signing_method() -> Self {
+        Self::default().flag("convert-signing-method", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +422,219 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn export_signing_requests_voluntary_exit() {
+    CommandLineTest::export_signing_requests()
+        .flag(
+            "--validators",
+            Some(&format!("{},{}", EXAMPLE_PUBKEY_0, EXAMPLE_PUBKEY_1)),
+        )
+        .flag("--request-type", Some("voluntary-exit"))
+        .flag("--exit-epoch", Some("1024"))
+        .flag("--output-path", Some("./requests.json"))
+        .assert_success(|config| {
+            let expected = ExportConfig {
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+                validators: vec![
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap(),
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap(),
+                ],
+                request_type: SigningRequestType::VoluntaryExit,
+                exit_epoch: Some(Epoch::new(1024)),
+                execution_address: None,
+                fee_recipient: None,
+                gas_limit: 30_000_000,
+                output_path: PathBuf::from("./requests.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn export_signing_requests_bls_to_execution_change() {
+    CommandLineTest::export_signing_requests()
+        .flag("--beacon-node", Some("http://localhost:1"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .flag("--request-type", Some("bls-to-execution-change"))
+        .flag("--execution-address", Some(EXAMPLE_ETH1_ADDRESS))
+        .flag("--output-path", Some("./requests.json"))
+        .assert_success(|config| {
+            let expected = ExportConfig {
+                bn_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                validators: vec![PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap()],
+                request_type: SigningRequestType::BlsToExecutionChange,
+                exit_epoch: None,
+                execution_address: Some(Address::from_str(EXAMPLE_ETH1_ADDRESS).unwrap()),
+                fee_recipient: None,
+                gas_limit: 30_000_000,
+                output_path: PathBuf::from("./requests.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn export_signing_requests_bls_to_execution_change_without_address() {
+    CommandLineTest::export_signing_requests()
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .flag("--request-type", Some("bls-to-execution-change"))
+        .flag("--output-path", Some("./requests.json"))
+        .assert_failed();
+}
+
+#[test]
+pub fn export_signing_requests_validator_registration() {
+    CommandLineTest::export_signing_requests()
+        .flag("--validators", Some(EXAMPLE_PUBKEY_1))
+        .flag("--request-type", Some("validator-registration"))
+        .flag("--suggested-fee-recipient", Some(EXAMPLE_ETH1_ADDRESS))
+        .flag("--gas-limit", Some("36000000"))
+        .flag("--output-path", Some("./requests.json"))
+        .assert_success(|config| {
+            let expected = ExportConfig {
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+                validators: vec![PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap()],
+                request_type: SigningRequestType::ValidatorRegistration,
+                exit_epoch: None,
+                execution_address: None,
+                fee_recipient: Some(Address::from_str(EXAMPLE_ETH1_ADDRESS).unwrap()),
+                gas_limit: 36_000_000,
+                output_path: PathBuf::from("./requests.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn sign_offline_defaults() {
+    CommandLineTest::sign_offline()
+        .flag("--requests-file", Some("./requests.json"))
+        .flag("--keystores", Some("./keystores,./withdrawal.json"))
+        .flag("--output-path", Some("./signed.json"))
+        .assert_success(|config| {
+            let expected = SignOfflineConfig {
+                requests_path: PathBuf::from("./requests.json"),
+                keystore_paths: vec![
+                    PathBuf::from("./keystores"),
+                    PathBuf::from("./withdrawal.json"),
+                ],
+                password_path: None,
+                stdin_inputs: cfg!(windows) || false,
+                output_path: PathBuf::from("./signed.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn sign_offline_misc_flags() {
+    CommandLineTest::sign_offline()
+        .flag("--requests-file", Some("./requests.json"))
+        .flag("--keystores", Some("./keystores"))
+        .flag("--password-file", Some("./password.txt"))
+        .flag("--stdin-inputs", None)
+        .flag("--output-path", Some("./signed.json"))
+        .assert_success(|config| {
+            let expected = SignOfflineConfig {
+                requests_path: PathBuf::from("./requests.json"),
+                keystore_paths: vec![PathBuf::from("./keystores")],
+                password_path: Some(PathBuf::from("./password.txt")),
+                stdin_inputs: true,
+                output_path: PathBuf::from("./signed.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn broadcast_signed_defaults() {
+    CommandLineTest::broadcast_signed()
+        .flag("--signed-file", Some("./signed.json"))
+        .assert_success(|config| {
+            let expected = BroadcastConfig {
+                signed_path: PathBuf::from("./signed.json"),
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn broadcast_signed_without_signed_file() {
+    CommandLineTest::broadcast_signed().assert_failed();
+}
+
+#[test]
+pub fn convert_signing_method_to_web3signer() {
+    CommandLineTest::convert_signing_method()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--web3signer-url", Some("http://localhost:9000"))
+        .flag(
+            "--validators",
+            Some(&format!("{},{}", EXAMPLE_PUBKEY_0, EXAMPLE_PUBKEY_1)),
+        )
+        .flag("--web3signer-request-timeout-ms", Some("2000"))
+        .assert_success(|config| {
+            let expected = ConvertConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                target: ConversionTarget::Web3Signer {
+                    validators: vec![
+                        PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap(),
+                        PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap(),
+                    ],
+                    url: "http://localhost:9000".to_string(),
+                    root_certificate_path: None,
+                    request_timeout_ms: Some(2000),
+                },
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn convert_signing_method_to_local_keystore() {
+    CommandLineTest::convert_signing_method()
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--keystores", Some("./keys,./keystore.json"))
+        .flag("--password-file", Some("./password.txt"))
+        .assert_success(|config| {
+            let expected = ConvertConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                target: ConversionTarget::LocalKeystore {
+                    keystore_paths: vec![PathBuf::from("./keys"), PathBuf::from("./keystore.json")],
+                    password_path: Some(PathBuf::from("./password.txt")),
+                    stdin_inputs: cfg!(windows) || false,
+                },
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn convert_signing_method_without_target() {
+    CommandLineTest::convert_signing_method()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
+
+#[test]
+pub fn convert_signing_method_with_both_targets() {
+    CommandLineTest::convert_signing_method()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--keystores", Some("./keys"))
+        .flag("--web3signer-url", Some("http://localhost:9000"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .assert_failed();
+}
+
+#[test]
+pub fn convert_signing_method_without_validators() {
+    CommandLineTest::convert_signing_method()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--web3signer-url", Some("http://localhost:9000"))
+        .assert_failed();
+}
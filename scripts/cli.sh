@@ -29,6 +29,10 @@ vm_cli=$($CMD vm --help)
 vm_cli_create=$($CMD vm create --help)
 vm_cli_import=$($CMD vm import --help)
 vm_cli_move=$($CMD vm move --help)
+vm_cli_export=$($CMD vm export-signing-requests --help)
+vm_cli_sign=$($CMD vm sign-offline --help)
+vm_cli_broadcast=$($CMD vm broadcast-signed --help)
+vm_cli_convert=$($CMD vm convert-signing-method --help)
 
 general=./help_general.md
 bn=./help_bn.md
@@ -38,6 +42,10 @@ vm=./help_vm.md
 vm_create=./help_vm_create.md
 vm_import=./help_vm_import.md
 vm_move=./help_vm_move.md
+vm_export=./help_vm_export_signing_requests.md
+vm_sign=./help_vm_sign_offline.md
+vm_broadcast=./help_vm_broadcast_signed.md
+vm_convert=./help_vm_convert_signing_method.md
 
 # create .md files
 write_to_file "$general_cli" "$general" "Lighthouse General Commands"
@@ -47,10 +55,14 @@ write_to_file "$vm_cli" "$vm" "Validator Manager"
 write_to_file "$vm_cli_create" "$vm_create" "Validator Manager Create"
 write_to_file "$vm_cli_import" "$vm_import" "Validator Manager Import"
 write_to_file "$vm_cli_move" "$vm_move" "Validator Manager Move"
+write_to_file "$vm_cli_export" "$vm_export" "Validator Manager Export Signing Requests"
+write_to_file "$vm_cli_sign" "$vm_sign" "Validator Manager Sign Offline"
+write_to_file "$vm_cli_broadcast" "$vm_broadcast" "Validator Manager Broadcast Signed"
+write_to_file "$vm_cli_convert" "$vm_convert" "Validator Manager Convert Signing Method"
 
 #input 1 = $1 = files; input 2 = $2 = new files
-files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md)
-new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move)
+files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md ./book/src/help_vm_export_signing_requests.md ./book/src/help_vm_sign_offline.md ./book/src/help_vm_broadcast_signed.md ./book/src/help_vm_convert_signing_method.md)
+new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move $vm_export $vm_sign $vm_broadcast $vm_convert)
 
 # function to check
 check() {
@@ -84,9 +96,12 @@ check ${files[3]} ${new_files[3]}
 check ${files[4]} ${new_files[4]}
 check ${files[5]} ${new_files[5]}
 check ${files[6]} ${new_files[6]}
+check ${files[7]} ${new_files[7]}
+check ${files[8]} ${new_files[8]}
+check ${files[9]} ${new_files[9]}
 
 # remove help files
-rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md
+rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md help_vm_export_signing_requests.md help_vm_sign_offline.md help_vm_broadcast_signed.md help_vm_convert_signing_method.md
 
 # only exit at the very end
 if [[ $changes == true ]]; then
@@ -1,7 +1,8 @@
+use crate::database::{ssz_decode, CurrentEpochKey};
 use crate::metrics::{self, SLASHER_COMPRESSION_RATIO, SLASHER_NUM_CHUNKS_UPDATED};
 use crate::{
-    AttesterSlashingStatus, Config, Database, Error, IndexedAttesterRecord, RwTransaction,
-    SlasherDB,
+    AttesterSlashingStatus, Config, Database, Error, IndexedAttesterRecord, RoTransaction,
+    RwTransaction, SlasherDB,
 };
 use flate2::bufread::{ZlibDecoder, ZlibEncoder};
 use serde::{Deserialize, Serialize};
@@ -38,6 +39,16 @@ impl Chunk {
         epoch: Epoch,
         config: &Config,
     ) -> Result<Epoch, Error> {
+        let distance = self.get_raw_distance(validator_index, epoch, config)?;
+        Ok(epoch + u64::from(distance))
+    }
+
+    pub fn get_raw_distance(
+        &self,
+        validator_index: u64,
+        epoch: Epoch,
+        config: &Config,
+    ) -> Result<u16, Error> {
         assert_eq!(
             self.data.len(),
             config.chunk_size * config.validator_chunk_size
@@ -47,7 +58,7 @@ impl Chunk {
         let cell_index = config.cell_index(validator_offset, chunk_offset);
         self.data
             .get(cell_index)
-            .map(|distance| epoch + u64::from(*distance))
+            .copied()
             .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
     }
 
@@ -160,10 +171,25 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         let Some(chunk_bytes) = txn.get(Self::select_db(db), &disk_key.to_be_bytes())? else {
             return Ok(None);
         };
+        Self::decode(chunk_bytes.borrow()).map(Some)
+    }
 
-        let chunk = bincode::deserialize_from(ZlibDecoder::new(chunk_bytes.borrow()))?;
+    fn load_read_only<E: EthSpec>(
+        db: &SlasherDB<E>,
+        txn: &RoTransaction<'_>,
+        validator_chunk_index: usize,
+        chunk_index: usize,
+        config: &Config,
+    ) -> Result<Option<Self>, Error> {
+        let disk_key = config.disk_key(validator_chunk_index, chunk_index);
+        let Some(chunk_bytes) = txn.get(Self::select_db(db), &disk_key.to_be_bytes())? else {
+            return Ok(None);
+        };
+        Self::decode(chunk_bytes.borrow()).map(Some)
+    }
 
-        Ok(Some(chunk))
+    fn decode(chunk_bytes: &[u8]) -> Result<Self, Error> {
+        Ok(bincode::deserialize_from(ZlibDecoder::new(chunk_bytes))?)
     }
 
     fn store<E: EthSpec>(
@@ -394,6 +420,140 @@ impl TargetArrayChunk for MaxTargetChunk {
     }
 }
 
+/// The min and max targets recorded for a validator at a single epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochTargets {
+    pub epoch: Epoch,
+    /// The minimum target of the validator's attestations with source epochs after `epoch`.
+    pub min_target: Option<Epoch>,
+    /// The maximum target of the validator's attestations with source epochs before `epoch`.
+    pub max_target: Option<Epoch>,
+}
+
+/// Whether the min and max target chunks with a given `chunk_index` are stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkStatus {
+    pub chunk_index: usize,
+    pub min_chunk_stored: bool,
+    pub max_chunk_stored: bool,
+}
+
+/// The state of a validator's min-max target arrays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayStatus {
+    pub validator_chunk_index: usize,
+    /// The latest epoch that the validator's arrays have been updated for.
+    pub current_epoch: Option<Epoch>,
+    /// The oldest epoch covered by the validator's arrays. Values for older epochs have been
+    /// overwritten.
+    pub pruning_horizon: Option<Epoch>,
+    pub chunks: Vec<ChunkStatus>,
+    pub targets: Vec<EpochTargets>,
+}
+
+/// Read the min-max target arrays of `validator_index` for epochs from `start_epoch` to
+/// `end_epoch` (inclusive), limited to the epochs which the arrays still cover.
+pub fn get_array_status<E: EthSpec>(
+    db: &SlasherDB<E>,
+    txn: &RoTransaction<'_>,
+    validator_index: u64,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    config: &Config,
+) -> Result<ArrayStatus, Error> {
+    let validator_chunk_index = config.validator_chunk_index(validator_index);
+    let Some(current_epoch) = txn
+        .get(
+            &db.databases.current_epochs_db,
+            CurrentEpochKey::new(validator_index).as_ref(),
+        )?
+        .map(ssz_decode)
+        .transpose()?
+    else {
+        return Ok(ArrayStatus {
+            validator_chunk_index,
+            current_epoch: None,
+            pruning_horizon: None,
+            chunks: vec![],
+            targets: vec![],
+        });
+    };
+    let pruning_horizon =
+        Epoch::new((current_epoch.as_u64() + 1).saturating_sub(config.history_length as u64));
+
+    let start_epoch = std::cmp::max(start_epoch, pruning_horizon);
+    let end_epoch = std::cmp::min(end_epoch, current_epoch);
+
+    let mut min_chunks = BTreeMap::<usize, Option<MinTargetChunk>>::new();
+    let mut max_chunks = BTreeMap::<usize, Option<MaxTargetChunk>>::new();
+    let mut targets = vec![];
+
+    for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).map(Epoch::new) {
+        let min_target = read_target(db, txn, &mut min_chunks, validator_index, epoch, config)?;
+        let max_target = read_target(db, txn, &mut max_chunks, validator_index, epoch, config)?;
+        targets.push(EpochTargets {
+            epoch,
+            min_target,
+            max_target,
+        });
+    }
+
+    let chunks = min_chunks
+        .iter()
+        .map(|(chunk_index, min_chunk)| ChunkStatus {
+            chunk_index: *chunk_index,
+            min_chunk_stored: min_chunk.is_some(),
+            max_chunk_stored: max_chunks
+                .get(chunk_index)
+                .map_or(false, |max_chunk| max_chunk.is_some()),
+        })
+        .collect();
+
+    Ok(ArrayStatus {
+        validator_chunk_index,
+        current_epoch: Some(current_epoch),
+        pruning_horizon: Some(pruning_horizon),
+        chunks,
+        targets,
+    })
+}
+
+/// Read the target for `validator_index` at `epoch`, loading its chunk into `chunks` if necessary.
+///
+/// Return `None` if the chunk isn't stored, or the target is the neutral element.
+fn read_target<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &RoTransaction<'_>,
+    chunks: &mut BTreeMap<usize, Option<T>>,
+    validator_index: u64,
+    epoch: Epoch,
+    config: &Config,
+) -> Result<Option<Epoch>, Error> {
+    let chunk_index = config.chunk_index(epoch);
+    let chunk = match chunks.entry(chunk_index) {
+        Entry::Occupied(occupied) => occupied.into_mut(),
+        Entry::Vacant(vacant) => vacant.insert(T::load_read_only(
+            db,
+            txn,
+            config.validator_chunk_index(validator_index),
+            chunk_index,
+            config,
+        )?),
+    };
+    let Some(chunk) = chunk else {
+        return Ok(None);
+    };
+
+    let distance = chunk
+        .chunk()
+        .get_raw_distance(validator_index, epoch, config)?;
+    if distance == T::neutral_element() {
+        Ok(None)
+    } else {
+        Ok(Some(epoch + u64::from(distance)))
+    }
+}
+
 pub fn get_chunk_for_update<'a, E: EthSpec, T: TargetArrayChunk>(
     db: &SlasherDB<E>,
     txn: &mut RwTransaction<'_>,
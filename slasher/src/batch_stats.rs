@@ -1,3 +1,5 @@
+use types::Epoch;
+
 #[derive(Debug)]
 pub struct BatchStats {
     pub block_stats: BlockStats,
@@ -14,3 +16,52 @@ pub struct BlockStats {
 pub struct AttestationStats {
     pub num_processed: usize,
 }
+
+/// Progress of a rescan of historical blocks from the beacon node's database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RescanStats {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    /// The most recent epoch whose blocks have been fully processed, if any.
+    pub last_processed_epoch: Option<Epoch>,
+    pub num_blocks: usize,
+    pub num_attestations: usize,
+    pub num_proposer_slashings: usize,
+    pub complete: bool,
+    /// The error which terminated the rescan, if it failed.
+    pub error: Option<String>,
+}
+
+impl RescanStats {
+    pub fn new(start_epoch: Epoch, end_epoch: Epoch) -> Self {
+        Self {
+            start_epoch,
+            end_epoch,
+            last_processed_epoch: None,
+            num_blocks: 0,
+            num_attestations: 0,
+            num_proposer_slashings: 0,
+            complete: false,
+            error: None,
+        }
+    }
+
+    /// Record the processing of `num_blocks` blocks from `epoch` and the `num_attestations` they
+    /// contain, which were applied to the database as part of `batch`.
+    pub fn add_epoch(
+        &mut self,
+        epoch: Epoch,
+        num_blocks: usize,
+        num_attestations: usize,
+        batch: &BatchStats,
+    ) {
+        self.last_processed_epoch = Some(epoch);
+        self.num_blocks += num_blocks;
+        self.num_attestations += num_attestations;
+        self.num_proposer_slashings += batch.block_stats.num_slashings;
+    }
+
+    pub fn in_progress(&self) -> bool {
+        !self.complete && self.error.is_none()
+    }
+}
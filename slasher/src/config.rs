@@ -15,16 +15,20 @@ pub const DEFAULT_MAX_DB_SIZE: usize = 512 * 1024; // 512 GiB
 pub const DEFAULT_ATTESTATION_ROOT_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(100_000);
 pub const DEFAULT_BROADCAST: bool = false;
 
-#[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+#[cfg(all(feature = "mdbx", not(any(feature = "lmdb", feature = "redb"))))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
 #[cfg(feature = "lmdb")]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Lmdb;
-#[cfg(not(any(feature = "mdbx", feature = "lmdb")))]
+#[cfg(all(feature = "redb", not(feature = "lmdb")))]
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Redb;
+#[cfg(not(any(feature = "mdbx", feature = "lmdb", feature = "redb")))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Disabled;
 
 pub const MAX_HISTORY_LENGTH: usize = 1 << 16;
 pub const MEGABYTE: usize = 1 << 20;
 pub const MDBX_DATA_FILENAME: &str = "mdbx.dat";
+pub const LMDB_DATA_FILENAME: &str = "data.mdb";
+pub const REDB_DATA_FILENAME: &str = "slasher.redb";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -45,6 +49,8 @@ pub struct Config {
     pub broadcast: bool,
     /// Database backend to use.
     pub backend: DatabaseBackend,
+    /// Backend to copy existing data from when the database for `backend` is empty.
+    pub migrate_from: Option<DatabaseBackend>,
 }
 
 /// Immutable configuration parameters which are stored on disk and checked for consistency.
@@ -64,9 +70,26 @@ pub enum DatabaseBackend {
     Mdbx,
     #[cfg(feature = "lmdb")]
     Lmdb,
+    #[cfg(feature = "redb")]
+    Redb,
     Disabled,
 }
 
+impl DatabaseBackend {
+    /// Name of the file which holds the data for this backend, or `None` if it is disabled.
+    pub fn data_filename(self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Self::Mdbx => Some(MDBX_DATA_FILENAME),
+            #[cfg(feature = "lmdb")]
+            Self::Lmdb => Some(LMDB_DATA_FILENAME),
+            #[cfg(feature = "redb")]
+            Self::Redb => Some(REDB_DATA_FILENAME),
+            Self::Disabled => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum DatabaseBackendOverride {
     Success(DatabaseBackend),
@@ -87,6 +110,7 @@ impl Config {
             attestation_root_cache_size: DEFAULT_ATTESTATION_ROOT_CACHE_SIZE,
             broadcast: DEFAULT_BROADCAST,
             backend: DEFAULT_BACKEND,
+            migrate_from: None,
         }
     }
 
@@ -1,24 +1,26 @@
 pub mod interface;
 mod lmdb_impl;
 mod mdbx_impl;
+mod redb_impl;
 
 use crate::{
-    metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
-    ProposerSlashingStatus,
+    metrics, migrate::migrate_backend, AttesterRecord, AttesterSlashingStatus,
+    CompactAttesterRecord, Config, Error, ProposerSlashingStatus,
 };
 use byteorder::{BigEndian, ByteOrder};
-use interface::{Environment, OpenDatabases, RwTransaction};
+use interface::{Environment, OpenDatabases, RoTransaction, RwTransaction};
 use lru::LruCache;
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
-use slog::{info, Logger};
+use slog::{debug, info, Logger};
 use ssz::{Decode, Encode};
 use std::borrow::{Borrow, Cow};
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tree_hash::TreeHash;
 use types::{
-    Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing, SignedBeaconBlockHeader, Slot,
+    AttesterSlashing, Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
+    SignedBeaconBlockHeader, Slot,
 };
 
 /// Current database schema version, to check compatibility of on-disk DB with software.
@@ -47,9 +49,12 @@ const MAX_TARGETS_DB: &str = "max_targets";
 const CURRENT_EPOCHS_DB: &str = "current_epochs";
 /// Map from `(slot, validator_index)` to `SignedBeaconBlockHeader`.
 const PROPOSERS_DB: &str = "proposers";
+/// Map from `(epoch, kind, slashing_root)` to every `AttesterSlashing` or `ProposerSlashing`
+/// detected.
+const SLASHINGS_DB: &str = "slashings";
 
 /// The number of DBs for MDBX to use (equal to the number of DBs defined above).
-const MAX_NUM_DBS: usize = 9;
+const MAX_NUM_DBS: usize = 10;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
 const METADATA_VERSION_KEY: &[u8] = &[0];
@@ -61,6 +66,7 @@ const PROPOSER_KEY_SIZE: usize = 16;
 const CURRENT_EPOCH_KEY_SIZE: usize = 8;
 const INDEXED_ATTESTATION_ID_SIZE: usize = 6;
 const INDEXED_ATTESTATION_ID_KEY_SIZE: usize = 40;
+const SLASHING_KEY_SIZE: usize = 41;
 
 #[derive(Debug)]
 pub struct SlasherDB<E: EthSpec> {
@@ -235,12 +241,63 @@ impl AsRef<[u8]> for IndexedAttestationId {
     }
 }
 
+/// The kind of slashing stored under a `SlashingKey`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SlashingKind {
+    Attester = 0,
+    Proposer = 1,
+}
+
+/// Database key for the `slashings` database.
+///
+/// Stored as big-endian `(epoch, kind, slashing_root)` to enable efficient iteration by epoch
+/// while listing and pruning. The epoch is the later of the two target epochs for an attester
+/// slashing, and the epoch of the proposals for a proposer slashing.
+pub struct SlashingKey {
+    data: [u8; SLASHING_KEY_SIZE],
+}
+
+impl SlashingKey {
+    pub fn new(epoch: Epoch, kind: SlashingKind, slashing_root: Hash256) -> Self {
+        let mut data = [0; SLASHING_KEY_SIZE];
+        data[0..8].copy_from_slice(&epoch.as_u64().to_be_bytes());
+        data[8] = kind as u8;
+        data[9..SLASHING_KEY_SIZE].copy_from_slice(slashing_root.as_bytes());
+        Self { data }
+    }
+
+    /// A key which sorts before every key for `epoch` and after every key for prior epochs.
+    pub fn epoch_bound(epoch: Epoch) -> [u8; 8] {
+        epoch.as_u64().to_be_bytes()
+    }
+
+    pub fn parse(data: Cow<[u8]>) -> Result<(Epoch, SlashingKind), Error> {
+        if data.len() != SLASHING_KEY_SIZE {
+            return Err(Error::SlashingKeyCorrupt { length: data.len() });
+        }
+        let epoch = Epoch::new(BigEndian::read_u64(&data[..8]));
+        let kind = match data[8] {
+            0 => SlashingKind::Attester,
+            1 => SlashingKind::Proposer,
+            kind => return Err(Error::UnknownSlashingKind(kind)),
+        };
+        Ok((epoch, kind))
+    }
+}
+
+impl AsRef<[u8]> for SlashingKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 /// Bincode deserialization specialised to `Cow<[u8]>`.
 fn bincode_deserialize<T: DeserializeOwned>(bytes: Cow<[u8]>) -> Result<T, Error> {
     Ok(bincode::deserialize(bytes.borrow())?)
 }
 
-fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
+pub(crate) fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
     Ok(T::from_ssz_bytes(bytes.borrow())?)
 }
 
@@ -250,6 +307,24 @@ impl<E: EthSpec> SlasherDB<E> {
 
         std::fs::create_dir_all(&config.database_path)?;
 
+        if let Some(from) = config.migrate_from.filter(|from| *from != config.backend) {
+            match migrate_backend(&config, from) {
+                Ok(num_entries) => info!(
+                    log,
+                    "Migrated slasher database";
+                    "from" => %from,
+                    "to" => %config.backend,
+                    "entries" => num_entries,
+                ),
+                Err(Error::MigrationTargetNotEmpty) => debug!(
+                    log,
+                    "Slasher database already migrated";
+                    "from" => %from,
+                ),
+                Err(e) => return Err(e),
+            }
+        }
+
         let env = Box::leak(Box::new(Environment::new(&config)?));
         let databases = env.create_databases()?;
 
@@ -292,6 +367,10 @@ impl<E: EthSpec> SlasherDB<E> {
         self.env.begin_rw_txn()
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        self.env.begin_ro_txn()
+    }
+
     pub fn load_schema_version(&self, txn: &mut RwTransaction<'_>) -> Result<Option<u64>, Error> {
         txn.get(&self.databases.metadata_db, METADATA_VERSION_KEY)?
             .map(bincode_deserialize)
@@ -663,6 +742,121 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 
+    /// Record a slashing detected by the slasher.
+    pub fn store_attester_slashing(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        slashing: &AttesterSlashing<E>,
+    ) -> Result<(), Error> {
+        let epoch = std::cmp::max(
+            slashing.attestation_1.data.target.epoch,
+            slashing.attestation_2.data.target.epoch,
+        );
+        txn.put(
+            &self.databases.slashings_db,
+            &SlashingKey::new(epoch, SlashingKind::Attester, slashing.tree_hash_root()),
+            &slashing.as_ssz_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Record a slashing detected by the slasher.
+    pub fn store_proposer_slashing(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        slashing: &ProposerSlashing,
+    ) -> Result<(), Error> {
+        let epoch = slashing
+            .signed_header_1
+            .message
+            .slot
+            .epoch(E::slots_per_epoch());
+        txn.put(
+            &self.databases.slashings_db,
+            &SlashingKey::new(epoch, SlashingKind::Proposer, slashing.tree_hash_root()),
+            &slashing.as_ssz_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Load the slashings detected for epochs from `start_epoch` to `end_epoch` (inclusive).
+    pub fn get_slashings(
+        &self,
+        txn: &RoTransaction<'_>,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<(Vec<AttesterSlashing<E>>, Vec<ProposerSlashing>), Error> {
+        let mut attester_slashings = vec![];
+        let mut proposer_slashings = vec![];
+
+        for (key, value) in txn.range(
+            &self.databases.slashings_db,
+            &SlashingKey::epoch_bound(start_epoch),
+            &SlashingKey::epoch_bound(end_epoch.saturating_add(1u64)),
+        )? {
+            match SlashingKey::parse(key)? {
+                (_, SlashingKind::Attester) => attester_slashings.push(ssz_decode(value)?),
+                (_, SlashingKind::Proposer) => proposer_slashings.push(ssz_decode(value)?),
+            }
+        }
+
+        Ok((attester_slashings, proposer_slashings))
+    }
+
+    /// Load the attestations recorded for `validator_index` with target epochs from
+    /// `start_epoch` to `end_epoch` (inclusive).
+    ///
+    /// Only attestations within the history length of the validator's latest target epoch, and
+    /// which haven't been pruned, are returned.
+    pub fn get_attestations_for_validator(
+        &self,
+        txn: &RoTransaction<'_>,
+        validator_index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<Vec<IndexedAttestation<E>>, Error> {
+        let Some(max_target) = txn
+            .get(
+                &self.databases.attesters_max_targets_db,
+                CurrentEpochKey::new(validator_index).as_ref(),
+            )?
+            .map(ssz_decode::<Epoch>)
+            .transpose()?
+        else {
+            return Ok(vec![]);
+        };
+        let min_target =
+            (max_target.as_u64() + 1).saturating_sub(self.config.history_length as u64);
+
+        let start_epoch = std::cmp::max(start_epoch.as_u64(), min_target);
+        let end_epoch = std::cmp::min(end_epoch, max_target).as_u64();
+
+        let mut attestations = vec![];
+        for target_epoch in (start_epoch..=end_epoch).map(Epoch::new) {
+            let attester_key = AttesterKey::new(validator_index, target_epoch, &self.config);
+            let Some(record) = txn
+                .get(&self.databases.attesters_db, attester_key.as_ref())?
+                .map(CompactAttesterRecord::parse)
+                .transpose()?
+                .filter(|record| !record.is_null())
+            else {
+                continue;
+            };
+
+            // The attestation may have been pruned before the attester record is overwritten.
+            let Some(bytes) = txn.get(
+                &self.databases.indexed_attestation_db,
+                record.indexed_attestation_id.as_ref(),
+            )?
+            else {
+                continue;
+            };
+            attestations.push(ssz_decode(bytes)?);
+        }
+
+        Ok(attestations)
+    }
+
     /// Attempt to prune the database, deleting old blocks and attestations.
     pub fn prune(&self, current_epoch: Epoch) -> Result<(), Error> {
         let mut txn = self.begin_rw_txn()?;
@@ -679,6 +873,42 @@ impl<E: EthSpec> SlasherDB<E> {
     ) -> Result<(), Error> {
         self.prune_proposers(current_epoch, txn)?;
         self.prune_indexed_attestations(current_epoch, txn)?;
+        self.prune_slashings(current_epoch, txn)?;
+        Ok(())
+    }
+
+    fn prune_slashings(
+        &self,
+        current_epoch: Epoch,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let min_epoch = current_epoch
+            .saturating_add(1u64)
+            .saturating_sub(self.config.history_length as u64);
+
+        let mut cursor = txn.cursor(&self.databases.slashings_db)?;
+
+        // Position cursor at first key, bailing out if the database is empty.
+        if cursor.first_key()?.is_none() {
+            return Ok(());
+        }
+
+        loop {
+            let (key_bytes, _) = cursor.get_current()?.ok_or(Error::MissingSlashingKey)?;
+
+            let (epoch, _) = SlashingKey::parse(key_bytes)?;
+            if epoch < min_epoch {
+                cursor.delete_current()?;
+
+                // End the loop if there is no next entry.
+                if cursor.next_key()?.is_none() {
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
         Ok(())
     }
 
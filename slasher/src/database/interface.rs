@@ -1,3 +1,4 @@
+use crate::database::MAX_NUM_DBS;
 use crate::{Config, DatabaseBackend, Error};
 use std::borrow::Cow;
 use std::marker::PhantomData;
@@ -7,6 +8,8 @@ use std::path::PathBuf;
 use crate::database::lmdb_impl;
 #[cfg(feature = "mdbx")]
 use crate::database::mdbx_impl;
+#[cfg(feature = "redb")]
+use crate::database::redb_impl;
 
 #[derive(Debug)]
 pub enum Environment {
@@ -14,6 +17,8 @@ pub enum Environment {
     Mdbx(mdbx_impl::Environment),
     #[cfg(feature = "lmdb")]
     Lmdb(lmdb_impl::Environment),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::Environment),
     Disabled,
 }
 
@@ -23,6 +28,19 @@ pub enum RwTransaction<'env> {
     Mdbx(mdbx_impl::RwTransaction<'env>),
     #[cfg(feature = "lmdb")]
     Lmdb(lmdb_impl::RwTransaction<'env>),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::RwTransaction<'env>),
+    Disabled(PhantomData<&'env ()>),
+}
+
+#[derive(Debug)]
+pub enum RoTransaction<'env> {
+    #[cfg(feature = "mdbx")]
+    Mdbx(mdbx_impl::RoTransaction<'env>),
+    #[cfg(feature = "lmdb")]
+    Lmdb(lmdb_impl::RoTransaction<'env>),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::RoTransaction<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -32,6 +50,8 @@ pub enum Database<'env> {
     Mdbx(mdbx_impl::Database<'env>),
     #[cfg(feature = "lmdb")]
     Lmdb(lmdb_impl::Database<'env>),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::Database<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -46,6 +66,25 @@ pub struct OpenDatabases<'env> {
     pub current_epochs_db: Database<'env>,
     pub proposers_db: Database<'env>,
     pub metadata_db: Database<'env>,
+    pub slashings_db: Database<'env>,
+}
+
+impl<'env> OpenDatabases<'env> {
+    /// Return every database, in a fixed order.
+    pub fn all(&self) -> [&Database<'env>; MAX_NUM_DBS] {
+        [
+            &self.indexed_attestation_db,
+            &self.indexed_attestation_id_db,
+            &self.attesters_db,
+            &self.attesters_max_targets_db,
+            &self.min_targets_db,
+            &self.max_targets_db,
+            &self.current_epochs_db,
+            &self.proposers_db,
+            &self.metadata_db,
+            &self.slashings_db,
+        ]
+    }
 }
 
 #[derive(Debug)]
@@ -54,6 +93,8 @@ pub enum Cursor<'env> {
     Mdbx(mdbx_impl::Cursor<'env>),
     #[cfg(feature = "lmdb")]
     Lmdb(lmdb_impl::Cursor<'env>),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::Cursor<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -67,6 +108,8 @@ impl Environment {
             DatabaseBackend::Mdbx => mdbx_impl::Environment::new(config).map(Environment::Mdbx),
             #[cfg(feature = "lmdb")]
             DatabaseBackend::Lmdb => lmdb_impl::Environment::new(config).map(Environment::Lmdb),
+            #[cfg(feature = "redb")]
+            DatabaseBackend::Redb => redb_impl::Environment::new(config).map(Environment::Redb),
             DatabaseBackend::Disabled => Err(Error::SlasherDatabaseBackendDisabled),
         }
     }
@@ -77,6 +120,8 @@ impl Environment {
             Self::Mdbx(env) => env.create_databases(),
             #[cfg(feature = "lmdb")]
             Self::Lmdb(env) => env.create_databases(),
+            #[cfg(feature = "redb")]
+            Self::Redb(env) => env.create_databases(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -87,6 +132,20 @@ impl Environment {
             Self::Mdbx(env) => env.begin_rw_txn().map(RwTransaction::Mdbx),
             #[cfg(feature = "lmdb")]
             Self::Lmdb(env) => env.begin_rw_txn().map(RwTransaction::Lmdb),
+            #[cfg(feature = "redb")]
+            Self::Redb(env) => env.begin_rw_txn().map(RwTransaction::Redb),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Self::Mdbx(env) => env.begin_ro_txn().map(RoTransaction::Mdbx),
+            #[cfg(feature = "lmdb")]
+            Self::Lmdb(env) => env.begin_ro_txn().map(RoTransaction::Lmdb),
+            #[cfg(feature = "redb")]
+            Self::Redb(env) => env.begin_ro_txn().map(RoTransaction::Redb),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -98,6 +157,8 @@ impl Environment {
             Self::Mdbx(env) => env.filenames(config),
             #[cfg(feature = "lmdb")]
             Self::Lmdb(env) => env.filenames(config),
+            #[cfg(feature = "redb")]
+            Self::Redb(env) => env.filenames(config),
             _ => vec![],
         }
     }
@@ -114,6 +175,8 @@ impl<'env> RwTransaction<'env> {
             (Self::Mdbx(txn), Database::Mdbx(db)) => txn.get(db, key),
             #[cfg(feature = "lmdb")]
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.get(db, key),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.get(db, key),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -129,6 +192,8 @@ impl<'env> RwTransaction<'env> {
             (Self::Mdbx(txn), Database::Mdbx(db)) => txn.put(db, key, value),
             #[cfg(feature = "lmdb")]
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.put(db, key, value),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.put(db, key, value),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -139,6 +204,8 @@ impl<'env> RwTransaction<'env> {
             (Self::Mdbx(txn), Database::Mdbx(db)) => txn.del(db, key),
             #[cfg(feature = "lmdb")]
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.del(db, key),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.del(db, key),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -149,6 +216,8 @@ impl<'env> RwTransaction<'env> {
             (Self::Mdbx(txn), Database::Mdbx(db)) => txn.cursor(db).map(Cursor::Mdbx),
             #[cfg(feature = "lmdb")]
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.cursor(db).map(Cursor::Lmdb),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.cursor(db).map(Cursor::Redb),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -159,6 +228,44 @@ impl<'env> RwTransaction<'env> {
             Self::Mdbx(txn) => txn.commit(),
             #[cfg(feature = "lmdb")]
             Self::Lmdb(txn) => txn.commit(),
+            #[cfg(feature = "redb")]
+            Self::Redb(txn) => txn.commit(),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+}
+
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        match (self, db) {
+            #[cfg(feature = "mdbx")]
+            (Self::Mdbx(txn), Database::Mdbx(db)) => txn.get(db, key),
+            #[cfg(feature = "lmdb")]
+            (Self::Lmdb(txn), Database::Lmdb(db)) => txn.get(db, key),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.get(db, key),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
+    /// Return the key value pairs with keys in the range `[from, to)`, in key order.
+    pub fn range(
+        &'env self,
+        db: &Database<'env>,
+        from: &[u8],
+        to: &[u8],
+    ) -> Result<Vec<(Key<'env>, Value<'env>)>, Error> {
+        match (self, db) {
+            #[cfg(feature = "mdbx")]
+            (Self::Mdbx(txn), Database::Mdbx(db)) => txn.range(db, from, to),
+            #[cfg(feature = "lmdb")]
+            (Self::Lmdb(txn), Database::Lmdb(db)) => txn.range(db, from, to),
+            #[cfg(feature = "redb")]
+            (Self::Redb(txn), Database::Redb(db)) => txn.range(db, from, to),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -172,6 +279,8 @@ impl<'env> Cursor<'env> {
             Cursor::Mdbx(cursor) => cursor.first_key(),
             #[cfg(feature = "lmdb")]
             Cursor::Lmdb(cursor) => cursor.first_key(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.first_key(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -183,6 +292,8 @@ impl<'env> Cursor<'env> {
             Cursor::Mdbx(cursor) => cursor.last_key(),
             #[cfg(feature = "lmdb")]
             Cursor::Lmdb(cursor) => cursor.last_key(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.last_key(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -193,6 +304,8 @@ impl<'env> Cursor<'env> {
             Cursor::Mdbx(cursor) => cursor.next_key(),
             #[cfg(feature = "lmdb")]
             Cursor::Lmdb(cursor) => cursor.next_key(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.next_key(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -204,6 +317,8 @@ impl<'env> Cursor<'env> {
             Cursor::Mdbx(cursor) => cursor.get_current(),
             #[cfg(feature = "lmdb")]
             Cursor::Lmdb(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.get_current(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -214,6 +329,8 @@ impl<'env> Cursor<'env> {
             Cursor::Mdbx(cursor) => cursor.delete_current(),
             #[cfg(feature = "lmdb")]
             Cursor::Lmdb(cursor) => cursor.delete_current(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.delete_current(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -224,6 +341,8 @@ impl<'env> Cursor<'env> {
             Self::Mdbx(cursor) => cursor.put(key, value),
             #[cfg(feature = "lmdb")]
             Self::Lmdb(cursor) => cursor.put(key, value),
+            #[cfg(feature = "redb")]
+            Self::Redb(cursor) => cursor.put(key, value),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -8,7 +8,7 @@ use crate::{
     },
 };
 use lmdb::{Cursor as _, DatabaseFlags, Transaction, WriteFlags};
-use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT};
+use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT, MDB_SET_RANGE};
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -21,6 +21,11 @@ pub struct RwTransaction<'env> {
     txn: lmdb::RwTransaction<'env>,
 }
 
+#[derive(Debug)]
+pub struct RoTransaction<'env> {
+    txn: lmdb::RoTransaction<'env>,
+}
+
 #[derive(Debug)]
 pub struct Database<'env> {
     db: lmdb::Database,
@@ -59,6 +64,7 @@ impl Environment {
             .create_db(Some(CURRENT_EPOCHS_DB), Self::db_flags())?;
         let proposers_db = self.env.create_db(Some(PROPOSERS_DB), Self::db_flags())?;
         let metadata_db = self.env.create_db(Some(METADATA_DB), Self::db_flags())?;
+        let slashings_db = self.env.create_db(Some(SLASHINGS_DB), Self::db_flags())?;
 
         let wrap = |db| {
             crate::Database::Lmdb(Database {
@@ -77,6 +83,7 @@ impl Environment {
             current_epochs_db: wrap(current_epochs_db),
             proposers_db: wrap(proposers_db),
             metadata_db: wrap(metadata_db),
+            slashings_db: wrap(slashings_db),
         })
     }
 
@@ -85,6 +92,11 @@ impl Environment {
         Ok(RwTransaction { txn })
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.env.begin_ro_txn()?;
+        Ok(RoTransaction { txn })
+    }
+
     pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
         vec![
             config.database_path.join("data.mdb"),
@@ -136,6 +148,36 @@ impl<'env> RwTransaction<'env> {
     }
 }
 
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        Ok(self.txn.get(db.db, key).optional()?.map(Cow::Borrowed))
+    }
+
+    pub fn range(
+        &'env self,
+        db: &Database<'env>,
+        from: &[u8],
+        to: &[u8],
+    ) -> Result<Vec<(Key<'env>, Value<'env>)>, Error> {
+        let cursor = self.txn.open_ro_cursor(db.db)?;
+        let mut entries = vec![];
+
+        let mut next = cursor.get(Some(from), None, MDB_SET_RANGE).optional()?;
+        while let Some((Some(key), value)) = next {
+            if key >= to {
+                break;
+            }
+            entries.push((Cow::Borrowed(key), Cow::Borrowed(value)));
+            next = cursor.get(None, None, MDB_NEXT).optional()?;
+        }
+        Ok(entries)
+    }
+}
+
 impl<'env> Cursor<'env> {
     pub fn first_key(&mut self) -> Result<Option<Key>, Error> {
         let opt_key = self
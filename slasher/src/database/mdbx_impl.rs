@@ -25,6 +25,11 @@ pub struct RwTransaction<'env> {
     txn: mdbx::Transaction<'env, mdbx::RW, mdbx::NoWriteMap>,
 }
 
+#[derive(Debug)]
+pub struct RoTransaction<'env> {
+    txn: mdbx::Transaction<'env, mdbx::RO, mdbx::NoWriteMap>,
+}
+
 #[derive(Debug)]
 pub struct Database<'env> {
     db: mdbx::Database<'env>,
@@ -55,6 +60,7 @@ impl Environment {
         txn.create_db(CURRENT_EPOCHS_DB)?;
         txn.create_db(PROPOSERS_DB)?;
         txn.create_db(METADATA_DB)?;
+        txn.create_db(SLASHINGS_DB)?;
 
         // This is all rather nasty
         let (_, mut databases) = txn.txn.commit_and_rebind_open_dbs()?;
@@ -74,6 +80,7 @@ impl Environment {
             current_epochs_db: next_db(),
             proposers_db: next_db(),
             metadata_db: next_db(),
+            slashings_db: next_db(),
         })
     }
 
@@ -82,6 +89,11 @@ impl Environment {
         Ok(RwTransaction { txn })
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.env.begin_ro_txn()?;
+        Ok(RoTransaction { txn })
+    }
+
     pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
         vec![
             config.database_path.join("mdbx.dat"),
@@ -153,6 +165,36 @@ impl<'env> RwTransaction<'env> {
     }
 }
 
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        Ok(self.txn.get(&db.db, key.as_ref())?)
+    }
+
+    pub fn range(
+        &'env self,
+        db: &Database<'env>,
+        from: &[u8],
+        to: &[u8],
+    ) -> Result<Vec<(Key<'env>, Value<'env>)>, Error> {
+        let mut cursor = self.txn.cursor(&db.db)?;
+        let mut entries = vec![];
+
+        let mut next: Option<(Key<'env>, Value<'env>)> = cursor.set_range(from)?;
+        while let Some((key, value)) = next {
+            if key.as_ref() >= to {
+                break;
+            }
+            entries.push((key, value));
+            next = cursor.next()?;
+        }
+        Ok(entries)
+    }
+}
+
 impl<'env> Cursor<'env> {
     pub fn first_key(&mut self) -> Result<Option<Cow<'env, [u8]>>, Error> {
         let opt_key = self.cursor.first()?.map(|(key_bytes, ())| key_bytes);
@@ -0,0 +1,268 @@
+#![cfg(feature = "redb")]
+
+use crate::{
+    config::REDB_DATA_FILENAME,
+    database::{
+        interface::{Key, OpenDatabases, Value},
+        *,
+    },
+    Config, Error,
+};
+use derivative::Derivative;
+use redb::{ReadableTable, TableDefinition};
+use std::borrow::Cow;
+use std::ops::Bound;
+use std::path::PathBuf;
+
+/// Each slasher database is stored in its own table, keyed by its name.
+type Table<'txn> = redb::Table<'txn, &'static [u8], &'static [u8]>;
+
+#[derive(Debug)]
+pub struct Environment {
+    db: redb::Database,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct RwTransaction<'env> {
+    #[derivative(Debug = "ignore")]
+    txn: redb::WriteTransaction,
+    _phantom: PhantomData<&'env ()>,
+}
+
+#[derive(Debug)]
+pub struct RoTransaction<'env> {
+    txn: redb::ReadTransaction,
+    _phantom: PhantomData<&'env ()>,
+}
+
+#[derive(Debug)]
+pub struct Database<'env> {
+    table_name: &'static str,
+    _phantom: PhantomData<&'env ()>,
+}
+
+/// redb has no cursors within write transactions, so the cursor tracks its current key and
+/// re-opens the table for every operation.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct Cursor<'env> {
+    #[derivative(Debug = "ignore")]
+    txn: &'env redb::WriteTransaction,
+    table_name: &'static str,
+    current_key: Option<Vec<u8>>,
+}
+
+fn table_definition(
+    table_name: &'static str,
+) -> TableDefinition<'static, &'static [u8], &'static [u8]> {
+    TableDefinition::new(table_name)
+}
+
+fn open_table<'txn>(
+    txn: &'txn redb::WriteTransaction,
+    table_name: &'static str,
+) -> Result<Table<'txn>, Error> {
+    Ok(txn.open_table(table_definition(table_name))?)
+}
+
+impl Environment {
+    pub fn new(config: &Config) -> Result<Environment, Error> {
+        let db = redb::Database::create(config.database_path.join(REDB_DATA_FILENAME))?;
+        Ok(Environment { db })
+    }
+
+    pub fn create_databases(&self) -> Result<OpenDatabases, Error> {
+        // Create all tables up front so that they exist for every subsequent transaction.
+        let txn = self.db.begin_write()?;
+        for table_name in [
+            INDEXED_ATTESTATION_DB,
+            INDEXED_ATTESTATION_ID_DB,
+            ATTESTERS_DB,
+            ATTESTERS_MAX_TARGETS_DB,
+            MIN_TARGETS_DB,
+            MAX_TARGETS_DB,
+            CURRENT_EPOCHS_DB,
+            PROPOSERS_DB,
+            METADATA_DB,
+            SLASHINGS_DB,
+        ] {
+            open_table(&txn, table_name)?;
+        }
+        txn.commit()?;
+
+        let wrap = |table_name| {
+            crate::Database::Redb(Database {
+                table_name,
+                _phantom: PhantomData,
+            })
+        };
+
+        Ok(OpenDatabases {
+            indexed_attestation_db: wrap(INDEXED_ATTESTATION_DB),
+            indexed_attestation_id_db: wrap(INDEXED_ATTESTATION_ID_DB),
+            attesters_db: wrap(ATTESTERS_DB),
+            attesters_max_targets_db: wrap(ATTESTERS_MAX_TARGETS_DB),
+            min_targets_db: wrap(MIN_TARGETS_DB),
+            max_targets_db: wrap(MAX_TARGETS_DB),
+            current_epochs_db: wrap(CURRENT_EPOCHS_DB),
+            proposers_db: wrap(PROPOSERS_DB),
+            metadata_db: wrap(METADATA_DB),
+            slashings_db: wrap(SLASHINGS_DB),
+        })
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
+        let txn = self.db.begin_write()?;
+        Ok(RwTransaction {
+            txn,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.db.begin_read()?;
+        Ok(RoTransaction {
+            txn,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
+        vec![config.database_path.join(REDB_DATA_FILENAME)]
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let table = open_table(&self.txn, db.table_name)?;
+        let value = table
+            .get(key.as_ref())?
+            .map(|value| Cow::Owned(value.value().to_vec()));
+        Ok(value)
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: &Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        open_table(&self.txn, db.table_name)?.insert(key.as_ref(), value.as_ref())?;
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: &Database, key: K) -> Result<(), Error> {
+        open_table(&self.txn, db.table_name)?.remove(key.as_ref())?;
+        Ok(())
+    }
+
+    pub fn cursor<'a>(&'a mut self, db: &Database) -> Result<Cursor<'a>, Error> {
+        Ok(Cursor {
+            txn: &self.txn,
+            table_name: db.table_name,
+            current_key: None,
+        })
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        self.txn.commit()?;
+        Ok(())
+    }
+}
+
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let table = self.txn.open_table(table_definition(db.table_name))?;
+        let value = table
+            .get(key.as_ref())?
+            .map(|value| Cow::Owned(value.value().to_vec()));
+        Ok(value)
+    }
+
+    pub fn range(
+        &'env self,
+        db: &Database<'env>,
+        from: &[u8],
+        to: &[u8],
+    ) -> Result<Vec<(Key<'env>, Value<'env>)>, Error> {
+        let table = self.txn.open_table(table_definition(db.table_name))?;
+        let mut entries = vec![];
+        for entry in table.range::<&[u8]>(from..to)? {
+            let (key, value) = entry?;
+            entries.push((
+                Cow::Owned(key.value().to_vec()),
+                Cow::Owned(value.value().to_vec()),
+            ));
+        }
+        Ok(entries)
+    }
+}
+
+impl<'env> Cursor<'env> {
+    fn table(&self) -> Result<Table<'env>, Error> {
+        open_table(self.txn, self.table_name)
+    }
+
+    /// Move the cursor to `key`, if it exists.
+    fn set_position(&mut self, key: Option<Vec<u8>>) -> Option<Key<'env>> {
+        let key = key?;
+        self.current_key = Some(key.clone());
+        Some(Cow::Owned(key))
+    }
+
+    pub fn first_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        let first = self.table()?.first()?.map(|(key, _)| key.value().to_vec());
+        Ok(self.set_position(first))
+    }
+
+    pub fn last_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        let last = self.table()?.last()?.map(|(key, _)| key.value().to_vec());
+        Ok(self.set_position(last))
+    }
+
+    pub fn next_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        let Some(current_key) = &self.current_key else {
+            return self.first_key();
+        };
+        let next = self
+            .table()?
+            .range::<&[u8]>((Bound::Excluded(current_key.as_slice()), Bound::Unbounded))?
+            .next()
+            .transpose()?
+            .map(|(key, _)| key.value().to_vec());
+        Ok(self.set_position(next))
+    }
+
+    pub fn get_current(&mut self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
+        let Some(current_key) = &self.current_key else {
+            return Ok(None);
+        };
+        let value = self
+            .table()?
+            .get(current_key.as_slice())?
+            .map(|value| value.value().to_vec());
+        Ok(value.map(|value| (Cow::Owned(current_key.clone()), Cow::Owned(value))))
+    }
+
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        if let Some(current_key) = &self.current_key {
+            self.table()?.remove(current_key.as_slice())?;
+        }
+        Ok(())
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        self.table()?.insert(key.as_ref(), value.as_ref())?;
+        self.current_key = Some(key.as_ref().to_vec());
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::{Config, DiskConfig};
 use std::io;
+use std::path::PathBuf;
 use types::Epoch;
 
 #[derive(Debug)]
@@ -8,6 +9,8 @@ pub enum Error {
     DatabaseMdbxError(mdbx::Error),
     #[cfg(feature = "lmdb")]
     DatabaseLmdbError(lmdb::Error),
+    #[cfg(feature = "redb")]
+    DatabaseRedbError(redb::Error),
     SlasherDatabaseBackendDisabled,
     MismatchedDatabaseVariant,
     DatabaseIOError(io::Error),
@@ -36,6 +39,18 @@ pub enum Error {
         config: DiskConfig,
     },
     ConfigMissing,
+    /// The database to migrate from doesn't exist.
+    MigrationSourceMissing(PathBuf),
+    /// The database to migrate to already contains data.
+    MigrationTargetNotEmpty,
+    /// A rescan of historical blocks is already running.
+    RescanInProgress,
+    /// Attestations from the requested epochs would be outside the slasher's history.
+    RescanOutOfRange {
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        current_epoch: Epoch,
+    },
     DistanceTooLarge,
     DistanceCalculationOverflow,
     /// Missing an attester record that we expected to exist.
@@ -58,6 +73,10 @@ pub enum Error {
     IndexedAttestationIdCorrupt {
         length: usize,
     },
+    SlashingKeyCorrupt {
+        length: usize,
+    },
+    UnknownSlashingKind(u8),
     MissingIndexedAttestation {
         id: u64,
     },
@@ -65,6 +84,7 @@ pub enum Error {
     MissingProposerKey,
     MissingIndexedAttestationId,
     MissingIndexedAttestationIdKey,
+    MissingSlashingKey,
     InconsistentAttestationDataRoot,
 }
 
@@ -88,6 +108,28 @@ impl From<lmdb::Error> for Error {
     }
 }
 
+#[cfg(feature = "redb")]
+macro_rules! impl_from_redb_error {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(e: $error) -> Self {
+                    Error::DatabaseRedbError(e.into())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "redb")]
+impl_from_redb_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError
+);
+
 impl From<io::Error> for Error {
     fn from(e: io::Error) -> Self {
         Error::DatabaseIOError(e)
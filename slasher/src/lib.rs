@@ -1,6 +1,6 @@
 #![deny(missing_debug_implementations)]
 #![cfg_attr(
-    not(any(feature = "mdbx", feature = "lmdb")),
+    not(any(feature = "mdbx", feature = "lmdb", feature = "redb")),
     allow(unused, clippy::drop_non_drop)
 )]
 
@@ -18,15 +18,18 @@ mod slasher;
 pub mod test_utils;
 
 pub use crate::slasher::Slasher;
+pub use array::{ArrayStatus, ChunkStatus, EpochTargets};
 pub use attestation_queue::{AttestationBatch, AttestationQueue, SimpleBatch};
 pub use attester_record::{AttesterRecord, CompactAttesterRecord, IndexedAttesterRecord};
+pub use batch_stats::{BatchStats, RescanStats};
 pub use block_queue::BlockQueue;
 pub use config::{Config, DatabaseBackend, DatabaseBackendOverride};
 pub use database::{
-    interface::{Database, Environment, RwTransaction},
+    interface::{Database, Environment, RoTransaction, RwTransaction},
     IndexedAttestationId, SlasherDB,
 };
 pub use error::Error;
+pub use migrate::migrate_backend;
 
 use types::{AttesterSlashing, EthSpec, IndexedAttestation, ProposerSlashing};
 
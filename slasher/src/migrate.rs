@@ -1,4 +1,7 @@
-use crate::{database::CURRENT_SCHEMA_VERSION, Error, SlasherDB};
+use crate::{
+    database::{interface::Environment, CURRENT_SCHEMA_VERSION},
+    Config, DatabaseBackend, Error, SlasherDB,
+};
 use types::EthSpec;
 
 impl<E: EthSpec> SlasherDB<E> {
@@ -27,3 +30,59 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 }
+
+/// Copy all data from the database of the `from` backend into the empty database of the configured
+/// backend, returning the number of entries copied.
+///
+/// Every backend stores the same keys and values, so entries are copied verbatim. The copy is
+/// made in a single transaction, so an interrupted migration leaves the new database empty.
+pub fn migrate_backend(config: &Config, from: DatabaseBackend) -> Result<usize, Error> {
+    let source_path = from
+        .data_filename()
+        .map(|filename| config.database_path.join(filename))
+        .ok_or(Error::SlasherDatabaseBackendDisabled)?;
+    if !source_path.exists() {
+        return Err(Error::MigrationSourceMissing(source_path));
+    }
+
+    let target_env = Environment::new(config)?;
+    let target_dbs = target_env.create_databases()?;
+    let mut target_txn = target_env.begin_rw_txn()?;
+    if target_txn
+        .cursor(&target_dbs.metadata_db)?
+        .first_key()?
+        .is_some()
+    {
+        return Err(Error::MigrationTargetNotEmpty);
+    }
+
+    let source_config = Config {
+        backend: from,
+        ..config.clone()
+    };
+    let source_env = Environment::new(&source_config)?;
+    let source_dbs = source_env.create_databases()?;
+    let mut source_txn = source_env.begin_rw_txn()?;
+
+    let mut num_entries = 0;
+    for (source_db, target_db) in source_dbs.all().into_iter().zip(target_dbs.all()) {
+        let mut cursor = source_txn.cursor(source_db)?;
+        if cursor.first_key()?.is_none() {
+            continue;
+        }
+        loop {
+            let Some((key, value)) = cursor.get_current()? else {
+                break;
+            };
+            target_txn.put(target_db, key, value)?;
+            num_entries += 1;
+
+            if cursor.next_key()?.is_none() {
+                break;
+            }
+        }
+    }
+    target_txn.commit()?;
+
+    Ok(num_entries)
+}
@@ -1,12 +1,12 @@
-use crate::batch_stats::{AttestationStats, BatchStats, BlockStats};
+use crate::batch_stats::{AttestationStats, BatchStats, BlockStats, RescanStats};
 use crate::metrics::{
     self, SLASHER_NUM_ATTESTATIONS_DEFERRED, SLASHER_NUM_ATTESTATIONS_DROPPED,
     SLASHER_NUM_ATTESTATIONS_STORED_PER_BATCH, SLASHER_NUM_ATTESTATIONS_VALID,
     SLASHER_NUM_BLOCKS_PROCESSED,
 };
 use crate::{
-    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
-    IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    array, ArrayStatus, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config,
+    Error, IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
@@ -21,8 +21,14 @@ pub struct Slasher<E: EthSpec> {
     db: SlasherDB<E>,
     attestation_queue: AttestationQueue<E>,
     block_queue: BlockQueue,
+    /// Separate queues for rescans, so that rescanned and live items are never processed together.
+    rescan_attestation_queue: AttestationQueue<E>,
+    rescan_block_queue: BlockQueue,
+    /// Held while a batch is applied to the database, so that batches never interleave.
+    processing_lock: Mutex<()>,
     attester_slashings: Mutex<HashSet<AttesterSlashing<E>>>,
     proposer_slashings: Mutex<HashSet<ProposerSlashing>>,
+    rescan_stats: Mutex<Option<RescanStats>>,
     config: Arc<Config>,
     log: Logger,
 }
@@ -40,8 +46,12 @@ impl<E: EthSpec> Slasher<E> {
             db,
             attestation_queue,
             block_queue,
+            rescan_attestation_queue: AttestationQueue::default(),
+            rescan_block_queue: BlockQueue::default(),
+            processing_lock: Mutex::new(()),
             attester_slashings,
             proposer_slashings,
+            rescan_stats: Mutex::new(None),
             config,
             log,
         })
@@ -75,11 +85,144 @@ impl<E: EthSpec> Slasher<E> {
         self.block_queue.queue(block_header);
     }
 
+    /// Accept a historical attestation from a rescan and queue it for processing by
+    /// `process_rescan_queued`.
+    pub fn accept_rescan_attestation(&self, attestation: IndexedAttestation<E>) {
+        self.rescan_attestation_queue.queue(attestation);
+    }
+
+    /// Accept a historical block from a rescan and queue it for processing by
+    /// `process_rescan_queued`.
+    pub fn accept_rescan_block_header(&self, block_header: SignedBeaconBlockHeader) {
+        self.rescan_block_queue.queue(block_header);
+    }
+
+    /// Begin a rescan of the historical blocks from `start_epoch` to `end_epoch` (inclusive).
+    ///
+    /// The blocks themselves are fed in by the caller using `accept_rescan_block_header` and
+    /// `accept_rescan_attestation`, and processed with `process_rescan_queued`. The caller should
+    /// record its progress using `update_rescan_stats`.
+    pub fn start_rescan(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        current_epoch: Epoch,
+    ) -> Result<(), Error> {
+        let mut rescan_stats = self.rescan_stats.lock();
+        if rescan_stats
+            .as_ref()
+            .map_or(false, RescanStats::in_progress)
+        {
+            return Err(Error::RescanInProgress);
+        }
+
+        // Attestations with a source older than the history length would be dropped, and
+        // attestations from the future would be deferred indefinitely.
+        if start_epoch > end_epoch
+            || end_epoch > current_epoch
+            || start_epoch + self.config.history_length as u64 <= current_epoch
+        {
+            return Err(Error::RescanOutOfRange {
+                start_epoch,
+                end_epoch,
+                current_epoch,
+            });
+        }
+
+        info!(
+            self.log,
+            "Starting slasher rescan";
+            "start_epoch" => start_epoch,
+            "end_epoch" => end_epoch,
+        );
+        *rescan_stats = Some(RescanStats::new(start_epoch, end_epoch));
+        Ok(())
+    }
+
+    /// Update the progress of the current rescan, if any.
+    pub fn update_rescan_stats(&self, f: impl FnOnce(&mut RescanStats)) {
+        if let Some(rescan_stats) = self.rescan_stats.lock().as_mut() {
+            f(rescan_stats);
+        }
+    }
+
+    /// Get the progress of the current or most recent rescan.
+    pub fn rescan_stats(&self) -> Option<RescanStats> {
+        self.rescan_stats.lock().clone()
+    }
+
+    /// Return the slashings detected for epochs from `start_epoch` to `end_epoch` (inclusive)
+    /// which haven't yet been pruned.
+    pub fn get_detected_slashings(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<(Vec<AttesterSlashing<E>>, Vec<ProposerSlashing>), Error> {
+        let txn = self.db.begin_ro_txn()?;
+        self.db.get_slashings(&txn, start_epoch, end_epoch)
+    }
+
+    /// Return the attestations stored for `validator_index` with target epochs from `start_epoch`
+    /// to `end_epoch` (inclusive).
+    pub fn get_validator_attestations(
+        &self,
+        validator_index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<Vec<IndexedAttestation<E>>, Error> {
+        let txn = self.db.begin_ro_txn()?;
+        self.db
+            .get_attestations_for_validator(&txn, validator_index, start_epoch, end_epoch)
+    }
+
+    /// Return the state of the min-max arrays for `validator_index` from `start_epoch` to
+    /// `end_epoch` (inclusive).
+    pub fn get_validator_array_status(
+        &self,
+        validator_index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<ArrayStatus, Error> {
+        let txn = self.db.begin_ro_txn()?;
+        array::get_array_status(
+            &self.db,
+            &txn,
+            validator_index,
+            start_epoch,
+            end_epoch,
+            &self.config,
+        )
+    }
+
     /// Apply queued blocks and attestations to the on-disk database, and detect slashings!
     pub fn process_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
+        self.process_queues(&self.block_queue, &self.attestation_queue, current_epoch)
+    }
+
+    /// Apply the blocks and attestations queued by a rescan to the on-disk database, and detect
+    /// slashings.
+    ///
+    /// Items queued by `accept_block_header` and `accept_attestation` are left for
+    /// `process_queued`, so the returned stats only cover the rescan.
+    pub fn process_rescan_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
+        self.process_queues(
+            &self.rescan_block_queue,
+            &self.rescan_attestation_queue,
+            current_epoch,
+        )
+    }
+
+    fn process_queues(
+        &self,
+        block_queue: &BlockQueue,
+        attestation_queue: &AttestationQueue<E>,
+        current_epoch: Epoch,
+    ) -> Result<BatchStats, Error> {
+        let _processing = self.processing_lock.lock();
         let mut txn = self.db.begin_rw_txn()?;
-        let block_stats = self.process_blocks(&mut txn)?;
-        let attestation_stats = self.process_attestations(current_epoch, &mut txn)?;
+        let block_stats = self.process_blocks(block_queue, &mut txn)?;
+        let attestation_stats =
+            self.process_attestations(attestation_queue, current_epoch, &mut txn)?;
         txn.commit()?;
         Ok(BatchStats {
             block_stats,
@@ -87,11 +230,15 @@ impl<E: EthSpec> Slasher<E> {
         })
     }
 
-    /// Apply queued blocks to the on-disk database.
+    /// Apply the blocks in `block_queue` to the on-disk database.
     ///
     /// Return the number of blocks
-    pub fn process_blocks(&self, txn: &mut RwTransaction<'_>) -> Result<BlockStats, Error> {
-        let blocks = self.block_queue.dequeue();
+    pub fn process_blocks(
+        &self,
+        block_queue: &BlockQueue,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<BlockStats, Error> {
+        let blocks = block_queue.dequeue();
         let num_processed = blocks.len();
         let mut slashings = vec![];
 
@@ -101,6 +248,7 @@ impl<E: EthSpec> Slasher<E> {
             if let ProposerSlashingStatus::DoubleVote(slashing) =
                 self.db.check_or_insert_block_proposal(txn, block)?
             {
+                self.db.store_proposer_slashing(txn, &slashing)?;
                 slashings.push(*slashing);
             }
         }
@@ -121,20 +269,21 @@ impl<E: EthSpec> Slasher<E> {
         })
     }
 
-    /// Apply queued attestations to the on-disk database.
+    /// Apply the attestations in `attestation_queue` to the on-disk database.
     pub fn process_attestations(
         &self,
+        attestation_queue: &AttestationQueue<E>,
         current_epoch: Epoch,
         txn: &mut RwTransaction<'_>,
     ) -> Result<AttestationStats, Error> {
-        let snapshot = self.attestation_queue.dequeue();
+        let snapshot = attestation_queue.dequeue();
         let num_processed = snapshot.len();
 
         // Filter attestations for relevance.
         let (snapshot, deferred, num_dropped) = self.validate(snapshot, current_epoch);
         let num_valid = snapshot.len();
         let num_deferred = deferred.len();
-        self.attestation_queue.requeue(deferred);
+        attestation_queue.requeue(deferred);
 
         debug!(
             self.log,
@@ -226,6 +375,9 @@ impl<E: EthSpec> Slasher<E> {
                             slashings.len()
                         );
                     }
+                    for slashing in &slashings {
+                        self.db.store_attester_slashing(txn, slashing)?;
+                    }
                     self.attester_slashings.lock().extend(slashings);
                 }
                 Err(e) => {
@@ -256,6 +408,9 @@ impl<E: EthSpec> Slasher<E> {
                         slashings.len()
                     );
                 }
+                for slashing in &slashings {
+                    self.db.store_attester_slashing(txn, slashing)?;
+                }
                 self.attester_slashings.lock().extend(slashings);
             }
             Err(e) => {
@@ -55,3 +55,72 @@ fn failed_override_with_existing_mdbx_db() {
     );
     assert_eq!(config.backend, DatabaseBackend::Lmdb);
 }
+
+#[cfg(feature = "redb")]
+fn database_entries(env: &slasher::Environment) -> Vec<Vec<(Vec<u8>, Vec<u8>)>> {
+    let databases = env.create_databases().unwrap();
+    let mut txn = env.begin_rw_txn().unwrap();
+    databases
+        .all()
+        .into_iter()
+        .map(|db| {
+            let mut cursor = txn.cursor(db).unwrap();
+            let mut entries = vec![];
+            if cursor.first_key().unwrap().is_some() {
+                while let Some((key, value)) = cursor.get_current().unwrap() {
+                    entries.push((key.to_vec(), value.to_vec()));
+                    if cursor.next_key().unwrap().is_none() {
+                        break;
+                    }
+                }
+            }
+            entries
+        })
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "redb")]
+fn migrate_lmdb_to_redb() {
+    use slasher::{migrate_backend, Environment, Error};
+
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::Redb;
+    assert!(matches!(
+        migrate_backend(&config, DatabaseBackend::Lmdb),
+        Err(Error::MigrationSourceMissing(_))
+    ));
+
+    let lmdb_config = Config {
+        backend: DatabaseBackend::Lmdb,
+        ..config.clone()
+    };
+    let expected = {
+        let env = Environment::new(&lmdb_config).unwrap();
+        let databases = env.create_databases().unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        for (i, db) in databases.all().into_iter().enumerate() {
+            for j in 0..=i as u8 {
+                txn.put(db, [j], [i as u8, j]).unwrap();
+            }
+        }
+        txn.commit().unwrap();
+        database_entries(&env)
+    };
+
+    assert_eq!(
+        migrate_backend(&config, DatabaseBackend::Lmdb).unwrap(),
+        expected.iter().map(Vec::len).sum::<usize>()
+    );
+    assert_eq!(
+        database_entries(&Environment::new(&config).unwrap()),
+        expected
+    );
+
+    // The migrated database is never overwritten.
+    assert!(matches!(
+        migrate_backend(&config, DatabaseBackend::Lmdb),
+        Err(Error::MigrationTargetNotEmpty)
+    ));
+}
@@ -1,4 +1,4 @@
-#![cfg(any(feature = "mdbx", feature = "lmdb"))]
+#![cfg(any(feature = "mdbx", feature = "lmdb", feature = "redb"))]
 
 use logging::test_logger;
 use slasher::{
@@ -62,3 +62,26 @@ fn block_pruning() {
         .epoch(slots_per_epoch)
         > current_epoch - config.history_length as u64));
 }
+
+#[test]
+fn rescan_queue_separate() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(0);
+
+    // A slashable pair of blocks from the network, and an unrelated block from a rescan.
+    slasher.accept_block_header(test_block(1, 0, 0));
+    slasher.accept_block_header(test_block(1, 0, 1));
+    slasher.accept_rescan_block_header(test_block(2, 1, 0));
+
+    // Processing the rescan only processes (and reports) the rescanned block.
+    let rescan_stats = slasher.process_rescan_queued(current_epoch).unwrap();
+    assert_eq!(rescan_stats.block_stats.num_processed, 1);
+    assert_eq!(rescan_stats.block_stats.num_slashings, 0);
+
+    let stats = slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(stats.block_stats.num_processed, 2);
+    assert_eq!(stats.block_stats.num_slashings, 1);
+    assert_eq!(slasher.get_proposer_slashings().len(), 1);
+}
@@ -0,0 +1,122 @@
+#![cfg(any(feature = "mdbx", feature = "lmdb", feature = "redb"))]
+
+use logging::test_logger;
+use slasher::{
+    test_utils::{att_slashing, block as test_block, indexed_att, E},
+    Config, EpochTargets, Slasher,
+};
+use tempfile::tempdir;
+use types::{Epoch, EthSpec};
+
+#[test]
+fn query_surround_slashing() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(5);
+
+    let att1 = indexed_att([0], 1, 3, 0);
+    let att2 = indexed_att([0], 0, 4, 0);
+
+    // Process the attestations separately so that `att2` is the surrounding attestation.
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    slasher.accept_attestation(att2.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    let (attester_slashings, proposer_slashings) = slasher
+        .get_detected_slashings(Epoch::new(0), current_epoch)
+        .unwrap();
+    assert_eq!(attester_slashings, vec![att_slashing(&att2, &att1)]);
+    assert!(proposer_slashings.is_empty());
+
+    // Slashings are keyed by the later of the two target epochs.
+    let (attester_slashings, _) = slasher
+        .get_detected_slashings(Epoch::new(0), Epoch::new(3))
+        .unwrap();
+    assert!(attester_slashings.is_empty());
+
+    // Querying the slashings doesn't consume them.
+    assert_eq!(slasher.get_attester_slashings().len(), 1);
+
+    let attestations = slasher
+        .get_validator_attestations(0, Epoch::new(0), current_epoch)
+        .unwrap();
+    assert_eq!(attestations, vec![att1.clone(), att2]);
+    let attestations = slasher
+        .get_validator_attestations(0, Epoch::new(4), Epoch::new(10))
+        .unwrap();
+    assert_eq!(attestations.len(), 1);
+    assert!(slasher
+        .get_validator_attestations(1, Epoch::new(0), current_epoch)
+        .unwrap()
+        .is_empty());
+
+    // Only `att1` is applied to the arrays, because `att2` is slashable.
+    let status = slasher
+        .get_validator_array_status(0, Epoch::new(0), Epoch::new(10))
+        .unwrap();
+    assert_eq!(status.validator_chunk_index, 0);
+    assert_eq!(status.current_epoch, Some(current_epoch));
+    assert_eq!(status.pruning_horizon, Some(Epoch::new(0)));
+    assert_eq!(status.chunks.len(), 1);
+    assert!(status.chunks[0].min_chunk_stored);
+    assert!(status.chunks[0].max_chunk_stored);
+    assert_eq!(status.targets.len(), 6);
+    assert_eq!(
+        status.targets[0],
+        EpochTargets {
+            epoch: Epoch::new(0),
+            min_target: Some(Epoch::new(3)),
+            max_target: None,
+        }
+    );
+    assert_eq!(
+        status.targets[2],
+        EpochTargets {
+            epoch: Epoch::new(2),
+            min_target: None,
+            max_target: Some(Epoch::new(3)),
+        }
+    );
+
+    let status = slasher
+        .get_validator_array_status(1, Epoch::new(0), current_epoch)
+        .unwrap();
+    assert_eq!(status.current_epoch, None);
+    assert!(status.targets.is_empty());
+}
+
+#[test]
+fn query_proposer_slashing_pruning() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.chunk_size = 2;
+    config.history_length = 2;
+    let slasher = Slasher::<E>::open(config.clone(), test_logger()).unwrap();
+
+    let slot = E::slots_per_epoch() + 1;
+    let current_epoch = Epoch::new(1);
+    slasher.accept_block_header(test_block(slot, 0, 0));
+    slasher.accept_block_header(test_block(slot, 0, 1));
+    slasher.process_queued(current_epoch).unwrap();
+
+    let (attester_slashings, proposer_slashings) = slasher
+        .get_detected_slashings(current_epoch, current_epoch)
+        .unwrap();
+    assert!(attester_slashings.is_empty());
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(
+        proposer_slashings,
+        slasher.get_proposer_slashings().into_iter().collect::<Vec<_>>()
+    );
+
+    // Slashings older than the history length are pruned.
+    slasher
+        .prune_database(current_epoch + config.history_length as u64)
+        .unwrap();
+    let (_, proposer_slashings) = slasher
+        .get_detected_slashings(Epoch::new(0), Epoch::new(10))
+        .unwrap();
+    assert!(proposer_slashings.is_empty());
+}
@@ -419,11 +419,14 @@ mod tests {
                     enabled: true,
                     voting_public_key: validator_pubkey.clone(),
                     graffiti: None,
+                    graffiti_template: None,
                     suggested_fee_recipient: None,
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    pending_exit: None,
+                    doppelganger: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
@@ -445,11 +448,14 @@ mod tests {
                     enabled: true,
                     voting_public_key: validator_pubkey.clone(),
                     graffiti: None,
+                    graffiti_template: None,
                     suggested_fee_recipient: None,
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    pending_exit: None,
+                    doppelganger: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
                         url: signer_rig.url.to_string(),
@@ -457,6 +463,7 @@ mod tests {
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
                         client_identity_password: Some(client_identity_password()),
+                        api_token: None,
                     }),
                 };
                 ValidatorStoreRig::new(
@@ -2,6 +2,7 @@
 
 use crate::test_utils::*;
 use crate::*;
+use tempfile::tempdir;
 use types::{AttestationData, Checkpoint, Epoch, Slot};
 
 pub fn build_checkpoint(epoch_num: u64) -> Checkpoint {
@@ -387,3 +388,44 @@ fn invalid_prev_surrounds_new_multiple() {
     }
     .run()
 }
+
+#[test]
+fn has_signed_in_epoch() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+    let slots_per_epoch = 32;
+
+    let pk = pubkey(0);
+    slashing_db.register_validator(pk).unwrap();
+
+    let attestation = attestation_data_builder(2, 3);
+    slashing_db
+        .check_and_insert_attestation(&pk, &attestation, DEFAULT_DOMAIN)
+        .unwrap();
+    slashing_db
+        .check_and_insert_block_signing_root(
+            &pk,
+            Slot::new(5 * slots_per_epoch + 1),
+            SigningRoot::from(Hash256::zero()),
+        )
+        .unwrap();
+
+    for (epoch, expected) in [(2, false), (3, true), (4, false), (5, true), (6, false)] {
+        assert_eq!(
+            slashing_db
+                .has_signed_in_epoch(&pk, Epoch::new(epoch), slots_per_epoch)
+                .unwrap(),
+            expected,
+            "epoch {}",
+            epoch
+        );
+    }
+
+    assert_eq!(
+        slashing_db
+            .has_signed_in_epoch(&pubkey(1), Epoch::new(3), slots_per_epoch)
+            .unwrap_err(),
+        NotSafe::UnregisteredValidator(pubkey(1))
+    );
+}
@@ -3,6 +3,7 @@
 use crate::test_utils::pubkey;
 use crate::*;
 use tempfile::tempdir;
+use types::{Epoch, Slot};
 
 #[test]
 fn export_non_existent_key() {
@@ -73,3 +74,54 @@ fn export_same_key_twice() {
         export_double.minify().unwrap()
     );
 }
+
+#[test]
+fn export_watermarks() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let key1 = pubkey(1);
+    let key2 = pubkey(2);
+    let key3 = pubkey(3);
+    slashing_db
+        .register_validators([&key1, &key2, &key3].into_iter())
+        .unwrap();
+
+    let signing_root = |i: u64| SigningRoot::from(Hash256::from_low_u64_be(i));
+    for (slot, root) in [(5, 1), (3, 2), (8, 3)] {
+        slashing_db
+            .check_and_insert_block_signing_root(&key1, Slot::new(slot), signing_root(root))
+            .unwrap();
+    }
+    for (source, target, root) in [(0, 1, 4), (1, 2, 5), (2, 3, 6)] {
+        slashing_db
+            .check_and_insert_attestation_signing_root(
+                &key1,
+                Epoch::new(source),
+                Epoch::new(target),
+                signing_root(root),
+            )
+            .unwrap();
+    }
+    slashing_db
+        .check_and_insert_block_signing_root(&key3, Slot::new(10), signing_root(7))
+        .unwrap();
+
+    // Disabled validators are exported too.
+    let key3_id = slashing_db.get_validator_id(&key3).unwrap();
+    slashing_db
+        .with_transaction(|txn| slashing_db.update_validator_status(txn, key3_id, false))
+        .unwrap();
+
+    let watermarks = slashing_db.export_watermarks(Hash256::zero()).unwrap();
+    assert_eq!(watermarks.data.len(), 3);
+
+    // The watermarks should match the minified export of all validators.
+    let minified = slashing_db
+        .export_interchange_info(Hash256::zero(), Some(&[key1, key2, key3]))
+        .unwrap()
+        .minify()
+        .unwrap();
+    assert!(watermarks.equiv(&minified));
+}
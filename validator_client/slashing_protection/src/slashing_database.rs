@@ -670,6 +670,40 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
+    /// Check whether `validator_pubkey` has signed a block in `epoch`, or an attestation targeting
+    /// `epoch`.
+    ///
+    /// Pruned blocks and attestations are not considered, so this is only meaningful for recent
+    /// epochs.
+    pub fn has_signed_in_epoch(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        epoch: Epoch,
+        slots_per_epoch: u64,
+    ) -> Result<bool, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let validator_id = self.get_validator_id_in_txn(&txn, validator_pubkey)?;
+
+        let signed = txn
+            .prepare(
+                "SELECT EXISTS(SELECT 1 FROM signed_attestations
+                               WHERE validator_id = ?1 AND target_epoch = ?2)
+                     OR EXISTS(SELECT 1 FROM signed_blocks
+                               WHERE validator_id = ?1 AND slot >= ?3 AND slot <= ?4)",
+            )?
+            .query_row(
+                params![
+                    validator_id,
+                    epoch,
+                    epoch.start_slot(slots_per_epoch),
+                    epoch.end_slot(slots_per_epoch)
+                ],
+                |row| row.get(0),
+            )?;
+        Ok(signed)
+    }
+
     /// Import slashing protection from another client in the interchange format.
     ///
     /// This function will atomically import the entire interchange, failing if *any*
@@ -851,6 +885,76 @@ impl SlashingDatabase {
         Ok(Interchange { metadata, data })
     }
 
+    /// Export the maximum block slot and maximum attestation source and target epochs of every
+    /// registered validator, without signing roots.
+    ///
+    /// Disabled validators are included, as they may be re-enabled on a standby after it is
+    /// promoted. The result is equivalent to a minified export of all validators, but is computed
+    /// by the database, making it cheap enough to export frequently.
+    pub fn export_watermarks(
+        &self,
+        genesis_validators_root: Hash256,
+    ) -> Result<Interchange, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        let data = txn
+            .prepare(
+                "SELECT validators.public_key, blocks.max_slot, attestations.max_source,
+                        attestations.max_target
+                 FROM validators
+                 LEFT JOIN (SELECT validator_id, MAX(slot) AS max_slot
+                            FROM signed_blocks
+                            GROUP BY validator_id) AS blocks
+                    ON blocks.validator_id = validators.id
+                 LEFT JOIN (SELECT validator_id, MAX(source_epoch) AS max_source,
+                                   MAX(target_epoch) AS max_target
+                            FROM signed_attestations
+                            GROUP BY validator_id) AS attestations
+                    ON attestations.validator_id = validators.id
+                 ORDER BY validators.id ASC",
+            )?
+            .query_and_then(params![], |row| {
+                let pubkey_str: String = row.get(0)?;
+                let pubkey = pubkey_str
+                    .parse()
+                    .map_err(InterchangeError::InvalidPubkey)?;
+                let max_slot: Option<Slot> = row.get(1)?;
+                let max_source: Option<Epoch> = row.get(2)?;
+                let max_target: Option<Epoch> = row.get(3)?;
+
+                let signed_blocks = max_slot
+                    .map(|slot| InterchangeBlock {
+                        slot,
+                        signing_root: None,
+                    })
+                    .into_iter()
+                    .collect();
+                let signed_attestations = match (max_source, max_target) {
+                    (Some(source_epoch), Some(target_epoch)) => vec![InterchangeAttestation {
+                        source_epoch,
+                        target_epoch,
+                        signing_root: None,
+                    }],
+                    (None, None) => vec![],
+                    _ => return Err(InterchangeError::MaxInconsistent),
+                };
+                Ok(InterchangeData {
+                    pubkey,
+                    signed_blocks,
+                    signed_attestations,
+                })
+            })?
+            .collect::<Result<_, InterchangeError>>()?;
+
+        let metadata = InterchangeMetadata {
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root,
+        };
+
+        Ok(Interchange { metadata, data })
+    }
+
     fn export_interchange_blocks_for_validator(
         &self,
         validator_id: i64,
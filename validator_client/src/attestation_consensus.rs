@@ -0,0 +1,235 @@
+//! Selects the `AttestationData` to sign from the responses of several beacon nodes.
+//!
+//! Beacon nodes are grouped by the head and target they vote for. If the largest group contains
+//! at least `quorum` beacon nodes its attestation data is signed, otherwise the configured
+//! `DisagreementAction` decides whether to sign the attestation data of the largest group or to
+//! skip the attestation.
+//!
+//! All beacon nodes are queried concurrently, and the quorum is built from the responses which
+//! arrive within `1 / CONSENSUS_TIMEOUT_QUOTIENT` of a slot.
+use serde::{Deserialize, Serialize};
+use strum::{EnumString, EnumVariantNames};
+use types::{AttestationData, Checkpoint, Hash256};
+
+/// Beacon nodes which don't return attestation data within `1 / CONSENSUS_TIMEOUT_QUOTIENT` of a
+/// slot are left out of the consensus, so that a slow beacon node doesn't delay the attestation.
+pub const CONSENSUS_TIMEOUT_QUOTIENT: u32 = 6;
+
+/// Configuration for requiring multiple beacon nodes to agree on attestation data.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttestationConsensus {
+    /// The number of beacon nodes which must agree on the head and target.
+    pub quorum: usize,
+    /// What to do if the quorum is not reached.
+    pub on_disagreement: DisagreementAction,
+}
+
+/// What to do if the beacon nodes do not reach a quorum on the attestation data.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum DisagreementAction {
+    /// Sign the attestation data returned by the most beacon nodes.
+    #[default]
+    Majority,
+    /// Do not attest.
+    Skip,
+}
+
+/// The result of comparing the attestation data returned by each beacon node.
+#[derive(Debug, PartialEq)]
+pub enum ConsensusOutcome {
+    /// At least `quorum` beacon nodes agreed.
+    Quorum(AttestationData),
+    /// The quorum was not reached, the attestation data of the largest group was chosen.
+    Majority(AttestationData),
+    /// The quorum was not reached and the attestation should not be signed.
+    Skip,
+}
+
+impl ConsensusOutcome {
+    /// A label for the outcome, used in metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsensusOutcome::Quorum(_) => "quorum",
+            ConsensusOutcome::Majority(_) => "majority",
+            ConsensusOutcome::Skip => "skip",
+        }
+    }
+}
+
+/// The attestation data returned by a group of beacon nodes which agree on the head and target.
+struct Vote {
+    head: Hash256,
+    target: Checkpoint,
+    /// The attestation data returned by the first beacon node in the group.
+    attestation_data: AttestationData,
+    beacon_nodes: Vec<String>,
+}
+
+/// The result of `select_attestation_data`.
+pub struct Consensus {
+    pub outcome: ConsensusOutcome,
+    /// The beacon nodes whose head or target differs from that of the largest group.
+    pub dissenting: Vec<String>,
+}
+
+/// Select the attestation data to sign from `responses`, which are pairs of beacon node and
+/// attestation data ordered from the most preferred beacon node.
+///
+/// Ties between groups of equal size are broken in favour of the most preferred beacon node.
+pub fn select_attestation_data(
+    responses: Vec<(String, AttestationData)>,
+    consensus: &AttestationConsensus,
+) -> Consensus {
+    let mut votes: Vec<Vote> = vec![];
+    for (beacon_node, attestation_data) in responses {
+        if let Some(vote) = votes.iter_mut().find(|vote| {
+            vote.head == attestation_data.beacon_block_root
+                && vote.target == attestation_data.target
+        }) {
+            vote.beacon_nodes.push(beacon_node);
+        } else {
+            votes.push(Vote {
+                head: attestation_data.beacon_block_root,
+                target: attestation_data.target,
+                attestation_data,
+                beacon_nodes: vec![beacon_node],
+            });
+        }
+    }
+
+    // Find the first of the largest groups, which is the group of the most preferred beacon node
+    // in case of a tie.
+    let Some(winner) = votes
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, vote)| vote.beacon_nodes.len())
+        .map(|(i, _)| i)
+    else {
+        return Consensus {
+            outcome: ConsensusOutcome::Skip,
+            dissenting: vec![],
+        };
+    };
+    let winner = votes.remove(winner);
+    let dissenting = votes
+        .into_iter()
+        .flat_map(|vote| vote.beacon_nodes)
+        .collect::<Vec<_>>();
+
+    let outcome = if winner.beacon_nodes.len() >= consensus.quorum {
+        ConsensusOutcome::Quorum(winner.attestation_data)
+    } else {
+        match consensus.on_disagreement {
+            DisagreementAction::Majority => ConsensusOutcome::Majority(winner.attestation_data),
+            DisagreementAction::Skip => ConsensusOutcome::Skip,
+        }
+    };
+
+    Consensus {
+        outcome,
+        dissenting,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, Slot};
+
+    fn attestation_data(head: u64, target_epoch: u64) -> AttestationData {
+        AttestationData {
+            slot: Slot::new(32),
+            index: 0,
+            beacon_block_root: Hash256::from_low_u64_be(head),
+            source: Checkpoint {
+                epoch: Epoch::new(0),
+                root: Hash256::zero(),
+            },
+            target: Checkpoint {
+                epoch: Epoch::new(target_epoch),
+                root: Hash256::from_low_u64_be(target_epoch),
+            },
+        }
+    }
+
+    fn responses(votes: &[(u64, u64)]) -> Vec<(String, AttestationData)> {
+        votes
+            .iter()
+            .enumerate()
+            .map(|(i, (head, target))| (format!("bn-{}", i), attestation_data(*head, *target)))
+            .collect()
+    }
+
+    fn consensus(quorum: usize, on_disagreement: DisagreementAction) -> AttestationConsensus {
+        AttestationConsensus {
+            quorum,
+            on_disagreement,
+        }
+    }
+
+    #[test]
+    fn quorum_reached() {
+        let result = select_attestation_data(
+            responses(&[(1, 1), (2, 1), (2, 1)]),
+            &consensus(2, DisagreementAction::Skip),
+        );
+        assert_eq!(
+            result.outcome,
+            ConsensusOutcome::Quorum(attestation_data(2, 1))
+        );
+        assert_eq!(result.dissenting, vec!["bn-0".to_string()]);
+    }
+
+    #[test]
+    fn differing_target_is_a_disagreement() {
+        let result = select_attestation_data(
+            responses(&[(1, 1), (1, 2), (1, 3)]),
+            &consensus(2, DisagreementAction::Majority),
+        );
+        assert_eq!(
+            result.outcome,
+            ConsensusOutcome::Majority(attestation_data(1, 1))
+        );
+        assert_eq!(
+            result.dissenting,
+            vec!["bn-1".to_string(), "bn-2".to_string()]
+        );
+    }
+
+    #[test]
+    fn majority_prefers_largest_group() {
+        let result = select_attestation_data(
+            responses(&[(1, 1), (2, 1), (2, 1), (3, 1)]),
+            &consensus(3, DisagreementAction::Majority),
+        );
+        assert_eq!(
+            result.outcome,
+            ConsensusOutcome::Majority(attestation_data(2, 1))
+        );
+        assert_eq!(
+            result.dissenting,
+            vec!["bn-0".to_string(), "bn-3".to_string()]
+        );
+    }
+
+    #[test]
+    fn skip_on_disagreement() {
+        let result = select_attestation_data(
+            responses(&[(1, 1), (2, 1)]),
+            &consensus(2, DisagreementAction::Skip),
+        );
+        assert_eq!(result.outcome, ConsensusOutcome::Skip);
+        assert_eq!(result.dissenting, vec!["bn-1".to_string()]);
+    }
+
+    #[test]
+    fn no_responses() {
+        let result = select_attestation_data(vec![], &consensus(1, DisagreementAction::Majority));
+        assert_eq!(result.outcome, ConsensusOutcome::Skip);
+        assert!(result.dissenting.is_empty());
+    }
+}
@@ -1,4 +1,8 @@
-use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
+use crate::attestation_consensus::{
+    select_attestation_data, AttestationConsensus, Consensus, ConsensusOutcome,
+    CONSENSUS_TIMEOUT_QUOTIENT,
+};
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, Errors, RequireSynced};
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
@@ -26,6 +30,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    attestation_consensus: Option<AttestationConsensus>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -36,6 +41,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            attestation_consensus: None,
         }
     }
 
@@ -64,6 +70,14 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn attestation_consensus(
+        mut self,
+        attestation_consensus: Option<AttestationConsensus>,
+    ) -> Self {
+        self.attestation_consensus = attestation_consensus;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
         Ok(AttestationService {
             inner: Arc::new(Inner {
@@ -82,6 +96,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                attestation_consensus: self.attestation_consensus,
             }),
         })
     }
@@ -94,6 +109,7 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    attestation_consensus: Option<AttestationConsensus>,
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
@@ -318,6 +334,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     ///
     /// Only one `Attestation` is downloaded from the BN. It is then cloned and signed by each
     /// validator and the list of individually-signed `Attestation` objects is returned to the BN.
+    ///
+    /// If attestation consensus is enabled, the `AttestationData` is instead downloaded from all
+    /// BNs and only signed if they agree on it. See `Self::attestation_data_by_consensus`.
     async fn produce_and_publish_attestations(
         &self,
         slot: Slot,
@@ -336,25 +355,34 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .ok_or("Unable to determine current slot from clock")?
             .epoch(E::slots_per_epoch());
 
-        let attestation_data = self
-            .beacon_nodes
-            .first_success(
-                RequireSynced::No,
-                OfflineOnFailure::Yes,
-                |beacon_node| async move {
-                    let _timer = metrics::start_timer_vec(
-                        &metrics::ATTESTATION_SERVICE_TIMES,
-                        &[metrics::ATTESTATIONS_HTTP_GET],
-                    );
-                    beacon_node
-                        .get_validator_attestation_data(slot, committee_index)
-                        .await
-                        .map_err(|e| format!("Failed to produce attestation data: {:?}", e))
-                        .map(|result| result.data)
-                },
-            )
-            .await
-            .map_err(|e| e.to_string())?;
+        let attestation_data = if let Some(consensus) = &self.attestation_consensus {
+            match self
+                .attestation_data_by_consensus(slot, committee_index, consensus)
+                .await?
+            {
+                Some(attestation_data) => attestation_data,
+                None => return Ok(None),
+            }
+        } else {
+            self.beacon_nodes
+                .first_success(
+                    RequireSynced::No,
+                    OfflineOnFailure::Yes,
+                    |beacon_node| async move {
+                        let _timer = metrics::start_timer_vec(
+                            &metrics::ATTESTATION_SERVICE_TIMES,
+                            &[metrics::ATTESTATIONS_HTTP_GET],
+                        );
+                        beacon_node
+                            .get_validator_attestation_data(slot, committee_index)
+                            .await
+                            .map_err(|e| format!("Failed to produce attestation data: {:?}", e))
+                            .map(|result| result.data)
+                    },
+                )
+                .await
+                .map_err(|e| e.to_string())?
+        };
 
         // Create futures to produce signed `Attestation` objects.
         let attestation_data_ref = &attestation_data;
@@ -477,6 +505,118 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(Some(attestation_data))
     }
 
+    /// Downloads `AttestationData` from all BNs concurrently and selects the attestation data to
+    /// sign according to `consensus`, using the responses which arrive before the timeout.
+    ///
+    /// Returns `None` if the BNs disagree and the attestation should be skipped.
+    async fn attestation_data_by_consensus(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+        consensus: &AttestationConsensus,
+    ) -> Result<Option<AttestationData>, String> {
+        let log = self.context.log();
+        let timeout = self.slot_clock.slot_duration() / CONSENSUS_TIMEOUT_QUOTIENT;
+
+        let mut responses = vec![];
+        let mut errors = vec![];
+        for result in self
+            .beacon_nodes
+            .broadcast_results(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                Some(timeout),
+                |beacon_node| async move {
+                    let _timer = metrics::start_timer_vec(
+                        &metrics::ATTESTATION_SERVICE_TIMES,
+                        &[metrics::ATTESTATIONS_HTTP_GET],
+                    );
+                    beacon_node
+                        .get_validator_attestation_data(slot, committee_index)
+                        .await
+                        .map_err(|e| format!("Failed to produce attestation data: {:?}", e))
+                        .map(|result| (beacon_node.to_string(), result.data))
+                },
+            )
+            .await
+        {
+            match result {
+                Ok(response) => responses.push(response),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if responses.is_empty() {
+            return Err(Errors(errors).to_string());
+        } else if !errors.is_empty() {
+            debug!(
+                log,
+                "Unable to download attestation data from all beacon nodes";
+                "error" => %Errors(errors),
+                "committee_index" => committee_index,
+                "slot" => slot.as_u64(),
+            );
+        }
+
+        let Consensus {
+            outcome,
+            dissenting,
+        } = select_attestation_data(responses, consensus);
+
+        metrics::inc_counter_vec(
+            &metrics::ATTESTATION_DATA_CONSENSUS_TOTAL,
+            &[outcome.as_str()],
+        );
+        for beacon_node in &dissenting {
+            metrics::inc_counter_vec(
+                &metrics::ATTESTATION_DATA_DISAGREEMENTS_TOTAL,
+                &[beacon_node],
+            );
+        }
+
+        match outcome {
+            ConsensusOutcome::Quorum(attestation_data) => {
+                if !dissenting.is_empty() {
+                    warn!(
+                        log,
+                        "Beacon nodes disagree on attestation data";
+                        "msg" => "the quorum was reached",
+                        "dissenting" => ?dissenting,
+                        "head_block" => ?attestation_data.beacon_block_root,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                }
+                Ok(Some(attestation_data))
+            }
+            ConsensusOutcome::Majority(attestation_data) => {
+                warn!(
+                    log,
+                    "Beacon nodes disagree on attestation data";
+                    "msg" => "the quorum was not reached, attesting to the majority",
+                    "quorum" => consensus.quorum,
+                    "dissenting" => ?dissenting,
+                    "head_block" => ?attestation_data.beacon_block_root,
+                    "committee_index" => committee_index,
+                    "slot" => slot.as_u64(),
+                );
+                Ok(Some(attestation_data))
+            }
+            ConsensusOutcome::Skip => {
+                error!(
+                    log,
+                    "Beacon nodes disagree on attestation data";
+                    "msg" => "the quorum was not reached, skipping attestation",
+                    "quorum" => consensus.quorum,
+                    "dissenting" => ?dissenting,
+                    "committee_index" => committee_index,
+                    "slot" => slot.as_u64(),
+                );
+                Ok(None)
+            }
+        }
+    }
+
     /// Performs the second step of the attesting process: downloading an aggregated `Attestation`,
     /// converting it into a `SignedAggregateAndProof` and returning it to the BN.
     ///
@@ -2,8 +2,11 @@
 //! "fallback" behaviour; it will try a request on all of the nodes until one or none of them
 //! succeed.
 
+use crate::beacon_node_health::{BeaconNodeHealth, HealthMeasurements};
 use crate::check_synced::check_synced;
-use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use crate::http_metrics::metrics::{
+    inc_counter_vec, set_int_gauge, ENDPOINT_ERRORS, ENDPOINT_REQUESTS, VC_BEACON_NODE_HEALTH_SCORE,
+};
 use environment::RuntimeContext;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
@@ -106,6 +109,8 @@ pub enum Error<T> {
     Unavailable(CandidateError),
     /// We attempted to contact the node but it failed.
     RequestFailed(T),
+    /// We attempted to contact the node but it didn't respond in time.
+    TimedOut,
 }
 
 impl<T> Error<T> {
@@ -148,6 +153,7 @@ pub enum CandidateError {
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    health: RwLock<HealthMeasurements>,
     _phantom: PhantomData<E>,
 }
 
@@ -157,6 +163,7 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            health: RwLock::new(HealthMeasurements::default()),
             _phantom: PhantomData,
         }
     }
@@ -176,6 +183,27 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         *self.status.write().await = Err(CandidateError::Offline)
     }
 
+    /// Record the outcome of a request to `self`, for the purpose of scoring its health.
+    async fn record_request(&self, success: bool) {
+        let mut health = self.health.write().await;
+        if success {
+            health.error_rate.record_success();
+        } else {
+            health.error_rate.record_error();
+        }
+    }
+
+    /// Returns a snapshot of the health of `self`, which is at position `index` in the list of
+    /// candidates.
+    pub async fn health(&self, index: usize) -> BeaconNodeHealth {
+        BeaconNodeHealth::new(
+            index,
+            self.beacon_node.to_string(),
+            *self.status.read().await,
+            &*self.health.read().await,
+        )
+    }
+
     /// Perform some queries against the node to determine if it is a good candidate, updating
     /// `self.status` and returning that result.
     pub async fn refresh_status<T: SlotClock>(
@@ -326,7 +354,9 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         log: &Logger,
     ) -> Result<(), CandidateError> {
         if let Some(slot_clock) = slot_clock {
-            check_synced(&self.beacon_node, slot_clock, Some(log)).await
+            let (status, syncing) = check_synced(&self.beacon_node, slot_clock, Some(log)).await;
+            self.health.write().await.syncing = syncing;
+            status
         } else {
             // Skip this check if we don't supply a slot clock.
             Ok(())
@@ -339,6 +369,8 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 /// identical query.
 pub struct BeaconNodeFallback<T, E> {
     candidates: Vec<CandidateBeaconNode<E>>,
+    /// The indices of `candidates`, from the healthiest to the least healthy.
+    ranking: RwLock<Vec<usize>>,
     slot_clock: Option<T>,
     broadcast_topics: Vec<ApiTopic>,
     spec: ChainSpec,
@@ -352,8 +384,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         spec: ChainSpec,
         log: Logger,
     ) -> Self {
+        let ranking = RwLock::new((0..candidates.len()).collect());
         Self {
             candidates,
+            ranking,
             slot_clock: None,
             broadcast_topics,
             spec,
@@ -424,6 +458,66 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
         // run all updates concurrently and ignore errors
         let _ = future::join_all(futures).await;
+
+        self.update_ranking().await;
+    }
+
+    /// Re-rank the candidates according to their health, so that requests are sent to the
+    /// healthiest candidate first.
+    ///
+    /// The error rates of the candidates are decayed afterwards, so that each update gives more
+    /// weight to the requests made since the previous one.
+    async fn update_ranking(&self) {
+        let mut health = Vec::with_capacity(self.candidates.len());
+        for (index, candidate) in self.candidates.iter().enumerate() {
+            let candidate_health = candidate.health(index).await;
+            set_int_gauge(
+                &VC_BEACON_NODE_HEALTH_SCORE,
+                &[candidate.beacon_node.as_ref()],
+                candidate_health.score() as i64,
+            );
+            health.push(candidate_health);
+            candidate.health.write().await.error_rate.decay();
+        }
+        health.sort_by(BeaconNodeHealth::compare);
+        let new_ranking = health.iter().map(|health| health.index).collect::<Vec<_>>();
+
+        let mut ranking = self.ranking.write().await;
+        if let (Some(&previous), Some(&new)) = (ranking.first(), new_ranking.first()) {
+            if previous != new {
+                info!(
+                    self.log,
+                    "Changed primary beacon node";
+                    "new_primary" => %self.candidates[new].beacon_node,
+                    "previous_primary" => %self.candidates[previous].beacon_node,
+                    "score" => health[0].score(),
+                );
+            }
+        }
+        *ranking = new_ranking;
+    }
+
+    /// Returns the candidates, from the healthiest to the least healthy.
+    async fn ranked_candidates(&self) -> Vec<&CandidateBeaconNode<E>> {
+        self.ranking
+            .read()
+            .await
+            .iter()
+            .filter_map(|&index| self.candidates.get(index))
+            .collect()
+    }
+
+    /// Returns a snapshot of the health of each candidate, from the healthiest to the least
+    /// healthy.
+    pub async fn health(&self) -> Vec<BeaconNodeHealth> {
+        let ranking = self.ranking.read().await.clone();
+        let mut health = Vec::with_capacity(ranking.len());
+        for index in ranking {
+            if let Some(candidate) = self.candidates.get(index) {
+                health.push(candidate.health(index).await);
+            }
+        }
+        health
     }
 
     /// Concurrently send a request to all candidates (regardless of
@@ -456,7 +550,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // Send the request to all BNs at the same time. This might involve some
         // queueing on the sending host, however I hope it will avoid bias
         // caused by sending requests at different times.
-        future::join_all(futures)
+        let measurements: Vec<_> = future::join_all(futures)
             .await
             .into_iter()
             .map(|(beacon_node_id, response_instant)| LatencyMeasurement {
@@ -464,7 +558,14 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 latency: response_instant
                     .and_then(|response| response.checked_duration_since(request_instant)),
             })
-            .collect()
+            .collect();
+
+        // Keep the latest measurement for scoring the health of each candidate.
+        for (candidate, measurement) in self.candidates.iter().zip(&measurements) {
+            candidate.health.write().await.latency = measurement.latency;
+        }
+
+        measurements
     }
 
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
@@ -500,8 +601,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
                 match func(&$candidate.beacon_node).await {
-                    Ok(val) => return Ok(val),
+                    Ok(val) => {
+                        $candidate.record_request(true).await;
+                        return Ok(val);
+                    }
                     Err(e) => {
+                        $candidate.record_request(false).await;
                         debug!(
                             log,
                             "Request to beacon node failed";
@@ -523,10 +628,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }};
         }
 
-        // First pass: try `func` on all synced and ready candidates.
+        // First pass: try `func` on all synced and ready candidates, healthiest first.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for candidate in self.ranked_candidates().await {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -579,16 +684,17 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         Err(Errors(errors))
     }
 
-    /// Run `func` against all candidates in `self`, collecting the result of `func` against each
-    /// candidate.
+    /// Run `func` against all candidates in `self` concurrently, collecting the result of `func`
+    /// against each candidate.
     ///
-    /// First this function will try all nodes with a suitable status. If no candidates are suitable
-    /// it will try updating the status of all unsuitable nodes and re-running `func` again.
+    /// The status of each candidate which isn't suitable is refreshed before `func` is run against
+    /// it.
     ///
     /// Note: This function returns `Ok(())` if `func` returned successfully on all beacon nodes.
     /// It returns a list of errors along with the beacon node id that failed for `func`.
     /// Since this ignores the actual result of `func`, this function should only be used for beacon
-    /// node calls whose results we do not care about, only that they completed successfully.
+    /// node calls whose results we do not care about, only that they completed successfully. Use
+    /// `broadcast_results` to obtain the results.
     pub async fn broadcast<'a, F, O, Err, R>(
         &'a self,
         require_synced: RequireSynced,
@@ -599,100 +705,123 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         F: Fn(&'a BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
     {
-        let mut results = vec![];
-        let mut to_retry = vec![];
-        let mut retry_unsynced = vec![];
-
-        // Run `func` using a `candidate`, returning the value or capturing errors.
-        //
-        // We use a macro instead of a closure here since it is not trivial to move `func` into a
-        // closure.
-        macro_rules! try_func {
-            ($candidate: ident) => {{
-                inc_counter_vec(&ENDPOINT_REQUESTS, &[$candidate.beacon_node.as_ref()]);
+        let errors: Vec<_> = self
+            .broadcast_results(require_synced, offline_on_failure, None, func)
+            .await
+            .into_iter()
+            .filter_map(|res| res.err())
+            .collect();
 
-                // There exists a race condition where `func` may be called when the candidate is
-                // actually not ready. We deem this an acceptable inefficiency.
-                match func(&$candidate.beacon_node).await {
-                    Ok(val) => results.push(Ok(val)),
-                    Err(e) => {
-                        // If we have an error on this function, make the client as not-ready.
-                        //
-                        // There exists a race condition where the candidate may have been marked
-                        // as ready between the `func` call and now. We deem this an acceptable
-                        // inefficiency.
-                        if matches!(offline_on_failure, OfflineOnFailure::Yes) {
-                            $candidate.set_offline().await;
-                        }
-                        results.push(Err((
-                            $candidate.beacon_node.to_string(),
-                            Error::RequestFailed(e),
-                        )));
-                        inc_counter_vec(&ENDPOINT_ERRORS, &[$candidate.beacon_node.as_ref()]);
-                    }
-                }
-            }};
+        if !errors.is_empty() {
+            Err(Errors(errors))
+        } else {
+            Ok(())
         }
+    }
 
-        // First pass: try `func` on all synced and ready candidates.
-        //
-        // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+    /// Run `func` against all candidates in `self` concurrently, returning the result of `func`
+    /// against each candidate.
+    ///
+    /// Results are ordered with synced candidates first, from the healthiest to the least healthy,
+    /// followed by unsynced candidates and then candidates which had to be refreshed before `func`
+    /// could be run against them. If a `timeout` is given, candidates which haven't returned a
+    /// result by then are abandoned and reported as `Error::TimedOut`.
+    pub async fn broadcast_results<'a, F, O, Err, R>(
+        &'a self,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        timeout: Option<Duration>,
+        func: F,
+    ) -> Vec<Result<O, (String, Error<Err>)>>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+    {
+        let mut ready = vec![];
+        let mut unsynced = vec![];
+        let mut to_refresh = vec![];
+
+        // Try synced and ready candidates first, so that they're preferred by callers which use
+        // the first result.
+        for candidate in self.ranked_candidates().await {
             match candidate.status(RequireSynced::Yes).await {
+                Ok(()) => ready.push(candidate),
                 Err(CandidateError::NotSynced) if require_synced == false => {
-                    // This client is unsynced we will try it after trying all synced clients
-                    retry_unsynced.push(candidate);
+                    unsynced.push(candidate)
                 }
-                Err(_) => {
-                    // This client was not ready on the first pass, we might try it again later.
-                    to_retry.push(candidate);
-                }
-                Ok(_) => try_func!(candidate),
+                Err(_) => to_refresh.push(candidate),
             }
         }
 
-        // Second pass: try `func` on ready unsynced candidates. This only runs if we permit
-        // unsynced candidates.
-        //
-        // Due to async race-conditions, it is possible that we will send a request to a candidate
-        // that has been set to an offline/unready status. This is acceptable.
-        if require_synced == false {
-            for candidate in retry_unsynced {
-                try_func!(candidate);
+        let func = &func;
+        let candidates = ready.into_iter().chain(unsynced).chain(to_refresh);
+        future::join_all(candidates.map(|candidate| async move {
+            let result = self.run_on_candidate(candidate, require_synced, offline_on_failure, func);
+            match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, result).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        candidate.record_request(false).await;
+                        inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
+                        Err((candidate.beacon_node.to_string(), Error::TimedOut))
+                    }
+                },
+                None => result.await,
             }
-        }
-
-        // Third pass: try again, attempting to make non-ready clients become ready.
-        for candidate in to_retry {
-            // If the candidate hasn't luckily transferred into the correct state in the meantime,
-            // force an update of the state.
-            let new_status = match candidate.status(require_synced).await {
-                Ok(()) => Ok(()),
-                Err(_) => {
-                    candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
-                        .await
-                }
-            };
+        }))
+        .await
+    }
 
-            match new_status {
-                Ok(()) => try_func!(candidate),
-                Err(CandidateError::NotSynced) if require_synced == false => try_func!(candidate),
-                Err(e) => {
-                    results.push(Err((
-                        candidate.beacon_node.to_string(),
-                        Error::Unavailable(e),
-                    )));
-                }
+    /// Run `func` against `candidate`, refreshing its status first if it isn't ready.
+    async fn run_on_candidate<'a, F, O, Err, R>(
+        &'a self,
+        candidate: &'a CandidateBeaconNode<E>,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        func: &F,
+    ) -> Result<O, (String, Error<Err>)>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+    {
+        // If the candidate hasn't luckily transferred into the correct state in the meantime,
+        // force an update of the state.
+        let status = match candidate.status(require_synced).await {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                candidate
+                    .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                    .await
             }
+        };
+        match status {
+            Ok(()) => (),
+            Err(CandidateError::NotSynced) if require_synced == false => (),
+            Err(e) => return Err((candidate.beacon_node.to_string(), Error::Unavailable(e))),
         }
 
-        let errors: Vec<_> = results.into_iter().filter_map(|res| res.err()).collect();
+        inc_counter_vec(&ENDPOINT_REQUESTS, &[candidate.beacon_node.as_ref()]);
 
-        if !errors.is_empty() {
-            Err(Errors(errors))
-        } else {
-            Ok(())
+        // There exists a race condition where `func` may be called when the candidate is
+        // actually not ready. We deem this an acceptable inefficiency.
+        match func(&candidate.beacon_node).await {
+            Ok(val) => {
+                candidate.record_request(true).await;
+                Ok(val)
+            }
+            Err(e) => {
+                candidate.record_request(false).await;
+                // If we have an error on this function, make the client as not-ready.
+                //
+                // There exists a race condition where the candidate may have been marked
+                // as ready between the `func` call and now. We deem this an acceptable
+                // inefficiency.
+                if matches!(offline_on_failure, OfflineOnFailure::Yes) {
+                    candidate.set_offline().await;
+                }
+                inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
+                Err((candidate.beacon_node.to_string(), Error::RequestFailed(e)))
+            }
         }
     }
 
@@ -0,0 +1,278 @@
+//! Scores the health of the candidates of a `BeaconNodeFallback`, so that requests are sent to the
+//! healthiest beacon node first.
+//!
+//! The score of a beacon node is a penalty (lower is better) combining its sync distance,
+//! execution layer status, measured latency and recent error rate. Penalties are applied in coarse
+//! increments so that small differences between otherwise healthy beacon nodes don't cause them to
+//! be re-ordered, in which case the order given on the command line is kept.
+use crate::beacon_node_fallback::CandidateError;
+use eth2::lighthouse_vc::types::{BeaconNodeHealthData, BeaconNodeStatus};
+use eth2::types::SyncingData;
+use std::cmp::Ordering;
+use std::time::Duration;
+use types::Slot;
+
+/// Penalty for each slot that a beacon node is behind the head of the chain.
+pub const SYNC_DISTANCE_PENALTY: u64 = 10;
+/// The sync distance beyond which no further penalty is applied.
+pub const MAX_PENALISED_SYNC_DISTANCE: u64 = 64;
+/// Penalty for a beacon node whose execution layer is offline.
+pub const EL_OFFLINE_PENALTY: u64 = 1_000;
+/// Penalty for a beacon node whose head is optimistic.
+pub const OPTIMISTIC_PENALTY: u64 = 500;
+/// Latency is penalised in increments of this duration.
+pub const LATENCY_INCREMENT: Duration = Duration::from_millis(100);
+/// Penalty for each `LATENCY_INCREMENT` of latency.
+pub const LATENCY_PENALTY: u64 = 5;
+/// The latency beyond which no further penalty is applied.
+pub const MAX_PENALISED_LATENCY: Duration = Duration::from_secs(4);
+/// The error rate is penalised in increments of 1 / `ERROR_RATE_INCREMENTS`.
+pub const ERROR_RATE_INCREMENTS: u64 = 10;
+/// Penalty for each increment of error rate.
+pub const ERROR_RATE_PENALTY: u64 = 20;
+/// The weight of past requests is multiplied by this factor each slot, so that the error rate
+/// reflects recent requests.
+pub const ERROR_RATE_DECAY: f64 = 0.5;
+
+/// The error rate of the requests to a beacon node, weighted towards recent requests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorRate {
+    requests: f64,
+    errors: f64,
+}
+
+impl ErrorRate {
+    pub fn record_success(&mut self) {
+        self.requests += 1.0;
+    }
+
+    pub fn record_error(&mut self) {
+        self.requests += 1.0;
+        self.errors += 1.0;
+    }
+
+    /// Reduce the weight of the requests recorded so far by `ERROR_RATE_DECAY`.
+    pub fn decay(&mut self) {
+        self.requests *= ERROR_RATE_DECAY;
+        self.errors *= ERROR_RATE_DECAY;
+    }
+
+    /// Returns the fraction of recent requests which failed, or 0 if there were none.
+    pub fn rate(&self) -> f64 {
+        if self.requests > 0.0 {
+            self.errors / self.requests
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Measurements of a beacon node which are used to score its health.
+#[derive(Debug, Clone, Default)]
+pub struct HealthMeasurements {
+    /// The response to the most recent `node/syncing` request, if it succeeded.
+    pub syncing: Option<SyncingData>,
+    /// The most recent latency measurement, if it succeeded.
+    pub latency: Option<Duration>,
+    pub error_rate: ErrorRate,
+}
+
+/// A snapshot of the health of a beacon node.
+#[derive(Debug, Clone)]
+pub struct BeaconNodeHealth {
+    /// The position of the beacon node in the `--beacon-nodes` list.
+    pub index: usize,
+    pub endpoint: String,
+    pub status: Result<(), CandidateError>,
+    pub sync_distance: Option<Slot>,
+    pub is_optimistic: Option<bool>,
+    pub el_offline: Option<bool>,
+    pub latency: Option<Duration>,
+    pub error_rate: f64,
+}
+
+impl BeaconNodeHealth {
+    pub fn new(
+        index: usize,
+        endpoint: String,
+        status: Result<(), CandidateError>,
+        measurements: &HealthMeasurements,
+    ) -> Self {
+        let syncing = measurements.syncing.as_ref();
+        Self {
+            index,
+            endpoint,
+            status,
+            sync_distance: syncing.map(|syncing| syncing.sync_distance),
+            is_optimistic: syncing.and_then(|syncing| syncing.is_optimistic),
+            el_offline: syncing.and_then(|syncing| syncing.el_offline),
+            latency: measurements.latency,
+            error_rate: measurements.error_rate.rate(),
+        }
+    }
+
+    /// Returns the health score of the beacon node. Lower is better.
+    pub fn score(&self) -> u64 {
+        let sync_distance_penalty = self.sync_distance.map_or(0, |distance| {
+            distance.as_u64().min(MAX_PENALISED_SYNC_DISTANCE)
+        }) * SYNC_DISTANCE_PENALTY;
+        let el_offline_penalty = if self.el_offline == Some(true) {
+            EL_OFFLINE_PENALTY
+        } else {
+            0
+        };
+        let optimistic_penalty = if self.is_optimistic == Some(true) {
+            OPTIMISTIC_PENALTY
+        } else {
+            0
+        };
+        let latency_penalty = self.latency.map_or(0, |latency| {
+            (latency.min(MAX_PENALISED_LATENCY).as_millis() / LATENCY_INCREMENT.as_millis()) as u64
+                * LATENCY_PENALTY
+        });
+        let error_rate_penalty =
+            (self.error_rate * ERROR_RATE_INCREMENTS as f64).floor() as u64 * ERROR_RATE_PENALTY;
+
+        sync_distance_penalty
+            + el_offline_penalty
+            + optimistic_penalty
+            + latency_penalty
+            + error_rate_penalty
+    }
+
+    /// Beacon nodes which are synced are always preferred to those which are not, followed by
+    /// those which are offline or otherwise unusable.
+    fn status_tier(&self) -> u8 {
+        match self.status {
+            Ok(()) => 0,
+            Err(CandidateError::NotSynced) => 1,
+            Err(CandidateError::Uninitialized)
+            | Err(CandidateError::Offline)
+            | Err(CandidateError::Incompatible) => 2,
+        }
+    }
+
+    /// Orders beacon nodes from healthiest to least healthy, falling back to their position in
+    /// the `--beacon-nodes` list.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        self.status_tier()
+            .cmp(&other.status_tier())
+            .then_with(|| self.score().cmp(&other.score()))
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+impl From<BeaconNodeHealth> for BeaconNodeHealthData {
+    fn from(health: BeaconNodeHealth) -> Self {
+        let status = match health.status {
+            Ok(()) => BeaconNodeStatus::Synced,
+            Err(CandidateError::NotSynced) => BeaconNodeStatus::NotSynced,
+            Err(CandidateError::Offline) => BeaconNodeStatus::Offline,
+            Err(CandidateError::Incompatible) => BeaconNodeStatus::Incompatible,
+            Err(CandidateError::Uninitialized) => BeaconNodeStatus::Uninitialized,
+        };
+        Self {
+            score: health.score(),
+            index: health.index,
+            endpoint: health.endpoint,
+            status,
+            sync_distance: health.sync_distance,
+            is_optimistic: health.is_optimistic,
+            el_offline: health.el_offline,
+            latency_ms: health.latency.map(|latency| latency.as_millis() as u64),
+            error_rate: health.error_rate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy(index: usize) -> BeaconNodeHealth {
+        BeaconNodeHealth {
+            index,
+            endpoint: format!("http://bn-{}:5052/", index),
+            status: Ok(()),
+            sync_distance: Some(Slot::new(0)),
+            is_optimistic: Some(false),
+            el_offline: Some(false),
+            latency: Some(Duration::from_millis(20)),
+            error_rate: 0.0,
+        }
+    }
+
+    fn ranking(mut health: Vec<BeaconNodeHealth>) -> Vec<usize> {
+        health.sort_by(BeaconNodeHealth::compare);
+        health.into_iter().map(|health| health.index).collect()
+    }
+
+    #[test]
+    fn healthy_nodes_keep_their_order() {
+        let mut fallback = healthy(1);
+        fallback.latency = Some(Duration::from_millis(60));
+        assert_eq!(healthy(0).score(), 0);
+        assert_eq!(fallback.score(), 0);
+        assert_eq!(ranking(vec![fallback, healthy(0)]), vec![0, 1]);
+    }
+
+    #[test]
+    fn degraded_primary_is_ranked_last() {
+        let mut primary = healthy(0);
+        primary.latency = Some(Duration::from_millis(1_500));
+        primary.error_rate = 0.5;
+        assert_eq!(
+            primary.score(),
+            15 * LATENCY_PENALTY + 5 * ERROR_RATE_PENALTY
+        );
+        assert_eq!(
+            ranking(vec![primary, healthy(1), healthy(2)]),
+            vec![1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn el_offline_and_optimistic_are_penalised() {
+        let mut el_offline = healthy(0);
+        el_offline.el_offline = Some(true);
+        let mut optimistic = healthy(1);
+        optimistic.is_optimistic = Some(true);
+        let mut behind = healthy(2);
+        behind.sync_distance = Some(Slot::new(3));
+        assert_eq!(
+            ranking(vec![el_offline, optimistic, behind, healthy(3)]),
+            vec![3, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn synced_nodes_are_preferred() {
+        let mut degraded = healthy(2);
+        degraded.latency = Some(Duration::from_secs(10));
+        degraded.error_rate = 1.0;
+        assert_eq!(
+            degraded.score(),
+            40 * LATENCY_PENALTY + ERROR_RATE_INCREMENTS * ERROR_RATE_PENALTY
+        );
+        let mut not_synced = healthy(0);
+        not_synced.status = Err(CandidateError::NotSynced);
+        let mut offline = healthy(1);
+        offline.status = Err(CandidateError::Offline);
+        assert_eq!(ranking(vec![offline, not_synced, degraded]), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn error_rate_decays() {
+        let mut error_rate = ErrorRate::default();
+        assert_eq!(error_rate.rate(), 0.0);
+
+        error_rate.record_error();
+        error_rate.record_success();
+        assert_eq!(error_rate.rate(), 0.5);
+
+        error_rate.decay();
+        error_rate.record_success();
+        error_rate.record_success();
+        assert_eq!(error_rate.rate(), 0.5 / 3.0);
+    }
+}
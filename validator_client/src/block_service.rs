@@ -2,7 +2,7 @@ use crate::beacon_node_fallback::{Error as FallbackError, Errors};
 use crate::{
     beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced},
     determine_graffiti,
-    graffiti_file::GraffitiFile,
+    graffiti_file::{GraffitiFile, GraffitiSetting},
     OfflineOnFailure,
 };
 use crate::{
@@ -22,8 +22,8 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::{
-    BlindedBeaconBlock, BlockType, EthSpec, Graffiti, PublicKeyBytes, SignedBlindedBeaconBlock,
-    Slot,
+    graffiti::GraffitiTemplateContext, BlindedBeaconBlock, BlockType, EthSpec, Graffiti,
+    PublicKeyBytes, SignedBlindedBeaconBlock, Slot,
 };
 
 #[derive(Debug)]
@@ -549,13 +549,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             }
         };
 
-        let graffiti = determine_graffiti(
-            &validator_pubkey,
-            log,
-            self.graffiti_file.clone(),
-            self.validator_store.graffiti(&validator_pubkey),
-            self.graffiti,
-        );
+        let graffiti = self.get_graffiti(slot, &validator_pubkey, log).await;
 
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
@@ -656,13 +650,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             }
         };
 
-        let graffiti = determine_graffiti(
-            &validator_pubkey,
-            log,
-            self.graffiti_file.clone(),
-            self.validator_store.graffiti(&validator_pubkey),
-            self.graffiti,
-        );
+        let graffiti = self.get_graffiti(slot, &validator_pubkey, log).await;
 
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
@@ -847,6 +835,69 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok::<_, BlockError>(unsigned_block)
     }
 
+    /// Returns the graffiti for a block proposed by `validator_pubkey` at `slot`, rendering it
+    /// if it is a template.
+    async fn get_graffiti(
+        &self,
+        slot: Slot,
+        validator_pubkey: &PublicKeyBytes,
+        log: &Logger,
+    ) -> Option<Graffiti> {
+        let graffiti = determine_graffiti(
+            validator_pubkey,
+            log,
+            self.graffiti_file.clone(),
+            self.validator_store.graffiti(validator_pubkey),
+            self.graffiti,
+        )?;
+
+        let el_client = match &graffiti {
+            GraffitiSetting::Template(template) if template.uses_el_client() => {
+                self.get_el_client_code(log).await
+            }
+            _ => None,
+        };
+        let context = GraffitiTemplateContext {
+            validator_index: self.validator_store.validator_index(validator_pubkey),
+            epoch: slot.epoch(E::slots_per_epoch()),
+            el_client: el_client.as_deref(),
+            cl_version: cl_version(),
+        };
+        Some(graffiti.render(&context))
+    }
+
+    /// Returns the client code of the execution engine connected to the first responsive beacon
+    /// node, if it reports exactly one.
+    async fn get_el_client_code(&self, log: &Logger) -> Option<String> {
+        let result = self
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::No,
+                |beacon_node| async move {
+                    beacon_node.get_lighthouse_execution_engine_versions().await
+                },
+            )
+            .await;
+
+        match result {
+            Ok(response) => match response.data.as_slice() {
+                [version] => Some(version.code.clone()),
+                // No versions indicates that the EL doesn't support `engine_getClientVersionV1`,
+                // whilst several indicate an EL multiplexer.
+                _ => None,
+            },
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to determine execution client for graffiti";
+                    "error" => %e,
+                );
+                None
+            }
+        }
+    }
+
     /// Returns the builder boost factor of the given public key.
     /// The priority order for fetching this value is:
     ///
@@ -878,6 +929,15 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
     }
 }
 
+/// Returns the version of this client used by graffiti templates, e.g. `v5.3.0`.
+fn cl_version() -> &'static str {
+    let version = lighthouse_version::VERSION
+        .strip_prefix("Lighthouse/")
+        .unwrap_or(lighthouse_version::VERSION);
+    // Remove the commit hash.
+    version.split('-').next().unwrap_or(version)
+}
+
 pub enum UnsignedBlock<E: EthSpec> {
     Full(FullBlockContents<E>),
     Blinded(BlindedBeaconBlock<E>),
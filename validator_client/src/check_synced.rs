@@ -1,4 +1,5 @@
 use crate::beacon_node_fallback::CandidateError;
+use eth2::types::SyncingData;
 use eth2::BeaconNodeHttpClient;
 use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
@@ -16,11 +17,13 @@ const SYNC_TOLERANCE: u64 = 4;
 ///
 ///  The second condition means the even if the beacon node thinks that it's syncing, we'll still
 ///  try to use it if it's close enough to the head.
+///
+///  The sync status reported by the beacon node is also returned, if it was reachable.
 pub async fn check_synced<T: SlotClock>(
     beacon_node: &BeaconNodeHttpClient,
     slot_clock: &T,
     log_opt: Option<&Logger>,
-) -> Result<(), CandidateError> {
+) -> (Result<(), CandidateError>, Option<SyncingData>) {
     let resp = match beacon_node.get_node_syncing().await {
         Ok(resp) => resp,
         Err(e) => {
@@ -32,7 +35,7 @@ pub async fn check_synced<T: SlotClock>(
                 )
             }
 
-            return Err(CandidateError::Offline);
+            return (Err(CandidateError::Offline), None);
         }
     };
 
@@ -74,9 +77,11 @@ pub async fn check_synced<T: SlotClock>(
         }
     }
 
-    if is_synced {
+    let status = if is_synced {
         Ok(())
     } else {
         Err(CandidateError::NotSynced)
-    }
+    };
+
+    (status, Some(resp.data))
 }
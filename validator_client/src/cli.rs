@@ -65,6 +65,34 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("attestation-consensus-quorum")
+                .long("attestation-consensus-quorum")
+                .value_name("COUNT")
+                .help("Download attestation data from all beacon nodes and only sign the head and \
+                       target agreed on by at least this many of them. Disabled by default, in \
+                       which case attestation data is downloaded from the first available beacon \
+                       node.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("attestation-consensus-fallback")
+                .long("attestation-consensus-fallback")
+                .value_name("ACTION")
+                .help("What to do when the beacon nodes do not reach the \
+                       --attestation-consensus-quorum. Possible values are: majority, to sign \
+                       the attestation data returned by the most beacon nodes, and skip, to not \
+                       attest.")
+                .requires("attestation-consensus-quorum")
+                .default_value_if(
+                    "attestation-consensus-quorum",
+                    ArgPredicate::IsPresent,
+                    "majority",
+                )
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("validators-dir")
                 .long("validators-dir")
@@ -324,6 +352,89 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        /* Remote signer HTTP server related arguments */
+        .arg(
+            Arg::new("remote-signer-server")
+                .long("remote-signer-server")
+                .help("Enable a HTTP server which implements the Web3Signer signing API, so that \
+                    other validator clients can use this one as a remote signer. Only validators \
+                    with a local keystore are served, and every message is checked against the \
+                    slashing protection database of this validator client. Requests must be \
+                    authenticated with the API token of this validator client. The server is not \
+                    encrypted and should only be exposed to trusted hosts. Disabled by default.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("remote-signer-server-address")
+                .long("remote-signer-server-address")
+                .requires("remote-signer-server")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the remote signer HTTP server. The server is \
+                    not encrypted, and therefore it is unsafe to publish on a public network.")
+                .default_value_if("remote-signer-server", ArgPredicate::IsPresent, "127.0.0.1")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("remote-signer-server-port")
+                .long("remote-signer-server-port")
+                .requires("remote-signer-server")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the remote signer HTTP server.")
+                .default_value_if("remote-signer-server", ArgPredicate::IsPresent, "5066")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("remote-signer-server-allow-unprotected")
+                .long("remote-signer-server-allow-unprotected")
+                .requires("remote-signer-server")
+                .help("Allow the remote signer HTTP server to sign voluntary exits, RANDAO \
+                    reveals and validator registrations. These messages are not covered by \
+                    slashing protection, so they are refused by default. Remote validator \
+                    clients can't propose blocks unless this flag is set.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        /*
+         * Slashing protection replication
+         */
+        .arg(
+            Arg::new("slashing-protection-standby")
+                .long("slashing-protection-standby")
+                .help("Start as a slashing protection standby, which receives slashing protection \
+                    data from a primary validator client and refuses to sign slashable messages \
+                    until it is promoted via the HTTP API. Requires the HTTP API to be enabled.")
+                .action(ArgAction::SetTrue)
+                .requires("http")
+                .conflicts_with("slashing-protection-replication-url")
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("slashing-protection-replication-url")
+                .long("slashing-protection-replication-url")
+                .value_name("URL")
+                .help("Replicate the slashing protection database to the standby validator client \
+                    whose HTTP API is at this URL. The standby must be started with \
+                    --slashing-protection-standby.")
+                .requires("slashing-protection-replication-token")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("slashing-protection-replication-token")
+                .long("slashing-protection-replication-token")
+                .value_name("PATH")
+                .help("Path to the API token of the standby validator client, which is used to \
+                    authenticate slashing protection replication requests.")
+                .requires("slashing-protection-replication-url")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Explorer metrics
          */
@@ -367,6 +478,21 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("doppelganger-lookback-epochs")
+                .long("doppelganger-lookback-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs prior to start-up in which doppelganger protection \
+                    checks for messages from the validators managed by this client. Messages \
+                    recorded in the slashing protection database of this client are ignored. \
+                    Each epoch found to be free of doppelgangers counts towards the detection \
+                    period, allowing a restarted validator client to resume its duties sooner. \
+                    Checking epochs prior to the previous epoch requires one request to the \
+                    beacon node per epoch. At most 16 epochs may be checked.")
+                .default_value("0")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-proposals")
                 .long("builder-proposals")
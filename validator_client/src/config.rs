@@ -1,6 +1,8 @@
+use crate::attestation_consensus::{AttestationConsensus, DisagreementAction};
 use crate::beacon_node_fallback::ApiTopic;
+use crate::doppelganger_service::MAX_LOOKBACK_EPOCHS;
 use crate::graffiti_file::GraffitiFile;
-use crate::{http_api, http_metrics};
+use crate::{http_api, http_metrics, remote_signer_server, slashing_protection_replication};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
 use directory::{
@@ -52,11 +54,21 @@ pub struct Config {
     pub http_api: http_api::Config,
     /// Configuration for the HTTP REST API.
     pub http_metrics: http_metrics::Config,
+    /// Configuration for the Web3Signer-compatible remote signer server.
+    pub remote_signer_server: remote_signer_server::Config,
+    /// Configuration for replicating the slashing protection database to a standby.
+    pub slashing_protection_replication: Option<slashing_protection_replication::Config>,
+    /// If true, start as a slashing protection standby which doesn't sign slashable messages
+    /// until it is promoted.
+    pub slashing_protection_standby: bool,
     /// Configuration for sending metrics to a remote explorer endpoint.
     pub monitoring_api: Option<monitoring_api::Config>,
     /// If true, enable functionality that monitors the network for attestations or proposals from
     /// any of the validators managed by this client before starting up.
     pub enable_doppelganger_protection: bool,
+    /// The number of epochs prior to start-up which doppelganger protection checks for messages
+    /// from the validators managed by this client.
+    pub doppelganger_lookback_epochs: u64,
     /// If true, then we publish validator specific metrics (e.g next attestation duty slot)
     /// for all our managed validators.
     /// Note: We publish validator specific metrics for low validator counts without this flag
@@ -73,6 +85,8 @@ pub struct Config {
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
     /// Enables broadcasting of various requests (by topic) to all beacon nodes.
     pub broadcast_topics: Vec<ApiTopic>,
+    /// Requires beacon nodes to agree on attestation data before it is signed.
+    pub attestation_consensus: Option<AttestationConsensus>,
     /// Enables a service which attempts to measure latency between the VC and BNs.
     pub enable_latency_measurement_service: bool,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
@@ -119,14 +133,19 @@ impl Default for Config {
             fee_recipient: None,
             http_api: <_>::default(),
             http_metrics: <_>::default(),
+            remote_signer_server: <_>::default(),
+            slashing_protection_replication: None,
+            slashing_protection_standby: false,
             monitoring_api: None,
             enable_doppelganger_protection: false,
+            doppelganger_lookback_epochs: 0,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
             broadcast_topics: vec![ApiTopic::Subscriptions],
+            attestation_consensus: None,
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
             enable_web3signer_slashing_protection: true,
@@ -261,6 +280,29 @@ impl Config {
                 .collect::<Result<_, _>>()?;
         }
 
+        if let Some(quorum) = parse_optional::<usize>(cli_args, "attestation-consensus-quorum")? {
+            if quorum == 0 || quorum > config.beacon_nodes.len() {
+                return Err(format!(
+                    "attestation-consensus-quorum must be between 1 and the number of beacon \
+                     nodes ({})",
+                    config.beacon_nodes.len()
+                ));
+            }
+            let on_disagreement = cli_args
+                .get_one::<String>("attestation-consensus-fallback")
+                .map(|action| {
+                    action
+                        .parse::<DisagreementAction>()
+                        .map_err(|_| format!("Unknown attestation-consensus-fallback: {action}"))
+                })
+                .transpose()?
+                .unwrap_or_default();
+            config.attestation_consensus = Some(AttestationConsensus {
+                quorum,
+                on_disagreement,
+            });
+        }
+
         /*
          * Web3 signer
          */
@@ -358,6 +400,53 @@ impl Config {
             config.http_metrics.allocator_metrics_enabled = false;
         }
 
+        /*
+         * Remote signer HTTP server
+         */
+
+        if cli_args.get_flag("remote-signer-server") {
+            config.remote_signer_server.enabled = true;
+        }
+
+        if let Some(address) = cli_args.get_one::<String>("remote-signer-server-address") {
+            config.remote_signer_server.listen_addr = address
+                .parse::<IpAddr>()
+                .map_err(|_| "remote-signer-server-address is not a valid IP address.")?;
+        }
+
+        if let Some(port) = cli_args.get_one::<String>("remote-signer-server-port") {
+            config.remote_signer_server.listen_port = port
+                .parse::<u16>()
+                .map_err(|_| "remote-signer-server-port is not a valid u16.")?;
+        }
+
+        if cli_args.get_flag("remote-signer-server-allow-unprotected") {
+            config.remote_signer_server.allow_unprotected_messages = true;
+        }
+
+        /*
+         * Slashing protection replication
+         */
+
+        if cli_args.get_flag("slashing-protection-standby") {
+            config.slashing_protection_standby = true;
+        }
+
+        if let Some(standby_url) =
+            parse_optional::<String>(cli_args, "slashing-protection-replication-url")?
+        {
+            config.slashing_protection_replication =
+                Some(slashing_protection_replication::Config {
+                    standby_url: SensitiveUrl::parse(&standby_url).map_err(|e| {
+                        format!("Invalid slashing-protection-replication-url: {:?}", e)
+                    })?,
+                    standby_api_token_path: parse_required(
+                        cli_args,
+                        "slashing-protection-replication-token",
+                    )?,
+                });
+        }
+
         /*
          * Explorer metrics
          */
@@ -376,6 +465,16 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        if let Some(lookback_epochs) = parse_optional(cli_args, "doppelganger-lookback-epochs")? {
+            if lookback_epochs > MAX_LOOKBACK_EPOCHS {
+                return Err(format!(
+                    "doppelganger-lookback-epochs cannot exceed {}",
+                    MAX_LOOKBACK_EPOCHS
+                ));
+            }
+            config.doppelganger_lookback_epochs = lookback_epochs;
+        }
+
         if cli_args.get_flag("builder-proposals") {
             config.builder_proposals = true;
         }
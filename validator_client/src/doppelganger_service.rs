@@ -15,6 +15,17 @@
 //! prevents a stale-mate where all validators will cease to function for a few epochs and then all
 //! start at the same time.
 //!
+//! ## Look-back
+//!
+//! Optionally, the liveness of a validator in the epochs *prior* to its registration is also
+//! checked. Liveness in these epochs is ignored if the slashing protection database shows that the
+//! validator signed a message in that epoch, since that is most likely a previous run of this
+//! validator client. Each look-back epoch without a doppelganger counts towards the remaining
+//! epochs, allowing a restarted validator client to resume signing sooner.
+//!
+//! Validators may also opt in or out of doppelganger protection individually using the
+//! `doppelganger` field of their validator definition.
+//!
 //! ## Caveat
 //!
 //! Presently doppelganger protection will never advance if the call at the last slot of each epoch
@@ -37,6 +48,7 @@ use eth2::types::LivenessResponseData;
 use parking_lot::RwLock;
 use slog::{crit, error, info, Logger};
 use slot_clock::SlotClock;
+use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
@@ -115,6 +127,11 @@ struct LivenessResponses {
 /// validators on the network.
 pub const DEFAULT_REMAINING_DETECTION_EPOCHS: u64 = 1;
 
+/// The maximum number of epochs prior to registration which may be checked for doppelgangers.
+///
+/// Each epoch requires the BN to load and transition a historic state.
+pub const MAX_LOOKBACK_EPOCHS: u64 = 16;
+
 /// Store the per-validator status of doppelganger checking.
 #[derive(Debug, PartialEq)]
 pub struct DoppelgangerState {
@@ -131,6 +148,35 @@ pub struct DoppelgangerState {
     /// The number of epochs that must be checked before this validator is considered
     /// doppelganger-free.
     remaining_epochs: u64,
+    /// The epochs prior to registration which are yet to be checked, if look-back is enabled.
+    lookback: Option<Lookback>,
+}
+
+/// A range of epochs prior to the registration of a validator which are checked for doppelgangers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Lookback {
+    /// The first epoch to check.
+    start_epoch: Epoch,
+    /// The epoch in which the validator was registered.
+    ///
+    /// This epoch is checked for doppelgangers but, being incomplete, does not count towards the
+    /// remaining epochs.
+    end_epoch: Epoch,
+}
+
+/// The progress of doppelganger detection for a single validator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetectionProgress {
+    /// The validator is waiting for `remaining_epochs` more epochs to be checked and is expected to
+    /// begin signing in `signing_epoch`.
+    Detecting {
+        remaining_epochs: u64,
+        signing_epoch: Epoch,
+    },
+    /// The validator has completed doppelganger detection.
+    Complete,
+    /// A doppelganger has been detected, the validator will never sign.
+    DoppelgangerDetected,
 }
 
 impl DoppelgangerState {
@@ -139,6 +185,34 @@ impl DoppelgangerState {
         self.remaining_epochs > 0
     }
 
+    /// Returns the progress of doppelganger detection, assuming that no doppelganger is found.
+    fn progress(&self) -> DetectionProgress {
+        if self.remaining_epochs == u64::MAX {
+            return DetectionProgress::DoppelgangerDetected;
+        } else if !self.requires_further_checks() {
+            return DetectionProgress::Complete;
+        }
+
+        // Detection of an epoch completes in the last slot of the following epoch, so the
+        // validator is able to perform all of its duties from the epoch after that.
+        let signing_epoch = match self.lookback {
+            Some(lookback) if self.remaining_epochs <= lookback.satisfied_epochs() => {
+                lookback.end_epoch.saturating_add(1_u64)
+            }
+            Some(lookback) => self.next_check_epoch.saturating_add(
+                (self.remaining_epochs - lookback.satisfied_epochs()).saturating_add(1),
+            ),
+            None => self
+                .next_check_epoch
+                .saturating_add(self.remaining_epochs.saturating_add(1)),
+        };
+
+        DetectionProgress::Detecting {
+            remaining_epochs: self.remaining_epochs,
+            signing_epoch,
+        }
+    }
+
     /// Updates the `DoppelgangerState` to consider the given `Epoch`'s doppelganger checks
     /// completed.
     fn complete_detection_in_epoch(&mut self, epoch: Epoch) {
@@ -151,6 +225,20 @@ impl DoppelgangerState {
     }
 }
 
+impl Lookback {
+    /// The number of epochs which count towards the remaining epochs once checked.
+    fn satisfied_epochs(&self) -> u64 {
+        self.end_epoch
+            .as_u64()
+            .saturating_sub(self.start_epoch.as_u64())
+    }
+
+    /// Returns `true` if `epoch` is checked by this look-back.
+    fn contains(&self, epoch: Epoch) -> bool {
+        self.start_epoch <= epoch && epoch <= self.end_epoch
+    }
+}
+
 /// Perform two requests to the BN to obtain the liveness data for `validator_indices`. One
 /// request will pertain to the `current_epoch`, the other to the `previous_epoch`.
 ///
@@ -268,8 +356,80 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
     }
 }
 
+/// Request the liveness data for `validator_indices` in `epoch`, which may be prior to the previous
+/// epoch.
+///
+/// The liveness endpoint of the BN only serves the previous, current and next epochs. The liveness
+/// in older epochs is approximated by the inclusion of timely attestations.
+async fn beacon_node_lookback_liveness<T: 'static + SlotClock, E: EthSpec>(
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    current_epoch: Epoch,
+    epoch: Epoch,
+    validator_indices: Vec<u64>,
+) -> Result<Vec<LivenessResponseData>, String> {
+    if epoch.saturating_add(1_u64) >= current_epoch {
+        return beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async {
+                    beacon_node
+                        .post_validator_liveness_epoch(epoch, &validator_indices)
+                        .await
+                        .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
+                        .map(|result| {
+                            result
+                                .data
+                                .into_iter()
+                                .map(|response| LivenessResponseData {
+                                    index: response.index,
+                                    epoch,
+                                    is_live: response.is_live,
+                                })
+                                .collect()
+                        })
+                },
+            )
+            .await
+            .map_err(|e| e.to_string());
+    }
+
+    // The inclusion data of the following epoch describes the attestations of `epoch` as those of
+    // the previous epoch.
+    beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async {
+                beacon_node
+                    .post_lighthouse_validator_inclusion(
+                        epoch.saturating_add(1_u64),
+                        &validator_indices,
+                    )
+                    .await
+                    .map_err(|e| format!("Failed query for validator inclusion: {:?}", e))
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+        .map(|result| {
+            result
+                .data
+                .into_iter()
+                .map(|response| LivenessResponseData {
+                    index: response.index,
+                    epoch,
+                    is_live: response.data.is_previous_epoch_target_attester
+                        || response.data.is_previous_epoch_head_attester,
+                })
+                .collect()
+        })
+}
+
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
+    /// The number of epochs prior to registration which are checked for doppelgangers.
+    lookback_epochs: u64,
     log: Logger,
 }
 
@@ -277,10 +437,18 @@ impl DoppelgangerService {
     pub fn new(log: Logger) -> Self {
         Self {
             doppelganger_states: <_>::default(),
+            lookback_epochs: 0,
             log,
         }
     }
 
+    /// Check the liveness of validators in the `lookback_epochs` epochs prior to their
+    /// registration, up to `MAX_LOOKBACK_EPOCHS`.
+    pub fn with_lookback_epochs(mut self, lookback_epochs: u64) -> Self {
+        self.lookback_epochs = cmp::min(lookback_epochs, MAX_LOOKBACK_EPOCHS);
+        self
+    }
+
     /// Starts a reoccurring future which will try to keep the doppelganger service updated each
     /// slot.
     pub fn start_update_service<E: EthSpec, T: 'static + SlotClock>(
@@ -291,15 +459,27 @@ impl DoppelgangerService {
         slot_clock: T,
     ) -> Result<(), String> {
         // Define the `get_index` function as one that uses the validator store.
-        let get_index = move |pubkey| validator_store.validator_index(&pubkey);
+        let store = validator_store.clone();
+        let get_index = move |pubkey| store.validator_index(&pubkey);
+
+        // Define the `has_signed_in_epoch` function as one that uses the slashing protection
+        // database.
+        let has_signed_in_epoch =
+            move |pubkey, epoch| validator_store.has_signed_in_epoch(&pubkey, epoch);
 
         // Define the `get_liveness` function as one that queries the beacon node API.
         let log = service.log.clone();
+        let nodes = beacon_nodes.clone();
         let get_liveness = move |current_epoch, validator_indices| {
-            beacon_node_liveness(
+            beacon_node_liveness(nodes.clone(), log.clone(), current_epoch, validator_indices)
+        };
+
+        // Define the `get_lookback_liveness` function as one that queries the beacon node API.
+        let get_lookback_liveness = move |current_epoch, epoch, validator_indices| {
+            beacon_node_lookback_liveness(
                 beacon_nodes.clone(),
-                log.clone(),
                 current_epoch,
+                epoch,
                 validator_indices,
             )
         };
@@ -342,6 +522,23 @@ impl DoppelgangerService {
                     }
 
                     if let Some(slot) = slot_clock.now() {
+                        if let Err(e) = service
+                            .detect_lookback_doppelgangers::<E, _, _, _, _, _>(
+                                slot,
+                                &get_index,
+                                &get_lookback_liveness,
+                                &has_signed_in_epoch,
+                                &mut shutdown_func,
+                            )
+                            .await
+                        {
+                            error!(
+                                service.log,
+                                "Error during doppelganger look-back";
+                                "error" => ?e
+                            );
+                        }
+
                         if let Err(e) = service
                             .detect_doppelgangers::<E, _, _, _, _>(
                                 slot,
@@ -388,14 +585,24 @@ impl DoppelgangerService {
             })
     }
 
+    /// Returns the progress of `validator` in the doppelganger protection process, or `None` if it
+    /// is unknown to the doppelganger service.
+    pub fn detection_progress(&self, validator: &PublicKeyBytes) -> Option<DetectionProgress> {
+        self.doppelganger_states
+            .read()
+            .get(validator)
+            .map(DoppelgangerState::progress)
+    }
+
     /// Register a new validator with the doppelganger service.
     ///
-    /// Validators added during the genesis epoch will not have doppelganger protection applied to
-    /// them.
+    /// Validators added during the genesis epoch, or with `protection_enabled == false`, will not
+    /// have doppelganger protection applied to them.
     pub fn register_new_validator<E: EthSpec, T: SlotClock>(
         &self,
         validator: PublicKeyBytes,
         slot_clock: &T,
+        protection_enabled: bool,
     ) -> Result<(), String> {
         let current_epoch = slot_clock
             // If registering before genesis, use the genesis slot.
@@ -404,7 +611,10 @@ impl DoppelgangerService {
             .epoch(E::slots_per_epoch());
         let genesis_epoch = slot_clock.genesis_slot().epoch(E::slots_per_epoch());
 
-        let remaining_epochs = if current_epoch <= genesis_epoch {
+        let remaining_epochs = if !protection_enabled {
+            // The validator has opted out of doppelganger protection.
+            0
+        } else if current_epoch <= genesis_epoch {
             // Disable doppelganger protection when the validator was initialized before genesis.
             //
             // Without this, all validators would simply miss the first
@@ -418,9 +628,18 @@ impl DoppelgangerService {
             DEFAULT_REMAINING_DETECTION_EPOCHS
         };
 
+        let lookback = (remaining_epochs > 0 && self.lookback_epochs > 0).then(|| Lookback {
+            start_epoch: cmp::max(
+                current_epoch.saturating_sub(self.lookback_epochs),
+                genesis_epoch,
+            ),
+            end_epoch: current_epoch,
+        });
+
         let state = DoppelgangerState {
             next_check_epoch: current_epoch.saturating_add(1_u64),
             remaining_epochs,
+            lookback,
         };
 
         self.doppelganger_states.write().insert(validator, state);
@@ -474,6 +693,169 @@ impl DoppelgangerService {
         )
     }
 
+    /// Contact the beacon node and try to detect if there are any doppelgangers in the epochs prior
+    /// to the registration of each validator, updating the state of `self`.
+    ///
+    /// The look-back of a validator is performed once in the last slot of its registration epoch,
+    /// or later. If any request fails, the look-back is attempted again in the next slot.
+    ///
+    /// ## Notes
+    ///
+    /// Liveness in an epoch in which the validator has signed a message according to
+    /// `has_signed_in_epoch` is ignored, since it is assumed to belong to a previous run of this
+    /// validator client.
+    async fn detect_lookback_doppelgangers<E, I, L, F, A, S>(
+        &self,
+        request_slot: Slot,
+        get_index: &I,
+        get_liveness: &L,
+        has_signed_in_epoch: &A,
+        shutdown_func: &mut S,
+    ) -> Result<(), String>
+    where
+        E: EthSpec,
+        I: Fn(PublicKeyBytes) -> Option<u64>,
+        L: Fn(Epoch, Epoch, Vec<u64>) -> F,
+        F: Future<Output = Result<Vec<LivenessResponseData>, String>>,
+        A: Fn(PublicKeyBytes, Epoch) -> Result<bool, String>,
+        S: FnMut(),
+    {
+        let request_epoch = request_slot.epoch(E::slots_per_epoch());
+
+        // Wait until the end of the registration epoch, by which time any messages from a previous
+        // run of this validator client in that epoch should have been observed.
+        let lookbacks = self
+            .doppelganger_states
+            .read()
+            .iter()
+            .filter_map(|(pubkey, state)| {
+                let lookback = state.lookback?;
+                (state.requires_further_checks()
+                    && request_slot >= lookback.end_epoch.end_slot(E::slots_per_epoch()))
+                .then_some((*pubkey, lookback))
+            })
+            .collect::<Vec<_>>();
+
+        // Maps validator indices to pubkeys and look-backs.
+        //
+        // As in `compute_detection_indices_map`, avoid interleaving the `self.doppelganger_states`
+        // lock with the lock used by `get_index`.
+        let mut indices_map = HashMap::with_capacity(lookbacks.len());
+        for (pubkey, lookback) in lookbacks {
+            if let Some(index) = get_index(pubkey) {
+                indices_map.insert(index, (pubkey, lookback));
+            }
+        }
+
+        let (Some(start_epoch), Some(end_epoch)) = (
+            indices_map
+                .values()
+                .map(|(_, lookback)| lookback.start_epoch)
+                .min(),
+            indices_map
+                .values()
+                .map(|(_, lookback)| lookback.end_epoch)
+                .max(),
+        ) else {
+            // Nothing to do.
+            return Ok(());
+        };
+
+        let mut violators = HashSet::new();
+        for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).map(Epoch::new) {
+            let indices = indices_map
+                .iter()
+                .filter(|(_, (_, lookback))| lookback.contains(epoch))
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>();
+            if indices.is_empty() {
+                continue;
+            }
+
+            for response in get_liveness(request_epoch, epoch, indices).await? {
+                // Abort the entire routine if the server starts returning junk.
+                if response.epoch != epoch {
+                    return Err(format!(
+                        "beacon node returned epoch {}, expecting {}",
+                        response.epoch, epoch
+                    ));
+                }
+
+                if !response.is_live {
+                    continue;
+                }
+
+                let Some((pubkey, _)) = indices_map.get(&response.index) else {
+                    crit!(
+                        self.log,
+                        "Inconsistent indices map";
+                        "validator_index" => response.index,
+                    );
+                    // Skip this result if an inconsistency is detected.
+                    continue;
+                };
+
+                if !has_signed_in_epoch(*pubkey, epoch)? {
+                    violators.insert(response.index);
+                }
+            }
+        }
+
+        if !violators.is_empty() {
+            crit!(
+                self.log,
+                "Doppelganger(s) detected";
+                "msg" => "A doppelganger occurs when two different validator clients run the \
+                    same public key. This validator client detected another instance of a local \
+                    validator on the network prior to starting and is shutting down to prevent \
+                    potential slashable offences. Ensure that you are not running a duplicate or \
+                    overlapping validator client",
+                "doppelganger_indices" => ?violators
+            );
+
+            // As in `process_liveness_responses`, stop all validator activity even if the
+            // validator client fails to shut down.
+            for state in self.doppelganger_states.write().values_mut() {
+                state.remaining_epochs = u64::MAX;
+            }
+
+            shutdown_func();
+            return Ok(());
+        }
+
+        // Hold the lock on `self` for the rest of this function.
+        let mut doppelganger_states = self.doppelganger_states.write();
+        for (index, (pubkey, lookback)) in indices_map {
+            let Some(state) = doppelganger_states.get_mut(&pubkey) else {
+                continue;
+            };
+
+            state.remaining_epochs = state
+                .remaining_epochs
+                .saturating_sub(lookback.satisfied_epochs());
+            state.lookback = None;
+
+            info!(
+                self.log,
+                "Found no doppelganger prior to start";
+                "further_checks_remaining" => state.remaining_epochs,
+                "lookback_epochs" => lookback.satisfied_epochs(),
+                "validator_index" => index
+            );
+
+            if state.remaining_epochs == 0 {
+                info!(
+                    self.log,
+                    "Doppelganger detection complete";
+                    "msg" => "starting validator",
+                    "validator_index" => index
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get a map of `validator_index` -> `validator_pubkey` for all validators still requiring
     /// further doppelganger checks.
     ///
@@ -718,17 +1100,24 @@ mod test {
 
     struct TestBuilder {
         validator_count: usize,
+        lookback_epochs: u64,
     }
 
     impl Default for TestBuilder {
         fn default() -> Self {
             Self {
                 validator_count: DEFAULT_VALIDATORS,
+                lookback_epochs: 0,
             }
         }
     }
 
     impl TestBuilder {
+        fn lookback_epochs(mut self, lookback_epochs: u64) -> Self {
+            self.lookback_epochs = lookback_epochs;
+            self
+        }
+
         fn build(self) -> TestScenario {
             let mut rng = XorShiftRng::from_seed([42; 16]);
             let slot_clock = TestingSlotClock::new(Slot::new(0), GENESIS_TIME, SLOT_DURATION);
@@ -738,7 +1127,8 @@ mod test {
                 validators: (0..self.validator_count)
                     .map(|_| PublicKeyBytes::random_for_test(&mut rng))
                     .collect(),
-                doppelganger: DoppelgangerService::new(log),
+                doppelganger: DoppelgangerService::new(log)
+                    .with_lookback_epochs(self.lookback_epochs),
                 slot_clock,
             }
         }
@@ -797,7 +1187,7 @@ mod test {
                 .expect("index should exist");
 
             self.doppelganger
-                .register_new_validator::<E, _>(pubkey, &self.slot_clock)
+                .register_new_validator::<E, _>(pubkey, &self.slot_clock, true)
                 .unwrap();
             self.doppelganger
                 .doppelganger_states
@@ -934,6 +1324,7 @@ mod test {
                 .assert_all_states(&DoppelgangerState {
                     next_check_epoch: genesis_epoch() + 1,
                     remaining_epochs: 0,
+                    lookback: None,
                 });
         }
     }
@@ -951,6 +1342,7 @@ mod test {
                 .assert_all_states(&DoppelgangerState {
                     next_check_epoch: epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    lookback: None,
                 });
         }
     }
@@ -971,6 +1363,7 @@ mod test {
                 &DoppelgangerState {
                     next_check_epoch: epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    lookback: None,
                 },
             )
             // Ensure validator 2 was not registered.
@@ -1004,6 +1397,47 @@ mod test {
     }
 
     impl TestScenario {
+        pub fn simulate_detect_lookback_doppelgangers<L, F, A>(
+            self,
+            slot: Slot,
+            should_shutdown: ShouldShutdown,
+            get_liveness: L,
+            has_signed_in_epoch: A,
+        ) -> Self
+        where
+            L: Fn(Epoch, Epoch, Vec<u64>) -> F,
+            F: Future<Output = Result<Vec<LivenessResponseData>, String>>,
+            A: Fn(PublicKeyBytes, Epoch) -> Result<bool, String>,
+        {
+            // Create a simulated shutdown sender.
+            let mut did_shutdown = false;
+            let mut shutdown_func = || did_shutdown = true;
+
+            // Create a simulated validator store that can resolve pubkeys to indices.
+            let pubkey_to_index = self.pubkey_to_index_map();
+            let get_index = |pubkey| pubkey_to_index.get(&pubkey).copied();
+
+            block_on(
+                self.doppelganger
+                    .detect_lookback_doppelgangers::<E, _, _, _, _, _>(
+                        slot,
+                        &get_index,
+                        &get_liveness,
+                        &has_signed_in_epoch,
+                        &mut shutdown_func,
+                    ),
+            )
+            .expect("detection should not error");
+
+            match should_shutdown {
+                ShouldShutdown::Yes if !did_shutdown => panic!("vc failed to shutdown"),
+                ShouldShutdown::No if did_shutdown => panic!("vc shutdown when it shouldn't"),
+                _ => (),
+            }
+
+            self
+        }
+
         pub fn simulate_detect_doppelgangers<L, F>(
             self,
             slot: Slot,
@@ -1119,6 +1553,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: starting_epoch + 1,
                 remaining_epochs: u64::MAX,
+                lookback: None,
             });
     }
 
@@ -1181,6 +1616,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: None,
             });
     }
 
@@ -1223,18 +1659,21 @@ mod test {
                 DoppelgangerState {
                     next_check_epoch: initial_epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    lookback: None,
                 }
             } else if !is_satisfaction_slot {
                 DoppelgangerState {
                     next_check_epoch: epoch - 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS
                         .saturating_sub(epochs_since_start.saturating_sub(2)),
+                    lookback: None,
                 }
             } else {
                 DoppelgangerState {
                     next_check_epoch: epoch,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS
                         .saturating_sub(epochs_since_start.saturating_sub(1)),
+                    lookback: None,
                 }
             };
 
@@ -1252,6 +1691,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: activation_slot.epoch(E::slots_per_epoch()),
                 remaining_epochs: 0,
+                lookback: None,
             });
     }
 
@@ -1282,6 +1722,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: None,
             })
             // Simulate a check in the skipped forward slot
             .simulate_detect_doppelgangers(
@@ -1298,6 +1739,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: skipped_forward_epoch,
                 remaining_epochs: 0,
+                lookback: None,
             });
     }
 
@@ -1328,6 +1770,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: None,
             })
             // Simulate a check in the skipped forward slot
             .simulate_detect_doppelgangers(
@@ -1348,6 +1791,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: u64::MAX,
+                lookback: None,
             });
     }
 
@@ -1378,6 +1822,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: None,
             })
             // Simulate a check in the skipped backward slot
             .simulate_detect_doppelgangers(
@@ -1395,6 +1840,7 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: None,
             });
     }
 
@@ -1460,4 +1906,240 @@ mod test {
 
         scenario.assert_all_enabled();
     }
+
+    fn get_lookback_responses(
+        epoch: Epoch,
+        detection_indices: &[u64],
+        is_live: bool,
+    ) -> Result<Vec<LivenessResponseData>, String> {
+        Ok(detection_indices
+            .iter()
+            .map(|&index| LivenessResponseData {
+                index,
+                epoch,
+                is_live,
+            })
+            .collect())
+    }
+
+    #[test]
+    fn opted_out_after_genesis_epoch() {
+        let epoch = genesis_epoch() + 1;
+        let scenario = TestBuilder::default()
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()));
+
+        for pubkey in &scenario.validators {
+            scenario
+                .doppelganger
+                .register_new_validator::<E, _>(*pubkey, &scenario.slot_clock, false)
+                .unwrap();
+        }
+
+        scenario
+            .assert_all_enabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: 0,
+                lookback: None,
+            });
+    }
+
+    #[test]
+    fn lookback_epochs_capped() {
+        let epoch = genesis_epoch() + 42;
+
+        TestBuilder::default()
+            .lookback_epochs(MAX_LOOKBACK_EPOCHS + 1)
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()))
+            .register_all_in_doppelganger_protection_if_enabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: Some(Lookback {
+                    start_epoch: epoch - MAX_LOOKBACK_EPOCHS,
+                    end_epoch: epoch,
+                }),
+            });
+    }
+
+    #[test]
+    fn lookback_without_doppelgangers() {
+        let lookback_epochs = 2;
+        let epoch = genesis_epoch() + 42;
+        let start_slot = epoch.start_slot(E::slots_per_epoch());
+        let end_slot = epoch.end_slot(E::slots_per_epoch());
+        let lookback = Lookback {
+            start_epoch: epoch - lookback_epochs,
+            end_epoch: epoch,
+        };
+
+        let scenario = TestBuilder::default()
+            .lookback_epochs(lookback_epochs)
+            .build()
+            .set_slot(start_slot)
+            .register_all_in_doppelganger_protection_if_enabled()
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                lookback: Some(lookback),
+            });
+
+        assert_eq!(
+            scenario
+                .doppelganger
+                .detection_progress(&scenario.validators[0]),
+            Some(DetectionProgress::Detecting {
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                signing_epoch: epoch + 1,
+            })
+        );
+
+        let scenario = scenario
+            // The look-back is not performed before the end of the registration epoch.
+            .simulate_detect_lookback_doppelgangers(
+                start_slot,
+                ShouldShutdown::No,
+                |_, _, _| {
+                    panic!("the beacon node should not get a request before the end of the epoch");
+
+                    // The compiler needs this, otherwise it complains that this isn't a future.
+                    #[allow(unreachable_code)]
+                    future::ready(get_lookback_responses(epoch, &[], false))
+                },
+                |_, _| panic!("the slashing protection database should not be checked"),
+            )
+            .assert_all_disabled()
+            // Validators were live in every epoch, but only because they signed before the restart.
+            .simulate_detect_lookback_doppelgangers(
+                end_slot,
+                ShouldShutdown::No,
+                |current_epoch, lookback_epoch, detection_indices: Vec<_>| {
+                    assert_eq!(current_epoch, epoch);
+                    assert!(lookback.contains(lookback_epoch));
+                    check_detection_indices(&detection_indices);
+
+                    future::ready(get_lookback_responses(
+                        lookback_epoch,
+                        &detection_indices,
+                        true,
+                    ))
+                },
+                |_, _| Ok(true),
+            )
+            .assert_all_enabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: 0,
+                lookback: None,
+            });
+
+        assert_eq!(
+            scenario
+                .doppelganger
+                .detection_progress(&scenario.validators[0]),
+            Some(DetectionProgress::Complete)
+        );
+    }
+
+    #[test]
+    fn lookback_with_doppelganger() {
+        let lookback_epochs = 2;
+        let epoch = genesis_epoch() + 42;
+        let doppelganger_epoch = epoch - 1;
+
+        let scenario = TestBuilder::default()
+            .lookback_epochs(lookback_epochs)
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()))
+            .register_all_in_doppelganger_protection_if_enabled()
+            .simulate_detect_lookback_doppelgangers(
+                epoch.end_slot(E::slots_per_epoch()),
+                ShouldShutdown::Yes,
+                |_, lookback_epoch, detection_indices: Vec<_>| {
+                    future::ready(get_lookback_responses(
+                        lookback_epoch,
+                        &detection_indices,
+                        lookback_epoch == doppelganger_epoch,
+                    ))
+                },
+                // This validator client didn't sign in the epoch in which validators were live.
+                |_, signed_epoch| Ok(signed_epoch != doppelganger_epoch),
+            )
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: u64::MAX,
+                lookback: Some(Lookback {
+                    start_epoch: epoch - lookback_epochs,
+                    end_epoch: epoch,
+                }),
+            });
+
+        assert_eq!(
+            scenario
+                .doppelganger
+                .detection_progress(&scenario.validators[0]),
+            Some(DetectionProgress::DoppelgangerDetected)
+        );
+    }
+
+    #[test]
+    fn lookback_shorter_than_remaining_epochs() {
+        let epoch = genesis_epoch() + 42;
+
+        let scenario = TestBuilder::default()
+            .lookback_epochs(1)
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()));
+
+        // Require an additional epoch beyond the look-back.
+        let pubkey = scenario.validators[0];
+        scenario
+            .doppelganger
+            .register_new_validator::<E, _>(pubkey, &scenario.slot_clock, true)
+            .unwrap();
+        scenario
+            .doppelganger
+            .doppelganger_states
+            .write()
+            .get_mut(&pubkey)
+            .unwrap()
+            .remaining_epochs = 2;
+
+        assert_eq!(
+            scenario.doppelganger.detection_progress(&pubkey),
+            Some(DetectionProgress::Detecting {
+                remaining_epochs: 2,
+                signing_epoch: epoch + 3,
+            })
+        );
+
+        let scenario = scenario.simulate_detect_lookback_doppelgangers(
+            epoch.end_slot(E::slots_per_epoch()),
+            ShouldShutdown::No,
+            |_, lookback_epoch, detection_indices: Vec<_>| {
+                future::ready(get_lookback_responses(
+                    lookback_epoch,
+                    &detection_indices,
+                    false,
+                ))
+            },
+            |_, _| Ok(false),
+        );
+
+        assert_eq!(
+            scenario.doppelganger.validator_status(pubkey),
+            DoppelgangerStatus::SigningDisabled(pubkey)
+        );
+        assert_eq!(
+            scenario.doppelganger.detection_progress(&pubkey),
+            Some(DetectionProgress::Detecting {
+                remaining_epochs: 1,
+                signing_epoch: epoch + 3,
+            })
+        );
+    }
 }
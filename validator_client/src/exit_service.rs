@@ -0,0 +1,300 @@
+//! Publishes the voluntary exits which have been scheduled in the validator definitions.
+//!
+//! At the start of each epoch the state of every validator with a pending exit is fetched from
+//! the beacon node. If the exit condition is met, a voluntary exit is signed and broadcast to all
+//! beacon nodes. The exit is broadcast again each epoch until it has been included on chain, at
+//! which point the pending exit is removed from the validator definitions.
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::validator_store::ValidatorStore;
+use crate::OfflineOnFailure;
+use account_utils::validator_definitions::ExitCondition;
+use environment::RuntimeContext;
+use eth2::types::{StateId, ValidatorData, ValidatorId};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{ChainSpec, Epoch, EthSpec, VoluntaryExit};
+
+/// Starts a service that publishes a voluntary exit for each validator with a pending exit once
+/// its condition is met.
+pub fn start_exit_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+) {
+    let log = context.log().clone();
+    let spec = context.eth2_config.spec.clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                // If we can't read the slot clock, just wait one slot. The exit will be
+                // published at the next check.
+                .unwrap_or_else(|| slot_clock.slot_duration());
+
+            sleep(sleep_time).await;
+
+            if let Err(e) =
+                publish_pending_exits(&validator_store, &beacon_nodes, &slot_clock, &spec, &log)
+                    .await
+            {
+                error!(
+                    log,
+                    "Unable to process pending exits";
+                    "error" => e,
+                );
+            }
+        }
+    };
+
+    context.executor.spawn(future, "exit_service");
+}
+
+async fn publish_pending_exits<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    slot_clock: &T,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<(), String> {
+    let pending_exits = validator_store
+        .initialized_validators()
+        .read()
+        .pending_exits();
+    if pending_exits.is_empty() {
+        return Ok(());
+    }
+
+    let current_epoch = slot_clock
+        .now()
+        .ok_or("Unable to read slot clock")?
+        .epoch(E::slots_per_epoch());
+
+    let validator_ids = pending_exits
+        .iter()
+        .map(|(pubkey, _)| ValidatorId::PublicKey(*pubkey))
+        .collect::<Vec<_>>();
+    let validator_ids = &validator_ids;
+    let validators = beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                beacon_node
+                    .get_beacon_states_validators(
+                        StateId::Head,
+                        Some(validator_ids.as_slice()),
+                        None,
+                    )
+                    .await
+            },
+        )
+        .await
+        .map_err(|e| format!("Unable to fetch validators: {}", e))?
+        .ok_or("Unable to fetch validators: head state not found")?
+        .data;
+
+    for (pubkey, condition) in pending_exits {
+        let Some(validator) = validators.iter().find(|v| v.validator.pubkey == pubkey) else {
+            debug!(
+                log,
+                "Validator with pending exit is unknown to the beacon chain";
+                "pubkey" => ?pubkey,
+            );
+            continue;
+        };
+
+        if validator.validator.exit_epoch != spec.far_future_epoch {
+            info!(
+                log,
+                "Validator has exited, removing pending exit";
+                "pubkey" => ?pubkey,
+                "exit_epoch" => validator.validator.exit_epoch,
+            );
+            let voting_public_key = pubkey
+                .decompress()
+                .map_err(|e| format!("Invalid pubkey {:?}: {:?}", pubkey, e))?;
+            validator_store
+                .initialized_validators()
+                .write()
+                .delete_pending_exit(&voting_public_key)
+                .map_err(|e| format!("Unable to remove pending exit: {:?}", e))?;
+            continue;
+        }
+
+        if !exit_condition_met(&condition, validator, current_epoch, spec) {
+            continue;
+        }
+
+        let voluntary_exit = VoluntaryExit {
+            epoch: current_epoch,
+            validator_index: validator.index,
+        };
+        let signed_voluntary_exit = match validator_store
+            .sign_voluntary_exit(pubkey, voluntary_exit)
+            .await
+        {
+            Ok(signed_voluntary_exit) => signed_voluntary_exit,
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to sign voluntary exit";
+                    "pubkey" => ?pubkey,
+                    "error" => ?e,
+                );
+                continue;
+            }
+        };
+
+        let signed_voluntary_exit = &signed_voluntary_exit;
+        match beacon_nodes
+            .broadcast(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .post_beacon_pool_voluntary_exits(signed_voluntary_exit)
+                        .await
+                },
+            )
+            .await
+        {
+            Ok(()) => info!(
+                log,
+                "Published voluntary exit";
+                "pubkey" => ?pubkey,
+                "validator_index" => validator.index,
+                "condition" => ?condition,
+            ),
+            Err(e) => warn!(
+                log,
+                "Unable to publish voluntary exit";
+                "msg" => "the exit will be published again next epoch",
+                "pubkey" => ?pubkey,
+                "error" => %e,
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the validator should exit in `current_epoch`.
+fn exit_condition_met(
+    condition: &ExitCondition,
+    validator: &ValidatorData,
+    current_epoch: Epoch,
+    spec: &ChainSpec,
+) -> bool {
+    match condition {
+        ExitCondition::AtEpoch { epoch } => current_epoch >= *epoch,
+        ExitCondition::BalanceBelow { balance_gwei } => validator.balance < *balance_gwei,
+        ExitCondition::Eth1WithdrawalCredentials => {
+            validator.validator.has_eth1_withdrawal_credential(spec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2::types::ValidatorStatus;
+    use types::{Hash256, MainnetEthSpec, PublicKeyBytes, Validator};
+
+    fn validator_data(balance: u64, withdrawal_credentials: Hash256) -> ValidatorData {
+        ValidatorData {
+            index: 0,
+            balance,
+            status: ValidatorStatus::ActiveOngoing,
+            validator: Validator {
+                pubkey: PublicKeyBytes::empty(),
+                withdrawal_credentials,
+                effective_balance: balance,
+                slashed: false,
+                activation_eligibility_epoch: Epoch::new(0),
+                activation_epoch: Epoch::new(0),
+                exit_epoch: Epoch::max_value(),
+                withdrawable_epoch: Epoch::max_value(),
+            },
+        }
+    }
+
+    #[test]
+    fn at_epoch() {
+        let spec = MainnetEthSpec::default_spec();
+        let validator = validator_data(32_000_000_000, Hash256::zero());
+        let condition = ExitCondition::AtEpoch {
+            epoch: Epoch::new(10),
+        };
+
+        assert!(!exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(9),
+            &spec
+        ));
+        assert!(exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(10),
+            &spec
+        ));
+        assert!(exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(11),
+            &spec
+        ));
+    }
+
+    #[test]
+    fn balance_below() {
+        let spec = MainnetEthSpec::default_spec();
+        let condition = ExitCondition::BalanceBelow {
+            balance_gwei: 31_000_000_000,
+        };
+
+        let validator = validator_data(31_000_000_000, Hash256::zero());
+        assert!(!exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(0),
+            &spec
+        ));
+
+        let validator = validator_data(30_999_999_999, Hash256::zero());
+        assert!(exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(0),
+            &spec
+        ));
+    }
+
+    #[test]
+    fn eth1_withdrawal_credentials() {
+        let spec = MainnetEthSpec::default_spec();
+        let condition = ExitCondition::Eth1WithdrawalCredentials;
+
+        let validator = validator_data(32_000_000_000, Hash256::zero());
+        assert!(!exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(0),
+            &spec
+        ));
+
+        let mut withdrawal_credentials = Hash256::zero();
+        withdrawal_credentials.as_bytes_mut()[0] = spec.eth1_address_withdrawal_prefix_byte;
+        let validator = validator_data(32_000_000_000, withdrawal_credentials);
+        assert!(exit_condition_met(
+            &condition,
+            &validator,
+            Epoch::new(0),
+            &spec
+        ));
+    }
+}
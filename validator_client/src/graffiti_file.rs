@@ -6,7 +6,10 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use bls::PublicKeyBytes;
-use types::{graffiti::GraffitiString, Graffiti};
+use types::{
+    graffiti::{GraffitiString, GraffitiTemplate, GraffitiTemplateContext},
+    Graffiti,
+};
 
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -17,18 +20,38 @@ pub enum Error {
     InvalidGraffiti(String),
 }
 
+/// The graffiti to use for a validator, which is either fixed or rendered from a template at
+/// proposal time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GraffitiSetting {
+    Fixed(Graffiti),
+    Template(GraffitiTemplate),
+}
+
+impl GraffitiSetting {
+    pub fn render(&self, context: &GraffitiTemplateContext) -> Graffiti {
+        match self {
+            GraffitiSetting::Fixed(graffiti) => *graffiti,
+            GraffitiSetting::Template(template) => template.render(context),
+        }
+    }
+}
+
 /// Struct to load validator graffitis from file.
 /// The graffiti file is expected to have the following structure
 ///
 /// default: Lighthouse
 /// public_key1: graffiti1
 /// public_key2: graffiti2
+/// public_key3: {el_client}LH #{validator_index}
 /// ...
+///
+/// Graffitis containing placeholders are treated as templates, see `GraffitiTemplate`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraffitiFile {
     graffiti_path: PathBuf,
-    graffitis: HashMap<PublicKeyBytes, Graffiti>,
-    default: Option<Graffiti>,
+    graffitis: HashMap<PublicKeyBytes, GraffitiSetting>,
+    default: Option<GraffitiSetting>,
 }
 
 impl GraffitiFile {
@@ -48,9 +71,13 @@ impl GraffitiFile {
     pub fn load_graffiti(
         &mut self,
         public_key: &PublicKeyBytes,
-    ) -> Result<Option<Graffiti>, Error> {
+    ) -> Result<Option<GraffitiSetting>, Error> {
         self.read_graffiti_file()?;
-        Ok(self.graffitis.get(public_key).copied().or(self.default))
+        Ok(self
+            .graffitis
+            .get(public_key)
+            .or(self.default.as_ref())
+            .cloned())
     }
 
     /// Reads from a graffiti file with the specified format and populates the default value
@@ -82,13 +109,21 @@ impl GraffitiFile {
 /// `Ok((None, graffiti))` represents the graffiti for the default key.
 /// `Ok((Some(pk), graffiti))` represents graffiti for the public key `pk`.
 /// Returns an error if the line is in the wrong format or does not contain a valid public key or graffiti.
-fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, Graffiti), Error> {
+fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, GraffitiSetting), Error> {
     if let Some(i) = line.find(':') {
         let (key, value) = line.split_at(i);
         // Note: `value.len() >=1` so `value[1..]` is safe
-        let graffiti = GraffitiString::from_str(value[1..].trim())
-            .map_err(Error::InvalidGraffiti)?
-            .into();
+        let value = value[1..].trim();
+        // Values without placeholders are parsed as before, so that existing graffitis containing
+        // braces remain valid.
+        let graffiti = match GraffitiTemplate::from_str(value) {
+            Ok(template) if template.has_placeholders() => GraffitiSetting::Template(template),
+            _ => GraffitiSetting::Fixed(
+                GraffitiString::from_str(value)
+                    .map_err(Error::InvalidGraffiti)?
+                    .into(),
+            ),
+        };
         if key == "default" {
             Ok((None, graffiti))
         } else {
@@ -111,9 +146,11 @@ mod tests {
     const CUSTOM_GRAFFITI1: &str = "custom-graffiti1";
     const CUSTOM_GRAFFITI2: &str = "graffitiwall:720:641:#ffff00";
     const EMPTY_GRAFFITI: &str = "";
+    const TEMPLATE_GRAFFITI: &str = "{el_client}LH #{validator_index} {rotate:gm|gn}";
     const PK1: &str = "0x800012708dc03f611751aad7a43a082142832b5c1aceed07ff9b543cf836381861352aa923c70eeb02018b638aa306aa";
     const PK2: &str = "0x80001866ce324de7d80ec73be15e2d064dcf121adf1b34a0d679f2b9ecbab40ce021e03bb877e1a2fe72eaaf475e6e21";
     const PK3: &str = "0x9035d41a8bc11b08c17d0d93d876087958c9d055afe86fce558e3b988d92434769c8d50b0b463708db80c6aae1160c02";
+    const PK4: &str = "0xa7cf39e4f93e94dd6b6f0e4c7cba0c5f4dc95fac0d7ccbc4a84d7e4ce0ae30f7d0dbba95d7ff0a4b4e8cb3a4d0b8e9c8";
 
     // Create a graffiti file in the required format and return a path to the file.
     fn create_graffiti_file() -> PathBuf {
@@ -121,6 +158,7 @@ mod tests {
         let pk1 = PublicKeyBytes::deserialize(&hex::decode(&PK1[2..]).unwrap()).unwrap();
         let pk2 = PublicKeyBytes::deserialize(&hex::decode(&PK2[2..]).unwrap()).unwrap();
         let pk3 = PublicKeyBytes::deserialize(&hex::decode(&PK3[2..]).unwrap()).unwrap();
+        let pk4 = PublicKeyBytes::deserialize(&hex::decode(&PK4[2..]).unwrap()).unwrap();
 
         let file_name = temp.into_path().join("graffiti.txt");
 
@@ -138,6 +176,9 @@ mod tests {
         graffiti_file
             .write_all(format!("{}:{}\n", pk3.as_hex_string(), EMPTY_GRAFFITI).as_bytes())
             .unwrap();
+        graffiti_file
+            .write_all(format!("{}: {}\n", pk4.as_hex_string(), TEMPLATE_GRAFFITI).as_bytes())
+            .unwrap();
         graffiti_file.flush().unwrap();
         file_name
     }
@@ -150,29 +191,50 @@ mod tests {
         let pk1 = PublicKeyBytes::deserialize(&hex::decode(&PK1[2..]).unwrap()).unwrap();
         let pk2 = PublicKeyBytes::deserialize(&hex::decode(&PK2[2..]).unwrap()).unwrap();
         let pk3 = PublicKeyBytes::deserialize(&hex::decode(&PK3[2..]).unwrap()).unwrap();
+        let pk4 = PublicKeyBytes::deserialize(&hex::decode(&PK4[2..]).unwrap()).unwrap();
 
         // Read once
         gf.read_graffiti_file().unwrap();
 
         assert_eq!(
             gf.load_graffiti(&pk1).unwrap().unwrap(),
-            GraffitiString::from_str(CUSTOM_GRAFFITI1).unwrap().into()
+            fixed(CUSTOM_GRAFFITI1)
         );
         assert_eq!(
             gf.load_graffiti(&pk2).unwrap().unwrap(),
-            GraffitiString::from_str(CUSTOM_GRAFFITI2).unwrap().into()
+            fixed(CUSTOM_GRAFFITI2)
         );
 
         assert_eq!(
             gf.load_graffiti(&pk3).unwrap().unwrap(),
-            GraffitiString::from_str(EMPTY_GRAFFITI).unwrap().into()
+            fixed(EMPTY_GRAFFITI)
+        );
+
+        let template = gf.load_graffiti(&pk4).unwrap().unwrap();
+        assert_eq!(
+            template,
+            GraffitiSetting::Template(GraffitiTemplate::from_str(TEMPLATE_GRAFFITI).unwrap())
+        );
+        let context = GraffitiTemplateContext {
+            validator_index: Some(1234),
+            epoch: types::Epoch::new(3),
+            el_client: Some("NM"),
+            cl_version: "v5.3.0",
+        };
+        assert_eq!(
+            template.render(&context),
+            GraffitiString::from_str("NMLH #1234 gn").unwrap().into()
         );
 
         // Random pk should return the default graffiti
         let random_pk = Keypair::random().pk.compress();
         assert_eq!(
             gf.load_graffiti(&random_pk).unwrap().unwrap(),
-            GraffitiString::from_str(DEFAULT_GRAFFITI).unwrap().into()
+            fixed(DEFAULT_GRAFFITI)
         );
     }
+
+    fn fixed(graffiti: &str) -> GraffitiSetting {
+        GraffitiSetting::Fixed(GraffitiString::from_str(graffiti).unwrap().into())
+    }
 }
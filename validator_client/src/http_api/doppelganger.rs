@@ -0,0 +1,40 @@
+use crate::validator_store::{DetectionProgress, DoppelgangerStatus, ValidatorStore};
+use eth2::lighthouse_vc::types::{DoppelgangerData, DoppelgangerProtectionStatus};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::{EthSpec, PublicKeyBytes};
+
+pub fn get_doppelganger_statuses<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Vec<DoppelgangerData> {
+    let pubkeys: Vec<PublicKeyBytes> = validator_store.voting_pubkeys(DoppelgangerStatus::ignored);
+    pubkeys
+        .into_iter()
+        .map(|pubkey| {
+            let (status, remaining_epochs, signing_epoch) =
+                match validator_store.doppelganger_progress(&pubkey) {
+                    None => (DoppelgangerProtectionStatus::Disabled, 0, None),
+                    Some(DetectionProgress::Complete) => {
+                        (DoppelgangerProtectionStatus::Complete, 0, None)
+                    }
+                    Some(DetectionProgress::DoppelgangerDetected) => {
+                        (DoppelgangerProtectionStatus::DoppelgangerDetected, 0, None)
+                    }
+                    Some(DetectionProgress::Detecting {
+                        remaining_epochs,
+                        signing_epoch,
+                    }) => (
+                        DoppelgangerProtectionStatus::Detecting,
+                        remaining_epochs,
+                        Some(signing_epoch),
+                    ),
+                };
+            DoppelgangerData {
+                pubkey,
+                status,
+                remaining_epochs,
+                signing_epoch,
+            }
+        })
+        .collect()
+}
@@ -2,7 +2,10 @@ use crate::validator_store::ValidatorStore;
 use bls::PublicKey;
 use slot_clock::SlotClock;
 use std::sync::Arc;
-use types::{graffiti::GraffitiString, EthSpec, Graffiti};
+use types::{
+    graffiti::{GraffitiString, GraffitiTemplate},
+    EthSpec, Graffiti,
+};
 
 pub fn get_graffiti<T: 'static + SlotClock + Clone, E: EthSpec>(
     validator_pubkey: PublicKey,
@@ -78,3 +81,70 @@ pub fn delete_graffiti<T: 'static + SlotClock + Clone, E: EthSpec>(
         }
     }
 }
+
+pub fn get_graffiti_template<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<Option<GraffitiTemplate>, warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rw_lock.read();
+    match initialized_validators.validator(&validator_pubkey.compress()) {
+        None => Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server".to_string(),
+        )),
+        Some(initialized_validator) => Ok(initialized_validator.get_graffiti_template().cloned()),
+    }
+}
+
+pub fn set_graffiti_template<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    graffiti_template: GraffitiTemplate,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    match initialized_validators.validator(&validator_pubkey.compress()) {
+        None => Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to update".to_string(),
+        )),
+        Some(initialized_validator) => {
+            if initialized_validator.get_graffiti_template() == Some(&graffiti_template) {
+                Ok(())
+            } else {
+                initialized_validators
+                    .set_graffiti_template(&validator_pubkey, graffiti_template)
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "A graffiti template was found, but failed to be updated.".to_string(),
+                        )
+                    })
+            }
+        }
+    }
+}
+
+pub fn delete_graffiti_template<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    match initialized_validators.validator(&validator_pubkey.compress()) {
+        None => Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to delete".to_string(),
+        )),
+        Some(initialized_validator) => {
+            if initialized_validator.get_graffiti_template().is_none() {
+                Ok(())
+            } else {
+                initialized_validators
+                    .delete_graffiti_template(&validator_pubkey)
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "A graffiti template was found, but failed to be removed.".to_string(),
+                        )
+                    })
+            }
+        }
+    }
+}
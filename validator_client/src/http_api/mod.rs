@@ -1,17 +1,30 @@
 mod api_secret;
 mod create_signed_voluntary_exit;
 mod create_validator;
+mod doppelganger;
 mod graffiti;
 mod keystores;
+mod pending_exits;
 mod remotekeys;
+mod signing_method;
 mod tests;
+mod threshold;
 
 pub mod test_utils;
 
-use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
+use crate::http_api::graffiti::{
+    delete_graffiti, delete_graffiti_template, get_graffiti, get_graffiti_template, set_graffiti,
+    set_graffiti_template,
+};
 
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
-use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
+use crate::http_api::doppelganger::get_doppelganger_statuses;
+use crate::http_api::pending_exits::{delete_pending_exit, get_pending_exits, set_pending_exit};
+use crate::http_api::threshold::sign_threshold_share;
+use crate::slashing_protection_replication::PromotionError;
+use crate::{
+    determine_graffiti, BeaconNodeFallback, GraffitiFile, GraffitiSetting, ValidatorStore,
+};
 use account_utils::{
     mnemonic_from_phrase,
     validator_definitions::{SigningDefinition, ValidatorDefinition, Web3SignerDefinition},
@@ -21,7 +34,7 @@ use create_validator::{
     create_validators_mnemonic, create_validators_web3signer, get_voting_password_storage,
 };
 use eth2::lighthouse_vc::{
-    std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse},
+    std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse, Interchange},
     types::{
         self as api_types, GenericResponse, GetGraffitiResponse, Graffiti, PublicKey,
         PublicKeyBytes, SetGraffitiRequest,
@@ -80,6 +93,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -190,6 +204,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || inner_beacon_nodes.clone())
+        .and_then(|beacon_nodes: Option<_>| async move {
+            beacon_nodes.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "beacon nodes are not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -305,6 +330,34 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/beacon/health
+    let get_lighthouse_beacon_health = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter.clone())
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and_then(
+            |beacon_nodes: Arc<BeaconNodeFallback<T, E>>, signer, task_executor: TaskExecutor| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let health = handle.block_on(beacon_nodes.health());
+                        Ok(GenericResponse::from(
+                            health
+                                .into_iter()
+                                .map(api_types::BeaconNodeHealthData::from)
+                                .collect::<Vec<_>>(),
+                        ))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     // GET lighthouse/validators
     let get_lighthouse_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -409,7 +462,11 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             graffiti_definition,
                             graffiti_flag,
                         );
-                        result.insert(key.to_string(), graffiti.map(|g| g.as_utf8_lossy()));
+                        let graffiti = graffiti.map(|graffiti| match graffiti {
+                            GraffitiSetting::Fixed(graffiti) => graffiti.as_utf8_lossy(),
+                            GraffitiSetting::Template(template) => template.to_string(),
+                        });
+                        result.insert(key.to_string(), graffiti);
                     }
                     Ok(api_types::GenericResponse::from(result))
                 })
@@ -626,11 +683,14 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 enabled: web3signer.enable,
                                 voting_public_key: web3signer.voting_public_key,
                                 graffiti: web3signer.graffiti,
+                                graffiti_template: None,
                                 suggested_fee_recipient: web3signer.suggested_fee_recipient,
                                 gas_limit: web3signer.gas_limit,
                                 builder_proposals: web3signer.builder_proposals,
                                 builder_boost_factor: web3signer.builder_boost_factor,
                                 prefer_builder_proposals: web3signer.prefer_builder_proposals,
+                                pending_exit: None,
+                                doppelganger: None,
                                 description: web3signer.description,
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
@@ -640,6 +700,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                         client_identity_path: web3signer.client_identity_path,
                                         client_identity_password: web3signer
                                             .client_identity_password,
+                                        api_token: None,
                                     },
                                 ),
                             })
@@ -658,6 +719,295 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/validators/signing_method
+    let post_validators_signing_method = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("signing_method"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_dir_filter.clone())
+        .and(secrets_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            move |request,
+                  signer,
+                  validator_dir,
+                  secrets_dir,
+                  validator_store,
+                  task_executor,
+                  log| {
+                let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
+                blocking_signed_json_task(signer, move || {
+                    signing_method::convert(
+                        request,
+                        validator_dir,
+                        secrets_dir,
+                        validator_store,
+                        task_executor,
+                        log,
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/validators/{validator_pubkey}/threshold_share
+    let post_validators_threshold_share = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("threshold_share"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(log_filter.clone())
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and_then(
+            |pubkey: PublicKey,
+             message: api_types::ThresholdSignableMessage<E>,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             log,
+             signer,
+             task_executor: TaskExecutor| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(handle) = task_executor.handle() {
+                        handle.block_on(sign_threshold_share(pubkey, message, validator_store, log))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_pubkey}/graffiti_template
+    let get_validators_graffiti_template = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti_template"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    let graffiti_template = get_graffiti_template(pubkey.clone(), validator_store)?;
+                    Ok(GenericResponse::from(api_types::GraffitiTemplateData {
+                        pubkey: pubkey.into(),
+                        graffiti_template,
+                    }))
+                })
+            },
+        );
+
+    // POST lighthouse/validators/{validator_pubkey}/graffiti_template
+    let post_validators_graffiti_template = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti_template"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(graffiti_file_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |pubkey: PublicKey,
+             body: api_types::SetGraffitiTemplateRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             graffiti_file: Option<GraffitiFile>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    if graffiti_file.is_some() {
+                        return Err(warp_utils::reject::invalid_auth(
+                            "Unable to update graffiti template as the \"--graffiti-file\" flag \
+                            is set"
+                                .to_string(),
+                        ));
+                    }
+                    set_graffiti_template(pubkey, body.graffiti_template, validator_store)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE lighthouse/validators/{validator_pubkey}/graffiti_template
+    let delete_validators_graffiti_template = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti_template"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(graffiti_file_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |pubkey: PublicKey,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             graffiti_file: Option<GraffitiFile>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    if graffiti_file.is_some() {
+                        return Err(warp_utils::reject::invalid_auth(
+                            "Unable to delete graffiti template as the \"--graffiti-file\" flag \
+                            is set"
+                                .to_string(),
+                        ));
+                    }
+                    delete_graffiti_template(pubkey, validator_store)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
+    // GET lighthouse/validators/pending_exits
+    let get_validators_pending_exits = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("pending_exits"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(GenericResponse::from(get_pending_exits(validator_store)))
+            })
+        });
+
+    // GET lighthouse/validators/doppelganger
+    let get_validators_doppelganger = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("doppelganger"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(GenericResponse::from(get_doppelganger_statuses(
+                    validator_store,
+                )))
+            })
+        });
+
+    // POST lighthouse/validators/{validator_pubkey}/pending_exit
+    let post_validators_pending_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("pending_exit"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |pubkey: PublicKey,
+             body: api_types::SetPendingExitRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    set_pending_exit(pubkey, body.condition, validator_store)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE lighthouse/validators/{validator_pubkey}/pending_exit
+    let delete_validators_pending_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("pending_exit"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    delete_pending_exit(pubkey, validator_store)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
+    // GET lighthouse/slashing_protection/standby
+    let get_slashing_protection_standby = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("standby"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                let last_replication = validator_store.slashing_protection_last_replication();
+                Ok(GenericResponse::from(
+                    api_types::SlashingProtectionStandbyData {
+                        standby: validator_store.is_slashing_protection_standby(),
+                        last_replication_epoch: last_replication
+                            .map(|replication| replication.epoch),
+                        last_replication_time: last_replication
+                            .map(|replication| replication.time.as_secs()),
+                    },
+                ))
+            })
+        });
+
+    // POST lighthouse/slashing_protection/replicate
+    let post_slashing_protection_replicate = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("replicate"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |interchange: Interchange, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    // Data from a primary is only accepted while this validator client is a
+                    // standby, so that a primary which is still running after its standby has
+                    // been promoted will notice.
+                    if !validator_store.is_slashing_protection_standby() {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "not a slashing protection standby".to_string(),
+                        ));
+                    }
+                    validator_store
+                        .import_replicated_slashing_protection(interchange)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to import slashing protection: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/slashing_protection/promote
+    let post_slashing_protection_promote = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("promote"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |request: api_types::SlashingProtectionPromoteRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    validator_store
+                        .promote_slashing_protection_standby(request.force)
+                        .map_err(|e| match e {
+                            PromotionError::NotStandby => warp_utils::reject::custom_bad_request(
+                                "not a slashing protection standby".to_string(),
+                            ),
+                            e => warp_utils::reject::custom_bad_request(format!(
+                                "unable to promote, set force to promote anyway: {:?}",
+                                e
+                            )),
+                        })
+                })
+            },
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -1309,8 +1659,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                     get_node_version
                         .or(get_lighthouse_health)
                         .or(get_lighthouse_spec)
+                        .or(get_lighthouse_beacon_health)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_validators_graffiti_template)
+                        .or(get_validators_pending_exits)
+                        .or(get_validators_doppelganger)
+                        .or(get_slashing_protection_standby)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
@@ -1325,6 +1680,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_validators_signing_method)
+                        .or(post_validators_threshold_share)
+                        .or(post_validators_graffiti_template)
+                        .or(post_validators_pending_exit)
+                        .or(post_slashing_protection_replicate)
+                        .or(post_slashing_protection_promote)
                         .or(post_validators_voluntary_exits)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
@@ -1337,6 +1698,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                     .and(patch_validators.recover(warp_utils::reject::handle_rejection)))
                 .or(warp::delete().and(
                     delete_lighthouse_keystores
+                        .or(delete_validators_graffiti_template)
+                        .or(delete_validators_pending_exit)
                         .or(delete_fee_recipient)
                         .or(delete_gas_limit)
                         .or(delete_std_keystores)
@@ -0,0 +1,72 @@
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::ExitCondition;
+use bls::PublicKey;
+use eth2::lighthouse_vc::types::PendingExitData;
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+pub fn get_pending_exits<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Vec<PendingExitData> {
+    validator_store
+        .initialized_validators()
+        .read()
+        .pending_exits()
+        .into_iter()
+        .map(|(pubkey, condition)| PendingExitData { pubkey, condition })
+        .collect()
+}
+
+pub fn set_pending_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    condition: ExitCondition,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    match initialized_validators.validator(&validator_pubkey.compress()) {
+        None => Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to update".to_string(),
+        )),
+        Some(initialized_validator) => {
+            if initialized_validator.get_pending_exit() == Some(&condition) {
+                Ok(())
+            } else {
+                initialized_validators
+                    .set_pending_exit(&validator_pubkey, condition)
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "A pending exit was found, but failed to be updated.".to_string(),
+                        )
+                    })
+            }
+        }
+    }
+}
+
+pub fn delete_pending_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    match initialized_validators.validator(&validator_pubkey.compress()) {
+        None => Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to delete".to_string(),
+        )),
+        Some(initialized_validator) => {
+            if initialized_validator.get_pending_exit().is_none() {
+                Ok(())
+            } else {
+                initialized_validators
+                    .delete_pending_exit(&validator_pubkey)
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "A pending exit was found, but failed to be removed.".to_string(),
+                        )
+                    })
+            }
+        }
+    }
+}
@@ -32,7 +32,7 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
             let validating_pubkey = def.voting_public_key.compress();
 
             match &def.signing_definition {
-                SigningDefinition::LocalKeystore { .. } => None,
+                SigningDefinition::LocalKeystore { .. } | SigningDefinition::Threshold(_) => None,
                 SigningDefinition::Web3Signer(Web3SignerDefinition { url, .. }) => {
                     Some(SingleListRemotekeysResponse {
                         pubkey: validating_pubkey,
@@ -122,11 +122,14 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         enabled: true,
         voting_public_key: pubkey,
         graffiti: None,
+        graffiti_template: None,
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        pending_exit: None,
+        doppelganger: None,
         description: String::from("Added by remotekey API"),
         signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
             url,
@@ -134,6 +137,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
             request_timeout_ms: None,
             client_identity_path: None,
             client_identity_password: None,
+            api_token: None,
         }),
     };
     handle
@@ -0,0 +1,227 @@
+//! Conversion of validators between local keystores and remote signers.
+use crate::ValidatorStore;
+use account_utils::{
+    validator_definitions::{SigningDefinition, Web3SignerDefinition},
+    ZeroizeString,
+};
+use eth2::lighthouse_vc::{
+    std_types::Status,
+    types::{
+        ConvertSigningMethodRequest, ConvertSigningMethodResponse, ConvertSigningMethodStatus,
+        SigningMethodConversion,
+    },
+};
+use eth2_keystore::Keystore;
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::runtime::Handle;
+use types::{EthSpec, PublicKey, PublicKeyBytes};
+use validator_dir::{keystore_password_path, Builder as ValidatorDirBuilder};
+use warp::Rejection;
+
+pub fn convert<T: SlotClock + 'static, E: EthSpec>(
+    request: ConvertSigningMethodRequest,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<ConvertSigningMethodResponse, Rejection> {
+    info!(
+        log,
+        "Converting validator signing methods via HTTP API";
+        "count" => request.validators.len(),
+    );
+
+    // Convert each validator. Some conversions may fail, so we record a status for each.
+    let mut statuses = Vec::with_capacity(request.validators.len());
+
+    for conversion in request.validators {
+        let pubkey = conversion.pubkey;
+        let status = if let Some(handle) = task_executor.handle() {
+            match convert_single_validator(
+                &pubkey,
+                conversion.signing_method,
+                validator_dir.clone(),
+                secrets_dir.clone(),
+                &validator_store,
+                handle,
+            ) {
+                Ok(status) => Status::ok(status),
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Error converting validator signing method, skipped";
+                        "pubkey" => ?pubkey,
+                        "error" => ?e,
+                    );
+                    Status::error(ConvertSigningMethodStatus::Error, e)
+                }
+            }
+        } else {
+            Status::error(
+                ConvertSigningMethodStatus::Error,
+                "validator client shutdown".into(),
+            )
+        };
+        statuses.push(status);
+    }
+
+    Ok(ConvertSigningMethodResponse { data: statuses })
+}
+
+fn convert_single_validator<T: SlotClock + 'static, E: EthSpec>(
+    pubkey_bytes: &PublicKeyBytes,
+    signing_method: SigningMethodConversion,
+    validator_dir_path: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: &ValidatorStore<T, E>,
+    handle: Handle,
+) -> Result<ConvertSigningMethodStatus, String> {
+    let pubkey = pubkey_bytes
+        .decompress()
+        .map_err(|e| format!("invalid pubkey, {:?}: {:?}", pubkey_bytes, e))?;
+
+    let Some(current_definition) = validator_store
+        .initialized_validators()
+        .read()
+        .validator_definitions()
+        .iter()
+        .find(|def| def.voting_public_key == pubkey)
+        .map(|def| def.signing_definition.clone())
+    else {
+        return Ok(ConvertSigningMethodStatus::NotFound);
+    };
+
+    match (current_definition, signing_method) {
+        (SigningDefinition::Threshold(_), _) => Err("cannot convert a threshold validator".into()),
+        (
+            SigningDefinition::LocalKeystore { .. },
+            SigningMethodConversion::LocalKeystore { .. },
+        ) => Ok(ConvertSigningMethodStatus::Unchanged),
+        (
+            SigningDefinition::Web3Signer(current),
+            SigningMethodConversion::Web3Signer { url, .. },
+        ) => {
+            if current.url == url {
+                Ok(ConvertSigningMethodStatus::Unchanged)
+            } else {
+                Err("cannot change the url of a remote signer validator".into())
+            }
+        }
+        (
+            SigningDefinition::LocalKeystore { .. },
+            SigningMethodConversion::Web3Signer {
+                url,
+                root_certificate_path,
+                request_timeout_ms,
+                client_identity_path,
+                client_identity_password,
+            },
+        ) => {
+            let signing_definition = SigningDefinition::Web3Signer(Web3SignerDefinition {
+                url,
+                root_certificate_path,
+                request_timeout_ms,
+                client_identity_path,
+                client_identity_password,
+                api_token: None,
+            });
+            replace_signing_definition(&pubkey, signing_definition, validator_store, &handle)?;
+            Ok(ConvertSigningMethodStatus::Converted)
+        }
+        (
+            SigningDefinition::Web3Signer(_),
+            SigningMethodConversion::LocalKeystore { keystore, password },
+        ) => {
+            convert_to_local_keystore(
+                &pubkey,
+                keystore,
+                password,
+                validator_dir_path,
+                secrets_dir,
+                validator_store,
+                &handle,
+            )?;
+            Ok(ConvertSigningMethodStatus::Converted)
+        }
+    }
+}
+
+/// Store `keystore` in the validators directory and convert the validator to use it.
+///
+/// The keystore and its password file are removed again if the conversion fails.
+fn convert_to_local_keystore<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: &PublicKey,
+    keystore: Keystore,
+    password: ZeroizeString,
+    validator_dir_path: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: &ValidatorStore<T, E>,
+    handle: &Handle,
+) -> Result<(), String> {
+    if keystore.public_key().as_ref() != Some(pubkey) {
+        return Err(format!(
+            "keystore pubkey does not match: {}",
+            keystore.pubkey()
+        ));
+    }
+
+    // Check that the password is correct before writing anything to disk.
+    keystore
+        .decrypt_keypair(password.as_ref())
+        .map_err(|e| format!("incorrect password: {:?}", e))?;
+
+    let password_path = secrets_dir
+        .as_ref()
+        .map(|secrets_dir| keystore_password_path(secrets_dir, &keystore));
+    if password_path.as_ref().map_or(false, |path| path.exists()) {
+        return Err("keystore password file already exists".into());
+    }
+
+    let validator_dir = ValidatorDirBuilder::new(validator_dir_path)
+        .password_dir_opt(secrets_dir)
+        .voting_keystore(keystore, password.as_ref())
+        .store_withdrawal_keystore(false)
+        .build()
+        .map_err(|e| format!("failed to build validator directory: {:?}", e))?;
+
+    // Drop validator dir so that the keystore can be re-locked by the new signing method.
+    let dir = validator_dir.dir().to_path_buf();
+    let voting_keystore_path = validator_dir.voting_keystore_path();
+    drop(validator_dir);
+
+    let signing_definition = SigningDefinition::LocalKeystore {
+        voting_keystore_path,
+        voting_keystore_password: password_path.is_none().then_some(password),
+        voting_keystore_password_path: password_path.clone(),
+    };
+
+    replace_signing_definition(pubkey, signing_definition, validator_store, handle).map_err(|e| {
+        // Clean up so that the keystore may be supplied again.
+        let _ = fs::remove_dir_all(&dir);
+        if let Some(password_path) = password_path {
+            let _ = fs::remove_file(password_path);
+        }
+        e
+    })
+}
+
+fn replace_signing_definition<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: &PublicKey,
+    signing_definition: SigningDefinition,
+    validator_store: &ValidatorStore<T, E>,
+    handle: &Handle,
+) -> Result<(), String> {
+    // Hold the write lock for the whole conversion so that the validator is never observed
+    // without a signing method.
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rwlock.write();
+    handle
+        .block_on(initialized_validators.convert_signing_method(pubkey, signing_definition))
+        .map_err(|e| format!("unable to convert signing method: {:?}", e))
+}
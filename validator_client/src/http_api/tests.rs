@@ -3,10 +3,13 @@
 
 mod keystores;
 
-use crate::doppelganger_service::DoppelgangerService;
+use crate::beacon_node_fallback::{BeaconNodeFallback, CandidateBeaconNode};
+use crate::doppelganger_service::{DoppelgangerService, DoppelgangerStatus};
 use crate::{
+    graffiti_file::GraffitiSetting,
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
+    signing_method::SigningMethod,
     Config, ValidatorDefinitions, ValidatorStore,
 };
 use account_utils::{
@@ -17,13 +20,16 @@ use deposit_contract::decode_eth1_tx_data;
 use eth2::{
     lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
     types::ErrorMessage as ApiErrorMessage,
-    Error as ApiError,
+    BeaconNodeHttpClient, Error as ApiError, Timeouts,
 };
 use eth2_keystore::KeystoreBuilder;
 use logging::test_logger;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
-use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+use slashing_protection::interchange::{self, Interchange, InterchangeData, InterchangeMetadata};
+use slashing_protection::{
+    SlashingDatabase, SLASHING_PROTECTION_FILENAME, SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+};
 use slot_clock::{SlotClock, TestingSlotClock};
 use std::future::Future;
 use std::marker::PhantomData;
@@ -44,6 +50,7 @@ struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<TestingSlotClock, E>>,
     url: SensitiveUrl,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
@@ -112,12 +119,29 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        // Beacon nodes which are never reachable, so that their health can be observed without
+        // running a beacon node.
+        let candidates = ["http://127.0.0.1:1", "http://127.0.0.1:2"]
+            .iter()
+            .map(|url| {
+                CandidateBeaconNode::new(BeaconNodeHttpClient::new(
+                    SensitiveUrl::parse(url).unwrap(),
+                    Timeouts::set_all(Duration::from_secs(1)),
+                ))
+            })
+            .collect();
+        let mut beacon_nodes =
+            BeaconNodeFallback::new(candidates, vec![], spec.clone(), log.clone());
+        beacon_nodes.set_slot_clock(slot_clock.clone());
+        let beacon_nodes = Arc::new(beacon_nodes);
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: Some(beacon_nodes.clone()),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -153,6 +177,7 @@ impl ApiTester {
             client,
             initialized_validators,
             validator_store,
+            beacon_nodes,
             url,
             slot_clock,
             _validator_dir: validator_dir,
@@ -246,6 +271,30 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_get_lighthouse_beacon_health(self, status: BeaconNodeStatus) -> Self {
+        let health = self
+            .client
+            .get_lighthouse_beacon_health()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(health.len(), 2);
+        for (i, beacon_node) in health.iter().enumerate() {
+            assert_eq!(beacon_node.index, i);
+            assert_eq!(beacon_node.status, status);
+            assert_eq!(beacon_node.sync_distance, None);
+        }
+
+        self
+    }
+
+    pub async fn update_beacon_nodes(self) -> Self {
+        self.beacon_nodes.update_all_candidates().await;
+        self
+    }
+
     pub fn vals_total(&self) -> usize {
         self.initialized_validators.read().num_total()
     }
@@ -500,6 +549,117 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_convert_signing_method(self) -> Self {
+        let password = random_password();
+        let keypair = Keypair::random();
+        let keystore = KeystoreBuilder::new(&keypair, password.as_bytes(), String::new())
+            .unwrap()
+            .build()
+            .unwrap();
+        let password: ZeroizeString = String::from_utf8(password.as_ref().to_vec())
+            .unwrap()
+            .into();
+        let pubkey = keypair.pk.compress();
+
+        self.client
+            .post_lighthouse_validators_keystore(&KeystoreValidatorsPostRequest {
+                enable: true,
+                password: password.clone(),
+                keystore: keystore.clone(),
+                graffiti: None,
+                suggested_fee_recipient: None,
+                gas_limit: None,
+                builder_proposals: None,
+                builder_boost_factor: None,
+                prefer_builder_proposals: None,
+            })
+            .await
+            .unwrap();
+        let initial_vals = self.vals_total();
+        let initial_enabled_vals = self.vals_enabled();
+
+        let convert = |pubkey, signing_method| ConvertSigningMethodRequest {
+            validators: vec![SingleConvertSigningMethodRequest {
+                pubkey,
+                signing_method,
+            }],
+        };
+        let web3signer = SigningMethodConversion::Web3Signer {
+            url: "http://signer.com/".to_string(),
+            root_certificate_path: None,
+            request_timeout_ms: None,
+            client_identity_path: None,
+            client_identity_password: None,
+        };
+        let local_keystore = SigningMethodConversion::LocalKeystore { keystore, password };
+
+        let is_web3signer = || {
+            matches!(
+                *self
+                    .initialized_validators
+                    .read()
+                    .signing_method(&pubkey)
+                    .unwrap(),
+                SigningMethod::Web3Signer { .. }
+            )
+        };
+
+        // Convert from the local keystore to a remote signer.
+        let response = self
+            .client
+            .post_lighthouse_validators_signing_method(&convert(pubkey, web3signer.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.data[0].status,
+            ConvertSigningMethodStatus::Converted
+        );
+        assert!(is_web3signer());
+        assert_eq!(self.vals_total(), initial_vals);
+        assert_eq!(self.vals_enabled(), initial_enabled_vals);
+
+        // Converting to the current signing method does nothing.
+        let response = self
+            .client
+            .post_lighthouse_validators_signing_method(&convert(pubkey, web3signer.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.data[0].status,
+            ConvertSigningMethodStatus::Unchanged
+        );
+
+        // Convert back to the local keystore.
+        let response = self
+            .client
+            .post_lighthouse_validators_signing_method(&convert(pubkey, local_keystore))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.data[0].status,
+            ConvertSigningMethodStatus::Converted
+        );
+        assert!(!is_web3signer());
+        assert_eq!(self.vals_total(), initial_vals);
+        assert_eq!(self.vals_enabled(), initial_enabled_vals);
+
+        // Unknown validators are not converted.
+        let response = self
+            .client
+            .post_lighthouse_validators_signing_method(&convert(
+                Keypair::random().pk.compress(),
+                web3signer,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.data[0].status,
+            ConvertSigningMethodStatus::NotFound
+        );
+
+        self
+    }
+
     pub async fn test_sign_voluntary_exits(self, index: usize, maybe_epoch: Option<Epoch>) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
         // manually setting validator index in `ValidatorStore`
@@ -762,7 +922,7 @@ impl ApiTester {
         let graffiti_str = GraffitiString::from_str(graffiti).unwrap();
         assert_eq!(
             self.validator_store.graffiti(&validator.voting_pubkey),
-            Some(graffiti_str.into())
+            Some(GraffitiSetting::Fixed(graffiti_str.into()))
         );
 
         self
@@ -810,6 +970,224 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_set_graffiti_template(self, index: usize, template: &str) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let template = GraffitiTemplate::from_str(template).unwrap();
+        self.client
+            .post_lighthouse_validators_graffiti_template(
+                &validator.voting_pubkey,
+                template.clone(),
+            )
+            .await
+            .unwrap();
+
+        let resp = self
+            .client
+            .get_lighthouse_validators_graffiti_template(&validator.voting_pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.graffiti_template, Some(template.clone()));
+
+        // The template takes precedence over the fixed graffiti.
+        assert_eq!(
+            self.validator_store.graffiti(&validator.voting_pubkey),
+            Some(GraffitiSetting::Template(template))
+        );
+
+        self
+    }
+
+    pub async fn test_delete_graffiti_template(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        self.client
+            .delete_lighthouse_validators_graffiti_template(&validator.voting_pubkey)
+            .await
+            .unwrap();
+
+        let resp = self
+            .client
+            .get_lighthouse_validators_graffiti_template(&validator.voting_pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.graffiti_template, None);
+
+        self
+    }
+
+    pub async fn test_set_pending_exit(self, index: usize, condition: ExitCondition) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        self.client
+            .post_lighthouse_validators_pending_exit(&validator.voting_pubkey, condition.clone())
+            .await
+            .unwrap();
+
+        let resp = self
+            .client
+            .get_lighthouse_validators_pending_exits()
+            .await
+            .unwrap()
+            .data;
+        assert!(resp.contains(&PendingExitData {
+            pubkey: validator.voting_pubkey,
+            condition: condition.clone(),
+        }));
+
+        // The pending exit should be persisted in the validator definitions.
+        let def = self
+            .initialized_validators
+            .read()
+            .validator_definitions()
+            .iter()
+            .find(|def| PublicKeyBytes::from(&def.voting_public_key) == validator.voting_pubkey)
+            .cloned()
+            .unwrap();
+        assert_eq!(def.pending_exit, Some(condition));
+
+        self
+    }
+
+    pub async fn test_delete_pending_exit(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        self.client
+            .delete_lighthouse_validators_pending_exit(&validator.voting_pubkey)
+            .await
+            .unwrap();
+
+        let resp = self
+            .client
+            .get_lighthouse_validators_pending_exits()
+            .await
+            .unwrap()
+            .data;
+        assert!(resp
+            .iter()
+            .all(|pending_exit| pending_exit.pubkey != validator.voting_pubkey));
+
+        self
+    }
+
+    pub async fn assert_pending_exits_count(self, count: usize) -> Self {
+        let resp = self
+            .client
+            .get_lighthouse_validators_pending_exits()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.len(), count);
+
+        self
+    }
+
+    pub async fn assert_doppelganger_statuses(
+        self,
+        count: usize,
+        status: DoppelgangerProtectionStatus,
+    ) -> Self {
+        let resp = self
+            .client
+            .get_lighthouse_validators_doppelganger()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.len(), count);
+        assert!(resp.iter().all(|data| data.status == status));
+
+        self
+    }
+
+    pub async fn assert_slashing_protection_standby(self, standby: bool) -> Self {
+        let resp = self
+            .client
+            .get_lighthouse_slashing_protection_standby()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.standby, standby);
+
+        // No validators are safe to sign while in standby.
+        let safe_pubkeys: Vec<PublicKeyBytes> = self
+            .validator_store
+            .voting_pubkeys(DoppelgangerStatus::only_safe);
+        assert_eq!(safe_pubkeys.is_empty(), standby);
+
+        self
+    }
+
+    pub async fn test_replicate_slashing_protection(self, index: usize, expect_ok: bool) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let data = InterchangeData {
+            pubkey: validator.voting_pubkey,
+            signed_blocks: vec![interchange::SignedBlock {
+                slot: Slot::new(10),
+                signing_root: None,
+            }],
+            signed_attestations: vec![interchange::SignedAttestation {
+                source_epoch: Epoch::new(2),
+                target_epoch: Epoch::new(3),
+                signing_root: None,
+            }],
+        };
+        let watermarks = Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+                genesis_validators_root: self.validator_store.genesis_validators_root(),
+            },
+            data: vec![data.clone()],
+        };
+
+        let result = self
+            .client
+            .post_lighthouse_slashing_protection_replicate(&watermarks)
+            .await;
+        if !expect_ok {
+            assert!(result.is_err());
+            return self;
+        }
+        result.unwrap();
+
+        let exported = self
+            .validator_store
+            .export_slashing_protection_watermarks()
+            .unwrap();
+        assert!(exported.data.contains(&data));
+
+        // The arrival of the replication is recorded.
+        let resp = self
+            .client
+            .get_lighthouse_slashing_protection_standby()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resp.last_replication_epoch, Some(self.get_current_epoch()));
+        assert!(resp.last_replication_time.is_some());
+
+        self
+    }
+
+    pub async fn test_promote_slashing_protection_standby(
+        self,
+        force: bool,
+        expect_ok: bool,
+    ) -> Self {
+        let result = self
+            .client
+            .post_lighthouse_slashing_protection_promote(&SlashingProtectionPromoteRequest {
+                force,
+            })
+            .await;
+        assert_eq!(result.is_ok(), expect_ok);
+
+        self
+    }
+
+    pub fn advance_epochs(self, epochs: u64) -> Self {
+        let slot = self.slot_clock.now().unwrap() + epochs * E::slots_per_epoch();
+        self.slot_clock.set_slot(slot.as_u64());
+        self
+    }
 }
 
 struct HdValidatorScenario {
@@ -846,6 +1224,8 @@ async fn routes_with_invalid_auth() {
         .await
         .test_with_invalid_auth(|client| async move { client.get_lighthouse_health().await })
         .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_beacon_health().await })
+        .await
         .test_with_invalid_auth(|client| async move {
             client.get_lighthouse_spec::<types::Config>().await
         })
@@ -920,6 +1300,18 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            let keypair = Keypair::random();
+            client
+                .post_lighthouse_validators_threshold_share::<E>(
+                    &keypair.pk.compress(),
+                    &ThresholdSignableMessage::RandaoReveal {
+                        epoch: Epoch::new(0),
+                    },
+                )
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move { client.get_keystores().await })
         .await
         .test_with_invalid_auth(|client| async move {
@@ -961,6 +1353,82 @@ async fn routes_with_invalid_auth() {
                 .set_graffiti(&PublicKeyBytes::empty(), GraffitiString::default())
                 .await
         })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_lighthouse_validators_graffiti_template(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_graffiti_template(
+                    &PublicKeyBytes::empty(),
+                    GraffitiString::default().into(),
+                )
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .delete_lighthouse_validators_graffiti_template(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_validators_pending_exits().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_validators_doppelganger().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_pending_exit(
+                    &PublicKeyBytes::empty(),
+                    ExitCondition::Eth1WithdrawalCredentials,
+                )
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .delete_lighthouse_validators_pending_exit(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_slashing_protection_standby().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_slashing_protection_replicate(&Interchange {
+                    metadata: InterchangeMetadata {
+                        interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+                        genesis_validators_root: Hash256::zero(),
+                    },
+                    data: vec![],
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_slashing_protection_promote(
+                    &SlashingProtectionPromoteRequest::default(),
+                )
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_signing_method(&ConvertSigningMethodRequest {
+                    validators: vec![],
+                })
+                .await
+        })
         .await;
 }
 
@@ -976,6 +1444,18 @@ async fn simple_getters() {
         .await;
 }
 
+#[tokio::test]
+async fn beacon_node_health() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_beacon_health(BeaconNodeStatus::Uninitialized)
+        .await
+        .update_beacon_nodes()
+        .await
+        .test_get_lighthouse_beacon_health(BeaconNodeStatus::Offline)
+        .await;
+}
+
 #[tokio::test]
 async fn hd_validator_creation() {
     ApiTester::new()
@@ -1315,6 +1795,85 @@ async fn validator_graffiti_api() {
         .await;
 }
 
+#[tokio::test]
+async fn validator_graffiti_template_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .set_graffiti(0, "Mr F was here")
+        .await
+        .test_set_graffiti_template(0, "{el_client}LH #{validator_index}")
+        .await
+        .test_delete_graffiti_template(0)
+        .await
+        .assert_graffiti(0, "Mr F was here")
+        .await;
+}
+
+#[tokio::test]
+async fn validator_pending_exit_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_pending_exits_count(0)
+        .await
+        .test_set_pending_exit(
+            0,
+            ExitCondition::AtEpoch {
+                epoch: Epoch::new(1000),
+            },
+        )
+        .await
+        .test_set_pending_exit(
+            1,
+            ExitCondition::BalanceBelow {
+                balance_gwei: 31_000_000_000,
+            },
+        )
+        .await
+        .assert_pending_exits_count(2)
+        .await
+        // Scheduling a new exit replaces the existing one.
+        .test_set_pending_exit(0, ExitCondition::Eth1WithdrawalCredentials)
+        .await
+        .assert_pending_exits_count(2)
+        .await
+        .test_delete_pending_exit(0)
+        .await
+        .assert_pending_exits_count(1)
+        .await;
+}
+
+#[tokio::test]
+async fn validator_doppelganger_api() {
+    // Doppelganger protection is disabled in the default config.
+    ApiTester::new()
+        .await
+        .assert_doppelganger_statuses(0, DoppelgangerProtectionStatus::Disabled)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_doppelganger_statuses(2, DoppelgangerProtectionStatus::Disabled)
+        .await;
+}
+
 #[tokio::test]
 async fn keystore_validator_creation() {
     ApiTester::new()
@@ -1358,3 +1917,98 @@ async fn web3signer_validator_creation() {
         .assert_enabled_validators_count(1)
         .assert_validators_count(1);
 }
+
+#[tokio::test]
+async fn validator_signing_method_conversion() {
+    ApiTester::new()
+        .await
+        .create_web3signer_validators(Web3SignerValidatorScenario {
+            count: 1,
+            enabled: true,
+        })
+        .await
+        .test_convert_signing_method()
+        .await
+        .assert_enabled_validators_count(2)
+        .assert_validators_count(2);
+}
+
+#[tokio::test]
+async fn slashing_protection_standby() {
+    let config = Config {
+        slashing_protection_standby: true,
+        ..Config::default()
+    };
+    ApiTester::new_with_config(config)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_slashing_protection_standby(true)
+        .await
+        // Promotion is refused until a replication has arrived.
+        .test_promote_slashing_protection_standby(false, false)
+        .await
+        .test_replicate_slashing_protection(0, true)
+        .await
+        // Promotion is refused once the last replication is stale.
+        .advance_epochs(2)
+        .test_promote_slashing_protection_standby(false, false)
+        .await
+        .assert_slashing_protection_standby(true)
+        .await
+        .test_replicate_slashing_protection(0, true)
+        .await
+        .advance_epochs(1)
+        .test_promote_slashing_protection_standby(false, true)
+        .await
+        .assert_slashing_protection_standby(false)
+        .await
+        // Once promoted, replication and promotion are refused.
+        .test_replicate_slashing_protection(1, false)
+        .await
+        .test_promote_slashing_protection_standby(true, false)
+        .await;
+}
+
+#[tokio::test]
+async fn slashing_protection_standby_forced_promotion() {
+    let config = Config {
+        slashing_protection_standby: true,
+        ..Config::default()
+    };
+    ApiTester::new_with_config(config)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_promote_slashing_protection_standby(true, true)
+        .await
+        .assert_slashing_protection_standby(false)
+        .await;
+}
+
+#[tokio::test]
+async fn slashing_protection_replication_refused_by_primary() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_slashing_protection_standby(false)
+        .await
+        .test_replicate_slashing_protection(0, false)
+        .await;
+}
@@ -0,0 +1,57 @@
+use crate::validator_store::{Error as ValidatorStoreError, ValidatorStore};
+use bls::{PublicKey, PublicKeyBytes};
+use eth2::lighthouse_vc::types::{
+    GenericResponse, ThresholdSignableMessage, ThresholdSignatureShare,
+};
+use slog::{debug, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+/// Sign `message` with our key share of the threshold validator `pubkey`, on behalf of a peer.
+pub async fn sign_threshold_share<T: 'static + SlotClock + Clone, E: EthSpec>(
+    pubkey: PublicKey,
+    message: ThresholdSignableMessage<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    log: Logger,
+) -> Result<GenericResponse<ThresholdSignatureShare>, warp::Rejection> {
+    let pubkey_bytes = PublicKeyBytes::from(pubkey);
+    if !validator_store.has_validator(&pubkey_bytes) {
+        return Err(warp_utils::reject::custom_not_found(format!(
+            "{} is disabled or not managed by this validator client",
+            pubkey_bytes.as_hex_string()
+        )));
+    }
+
+    let (share_index, signature) = validator_store
+        .sign_threshold_share(pubkey_bytes, message)
+        .await
+        .map_err(|e| match e {
+            ValidatorStoreError::Slashable(_)
+            | ValidatorStoreError::DoppelgangerProtected(_)
+            | ValidatorStoreError::SlashingProtectionStandby(_)
+            | ValidatorStoreError::GreaterThanCurrentSlot { .. }
+            | ValidatorStoreError::GreaterThanCurrentEpoch { .. } => {
+                warp_utils::reject::custom_bad_request(format!(
+                    "Refusing to sign threshold share: {:?}",
+                    e
+                ))
+            }
+            e => warp_utils::reject::custom_server_error(format!(
+                "Failed to sign threshold share: {:?}",
+                e
+            )),
+        })?;
+
+    debug!(
+        log,
+        "Released threshold share";
+        "validator" => pubkey_bytes.as_hex_string(),
+        "share_index" => share_index,
+    );
+
+    Ok(GenericResponse::from(ThresholdSignatureShare {
+        share_index,
+        signature,
+    }))
+}
@@ -37,6 +37,8 @@ pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const THRESHOLD: &str = "threshold";
+pub const FAILED: &str = "failed";
 
 pub use lighthouse_metrics::*;
 
@@ -84,6 +86,16 @@ lazy_static::lazy_static! {
         "Total count of attempted ContributionAndProof signings",
         &["status"]
     );
+    pub static ref SIGNED_THRESHOLD_SHARES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signed_threshold_shares_total",
+        "Total count of attempted threshold signature share signings for peers",
+        &["status"]
+    );
+    pub static ref SIGNED_REMOTE_SIGNER_REQUESTS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signed_remote_signer_requests_total",
+        "Total count of attempted signings for clients of the remote signer server",
+        &["status"]
+    );
     pub static ref SIGNED_SYNC_SELECTION_PROOFS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
         "vc_signed_sync_selection_proofs_total",
         "Total count of attempted SyncSelectionProof signings",
@@ -109,10 +121,33 @@ lazy_static::lazy_static! {
         "Duration to perform attestation service tasks",
         &["task"]
     );
+    pub static ref ATTESTATION_DATA_CONSENSUS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_attestation_data_consensus_total",
+        "Total count of attestation data consensus outcomes between beacon nodes",
+        &["outcome"]
+    );
+    pub static ref ATTESTATION_DATA_DISAGREEMENTS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_attestation_data_disagreements_total",
+        "Total count of attestation data from each beacon node which disagreed with most beacon nodes",
+        &["endpoint"]
+    );
     pub static ref SLASHING_PROTECTION_PRUNE_TIMES: Result<Histogram> = try_create_histogram(
         "vc_slashing_protection_prune_times_seconds",
         "Time required to prune the slashing protection DB",
     );
+    pub static ref SLASHING_PROTECTION_REPLICATIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_slashing_protection_replications_total",
+        "Total count of attempted replications of slashing protection to the standby",
+        &["status"]
+    );
+    pub static ref SLASHING_PROTECTION_REPLICATION_TIMES: Result<Histogram> = try_create_histogram(
+        "vc_slashing_protection_replication_times_seconds",
+        "Time required to replicate slashing protection to the standby",
+    );
+    pub static ref SLASHING_PROTECTION_STANDBY: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_standby",
+        "Set to 1 while this validator client is a slashing protection standby",
+    );
     pub static ref BLOCK_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_beacon_block_service_task_times_seconds",
         "Duration to perform beacon block service tasks",
@@ -201,6 +236,11 @@ lazy_static::lazy_static! {
         "vc_beacon_node_latency_primary_endpoint",
         "Round-trip latency for the primary BN endpoint",
     );
+    pub static ref VC_BEACON_NODE_HEALTH_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_beacon_node_health_score",
+        "The health score of each BN, lower is healthier",
+        &["endpoint"]
+    );
 }
 
 pub fn gather_prometheus_metrics<E: EthSpec>(
@@ -219,6 +259,13 @@ pub fn gather_prometheus_metrics<E: EthSpec>(
             }
         }
 
+        if let Some(validator_store) = &shared.validator_store {
+            set_gauge(
+                &SLASHING_PROTECTION_STANDBY,
+                validator_store.is_slashing_protection_standby() as i64,
+            );
+        }
+
         if let Some(duties_service) = &shared.duties_service {
             if let Some(slot) = duties_service.slot_clock.now() {
                 let current_epoch = slot.epoch(E::slots_per_epoch());
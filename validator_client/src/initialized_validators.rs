@@ -6,20 +6,26 @@
 //! The `InitializedValidators` struct in this file serves as the source-of-truth of which
 //! validators are managed by this validator client.
 
-use crate::signing_method::SigningMethod;
+use crate::graffiti_file::GraffitiSetting;
+use crate::signing_method::{SigningMethod, ThresholdPeer};
 use account_utils::{
     read_password, read_password_from_user, read_password_string,
     validator_definitions::{
-        self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
-        CONFIG_FILENAME,
+        self, ExitCondition, SigningDefinition, ThresholdDefinition, ValidatorDefinition,
+        ValidatorDefinitions, Web3SignerDefinition, CONFIG_FILENAME,
     },
     ZeroizeString,
 };
+use eth2::lighthouse_vc::http_client::ValidatorClientHttpClient;
 use eth2_keystore::Keystore;
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
-use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Certificate, Client, Error as ReqwestError, Identity,
+};
+use sensitive_url::SensitiveUrl;
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -27,7 +33,7 @@ use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use types::graffiti::GraffitiString;
+use types::graffiti::{GraffitiString, GraffitiTemplate};
 use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
 use url::{ParseError, Url};
 use validator_dir::Builder as ValidatorDirBuilder;
@@ -109,6 +115,8 @@ pub enum Error {
     MissingWeb3SignerClientIdentityPassword,
     InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
     InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
+    /// The API token for the remote signer is not a valid header value.
+    InvalidWeb3SignerApiToken,
     UnableToBuildWeb3SignerClient(ReqwestError),
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
@@ -117,6 +125,12 @@ pub enum Error {
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
     UnableToDeletePasswordFile(PathBuf, io::Error),
+    /// The threshold or share indices of a threshold validator are invalid.
+    InvalidThresholdDefinition(String),
+    /// There is no password for the key share keystore of a threshold validator.
+    MissingThresholdSharePassword(PathBuf),
+    /// Unable to build the client for a threshold validator's peer.
+    InvalidThresholdPeer(String),
 }
 
 impl From<LockfileError> for Error {
@@ -129,11 +143,14 @@ impl From<LockfileError> for Error {
 pub struct InitializedValidator {
     signing_method: Arc<SigningMethod>,
     graffiti: Option<Graffiti>,
+    graffiti_template: Option<GraffitiTemplate>,
     suggested_fee_recipient: Option<Address>,
     gas_limit: Option<u64>,
     builder_proposals: Option<bool>,
     builder_boost_factor: Option<u64>,
     prefer_builder_proposals: Option<bool>,
+    pending_exit: Option<ExitCondition>,
+    doppelganger: Option<bool>,
     /// The validators index in `state.validators`, to be updated by an external service.
     index: Option<u64>,
 }
@@ -151,6 +168,13 @@ impl InitializedValidator {
             .ok(),
             // Web3Signer validators do not have any lockfiles.
             SigningMethod::Web3Signer { .. } => None,
+            SigningMethod::Threshold {
+                ref share_keystore_lockfile,
+                ..
+            } => MutexGuard::try_map(share_keystore_lockfile.lock(), |option_lockfile| {
+                option_lockfile.as_mut()
+            })
+            .ok(),
         }
     }
 
@@ -181,6 +205,14 @@ impl InitializedValidator {
     pub fn get_graffiti(&self) -> Option<Graffiti> {
         self.graffiti
     }
+
+    pub fn get_graffiti_template(&self) -> Option<&GraffitiTemplate> {
+        self.graffiti_template.as_ref()
+    }
+
+    pub fn get_pending_exit(&self) -> Option<&ExitCondition> {
+        self.pending_exit.as_ref()
+    }
 }
 
 fn open_keystore(path: &Path) -> Result<Keystore, Error> {
@@ -312,6 +344,7 @@ impl InitializedValidator {
                                 web3_signer.root_certificate_path.clone(),
                                 web3_signer.client_identity_path.clone(),
                                 web3_signer.client_identity_password.clone(),
+                                web3_signer.api_token.clone(),
                                 request_timeout,
                                 config.web3_signer_keep_alive_timeout,
                                 config.web3_signer_max_idle_connections,
@@ -328,6 +361,7 @@ impl InitializedValidator {
                         web3_signer.root_certificate_path.clone(),
                         web3_signer.client_identity_path.clone(),
                         web3_signer.client_identity_password.clone(),
+                        web3_signer.api_token.clone(),
                         request_timeout,
                         config.web3_signer_keep_alive_timeout,
                         config.web3_signer_max_idle_connections,
@@ -343,16 +377,22 @@ impl InitializedValidator {
                     voting_public_key: def.voting_public_key,
                 }
             }
+            SigningDefinition::Threshold(threshold) => {
+                build_threshold_signing_method(threshold, def.voting_public_key).await?
+            }
         };
 
         Ok(Self {
             signing_method: Arc::new(signing_method),
             graffiti: def.graffiti.map(Into::into),
+            graffiti_template: def.graffiti_template,
             suggested_fee_recipient: def.suggested_fee_recipient,
             gas_limit: def.gas_limit,
             builder_proposals: def.builder_proposals,
             builder_boost_factor: def.builder_boost_factor,
             prefer_builder_proposals: def.prefer_builder_proposals,
+            pending_exit: def.pending_exit,
+            doppelganger: def.doppelganger,
             index: None,
         })
     }
@@ -363,6 +403,9 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
             SigningMethod::Web3Signer {
                 voting_public_key, ..
+            }
+            | SigningMethod::Threshold {
+                voting_public_key, ..
             } => voting_public_key,
         }
     }
@@ -390,6 +433,100 @@ pub fn load_pkcs12_identity<P: AsRef<Path>>(
         .map_err(Error::InvalidWeb3SignerClientIdentityCertificate)
 }
 
+/// Decrypt the local key share and build clients for the peers of a threshold validator.
+async fn build_threshold_signing_method(
+    threshold: ThresholdDefinition,
+    voting_public_key: PublicKey,
+) -> Result<SigningMethod, Error> {
+    let ThresholdDefinition {
+        threshold,
+        share_index,
+        share_keystore_path,
+        share_keystore_password_path,
+        share_keystore_password,
+        peers,
+    } = threshold;
+
+    let share_count = peers.len() + 1;
+    if threshold == 0 || threshold > share_count {
+        return Err(Error::InvalidThresholdDefinition(format!(
+            "threshold {} is invalid for {} key shares",
+            threshold, share_count
+        )));
+    }
+    let mut share_indices = HashSet::new();
+    for index in std::iter::once(share_index).chain(peers.iter().map(|peer| peer.share_index)) {
+        if index == 0 || !share_indices.insert(index) {
+            return Err(Error::InvalidThresholdDefinition(format!(
+                "share index {} is zero or duplicated",
+                index
+            )));
+        }
+    }
+
+    let share_keystore = open_keystore(&share_keystore_path)?;
+    let password = match (share_keystore_password, share_keystore_password_path) {
+        (Some(password), _) => password,
+        (None, Some(path)) => {
+            read_password_string(path).map_err(Error::UnableToReadValidatorPassword)?
+        }
+        (None, None) => return Err(Error::MissingThresholdSharePassword(share_keystore_path)),
+    };
+    // Decrypting the keystore is slow, so keep it off the core executor.
+    let share_keypair = tokio::task::spawn_blocking(move || {
+        share_keystore
+            .decrypt_keypair(password.as_ref())
+            .map_err(Error::UnableToDecryptKeystore)
+    })
+    .await
+    .map_err(Error::TokioJoin)??;
+
+    let lockfile_path = get_lockfile_path(&share_keystore_path)
+        .ok_or_else(|| Error::BadVotingKeystorePath(share_keystore_path.clone()))?;
+    let share_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
+
+    let peers = peers
+        .into_iter()
+        .map(|peer| {
+            let url = SensitiveUrl::parse(&peer.url)
+                .map_err(|e| Error::InvalidThresholdPeer(format!("{:?}", e)))?;
+            let api_token =
+                ValidatorClientHttpClient::load_api_token_from_file(&peer.api_token_path)
+                    .map_err(|e| Error::InvalidThresholdPeer(format!("{:?}", e)))?;
+            let request_timeout = peer
+                .request_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+            let http_client = Client::builder()
+                .timeout(request_timeout)
+                .build()
+                .map_err(|e| Error::InvalidThresholdPeer(e.to_string()))?;
+            let client = ValidatorClientHttpClient::from_components(
+                url,
+                http_client,
+                api_token.as_str().to_string(),
+            )
+            .map_err(|e| Error::InvalidThresholdPeer(format!("{:?}", e)))?;
+
+            Ok(ThresholdPeer {
+                share_index: peer.share_index,
+                share_public_key: peer.share_public_key,
+                client,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(SigningMethod::Threshold {
+        voting_public_key,
+        threshold,
+        share_index,
+        share_keystore_path,
+        share_keystore_lockfile,
+        share_keypair: Arc::new(share_keypair),
+        peers,
+    })
+}
+
 fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Result<Url, ParseError> {
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
@@ -398,6 +535,7 @@ fn build_web3_signer_client(
     root_certificate_path: Option<PathBuf>,
     client_identity_path: Option<PathBuf>,
     client_identity_password: Option<String>,
+    api_token: Option<String>,
     request_timeout: Duration,
     keep_alive_timeout: Option<Duration>,
     max_idle_connections: Option<usize>,
@@ -427,6 +565,17 @@ fn build_web3_signer_client(
         builder
     };
 
+    let builder = if let Some(api_token) = api_token {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", api_token))
+            .map_err(|_| Error::InvalidWeb3SignerApiToken)?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value);
+        builder.default_headers(headers)
+    } else {
+        builder
+    };
+
     builder
         .build()
         .map_err(Error::UnableToBuildWeb3SignerClient)
@@ -696,6 +845,156 @@ impl InitializedValidators {
         Ok(keystore_and_password)
     }
 
+    /// Replace the signing definition of the validator with `pubkey` by `signing_definition`,
+    /// converting it from a local keystore to a remote signer or vice versa.
+    ///
+    /// The new signing method is initialized before it replaces the existing one, so the validator
+    /// is never left without a signing method, nor with two. The voting public key doesn't change,
+    /// so the validator keeps its slashing protection history. If the validator was using a local
+    /// keystore, the keystore is deleted once it has been replaced.
+    pub async fn convert_signing_method(
+        &mut self,
+        pubkey: &PublicKey,
+        signing_definition: SigningDefinition,
+    ) -> Result<(), Error> {
+        let def_index = self
+            .definitions
+            .as_slice()
+            .iter()
+            .position(|def| &def.voting_public_key == pubkey)
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+        let old_def = self.definitions.as_slice()[def_index].clone();
+
+        match (&old_def.signing_definition, &signing_definition) {
+            (SigningDefinition::LocalKeystore { .. }, SigningDefinition::Web3Signer(_))
+            | (SigningDefinition::Web3Signer(_), SigningDefinition::LocalKeystore { .. }) => (),
+            _ => return Err(Error::InvalidActionOnValidator),
+        }
+
+        let mut new_def = old_def.clone();
+        new_def.signing_definition = signing_definition;
+
+        // 1. Initialize the new signing method.
+        //
+        // Nothing has been modified yet, so the validator continues to use its existing signing
+        // method if this fails.
+        let new_validator = if new_def.enabled {
+            Some(
+                InitializedValidator::from_definition(
+                    new_def.clone(),
+                    &mut KeyCache::new(),
+                    &mut HashMap::new(),
+                    &mut self.web3_signer_client_map,
+                    &self.config,
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+
+        // 2. Remove the old keystore from the key cache, whilst it is still defined so that the
+        // rest of the key cache can be decrypted.
+        let old_keystore = match &old_def.signing_definition {
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                ..
+            } => {
+                let keystore = open_keystore(voting_keystore_path)?;
+                let key_cache = KeyCache::open_or_create(&self.validators_dir)
+                    .map_err(Error::UnableToOpenKeyCache)?;
+                let mut decrypted_key_cache = self
+                    .decrypt_key_cache(key_cache, &mut <_>::default(), OnDecryptFailure::CreateNew)
+                    .await?;
+                decrypted_key_cache.remove(keystore.uuid());
+                decrypted_key_cache
+                    .save(&self.validators_dir)
+                    .map_err(Error::UnableToSaveKeyCache)?;
+                Some(keystore)
+            }
+            SigningDefinition::Web3Signer(_) | SigningDefinition::Threshold(_) => None,
+        };
+
+        // 3. Save the new definition, restoring the old one if it can't be saved.
+        self.definitions.as_mut_slice()[def_index] = new_def;
+        if let Err(e) = self.definitions.save(&self.validators_dir) {
+            self.definitions.as_mut_slice()[def_index] = old_def;
+            return Err(Error::UnableToSaveDefinitions(e));
+        }
+
+        // 4. Swap the signing method in a single step.
+        let pubkey_bytes = pubkey.compress();
+        let old_validator = if let Some(mut new_validator) = new_validator {
+            new_validator.index = self.get_index(&pubkey_bytes);
+            self.validators.insert(pubkey_bytes, new_validator)
+        } else {
+            None
+        };
+
+        info!(
+            self.log,
+            "Converted validator signing method";
+            "signing_method" => if old_keystore.is_some() { "remote_signer" } else { "local_keystore" },
+            "voting_pubkey" => ?pubkey,
+        );
+
+        // 5. Delete the old keystore and its password file, if it's not used by any definition.
+        //
+        // The conversion has already taken effect, so failures are logged rather than returned.
+        if let (
+            Some(keystore),
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path,
+                ..
+            },
+        ) = (old_keystore, old_def.signing_definition)
+        {
+            if let Some(old_validator) = old_validator {
+                if let SigningMethod::LocalKeystore {
+                    ref voting_keystore_lockfile,
+                    ..
+                } = *old_validator.signing_method
+                {
+                    // Drop the lock file so that it may be deleted.
+                    drop(voting_keystore_lockfile.lock().take());
+                }
+            }
+
+            if let Err(e) = self.delete_keystore_or_validator_dir(&voting_keystore_path, &keystore)
+            {
+                warn!(
+                    self.log,
+                    "Unable to delete converted keystore";
+                    "error" => ?e,
+                    "path" => %voting_keystore_path.display(),
+                );
+            }
+
+            if let Some(password_path) =
+                voting_keystore_password_path.and_then(|p| p.canonicalize().ok())
+            {
+                if self
+                    .definitions
+                    .iter_voting_keystore_password_paths()
+                    .filter_map(|existing| existing.canonicalize().ok())
+                    .all(|existing| existing != password_path)
+                {
+                    if let Err(e) = fs::remove_file(&password_path) {
+                        warn!(
+                            self.log,
+                            "Unable to delete keystore password file";
+                            "error" => ?e,
+                            "path" => %password_path.display(),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Attempt to delete the voting keystore file, or its entire validator directory.
     ///
     /// Some parts of the VC assume the existence of a validator based on the existence of a
@@ -810,11 +1109,97 @@ impl InitializedValidators {
         Ok(())
     }
 
-    /// Returns a `HashMap` of `public_key` -> `graffiti` for all initialized validators.
-    pub fn get_all_validators_graffiti(&self) -> HashMap<&PublicKeyBytes, Option<Graffiti>> {
+    /// Returns the `graffiti_template` for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn graffiti_template(&self, public_key: &PublicKeyBytes) -> Option<GraffitiTemplate> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.graffiti_template.clone())
+    }
+
+    /// Sets the `InitializedValidator` and `ValidatorDefinition` `graffiti_template` values.
+    ///
+    /// ## Notes
+    ///
+    /// Setting a validator `graffiti_template` will cause `self.definitions` to be updated and
+    /// saved to disk.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_graffiti_template(
+        &mut self,
+        voting_public_key: &PublicKey,
+        graffiti_template: GraffitiTemplate,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.graffiti_template = Some(graffiti_template.clone());
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.graffiti_template = Some(graffiti_template);
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+        Ok(())
+    }
+
+    /// Removes the `InitializedValidator` and `ValidatorDefinition` `graffiti_template` values.
+    ///
+    /// ## Notes
+    ///
+    /// Removing a validator `graffiti_template` will cause `self.definitions` to be updated and
+    /// saved to disk. The validator will then fall back to its `graffiti`, if it is set.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn delete_graffiti_template(&mut self, voting_public_key: &PublicKey) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.graffiti_template = None;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.graffiti_template = None;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
+    /// Returns the graffiti setting for a given public key specified in the
+    /// `ValidatorDefinitions`. The `graffiti_template` takes precedence over the `graffiti`.
+    pub fn graffiti_setting(&self, public_key: &PublicKeyBytes) -> Option<GraffitiSetting> {
+        let validator = self.validators.get(public_key)?;
+        validator
+            .graffiti_template
+            .clone()
+            .map(GraffitiSetting::Template)
+            .or(validator.graffiti.map(GraffitiSetting::Fixed))
+    }
+
+    /// Returns a `HashMap` of `public_key` -> graffiti setting for all initialized validators.
+    pub fn get_all_validators_graffiti(&self) -> HashMap<&PublicKeyBytes, Option<GraffitiSetting>> {
         let mut result = HashMap::new();
         for public_key in self.validators.keys() {
-            result.insert(public_key, self.graffiti(public_key));
+            result.insert(public_key, self.graffiti_setting(public_key));
         }
         result
     }
@@ -857,6 +1242,92 @@ impl InitializedValidators {
             .and_then(|v| v.prefer_builder_proposals)
     }
 
+    /// Returns the `doppelganger` override for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn doppelganger(&self, public_key: &PublicKeyBytes) -> Option<bool> {
+        self.validators.get(public_key).and_then(|v| v.doppelganger)
+    }
+
+    /// Returns a `Vec` of `public_key` -> `pending_exit` for all initialized validators with a
+    /// pending exit.
+    pub fn pending_exits(&self) -> Vec<(PublicKeyBytes, ExitCondition)> {
+        self.validators
+            .iter()
+            .filter_map(|(public_key, v)| {
+                v.pending_exit
+                    .clone()
+                    .map(|condition| (*public_key, condition))
+            })
+            .collect()
+    }
+
+    /// Sets the `InitializedValidator` and `ValidatorDefinition` `pending_exit` values.
+    ///
+    /// ## Notes
+    ///
+    /// Setting a validator `pending_exit` will cause `self.definitions` to be updated and saved to
+    /// disk.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_pending_exit(
+        &mut self,
+        voting_public_key: &PublicKey,
+        pending_exit: ExitCondition,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.pending_exit = Some(pending_exit.clone());
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.pending_exit = Some(pending_exit);
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+        Ok(())
+    }
+
+    /// Removes the `InitializedValidator` and `ValidatorDefinition` `pending_exit` values.
+    ///
+    /// ## Notes
+    ///
+    /// Removing a validator `pending_exit` will cause `self.definitions` to be updated and saved to
+    /// disk.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn delete_pending_exit(&mut self, voting_public_key: &PublicKey) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.pending_exit = None;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.pending_exit = None;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
     /// Returns an `Option` of a reference to an `InitializedValidator` for a given public key specified in the
     /// `ValidatorDefinitions`.
     pub fn validator(&self, public_key: &PublicKeyBytes) -> Option<&InitializedValidator> {
@@ -1123,8 +1594,8 @@ impl InitializedValidators {
                     };
                     definitions_map.insert(*key_store.uuid(), def);
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and threshold validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::Threshold(_) => (),
             }
         }
 
@@ -1167,8 +1638,8 @@ impl InitializedValidators {
                     passwords.push(pw);
                     public_keys.push(def.voting_public_key.clone());
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and threshold validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::Threshold(_) => (),
             };
         }
 
@@ -1323,6 +1794,41 @@ impl InitializedValidators {
                                     "validator" => format!("{:?}", def.voting_public_key)
                                 );
 
+                                // Exit on an invalid validator.
+                                return Err(e);
+                            }
+                        }
+                    }
+                    SigningDefinition::Threshold(_) => {
+                        match InitializedValidator::from_definition(
+                            def.clone(),
+                            &mut key_cache,
+                            &mut key_stores,
+                            &mut None,
+                            &self.config,
+                        )
+                        .await
+                        {
+                            Ok(init) => {
+                                self.validators
+                                    .insert(init.voting_public_key().compress(), init);
+
+                                info!(
+                                    self.log,
+                                    "Enabled validator";
+                                    "signing_method" => "threshold",
+                                    "voting_pubkey" => format!("{:?}", def.voting_public_key),
+                                );
+                            }
+                            Err(e) => {
+                                error!(
+                                    self.log,
+                                    "Failed to initialize validator";
+                                    "error" => format!("{:?}", e),
+                                    "signing_method" => "threshold",
+                                    "validator" => format!("{:?}", def.voting_public_key)
+                                );
+
                                 // Exit on an invalid validator.
                                 return Err(e);
                             }
@@ -1340,8 +1846,8 @@ impl InitializedValidators {
                             disabled_uuids.insert(*key_store.uuid());
                         }
                     }
-                    // Remote signers do not interact with the key cache.
-                    SigningDefinition::Web3Signer { .. } => (),
+                    // Remote signers and threshold validators do not interact with the key cache.
+                    SigningDefinition::Web3Signer { .. } | SigningDefinition::Threshold(_) => (),
                 }
 
                 info!(
@@ -1419,8 +1925,8 @@ impl InitializedValidators {
                         passwords.insert(def.voting_public_key.clone(), password);
                     }
                 }
-                // Remote signers don't have passwords.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signers don't have passwords. Threshold key share passwords are kept.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::Threshold(_) => (),
             };
         }
 
@@ -1,16 +1,21 @@
+mod attestation_consensus;
 mod attestation_service;
 mod beacon_node_fallback;
+mod beacon_node_health;
 mod block_service;
 mod check_synced;
 mod cli;
 mod duties_service;
+mod exit_service;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
 mod latency;
 mod notifier;
 mod preparation_service;
+mod remote_signer_server;
 mod signing_method;
+mod slashing_protection_replication;
 mod sync_committee_service;
 
 pub mod config;
@@ -19,6 +24,7 @@ pub mod http_api;
 pub mod initialized_validators;
 pub mod validator_store;
 
+pub use attestation_consensus::{AttestationConsensus, DisagreementAction};
 pub use beacon_node_fallback::ApiTopic;
 pub use cli::cli_app;
 pub use config::Config;
@@ -33,7 +39,7 @@ use crate::beacon_node_fallback::{
     RequireSynced,
 };
 use crate::doppelganger_service::DoppelgangerService;
-use crate::graffiti_file::GraffitiFile;
+use crate::graffiti_file::{GraffitiFile, GraffitiSetting};
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
 use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
@@ -429,13 +435,21 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         let proposer_nodes = Arc::new(proposer_nodes);
         start_fallback_updater_service(context.clone(), proposer_nodes.clone())?;
 
-        let doppelganger_service = if config.enable_doppelganger_protection {
-            Some(Arc::new(DoppelgangerService::new(
-                context
-                    .service_context(DOPPELGANGER_SERVICE_NAME.into())
-                    .log()
-                    .clone(),
-            )))
+        // Validators may opt in to doppelganger protection even when it's disabled by default.
+        let doppelganger_opt_in = validators
+            .validator_definitions()
+            .iter()
+            .any(|def| def.enabled && def.doppelganger == Some(true));
+        let doppelganger_service = if config.enable_doppelganger_protection || doppelganger_opt_in {
+            Some(Arc::new(
+                DoppelgangerService::new(
+                    context
+                        .service_context(DOPPELGANGER_SERVICE_NAME.into())
+                        .log()
+                        .clone(),
+                )
+                .with_lookback_epochs(config.doppelganger_lookback_epochs),
+            ))
         } else {
             None
         };
@@ -510,6 +524,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .attestation_consensus(config.attestation_consensus)
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -561,6 +576,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -589,6 +605,34 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        if self.config.remote_signer_server.enabled {
+            let ctx = Arc::new(remote_signer_server::Context {
+                config: self.config.remote_signer_server.clone(),
+                api_secret: ApiSecret::create_or_open(&self.config.validator_dir)?,
+                validator_store: self.validator_store.clone(),
+                log: log.clone(),
+            });
+
+            let exit = self.context.executor.exit();
+
+            let (_listen_addr, server) = remote_signer_server::serve(ctx, exit)
+                .map_err(|e| format!("Unable to start remote signer server: {:?}", e))?;
+
+            self.context
+                .clone()
+                .executor
+                .spawn_without_exit(server, "remote-signer-server");
+        }
+
+        if self.validator_store.is_slashing_protection_standby() {
+            warn!(
+                log,
+                "Running as slashing protection standby";
+                "msg" => "no slashable messages will be signed until this validator client is \
+                    promoted via the HTTP API",
+            );
+        }
+
         // Wait until genesis has occurred.
         wait_for_genesis(&self.beacon_nodes, self.genesis_time, &self.context).await?;
 
@@ -638,6 +682,24 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             );
         }
 
+        exit_service::start_exit_service(
+            self.context.service_context("exit".into()),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+            self.duties_service.slot_clock.clone(),
+        );
+
+        if let Some(replication_config) = self.config.slashing_protection_replication.clone() {
+            slashing_protection_replication::start_slashing_protection_replication_service(
+                self.context
+                    .service_context("slashing_protection_replication".into()),
+                replication_config,
+                self.validator_store.clone(),
+                self.duties_service.slot_clock.clone(),
+            )
+            .map_err(|e| format!("Unable to start slashing protection replication: {}", e))?;
+        }
+
         Ok(())
     }
 }
@@ -852,9 +914,9 @@ pub fn determine_graffiti(
     validator_pubkey: &PublicKeyBytes,
     log: &Logger,
     graffiti_file: Option<GraffitiFile>,
-    validator_definition_graffiti: Option<Graffiti>,
+    validator_definition_graffiti: Option<GraffitiSetting>,
     graffiti_flag: Option<Graffiti>,
-) -> Option<Graffiti> {
+) -> Option<GraffitiSetting> {
     graffiti_file
         .and_then(|mut g| match g.load_graffiti(validator_pubkey) {
             Ok(g) => g,
@@ -864,5 +926,5 @@ pub fn determine_graffiti(
             }
         })
         .or(validator_definition_graffiti)
-        .or(graffiti_flag)
+        .or(graffiti_flag.map(GraffitiSetting::Fixed))
 }
@@ -0,0 +1,787 @@
+//! Provides a HTTP server which implements the signing API of Web3Signer, allowing other validator
+//! clients to use this one as a remote signer.
+//!
+//! Only validators with a local keystore are served. Every message is signed by the
+//! `ValidatorStore`, so blocks and attestations are checked against the slashing protection
+//! database of this validator client before they are signed.
+//!
+//! Voluntary exits, RANDAO reveals and validator registrations are not covered by slashing
+//! protection, so they are only signed if `Config::allow_unprotected_messages` is set.
+//!
+//! Every request must carry the API token of this validator client in its `Authorization` header,
+//! just like the HTTP API. The server has no encryption, so it should only be exposed to trusted
+//! hosts, e.g. via a HTTPS reverse-proxy.
+use crate::http_api::ApiSecret;
+use crate::signing_method::{MessageType, OwnedSigningRequest, SigningMethod, SigningResponse};
+use crate::validator_store::{Error as ValidatorStoreError, ValidatorStore};
+use lighthouse_version::version_with_platform;
+use serde::{Deserialize, Serialize};
+use slog::{crit, debug, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use types::{EthSpec, PublicKeyBytes};
+use warp::{
+    http::{header::CONTENT_TYPE, response::Response, StatusCode},
+    hyper::body::Bytes,
+    Filter, Reply,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Warp(#[allow(dead_code)] warp::Error),
+    Other(#[allow(dead_code)] String),
+}
+
+impl From<warp::Error> for Error {
+    fn from(e: warp::Error) -> Self {
+        Error::Warp(e)
+    }
+}
+
+impl From<String> for Error {
+    fn from(e: String) -> Self {
+        Error::Other(e)
+    }
+}
+
+/// A wrapper around all the items required to spawn the HTTP server.
+pub struct Context<T: SlotClock, E: EthSpec> {
+    pub config: Config,
+    pub api_secret: ApiSecret,
+    pub validator_store: Arc<ValidatorStore<T, E>>,
+    pub log: Logger,
+}
+
+/// Configuration for the HTTP server.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub enabled: bool,
+    pub listen_addr: IpAddr,
+    pub listen_port: u16,
+    /// Sign voluntary exits, RANDAO reveals and validator registrations, which are not checked
+    /// against the slashing protection database.
+    pub allow_unprotected_messages: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            listen_port: 5066,
+            allow_unprotected_messages: false,
+        }
+    }
+}
+
+/// Creates a server that will serve requests using information from `ctx`.
+///
+/// The server will shut down gracefully when the `shutdown` future resolves.
+///
+/// ## Returns
+///
+/// This function will bind the server to the provided address and then return a tuple of:
+///
+/// - `SocketAddr`: the address that the HTTP server will listen on.
+/// - `Future`: the actual server future that will need to be awaited.
+///
+/// ## Errors
+///
+/// Returns an error if the server is unable to bind or there is another error during
+/// configuration.
+pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
+    ctx: Arc<Context<T, E>>,
+    shutdown: impl Future<Output = ()> + Send + Sync + 'static,
+) -> Result<(SocketAddr, impl Future<Output = ()>), Error> {
+    let config = &ctx.config;
+    let log = ctx.log.clone();
+
+    // Sanity check.
+    if !config.enabled {
+        crit!(log, "Cannot start disabled remote signer HTTP server");
+        return Err(Error::Other(
+            "A disabled remote signer server should not be started".to_string(),
+        ));
+    }
+
+    let authorization_header_filter = ctx.api_secret.authorization_header_filter();
+
+    let inner_ctx = ctx.clone();
+    let ctx_filter = warp::any().map(move || inner_ctx.clone());
+
+    // GET upcheck
+    let get_upcheck = warp::path("upcheck")
+        .and(warp::path::end())
+        .and(warp::get())
+        .map(|| text_response(StatusCode::OK, "OK".to_string()));
+
+    // GET api/v1/eth2/publicKeys
+    let get_public_keys = warp::path("api")
+        .and(warp::path("v1"))
+        .and(warp::path("eth2"))
+        .and(warp::path("publicKeys"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(ctx_filter.clone())
+        .map(|ctx: Arc<Context<T, E>>| {
+            let public_keys = local_keystore_pubkeys(&ctx.validator_store);
+            warp::reply::json(&public_keys).into_response()
+        });
+
+    // POST api/v1/eth2/sign/{identifier}
+    let post_sign =
+        warp::path("api")
+            .and(warp::path("v1"))
+            .and(warp::path("eth2"))
+            .and(warp::path("sign"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::header::optional::<String>("accept"))
+            .and(warp::body::bytes())
+            .and(ctx_filter)
+            .then(
+                |identifier: String,
+                 accept: Option<String>,
+                 body: Bytes,
+                 ctx: Arc<Context<T, E>>| async move {
+                    let json = accept.is_some_and(|accept| accept.contains("application/json"));
+                    match sign(&identifier, &body, &ctx).await {
+                        Ok(signature) if json => {
+                            warp::reply::json(&SigningResponse { signature }).into_response()
+                        }
+                        Ok(signature) => text_response(StatusCode::OK, signature.to_string()),
+                        Err((status, message)) => {
+                            debug!(
+                                ctx.log,
+                                "Refused remote signing request";
+                                "identifier" => identifier,
+                                "status" => %status,
+                                "error" => &message,
+                            );
+                            text_response(status, message)
+                        }
+                    }
+                },
+            );
+
+    let routes = warp::any()
+        .and(authorization_header_filter)
+        // Note: it is critical that the `authorization_header_filter` is applied to all routes.
+        .and(
+            get_upcheck
+                .or(get_public_keys)
+                .unify()
+                .or(post_sign)
+                .unify(),
+        )
+        .recover(warp_utils::reject::handle_rejection)
+        // Add a `Server` header.
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()));
+
+    let (listening_socket, server) = warp::serve(routes).try_bind_with_graceful_shutdown(
+        SocketAddr::new(config.listen_addr, config.listen_port),
+        async {
+            shutdown.await;
+        },
+    )?;
+
+    info!(
+        log,
+        "Remote signer HTTP server started";
+        "listen_address" => listening_socket.to_string(),
+    );
+
+    Ok((listening_socket, server))
+}
+
+fn text_response(status: StatusCode, body: String) -> warp::reply::Response {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain")
+        .body(body.into())
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Returns the public keys of the enabled validators which can be signed for by this server.
+fn local_keystore_pubkeys<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+) -> Vec<PublicKeyBytes> {
+    let initialized_validators = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators.read();
+    initialized_validators
+        .iter_voting_pubkeys()
+        .filter(|pubkey| is_local_keystore(initialized_validators.signing_method(pubkey)))
+        .copied()
+        .collect()
+}
+
+fn is_local_keystore(signing_method: Option<Arc<SigningMethod>>) -> bool {
+    matches!(
+        signing_method.as_deref(),
+        Some(SigningMethod::LocalKeystore { .. })
+    )
+}
+
+/// Sign the request in `body` with the key identified by `identifier`.
+///
+/// Errors are returned with the status code used by Web3Signer for the same failure.
+async fn sign<T: SlotClock + 'static, E: EthSpec>(
+    identifier: &str,
+    body: &[u8],
+    ctx: &Context<T, E>,
+) -> Result<types::Signature, (StatusCode, String)> {
+    let validator_store = &ctx.validator_store;
+
+    let pubkey = identifier.parse::<PublicKeyBytes>().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid identifier: {}", e),
+        )
+    })?;
+    let signing_method = validator_store
+        .initialized_validators()
+        .read()
+        .signing_method(&pubkey);
+    if !is_local_keystore(signing_method) {
+        return Err((StatusCode::NOT_FOUND, "Public Key not found".to_string()));
+    }
+
+    let request = serde_json::from_slice::<OwnedSigningRequest<E>>(body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+    if !ctx.config.allow_unprotected_messages
+        && matches!(
+            request.message_type,
+            MessageType::VoluntaryExit
+                | MessageType::RandaoReveal
+                | MessageType::ValidatorRegistration
+        )
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!(
+                "Signing not allowed: {:?} messages are disabled on this server",
+                request.message_type
+            ),
+        ));
+    }
+    if request.message_type != request.object.message_type() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Invalid request: type {:?} does not match the message",
+                request.message_type
+            ),
+        ));
+    }
+    // Only deposits and validator registrations are signed with a fork-independent domain,
+    // everything else must be checked against this validator client's fork schedule.
+    match &request.fork_info {
+        Some(fork_info) => {
+            if fork_info.genesis_validators_root != validator_store.genesis_validators_root() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Invalid request: unknown genesis_validators_root".to_string(),
+                ));
+            }
+            // The signing domain is always computed from this validator client's fork schedule,
+            // so refuse requests from a client which is following a different schedule.
+            if !validator_store.is_known_fork(&fork_info.fork) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid request: unknown fork {:?}", fork_info.fork),
+                ));
+            }
+        }
+        None if !matches!(
+            request.message_type,
+            MessageType::Deposit | MessageType::ValidatorRegistration
+        ) =>
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Invalid request: fork_info is required for {:?} messages",
+                    request.message_type
+                ),
+            ));
+        }
+        None => (),
+    }
+    let message = request
+        .object
+        .into_signable_message()
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+
+    validator_store
+        .sign_remote_signer_request(pubkey, message, request.signing_root)
+        .await
+        .map_err(|e| match e {
+            ValidatorStoreError::UnknownPubkey(_) => {
+                (StatusCode::NOT_FOUND, "Public Key not found".to_string())
+            }
+            ValidatorStoreError::SigningRootMismatch { .. } => {
+                (StatusCode::BAD_REQUEST, format!("Invalid request: {:?}", e))
+            }
+            ValidatorStoreError::Slashable(_)
+            | ValidatorStoreError::DoppelgangerProtected(_)
+            | ValidatorStoreError::SlashingProtectionStandby(_)
+            | ValidatorStoreError::GreaterThanCurrentSlot { .. }
+            | ValidatorStoreError::GreaterThanCurrentEpoch { .. } => (
+                StatusCode::PRECONDITION_FAILED,
+                format!("Signing not allowed: {:?}", e),
+            ),
+            e => {
+                warn!(
+                    ctx.log,
+                    "Failed to sign remote signing request";
+                    "validator" => ?pubkey,
+                    "error" => ?e,
+                );
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to sign: {:?}", e),
+                )
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doppelganger_service::DoppelgangerService;
+    use crate::initialized_validators::InitializedValidators;
+    use account_utils::validator_definitions::{
+        PasswordStorage, ValidatorDefinition, ValidatorDefinitions,
+    };
+    use account_utils::ZeroizeString;
+    use eth2_keystore::json_keystore::{Kdf, Scrypt};
+    use eth2_keystore::{KeystoreBuilder, DKLEN};
+    use logging::test_logger;
+    use serde_json::{json, Value};
+    use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+    use slot_clock::TestingSlotClock;
+    use std::fs::File;
+    use std::time::Duration;
+    use task_executor::test_utils::TestRuntime;
+    use tempfile::{tempdir, TempDir};
+    use tokio::sync::oneshot;
+    use types::{
+        AttestationData, ChainSpec, Checkpoint, Domain, Epoch, Fork, Hash256, Keypair,
+        MainnetEthSpec, SignedRoot, Slot,
+    };
+
+    type E = MainnetEthSpec;
+
+    /// The epoch the tests run in. It is after genesis so that doppelganger protection applies.
+    const CURRENT_EPOCH: u64 = 3;
+    const GENESIS_VALIDATORS_ROOT: Hash256 = Hash256::repeat_byte(42);
+    const PASSWORD: &str = "password";
+
+    struct Tester {
+        url: String,
+        api_token: String,
+        client: reqwest::Client,
+        spec: ChainSpec,
+        validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+        validator_dir: TempDir,
+        _test_runtime: TestRuntime,
+        _server_shutdown: oneshot::Sender<()>,
+    }
+
+    impl Tester {
+        async fn new(config: Config) -> Self {
+            let log = test_logger();
+            let validator_dir = tempdir().unwrap();
+
+            let validator_defs =
+                ValidatorDefinitions::open_or_create(validator_dir.path()).unwrap();
+            let initialized_validators = InitializedValidators::from_definitions(
+                validator_defs,
+                validator_dir.path().into(),
+                Default::default(),
+                log.clone(),
+            )
+            .await
+            .unwrap();
+            let slashing_protection = SlashingDatabase::open_or_create(
+                &validator_dir.path().join(SLASHING_PROTECTION_FILENAME),
+            )
+            .unwrap();
+
+            let spec = E::default_spec();
+            let slot_clock =
+                TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
+            slot_clock.set_slot(current_slot().as_u64());
+
+            let test_runtime = TestRuntime::default();
+            let validator_store = Arc::new(ValidatorStore::<_, E>::new(
+                initialized_validators,
+                slashing_protection,
+                GENESIS_VALIDATORS_ROOT,
+                spec.clone(),
+                Some(Arc::new(DoppelgangerService::new(log.clone()))),
+                slot_clock,
+                &crate::Config::default(),
+                test_runtime.task_executor.clone(),
+                log.clone(),
+            ));
+
+            let api_secret = ApiSecret::create_or_open(validator_dir.path()).unwrap();
+            let api_token = api_secret.api_token();
+
+            let ctx = Arc::new(Context {
+                config: Config {
+                    enabled: true,
+                    listen_port: 0,
+                    ..config
+                },
+                api_secret,
+                validator_store: validator_store.clone(),
+                log,
+            });
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            let server_shutdown = async {
+                let _ = shutdown_rx.await;
+            };
+            let (listening_socket, server) = serve(ctx, server_shutdown).unwrap();
+            tokio::spawn(server);
+
+            Self {
+                url: format!("http://{}", listening_socket),
+                api_token,
+                client: reqwest::Client::new(),
+                spec,
+                validator_store,
+                validator_dir,
+                _test_runtime: test_runtime,
+                _server_shutdown: shutdown_tx,
+            }
+        }
+
+        /// Adds a validator with a local keystore, returning its keypair.
+        async fn add_validator(&self, doppelganger_protection: bool) -> Keypair {
+            let keypair = Keypair::random();
+            let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), String::new())
+                .unwrap()
+                .kdf(insecure_kdf())
+                .build()
+                .unwrap();
+            let path = self
+                .validator_dir
+                .path()
+                .join(format!("{}.json", keystore.uuid()));
+            keystore
+                .to_json_writer(File::create(&path).unwrap())
+                .unwrap();
+
+            let mut validator_def = ValidatorDefinition::new_keystore_with_password(
+                &path,
+                PasswordStorage::ValidatorDefinitions(ZeroizeString::from(PASSWORD.to_string())),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            validator_def.doppelganger = Some(doppelganger_protection);
+            self.validator_store
+                .add_validator(validator_def)
+                .await
+                .unwrap();
+
+            keypair
+        }
+
+        fn fork(&self) -> Fork {
+            self.spec.fork_at_epoch(Epoch::new(CURRENT_EPOCH))
+        }
+
+        fn fork_info(&self, fork: Fork) -> Value {
+            json!({
+                "fork": fork,
+                "genesis_validators_root": GENESIS_VALIDATORS_ROOT,
+            })
+        }
+
+        fn attestation_request(&self, data: &AttestationData) -> Value {
+            json!({
+                "type": "ATTESTATION",
+                "fork_info": self.fork_info(self.fork()),
+                "attestation": data,
+            })
+        }
+
+        fn randao_reveal_request(&self) -> Value {
+            json!({
+                "type": "RANDAO_REVEAL",
+                "fork_info": self.fork_info(self.fork()),
+                "randao_reveal": { "epoch": Epoch::new(CURRENT_EPOCH) },
+            })
+        }
+
+        fn attestation_signing_root(&self, data: &AttestationData) -> Hash256 {
+            let domain = self.spec.get_domain(
+                data.target.epoch,
+                Domain::BeaconAttester,
+                &self.fork(),
+                GENESIS_VALIDATORS_ROOT,
+            );
+            data.signing_root(domain)
+        }
+
+        /// Sends `request` to the signing endpoint for `pubkey`, returning the status code and the
+        /// signature if the request succeeded.
+        async fn sign(
+            &self,
+            pubkey: PublicKeyBytes,
+            request: &Value,
+        ) -> (u16, Option<types::Signature>) {
+            self.sign_with_token(pubkey, request, &self.api_token).await
+        }
+
+        /// Like `sign`, but authenticates with `api_token`.
+        async fn sign_with_token(
+            &self,
+            pubkey: PublicKeyBytes,
+            request: &Value,
+            api_token: &str,
+        ) -> (u16, Option<types::Signature>) {
+            let response = self
+                .client
+                .post(format!(
+                    "{}/api/v1/eth2/sign/{}",
+                    self.url,
+                    pubkey.as_hex_string()
+                ))
+                .bearer_auth(api_token)
+                .header("accept", "application/json")
+                .json(request)
+                .send()
+                .await
+                .unwrap();
+            let status = response.status().as_u16();
+            let signature = if status == 200 {
+                Some(response.json::<SigningResponse>().await.unwrap().signature)
+            } else {
+                None
+            };
+            (status, signature)
+        }
+    }
+
+    fn current_slot() -> Slot {
+        Epoch::new(CURRENT_EPOCH).start_slot(E::slots_per_epoch())
+    }
+
+    fn attestation_data(block_root: Hash256) -> AttestationData {
+        AttestationData {
+            slot: current_slot(),
+            index: 0,
+            beacon_block_root: block_root,
+            source: Checkpoint {
+                epoch: Epoch::new(CURRENT_EPOCH - 1),
+                root: Hash256::zero(),
+            },
+            target: Checkpoint {
+                epoch: Epoch::new(CURRENT_EPOCH),
+                root: block_root,
+            },
+        }
+    }
+
+    /// A key derivation function which is cheap enough to use in tests.
+    fn insecure_kdf() -> Kdf {
+        Kdf::Scrypt(Scrypt {
+            dklen: DKLEN,
+            n: 2,
+            p: 1,
+            r: 8,
+            salt: vec![1; 32].into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn sign_attestation() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let (status, signature) = tester
+            .sign(keypair.pk.compress(), &tester.attestation_request(&data))
+            .await;
+        assert_eq!(status, 200);
+        assert!(signature
+            .unwrap()
+            .verify(&keypair.pk, tester.attestation_signing_root(&data)));
+
+        // The same message can be signed again, so that clients can retry requests.
+        let (status, _) = tester
+            .sign(keypair.pk.compress(), &tester.attestation_request(&data))
+            .await;
+        assert_eq!(status, 200);
+    }
+
+    #[tokio::test]
+    async fn requests_without_api_token_are_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let (status, _) = tester
+            .sign_with_token(
+                keypair.pk.compress(),
+                &tester.attestation_request(&data),
+                "api-token-0x00",
+            )
+            .await;
+        assert_eq!(status, 403);
+
+        for path in ["upcheck", "api/v1/eth2/publicKeys"] {
+            let response = tester
+                .client
+                .get(format!("{}/{}", tester.url, path))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), 401);
+        }
+
+        // The validator has not signed anything, so the same attestation can still be signed.
+        let (status, _) = tester
+            .sign(keypair.pk.compress(), &tester.attestation_request(&data))
+            .await;
+        assert_eq!(status, 200);
+    }
+
+    #[tokio::test]
+    async fn slashable_attestation_is_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+
+        let data = attestation_data(Hash256::repeat_byte(1));
+        let (status, _) = tester
+            .sign(keypair.pk.compress(), &tester.attestation_request(&data))
+            .await;
+        assert_eq!(status, 200);
+
+        // A different attestation with the same target is a double vote.
+        let double_vote = attestation_data(Hash256::repeat_byte(2));
+        let (status, _) = tester
+            .sign(
+                keypair.pk.compress(),
+                &tester.attestation_request(&double_vote),
+            )
+            .await;
+        assert_eq!(status, 412);
+    }
+
+    #[tokio::test]
+    async fn doppelganger_protected_validator_is_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(true).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let (status, _) = tester
+            .sign(keypair.pk.compress(), &tester.attestation_request(&data))
+            .await;
+        assert_eq!(status, 412);
+    }
+
+    #[tokio::test]
+    async fn unknown_validator_is_not_found() {
+        let tester = Tester::new(Config::default()).await;
+        tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let (status, _) = tester
+            .sign(
+                Keypair::random().pk.compress(),
+                &tester.attestation_request(&data),
+            )
+            .await;
+        assert_eq!(status, 404);
+    }
+
+    #[tokio::test]
+    async fn mismatched_signing_root_is_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let mut request = tester.attestation_request(&data);
+        request["signingRoot"] = json!(Hash256::repeat_byte(7));
+        let (status, _) = tester.sign(keypair.pk.compress(), &request).await;
+        assert_eq!(status, 400);
+
+        // The refused request must not have been recorded by slashing protection, so a matching
+        // request for a different block is still allowed.
+        let data = attestation_data(Hash256::repeat_byte(2));
+        let mut request = tester.attestation_request(&data);
+        request["signingRoot"] = json!(tester.attestation_signing_root(&data));
+        let (status, _) = tester.sign(keypair.pk.compress(), &request).await;
+        assert_eq!(status, 200);
+    }
+
+    #[tokio::test]
+    async fn unknown_fork_is_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let mut request = tester.attestation_request(&data);
+        request["fork_info"] = tester.fork_info(Fork {
+            previous_version: [1; 4],
+            current_version: [2; 4],
+            epoch: Epoch::new(0),
+        });
+        let (status, _) = tester.sign(keypair.pk.compress(), &request).await;
+        assert_eq!(status, 400);
+    }
+
+    #[tokio::test]
+    async fn missing_fork_info_is_refused() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let data = attestation_data(Hash256::repeat_byte(1));
+
+        let mut request = tester.attestation_request(&data);
+        request.as_object_mut().unwrap().remove("fork_info");
+        let (status, _) = tester.sign(keypair.pk.compress(), &request).await;
+        assert_eq!(status, 400);
+    }
+
+    #[tokio::test]
+    async fn unprotected_messages_require_opt_in() {
+        let tester = Tester::new(Config::default()).await;
+        let keypair = tester.add_validator(false).await;
+        let (status, _) = tester
+            .sign(keypair.pk.compress(), &tester.randao_reveal_request())
+            .await;
+        assert_eq!(status, 403);
+
+        let tester = Tester::new(Config {
+            allow_unprotected_messages: true,
+            ..Config::default()
+        })
+        .await;
+        let keypair = tester.add_validator(false).await;
+        let (status, signature) = tester
+            .sign(keypair.pk.compress(), &tester.randao_reveal_request())
+            .await;
+        assert_eq!(status, 200);
+        let domain = tester.spec.get_domain(
+            Epoch::new(CURRENT_EPOCH),
+            Domain::Randao,
+            &tester.fork(),
+            GENESIS_VALIDATORS_ROOT,
+        );
+        assert!(signature
+            .unwrap()
+            .verify(&keypair.pk, Epoch::new(CURRENT_EPOCH).signing_root(domain)));
+    }
+}
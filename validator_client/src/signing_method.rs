@@ -2,6 +2,7 @@
 //!
 //! - Via a local `Keypair`.
 //! - Via a remote signer (Web3Signer)
+//! - Via a threshold of key shares held by peer validator clients.
 
 use crate::http_metrics::metrics;
 use eth2_keystore::Keystore;
@@ -13,10 +14,12 @@ use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::*;
 use url::Url;
-use web3signer::{ForkInfo, SigningRequest, SigningResponse};
+use web3signer::{ForkInfo, SigningRequest};
 
-pub use web3signer::Web3SignerObject;
+pub use threshold::ThresholdPeer;
+pub use web3signer::{MessageType, OwnedSigningRequest, SigningResponse, Web3SignerObject};
 
+mod threshold;
 mod web3signer;
 
 #[derive(Debug, PartialEq)]
@@ -31,6 +34,15 @@ pub enum Error {
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
+    NotThresholdValidator,
+    InsufficientThresholdShares {
+        threshold: usize,
+        received: usize,
+        errors: Vec<String>,
+    },
+    UnableToCombineThresholdShares(bls::Error),
+    InvalidThresholdSignature,
+    NotLocalKeystore,
 }
 
 /// Enumerates all messages that can be signed by a validator.
@@ -93,6 +105,17 @@ pub enum SigningMethod {
         http_client: Client,
         voting_public_key: PublicKey,
     },
+    /// A validator whose signatures are combined from the signatures of `threshold` key shares,
+    /// one held locally and the rest by peer validator clients.
+    Threshold {
+        voting_public_key: PublicKey,
+        threshold: usize,
+        share_index: u64,
+        share_keystore_path: PathBuf,
+        share_keystore_lockfile: Mutex<Option<Lockfile>>,
+        share_keypair: Arc<Keypair>,
+        peers: Vec<ThresholdPeer>,
+    },
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -128,6 +151,57 @@ impl SigningMethod {
             // Slashing protection is only required for remote signer keys when the configuration
             // dictates that it is desired.
             SigningMethod::Web3Signer { .. } => enable_web3signer_slashing_protection,
+            // Each peer checks its own slashing protection before releasing its share, but our
+            // own share must be protected locally. DO NOT TURN THIS OFF.
+            SigningMethod::Threshold { .. } => true,
+        }
+    }
+
+    /// Return the signature of `signing_root` by the local key share of a threshold validator,
+    /// along with the index of the share.
+    ///
+    /// This is used to release a share to a peer, and should only be called after the message
+    /// has been checked against slashing protection.
+    pub async fn get_threshold_share(
+        &self,
+        signing_root: Hash256,
+        executor: &TaskExecutor,
+    ) -> Result<(u64, Signature), Error> {
+        match self {
+            SigningMethod::Threshold {
+                share_index,
+                share_keypair,
+                ..
+            } => {
+                let signature =
+                    sign_with_keypair(share_keypair.clone(), signing_root, executor).await?;
+                Ok((*share_index, signature))
+            }
+            SigningMethod::LocalKeystore { .. } | SigningMethod::Web3Signer { .. } => {
+                Err(Error::NotThresholdValidator)
+            }
+        }
+    }
+
+    /// Return the signature of `signing_root` by a local voting keystore.
+    ///
+    /// This is used to serve signing requests from other validator clients, and should only be
+    /// called after the message has been checked against slashing protection.
+    pub async fn get_local_signature(
+        &self,
+        signing_root: Hash256,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        match self {
+            SigningMethod::LocalKeystore { voting_keypair, .. } => {
+                let _timer =
+                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
+
+                sign_with_keypair(voting_keypair.clone(), signing_root, executor).await
+            }
+            SigningMethod::Web3Signer { .. } | SigningMethod::Threshold { .. } => {
+                Err(Error::NotLocalKeystore)
+            }
         }
     }
 
@@ -169,18 +243,7 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
 
-                let voting_keypair = voting_keypair.clone();
-                // Spawn a blocking task to produce the signature. This avoids blocking the core
-                // tokio executor.
-                let signature = executor
-                    .spawn_blocking_handle(
-                        move || voting_keypair.sk.sign(signing_root),
-                        "local_keystore_signer",
-                    )
-                    .ok_or(Error::ShuttingDown)?
-                    .await
-                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
-                Ok(signature)
+                sign_with_keypair(voting_keypair.clone(), signing_root, executor).await
             }
             SigningMethod::Web3Signer {
                 signing_url,
@@ -256,6 +319,47 @@ impl SigningMethod {
 
                 Ok(response.signature)
             }
+            SigningMethod::Threshold {
+                voting_public_key,
+                threshold,
+                share_index,
+                share_keypair,
+                peers,
+                ..
+            } => {
+                let _timer =
+                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::THRESHOLD]);
+
+                let own_share =
+                    sign_with_keypair(share_keypair.clone(), signing_root, executor).await?;
+
+                threshold::get_threshold_signature(
+                    voting_public_key,
+                    *threshold,
+                    (*share_index, own_share),
+                    peers,
+                    &signable_message.to_threshold_message(),
+                    signing_root,
+                )
+                .await
+            }
         }
     }
 }
+
+/// Sign `signing_root` with `keypair` on a blocking task. This avoids blocking the core tokio
+/// executor.
+async fn sign_with_keypair(
+    keypair: Arc<Keypair>,
+    signing_root: Hash256,
+    executor: &TaskExecutor,
+) -> Result<Signature, Error> {
+    executor
+        .spawn_blocking_handle(
+            move || keypair.sk.sign(signing_root),
+            "local_keystore_signer",
+        )
+        .ok_or(Error::ShuttingDown)?
+        .await
+        .map_err(|e| Error::TokioJoin(e.to_string()))
+}
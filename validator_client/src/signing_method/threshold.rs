@@ -0,0 +1,132 @@
+//! Collects signature shares for a threshold validator from peer validator clients and combines
+//! them into a signature by the validator's key.
+
+use super::{Error, SignableMessage};
+use eth2::lighthouse_vc::{
+    http_client::ValidatorClientHttpClient, types::ThresholdSignableMessage,
+};
+use futures::stream::{FuturesUnordered, StreamExt};
+use types::*;
+
+/// A peer validator client which holds one of the key shares of a threshold validator.
+pub struct ThresholdPeer {
+    pub share_index: u64,
+    pub share_public_key: PublicKey,
+    pub client: ValidatorClientHttpClient,
+}
+
+impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Payload> {
+    /// Returns the message in the form that is sent to peers, so that they can check it against
+    /// slashing protection before signing it.
+    pub fn to_threshold_message(&self) -> ThresholdSignableMessage<E> {
+        match self {
+            SignableMessage::RandaoReveal(epoch) => {
+                ThresholdSignableMessage::RandaoReveal { epoch: *epoch }
+            }
+            SignableMessage::BeaconBlock(block) => {
+                ThresholdSignableMessage::BlockHeader(block.block_header())
+            }
+            SignableMessage::AttestationData(a) => {
+                ThresholdSignableMessage::Attestation((*a).clone())
+            }
+            SignableMessage::SignedAggregateAndProof(a) => {
+                ThresholdSignableMessage::AggregateAndProof((*a).clone())
+            }
+            SignableMessage::SelectionProof(slot) => {
+                ThresholdSignableMessage::SelectionProof { slot: *slot }
+            }
+            SignableMessage::SyncSelectionProof(s) => {
+                ThresholdSignableMessage::SyncSelectionProof((*s).clone())
+            }
+            SignableMessage::SyncCommitteeSignature {
+                beacon_block_root,
+                slot,
+            } => ThresholdSignableMessage::SyncCommitteeMessage {
+                beacon_block_root: *beacon_block_root,
+                slot: *slot,
+            },
+            SignableMessage::SignedContributionAndProof(c) => {
+                ThresholdSignableMessage::ContributionAndProof((*c).clone())
+            }
+            SignableMessage::ValidatorRegistration(v) => {
+                ThresholdSignableMessage::ValidatorRegistration((*v).clone())
+            }
+            SignableMessage::VoluntaryExit(e) => {
+                ThresholdSignableMessage::VoluntaryExit((*e).clone())
+            }
+        }
+    }
+}
+
+/// Request signature shares for `message` from `peers` until `threshold` valid shares (including
+/// `own_share`) have been collected, then combine them.
+///
+/// Each share is verified against the peer's share public key before it is used, so a faulty peer
+/// can't prevent a signature from being produced while enough other peers are available.
+pub async fn get_threshold_signature<E: EthSpec>(
+    voting_public_key: &PublicKey,
+    threshold: usize,
+    own_share: (u64, Signature),
+    peers: &[ThresholdPeer],
+    message: &ThresholdSignableMessage<E>,
+    signing_root: Hash256,
+) -> Result<Signature, Error> {
+    let voting_pubkey_bytes = voting_public_key.compress();
+    let voting_pubkey_bytes = &voting_pubkey_bytes;
+
+    let mut requests = peers
+        .iter()
+        .map(|peer| async move {
+            let result = peer
+                .client
+                .post_lighthouse_validators_threshold_share(voting_pubkey_bytes, message)
+                .await;
+            (peer, result)
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    let mut shares = vec![own_share];
+    let mut errors = vec![];
+    while shares.len() < threshold {
+        let Some((peer, result)) = requests.next().await else {
+            break;
+        };
+        match result {
+            Ok(response)
+                if response.data.share_index == peer.share_index
+                    && response
+                        .data
+                        .signature
+                        .verify(&peer.share_public_key, signing_root) =>
+            {
+                shares.push((peer.share_index, response.data.signature));
+            }
+            Ok(_) => errors.push(format!(
+                "share {}: invalid signature share",
+                peer.share_index
+            )),
+            Err(e) => errors.push(format!("share {}: {:?}", peer.share_index, e)),
+        }
+    }
+
+    if shares.len() < threshold {
+        return Err(Error::InsufficientThresholdShares {
+            threshold,
+            received: shares.len(),
+            errors,
+        });
+    }
+
+    let shares = shares
+        .iter()
+        .map(|(share_index, signature)| (*share_index, signature))
+        .collect::<Vec<_>>();
+    let signature = Signature::combine_threshold_shares(&shares)
+        .map_err(Error::UnableToCombineThresholdShares)?;
+
+    if signature.verify(voting_public_key, signing_root) {
+        Ok(signature)
+    } else {
+        Err(Error::InvalidThresholdSignature)
+    }
+}
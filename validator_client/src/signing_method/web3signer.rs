@@ -1,10 +1,12 @@
-//! Contains the types required to make JSON requests to Web3Signer servers.
+//! Contains the types required to make JSON requests to Web3Signer servers, and to receive them in
+//! the remote signer server.
 
 use super::Error;
+use eth2::lighthouse_vc::types::ThresholdSignableMessage;
 use serde::{Deserialize, Serialize};
 use types::*;
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MessageType {
     AggregationSlot,
@@ -20,7 +22,7 @@ pub enum MessageType {
     ValidatorRegistration,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ForkName {
     Phase0,
@@ -31,7 +33,7 @@ pub enum ForkName {
     Electra,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ForkInfo {
     pub fork: Fork,
     pub genesis_validators_root: Hash256,
@@ -144,7 +146,151 @@ pub struct SigningRequest<'a, E: EthSpec, Payload: AbstractExecPayload<E>> {
     pub object: Web3SignerObject<'a, E, Payload>,
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SigningResponse {
     pub signature: Signature,
 }
+
+impl From<ForkName> for types::ForkName {
+    fn from(fork_name: ForkName) -> Self {
+        match fork_name {
+            ForkName::Phase0 => types::ForkName::Base,
+            ForkName::Altair => types::ForkName::Altair,
+            ForkName::Bellatrix => types::ForkName::Bellatrix,
+            ForkName::Capella => types::ForkName::Capella,
+            ForkName::Deneb => types::ForkName::Deneb,
+            ForkName::Electra => types::ForkName::Electra,
+        }
+    }
+}
+
+/// The owned counterpart of `Web3SignerObject`, as received by the remote signer server.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(bound = "E: EthSpec", rename_all = "snake_case")]
+pub enum OwnedWeb3SignerObject<E: EthSpec> {
+    AggregationSlot {
+        slot: Slot,
+    },
+    AggregateAndProof(AggregateAndProof<E>),
+    Attestation(AttestationData),
+    BeaconBlock {
+        version: ForkName,
+        /// The block is decoded once its fork is known, see `Self::into_signable_message`.
+        #[serde(default)]
+        block: Option<serde_json::Value>,
+        #[serde(default)]
+        block_header: Option<BeaconBlockHeader>,
+    },
+    Deposit {
+        pubkey: PublicKeyBytes,
+        withdrawal_credentials: Hash256,
+        #[serde(with = "serde_utils::quoted_u64")]
+        amount: u64,
+        #[serde(with = "serde_utils::bytes_4_hex")]
+        genesis_fork_version: [u8; 4],
+    },
+    RandaoReveal {
+        epoch: Epoch,
+    },
+    VoluntaryExit(VoluntaryExit),
+    SyncCommitteeMessage {
+        beacon_block_root: Hash256,
+        slot: Slot,
+    },
+    SyncAggregatorSelectionData(SyncAggregatorSelectionData),
+    ContributionAndProof(ContributionAndProof<E>),
+    ValidatorRegistration(ValidatorRegistrationData),
+}
+
+impl<E: EthSpec> OwnedWeb3SignerObject<E> {
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            OwnedWeb3SignerObject::AggregationSlot { .. } => MessageType::AggregationSlot,
+            OwnedWeb3SignerObject::AggregateAndProof(_) => MessageType::AggregateAndProof,
+            OwnedWeb3SignerObject::Attestation(_) => MessageType::Attestation,
+            OwnedWeb3SignerObject::BeaconBlock { .. } => MessageType::BlockV2,
+            OwnedWeb3SignerObject::Deposit { .. } => MessageType::Deposit,
+            OwnedWeb3SignerObject::RandaoReveal { .. } => MessageType::RandaoReveal,
+            OwnedWeb3SignerObject::VoluntaryExit(_) => MessageType::VoluntaryExit,
+            OwnedWeb3SignerObject::SyncCommitteeMessage { .. } => MessageType::SyncCommitteeMessage,
+            OwnedWeb3SignerObject::SyncAggregatorSelectionData(_) => {
+                MessageType::SyncCommitteeSelectionProof
+            }
+            OwnedWeb3SignerObject::ContributionAndProof(_) => {
+                MessageType::SyncCommitteeContributionAndProof
+            }
+            OwnedWeb3SignerObject::ValidatorRegistration(_) => MessageType::ValidatorRegistration,
+        }
+    }
+
+    /// Convert the object into a message which can be checked against slashing protection and
+    /// signed by the `ValidatorStore`.
+    ///
+    /// Blocks are reduced to their headers, which have the same signing root. Deposits are not
+    /// supported since they aren't signed by the validator client.
+    pub fn into_signable_message(self) -> Result<ThresholdSignableMessage<E>, String> {
+        let message = match self {
+            OwnedWeb3SignerObject::AggregationSlot { slot } => {
+                ThresholdSignableMessage::SelectionProof { slot }
+            }
+            OwnedWeb3SignerObject::AggregateAndProof(a) => {
+                ThresholdSignableMessage::AggregateAndProof(a)
+            }
+            OwnedWeb3SignerObject::Attestation(a) => ThresholdSignableMessage::Attestation(a),
+            OwnedWeb3SignerObject::BeaconBlock {
+                version,
+                block,
+                block_header,
+            } => match (block, block_header) {
+                (None, Some(header)) => ThresholdSignableMessage::BlockHeader(header),
+                (Some(block), None) => {
+                    let block = BeaconBlock::<E>::deserialize_by_fork::<serde_json::Value>(
+                        block,
+                        version.into(),
+                    )
+                    .map_err(|e| format!("invalid block: {}", e))?;
+                    ThresholdSignableMessage::BlockHeader(block.block_header())
+                }
+                _ => return Err("exactly one of block or block_header is required".to_string()),
+            },
+            OwnedWeb3SignerObject::Deposit { .. } => {
+                return Err("deposit signing is not supported".to_string())
+            }
+            OwnedWeb3SignerObject::RandaoReveal { epoch } => {
+                ThresholdSignableMessage::RandaoReveal { epoch }
+            }
+            OwnedWeb3SignerObject::VoluntaryExit(e) => ThresholdSignableMessage::VoluntaryExit(e),
+            OwnedWeb3SignerObject::SyncCommitteeMessage {
+                beacon_block_root,
+                slot,
+            } => ThresholdSignableMessage::SyncCommitteeMessage {
+                beacon_block_root,
+                slot,
+            },
+            OwnedWeb3SignerObject::SyncAggregatorSelectionData(s) => {
+                ThresholdSignableMessage::SyncSelectionProof(s)
+            }
+            OwnedWeb3SignerObject::ContributionAndProof(c) => {
+                ThresholdSignableMessage::ContributionAndProof(c)
+            }
+            OwnedWeb3SignerObject::ValidatorRegistration(v) => {
+                ThresholdSignableMessage::ValidatorRegistration(v)
+            }
+        };
+        Ok(message)
+    }
+}
+
+/// The owned counterpart of `SigningRequest`, as received by the remote signer server.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct OwnedSigningRequest<E: EthSpec> {
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    #[serde(default)]
+    pub fork_info: Option<ForkInfo>,
+    #[serde(rename = "signingRoot", default)]
+    pub signing_root: Option<Hash256>,
+    #[serde(flatten)]
+    pub object: OwnedWeb3SignerObject<E>,
+}
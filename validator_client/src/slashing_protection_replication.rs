@@ -0,0 +1,164 @@
+//! Replicates the slashing protection database of this validator client to a standby validator
+//! client.
+//!
+//! Several times per slot the watermarks of every registered validator (its maximum block slot and
+//! maximum attestation source and target epochs) are exported and sent to the standby via its
+//! authenticated HTTP API. The standby imports them into its own database, so that it refuses to
+//! sign anything at or below them once it is promoted.
+//!
+//! Watermarks are only sent when they have changed since the last successful replication, or once
+//! per epoch otherwise. The standby records when each replication arrives, and refuses to be
+//! promoted when it hasn't received one recently unless promotion is forced.
+use crate::http_metrics::metrics;
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::{http_client::ValidatorClientHttpClient, std_types::Interchange};
+use reqwest::Client;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{debug, error, warn};
+use slot_clock::SlotClock;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec};
+
+/// Number of times per slot that the watermarks are replicated.
+pub const REPLICATIONS_PER_SLOT: u32 = 4;
+
+/// Timeout for requests to the standby validator client.
+pub const REPLICATION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of epochs after the last replication arrived for which a standby may be promoted
+/// without being forced.
+pub const MAX_REPLICATION_AGE_EPOCHS: u64 = 1;
+
+/// A replication received by a standby validator client.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Replication {
+    /// The time at which the replication arrived, since the UNIX epoch.
+    pub time: Duration,
+    /// The epoch in which the replication arrived.
+    pub epoch: Epoch,
+}
+
+/// The reasons that a validator client may refuse to be promoted from a standby.
+#[derive(Debug, PartialEq)]
+pub enum PromotionError {
+    NotStandby,
+    /// No replication has been received since the validator client started.
+    NoReplication,
+    /// The last replication arrived too long ago for the standby to be safe to promote.
+    StaleReplication {
+        last_replication_epoch: Epoch,
+        current_epoch: Epoch,
+    },
+}
+
+/// Configuration for replicating the slashing protection database to a standby.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// The HTTP API of the standby validator client.
+    pub standby_url: SensitiveUrl,
+    /// The API token of the standby validator client.
+    pub standby_api_token_path: PathBuf,
+}
+
+/// Starts a service that periodically replicates the slashing protection watermarks of
+/// `validator_store` to the standby validator client described by `config`.
+pub fn start_slashing_protection_replication_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    config: Config,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+) -> Result<(), String> {
+    let log = context.log().clone();
+    let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
+    let interval = slot_duration / REPLICATIONS_PER_SLOT;
+
+    let api_token =
+        ValidatorClientHttpClient::load_api_token_from_file(&config.standby_api_token_path)
+            .map_err(|e| format!("Unable to read standby API token: {:?}", e))?;
+    let http_client = Client::builder()
+        .timeout(REPLICATION_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+    let client = ValidatorClientHttpClient::from_components(
+        config.standby_url,
+        http_client,
+        api_token.as_str().to_string(),
+    )
+    .map_err(|e| format!("Unable to create standby client: {:?}", e))?;
+
+    let future = async move {
+        let mut last_replicated: Option<(Interchange, Epoch)> = None;
+
+        loop {
+            sleep(interval).await;
+
+            let watermarks = match validator_store.export_slashing_protection_watermarks() {
+                Ok(watermarks) => watermarks,
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to export slashing protection watermarks";
+                        "error" => ?e,
+                    );
+                    continue;
+                }
+            };
+
+            // Resend unchanged watermarks once per epoch so that the standby knows that they're
+            // still current.
+            let epoch = slot_clock
+                .now()
+                .map_or(Epoch::new(0), |slot| slot.epoch(E::slots_per_epoch()));
+            if last_replicated
+                .as_ref()
+                .is_some_and(|(last, last_epoch)| *last == watermarks && *last_epoch == epoch)
+            {
+                continue;
+            }
+
+            let timer = metrics::start_timer(&metrics::SLASHING_PROTECTION_REPLICATION_TIMES);
+            let result = client
+                .post_lighthouse_slashing_protection_replicate(&watermarks)
+                .await;
+            drop(timer);
+
+            match result {
+                Ok(()) => {
+                    metrics::inc_counter_vec(
+                        &metrics::SLASHING_PROTECTION_REPLICATIONS_TOTAL,
+                        &[metrics::SUCCESS],
+                    );
+                    debug!(
+                        log,
+                        "Replicated slashing protection";
+                        "validators" => watermarks.data.len(),
+                    );
+                    last_replicated = Some((watermarks, epoch));
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::SLASHING_PROTECTION_REPLICATIONS_TOTAL,
+                        &[metrics::FAILED],
+                    );
+                    warn!(
+                        log,
+                        "Unable to replicate slashing protection";
+                        "msg" => "the standby will not be safe to promote until replication succeeds",
+                        "error" => %e,
+                    );
+                }
+            }
+        }
+    };
+
+    context
+        .executor
+        .spawn(future, "slashing_protection_replication");
+
+    Ok(())
+}
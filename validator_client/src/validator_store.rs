@@ -1,11 +1,14 @@
 use crate::{
     doppelganger_service::DoppelgangerService,
+    graffiti_file::GraffitiSetting,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
+    slashing_protection_replication::{PromotionError, Replication, MAX_REPLICATION_AGE_EPOCHS},
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use eth2::lighthouse_vc::types::ThresholdSignableMessage;
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
@@ -14,32 +17,44 @@ use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::marker::PhantomData;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
     AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
-    Domain, Epoch, EthSpec, Fork, ForkName, Graffiti, Hash256, PublicKeyBytes, SelectionProof,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
+    Domain, Epoch, EthSpec, Fork, ForkName, Hash256, PublicKeyBytes, SelectionProof, Signature,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
     SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncAggregatorSelectionData,
     SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
     ValidatorRegistrationData, VoluntaryExit,
 };
 
-pub use crate::doppelganger_service::DoppelgangerStatus;
+pub use crate::doppelganger_service::{DetectionProgress, DoppelgangerStatus};
 use crate::preparation_service::ProposalData;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
     DoppelgangerProtected(PublicKeyBytes),
+    SlashingProtectionStandby(PublicKeyBytes),
     UnknownToDoppelgangerService(PublicKeyBytes),
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
     SameData,
-    GreaterThanCurrentSlot { slot: Slot, current_slot: Slot },
-    GreaterThanCurrentEpoch { epoch: Epoch, current_epoch: Epoch },
+    GreaterThanCurrentSlot {
+        slot: Slot,
+        current_slot: Slot,
+    },
+    GreaterThanCurrentEpoch {
+        epoch: Epoch,
+        current_epoch: Epoch,
+    },
     UnableToSignAttestation(AttestationError),
     UnableToSign(SigningError),
+    SigningRootMismatch {
+        expected: Hash256,
+        computed: Hash256,
+    },
 }
 
 impl From<SigningError> for Error {
@@ -62,10 +77,17 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    /// Set while this validator client is a standby which receives slashing protection data from
+    /// a primary validator client and must not sign slashable messages.
+    slashing_protection_standby: AtomicBool,
+    /// The last replication received from a primary while this validator client is a standby.
+    slashing_protection_last_replication: Mutex<Option<Replication>>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    /// Whether doppelganger protection applies to validators without a `doppelganger` override.
+    enable_doppelganger_protection: bool,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
     gas_limit: Option<u64>,
@@ -97,10 +119,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            slashing_protection_standby: AtomicBool::new(config.slashing_protection_standby),
+            slashing_protection_last_replication: Mutex::new(None),
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
             doppelganger_service,
+            enable_doppelganger_protection: config.enable_doppelganger_protection,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
             gas_limit: config.gas_limit,
@@ -120,8 +145,15 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// This function has no effect if doppelganger protection is disabled.
     pub fn register_all_in_doppelganger_protection_if_enabled(&self) -> Result<(), String> {
         if let Some(doppelganger_service) = &self.doppelganger_service {
-            for pubkey in self.validators.read().iter_voting_pubkeys() {
-                doppelganger_service.register_new_validator::<E, _>(*pubkey, &self.slot_clock)?
+            let validators = self.validators.read();
+            for pubkey in validators.iter_voting_pubkeys() {
+                doppelganger_service.register_new_validator::<E, _>(
+                    *pubkey,
+                    &self.slot_clock,
+                    validators
+                        .doppelganger(pubkey)
+                        .unwrap_or(self.enable_doppelganger_protection),
+                )?
             }
         }
 
@@ -133,10 +165,116 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.doppelganger_service.is_some()
     }
 
+    /// Returns the progress of doppelganger detection for `validator_pubkey`, or `None` if
+    /// doppelganger protection does not apply to it.
+    pub fn doppelganger_progress(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Option<DetectionProgress> {
+        let doppelganger_service = self.doppelganger_service.as_ref()?;
+        let protection_enabled = self
+            .validators
+            .read()
+            .doppelganger(validator_pubkey)
+            .unwrap_or(self.enable_doppelganger_protection);
+        if protection_enabled {
+            doppelganger_service.detection_progress(validator_pubkey)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the slashing protection database records a block or an attestation signed
+    /// by `validator_pubkey` in `epoch`.
+    pub fn has_signed_in_epoch(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        epoch: Epoch,
+    ) -> Result<bool, String> {
+        self.slashing_protection
+            .has_signed_in_epoch(validator_pubkey, epoch, E::slots_per_epoch())
+            .map_err(|e| format!("Failed to query slashing protection database: {:?}", e))
+    }
+
+    /// Returns `true` if this validator client is a slashing protection standby which must not
+    /// sign slashable messages until it is promoted.
+    pub fn is_slashing_protection_standby(&self) -> bool {
+        self.slashing_protection_standby.load(Ordering::Acquire)
+    }
+
+    /// Returns the last replication received from a primary validator client, if any.
+    pub fn slashing_protection_last_replication(&self) -> Option<Replication> {
+        *self.slashing_protection_last_replication.lock()
+    }
+
+    /// Import slashing protection watermarks replicated from a primary validator client, and
+    /// record their arrival.
+    pub fn import_replicated_slashing_protection(
+        &self,
+        interchange: Interchange,
+    ) -> Result<(), InterchangeError> {
+        self.import_slashing_protection(interchange)?;
+        *self.slashing_protection_last_replication.lock() = Some(Replication {
+            time: self.slot_clock.now_duration().unwrap_or_default(),
+            epoch: self.current_epoch(),
+        });
+        Ok(())
+    }
+
+    /// Promote this validator client from a slashing protection standby, allowing it to sign
+    /// messages for its validators.
+    ///
+    /// Promotion is refused if no replication has arrived in the last
+    /// `MAX_REPLICATION_AGE_EPOCHS`, unless `force` is set.
+    pub fn promote_slashing_protection_standby(&self, force: bool) -> Result<(), PromotionError> {
+        // Hold the lock so that the standby isn't promoted while a replication is arriving.
+        let last_replication = self.slashing_protection_last_replication.lock();
+        if !self.is_slashing_protection_standby() {
+            return Err(PromotionError::NotStandby);
+        }
+
+        if !force {
+            let current_epoch = self.current_epoch();
+            match *last_replication {
+                None => return Err(PromotionError::NoReplication),
+                Some(replication)
+                    if current_epoch > replication.epoch + MAX_REPLICATION_AGE_EPOCHS =>
+                {
+                    return Err(PromotionError::StaleReplication {
+                        last_replication_epoch: replication.epoch,
+                        current_epoch,
+                    });
+                }
+                Some(_) => (),
+            }
+        }
+
+        self.slashing_protection_standby
+            .store(false, Ordering::Release);
+        info!(
+            self.log,
+            "Promoted from slashing protection standby";
+            "msg" => "signing is now enabled for all validators",
+            "last_replication_epoch" => ?last_replication.map(|replication| replication.epoch),
+            "forced" => force,
+        );
+        Ok(())
+    }
+
+    fn current_epoch(&self) -> Epoch {
+        self.slot_clock
+            .now()
+            .map_or(Epoch::new(0), |slot| slot.epoch(E::slots_per_epoch()))
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
 
+    pub fn genesis_validators_root(&self) -> Hash256 {
+        self.genesis_validators_root
+    }
+
     /// Indicates if the `voting_public_key` exists in self and is enabled.
     pub fn has_validator(&self, voting_public_key: &PublicKeyBytes) -> bool {
         self.validators
@@ -196,9 +334,22 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .register_validator(validator_pubkey)
             .map_err(|e| format!("failed to register validator: {:?}", e))?;
 
+        let doppelganger_protection = validator_def
+            .doppelganger
+            .unwrap_or(self.enable_doppelganger_protection);
         if let Some(doppelganger_service) = &self.doppelganger_service {
-            doppelganger_service
-                .register_new_validator::<E, _>(validator_pubkey, &self.slot_clock)?;
+            doppelganger_service.register_new_validator::<E, _>(
+                validator_pubkey,
+                &self.slot_clock,
+                doppelganger_protection,
+            )?;
+        } else if doppelganger_protection {
+            warn!(
+                self.log,
+                "Doppelganger protection not applied";
+                "msg" => "restart the validator client to enable doppelganger protection",
+                "validator" => ?validator_pubkey,
+            );
         }
 
         self.validators
@@ -246,6 +397,9 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     ///     protection and are safe-enough to sign messages.
     /// - `DoppelgangerStatus::ignored`: returns all the pubkeys from `only_safe` *plus* those still
     ///     undergoing protection. This is useful for collecting duties or other non-signing tasks.
+    ///
+    /// All pubkeys are treated as undergoing protection while this validator client is a slashing
+    /// protection standby.
     #[allow(clippy::needless_collect)] // Collect is required to avoid holding a lock.
     pub fn voting_pubkeys<I, F>(&self, filter_func: F) -> I
     where
@@ -260,6 +414,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .iter_voting_pubkeys()
             .cloned()
             .collect::<Vec<_>>();
+        let standby = self.is_slashing_protection_standby();
 
         pubkeys
             .into_iter()
@@ -270,6 +425,12 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     // Allow signing on all pubkeys if doppelganger protection is disabled.
                     .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey))
             })
+            .map(|status| match status {
+                DoppelgangerStatus::SigningEnabled(pubkey) if standby => {
+                    DoppelgangerStatus::SigningDisabled(pubkey)
+                }
+                status => status,
+            })
             .filter_map(filter_func)
             .collect()
     }
@@ -300,7 +461,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
     /// Check if the `validator_pubkey` is permitted by the doppleganger protection to sign
     /// messages.
+    ///
+    /// No pubkeys are permitted to sign while this validator client is a slashing protection
+    /// standby.
     pub fn doppelganger_protection_allows_signing(&self, validator_pubkey: PublicKeyBytes) -> bool {
+        if self.is_slashing_protection_standby() {
+            return false;
+        }
         self.doppelganger_service
             .as_ref()
             // If there's no doppelganger service then we assume it is purposefully disabled and
@@ -321,6 +488,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.spec.fork_at_epoch(epoch)
     }
 
+    /// Returns `true` if `fork` is one of the forks in this validator client's fork schedule.
+    pub fn is_known_fork(&self, fork: &Fork) -> bool {
+        self.fork(fork.epoch) == *fork
+    }
+
     pub fn produce_block_v3(&self) -> bool {
         self.produce_block_v3
     }
@@ -331,7 +503,9 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
-        if self.doppelganger_protection_allows_signing(validator_pubkey) {
+        if self.is_slashing_protection_standby() {
+            Err(Error::SlashingProtectionStandby(validator_pubkey))
+        } else if self.doppelganger_protection_allows_signing(validator_pubkey) {
             self.validators
                 .read()
                 .signing_method(&validator_pubkey)
@@ -410,8 +584,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(signature)
     }
 
-    pub fn graffiti(&self, validator_pubkey: &PublicKeyBytes) -> Option<Graffiti> {
-        self.validators.read().graffiti(validator_pubkey)
+    /// Returns the graffiti setting from the validator definition, which is either a fixed
+    /// graffiti or a template.
+    pub fn graffiti(&self, validator_pubkey: &PublicKeyBytes) -> Option<GraffitiSetting> {
+        self.validators.read().graffiti_setting(validator_pubkey)
     }
 
     /// Returns the fee recipient for the given public key. The priority order for fetching
@@ -969,6 +1145,213 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(SignedContributionAndProof { message, signature })
     }
 
+    /// Signs `message` with this validator client's key share of the threshold validator
+    /// `validator_pubkey`, so that a peer can combine it with other shares.
+    ///
+    /// Blocks and attestations are checked against (and recorded in) the slashing protection
+    /// database before the share is released. Unlike `sign_block` and `sign_attestation`, a
+    /// message which has already been signed is signed again, since every peer which signs a
+    /// message will request our share of it.
+    pub async fn sign_threshold_share(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        message: ThresholdSignableMessage<E>,
+    ) -> Result<(u64, Signature), Error> {
+        let (signing_method, signing_root) = self.check_remote_signing_request(
+            validator_pubkey,
+            &message,
+            None,
+            &metrics::SIGNED_THRESHOLD_SHARES_TOTAL,
+        )?;
+
+        let share = signing_method
+            .get_threshold_share(signing_root, &self.task_executor)
+            .await?;
+
+        metrics::inc_counter_vec(&metrics::SIGNED_THRESHOLD_SHARES_TOTAL, &[metrics::SUCCESS]);
+
+        Ok(share)
+    }
+
+    /// Signs `message` with the local keystore of `validator_pubkey` on behalf of a client of the
+    /// remote signer server.
+    ///
+    /// The signing root is computed from `message` and must match `expected_signing_root`, if it
+    /// is provided. As with threshold shares, blocks and attestations are checked against the
+    /// slashing protection database and a message which has already been signed is signed again.
+    pub async fn sign_remote_signer_request(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        message: ThresholdSignableMessage<E>,
+        expected_signing_root: Option<Hash256>,
+    ) -> Result<Signature, Error> {
+        let (signing_method, signing_root) = self.check_remote_signing_request(
+            validator_pubkey,
+            &message,
+            expected_signing_root,
+            &metrics::SIGNED_REMOTE_SIGNER_REQUESTS_TOTAL,
+        )?;
+
+        let signature = signing_method
+            .get_local_signature(signing_root, &self.task_executor)
+            .await?;
+
+        metrics::inc_counter_vec(
+            &metrics::SIGNED_REMOTE_SIGNER_REQUESTS_TOTAL,
+            &[metrics::SUCCESS],
+        );
+
+        Ok(signature)
+    }
+
+    /// Returns the signing method and signing root to use for a `message` which was received from
+    /// another validator client, after checking it against the slashing protection database.
+    ///
+    /// Slashable messages are counted in `metric`.
+    fn check_remote_signing_request(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        message: &ThresholdSignableMessage<E>,
+        expected_signing_root: Option<Hash256>,
+        metric: &metrics::Result<metrics::IntCounterVec>,
+    ) -> Result<(Arc<SigningMethod>, Hash256), Error> {
+        let check_signing_root = |signing_root: Hash256| match expected_signing_root {
+            Some(expected) if expected != signing_root => Err(Error::SigningRootMismatch {
+                expected,
+                computed: signing_root,
+            }),
+            _ => Ok(()),
+        };
+        let slots_per_epoch = E::slots_per_epoch();
+        let current_slot = self
+            .slot_clock
+            .now()
+            .unwrap_or_else(|| self.slot_clock.genesis_slot());
+        let current_epoch = current_slot.epoch(slots_per_epoch);
+        let get_domain_hash = |domain: Domain, signing_epoch: Epoch| {
+            self.signing_context(domain, signing_epoch)
+                .domain_hash(&self.spec)
+        };
+
+        let (signing_method, signing_root) = match message {
+            ThresholdSignableMessage::RandaoReveal { epoch } => (
+                self.doppelganger_checked_signing_method(validator_pubkey)?,
+                epoch.signing_root(get_domain_hash(Domain::Randao, *epoch)),
+            ),
+            ThresholdSignableMessage::BlockHeader(header) => {
+                if header.slot > current_slot {
+                    return Err(Error::GreaterThanCurrentSlot {
+                        slot: header.slot,
+                        current_slot,
+                    });
+                }
+                let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+                let domain_hash =
+                    get_domain_hash(Domain::BeaconProposer, header.slot.epoch(slots_per_epoch));
+                check_signing_root(header.signing_root(domain_hash))?;
+                self.check_remote_slashing_status(
+                    self.slashing_protection.check_and_insert_block_proposal(
+                        &validator_pubkey,
+                        header,
+                        domain_hash,
+                    ),
+                    metric,
+                )?;
+                (signing_method, header.signing_root(domain_hash))
+            }
+            ThresholdSignableMessage::Attestation(data) => {
+                if data.target.epoch > current_epoch {
+                    return Err(Error::GreaterThanCurrentEpoch {
+                        epoch: data.target.epoch,
+                        current_epoch,
+                    });
+                }
+                let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+                let domain_hash = get_domain_hash(Domain::BeaconAttester, data.target.epoch);
+                check_signing_root(data.signing_root(domain_hash))?;
+                self.check_remote_slashing_status(
+                    self.slashing_protection.check_and_insert_attestation(
+                        &validator_pubkey,
+                        data,
+                        domain_hash,
+                    ),
+                    metric,
+                )?;
+                (signing_method, data.signing_root(domain_hash))
+            }
+            ThresholdSignableMessage::AggregateAndProof(message) => (
+                self.doppelganger_checked_signing_method(validator_pubkey)?,
+                message.signing_root(get_domain_hash(
+                    Domain::AggregateAndProof,
+                    message.aggregate.data.target.epoch,
+                )),
+            ),
+            ThresholdSignableMessage::SelectionProof { slot } => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                slot.signing_root(get_domain_hash(
+                    Domain::SelectionProof,
+                    slot.epoch(slots_per_epoch),
+                )),
+            ),
+            ThresholdSignableMessage::SyncSelectionProof(message) => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                message.signing_root(get_domain_hash(
+                    Domain::SyncCommitteeSelectionProof,
+                    message.slot.epoch(slots_per_epoch),
+                )),
+            ),
+            ThresholdSignableMessage::SyncCommitteeMessage {
+                beacon_block_root,
+                slot,
+            } => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                beacon_block_root.signing_root(get_domain_hash(
+                    Domain::SyncCommittee,
+                    slot.epoch(slots_per_epoch),
+                )),
+            ),
+            ThresholdSignableMessage::ContributionAndProof(message) => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                message.signing_root(get_domain_hash(
+                    Domain::ContributionAndProof,
+                    message.contribution.slot.epoch(slots_per_epoch),
+                )),
+            ),
+            ThresholdSignableMessage::ValidatorRegistration(message) => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                message.signing_root(self.spec.get_builder_domain()),
+            ),
+            ThresholdSignableMessage::VoluntaryExit(message) => (
+                self.doppelganger_bypassed_signing_method(validator_pubkey)?,
+                message.signing_root(get_domain_hash(Domain::VoluntaryExit, message.epoch)),
+            ),
+        };
+        check_signing_root(signing_root)?;
+
+        Ok((signing_method, signing_root))
+    }
+
+    /// Returns an error if a message from another validator client must not be signed due to
+    /// `slashing_status`.
+    fn check_remote_slashing_status(
+        &self,
+        slashing_status: Result<Safe, NotSafe>,
+        metric: &metrics::Result<metrics::IntCounterVec>,
+    ) -> Result<(), Error> {
+        match slashing_status {
+            Ok(Safe::Valid) | Ok(Safe::SameData) => Ok(()),
+            Err(e) => {
+                crit!(
+                    self.log,
+                    "Not signing slashable message for remote client";
+                    "error" => ?e,
+                );
+                metrics::inc_counter_vec(metric, &[metrics::SLASHABLE]);
+                Err(Error::Slashable(e))
+            }
+        }
+    }
+
     pub fn import_slashing_protection(
         &self,
         interchange: Interchange,
@@ -978,6 +1361,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(())
     }
 
+    /// Export the slashing protection watermarks of all registered validators, for replication to
+    /// a standby validator client.
+    pub fn export_slashing_protection_watermarks(&self) -> Result<Interchange, InterchangeError> {
+        self.slashing_protection
+            .export_watermarks(self.genesis_validators_root)
+    }
+
     /// Export slashing protection data while also disabling the given keys in the database.
     ///
     /// If any key is unknown to the slashing protection database it will be silently omitted
@@ -0,0 +1,159 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use types::*;
+
+pub const CMD: &str = "broadcast-signed";
+pub const SIGNED_FILE_FLAG: &str = "signed-file";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about("Publishes the messages signed by the \"sign-offline\" command to a beacon node.")
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(SIGNED_FILE_FLAG)
+                .long(SIGNED_FILE_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path to the signed messages JSON file.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. The signed \
+                    messages are published to this beacon node.",
+                )
+                .default_value("http://localhost:5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BroadcastConfig {
+    pub signed_path: PathBuf,
+    pub bn_url: SensitiveUrl,
+}
+
+impl BroadcastConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            signed_path: clap_utils::parse_required(matches, SIGNED_FILE_FLAG)?,
+            bn_url: clap_utils::parse_required(matches, BEACON_NODE_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = BroadcastConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: BroadcastConfig) -> Result<(), String> {
+    let BroadcastConfig {
+        signed_path,
+        bn_url,
+    } = config;
+
+    let signed_messages: SignedMessages = read_from_json_file(&signed_path)?;
+
+    let bn_http_client =
+        BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+
+    // Signatures over a different genesis validators root are invalid, so avoid publishing
+    // messages intended for another network.
+    let genesis_validators_root = bn_http_client
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Failed to get genesis from beacon node: {:?}", e))?
+        .data
+        .genesis_validators_root;
+    if genesis_validators_root != signed_messages.genesis_validators_root {
+        return Err(format!(
+            "Signed messages are for genesis validators root {:?} but the beacon node \
+            has {:?}",
+            signed_messages.genesis_validators_root, genesis_validators_root
+        ));
+    }
+
+    let mut exits = vec![];
+    let mut bls_to_execution_changes = vec![];
+    let mut registrations = vec![];
+    for message in signed_messages.messages {
+        match message {
+            SignedMessage::VoluntaryExit(exit) => exits.push(exit),
+            SignedMessage::BlsToExecutionChange(change) => bls_to_execution_changes.push(change),
+            SignedMessage::ValidatorRegistration(registration) => registrations.push(registration),
+        }
+    }
+
+    // Exits are published individually so that an exit which is rejected (e.g. because its epoch
+    // hasn't been reached) does not prevent the others from being published.
+    let mut failed_exits = 0;
+    for exit in &exits {
+        let validator_index = exit.message.validator_index;
+        match bn_http_client.post_beacon_pool_voluntary_exits(exit).await {
+            Ok(()) => eprintln!("Published voluntary exit for validator {}", validator_index),
+            Err(e) => {
+                failed_exits += 1;
+                eprintln!(
+                    "Failed to publish voluntary exit for validator {}: {:?}",
+                    validator_index, e
+                )
+            }
+        }
+    }
+
+    if !bls_to_execution_changes.is_empty() {
+        bn_http_client
+            .post_beacon_pool_bls_to_execution_changes(&bls_to_execution_changes)
+            .await
+            .map_err(|e| format!("Failed to publish BLS to execution changes: {:?}", e))?;
+        eprintln!(
+            "Published {} BLS to execution changes",
+            bls_to_execution_changes.len()
+        );
+    }
+
+    if !registrations.is_empty() {
+        bn_http_client
+            .post_validator_register_validator(&registrations)
+            .await
+            .map_err(|e| format!("Failed to publish validator registrations: {:?}", e))?;
+        eprintln!("Published {} validator registrations", registrations.len());
+    }
+
+    if failed_exits > 0 {
+        Err(format!(
+            "Failed to publish {} of {} voluntary exits",
+            failed_exits,
+            exits.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use account_utils::{strip_off_newlines, ZeroizeString};
+use account_utils::{eth2_keystore::Keystore, strip_off_newlines, ZeroizeString};
 use eth2::lighthouse_vc::std_types::{InterchangeJsonStr, KeystoreJsonStr};
 use eth2::{
     lighthouse_vc::{
@@ -8,7 +8,7 @@ use eth2::{
     },
     SensitiveUrl,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tree_hash::TreeHash;
@@ -152,6 +152,50 @@ impl ValidatorSpecification {
     }
 }
 
+/// Unsigned messages exported by the `export-signing-requests` command, to be signed by the
+/// `sign-offline` command.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SigningRequests {
+    pub genesis_validators_root: Hash256,
+    pub requests: Vec<SigningRequest>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SigningRequest {
+    /// Signed by the voting key of `pubkey`.
+    VoluntaryExit {
+        pubkey: PublicKeyBytes,
+        message: VoluntaryExit,
+    },
+    /// Signed by the withdrawal key matching `withdrawal_credentials`. The withdrawal public key
+    /// isn't known to the beacon chain, so it is determined when signing.
+    BlsToExecutionChange {
+        #[serde(with = "serde_utils::quoted_u64")]
+        validator_index: u64,
+        withdrawal_credentials: Hash256,
+        to_execution_address: Address,
+    },
+    /// Signed by the voting key of `message.pubkey`.
+    ValidatorRegistration { message: ValidatorRegistrationData },
+}
+
+/// Signed messages produced by the `sign-offline` command, to be published by the
+/// `broadcast-signed` command.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedMessages {
+    pub genesis_validators_root: Hash256,
+    pub messages: Vec<SignedMessage>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignedMessage {
+    VoluntaryExit(SignedVoluntaryExit),
+    BlsToExecutionChange(SignedBlsToExecutionChange),
+    ValidatorRegistration(SignedValidatorRegistrationData),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct CreateSpec {
     pub mnemonic: String,
@@ -365,3 +409,38 @@ pub fn write_to_json_file<P: AsRef<Path>, S: Serialize>(
     serde_json::to_writer(&mut file, contents)
         .map_err(|e| format!("Failed to write JSON to {:?}: {:?}", path.as_ref(), e))
 }
+
+/// Collect the keystores at `path`, which is either a keystore file or a directory which is
+/// searched recursively for files with a `.json` extension.
+pub fn find_keystores(path: &Path, keystores: &mut Vec<(PathBuf, Keystore)>) -> Result<(), String> {
+    if path.is_dir() {
+        let entries =
+            fs::read_dir(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+        for entry in entries {
+            let entry_path = entry
+                .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?
+                .path();
+            if entry_path.is_dir()
+                || entry_path.extension().and_then(|ext| ext.to_str()) == Some("json")
+            {
+                find_keystores(&entry_path, keystores)?;
+            }
+        }
+    } else {
+        let keystore = Keystore::from_json_file(path)
+            .map_err(|e| format!("Unable to read keystore {:?}: {:?}", path, e))?;
+        keystores.push((path.to_path_buf(), keystore));
+    }
+    Ok(())
+}
+
+/// Read some object from a JSON file.
+pub fn read_from_json_file<P: AsRef<Path>, D: DeserializeOwned>(path: P) -> Result<D, String> {
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .create(false)
+        .open(&path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", path.as_ref(), e))?;
+    serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse JSON in {:?}: {:?}", path.as_ref(), e))
+}
@@ -0,0 +1,340 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::{read_password_from_user, read_password_string};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    lighthouse_vc::types::{
+        ConvertSigningMethodRequest, ConvertSigningMethodStatus, SigningMethodConversion,
+        SingleConvertSigningMethodRequest,
+    },
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::PublicKeyBytes;
+
+pub const CMD: &str = "convert-signing-method";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const KEYSTORES_FLAG: &str = "keystores";
+pub const PASSWORD_FILE_FLAG: &str = "password-file";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const WEB3SIGNER_URL_FLAG: &str = "web3signer-url";
+pub const WEB3SIGNER_ROOT_CERTIFICATE_PATH_FLAG: &str = "web3signer-root-certificate-path";
+pub const WEB3SIGNER_REQUEST_TIMEOUT_FLAG: &str = "web3signer-request-timeout-ms";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Converts validators on a validator client between local keystores and a remote \
+            signer (Web3Signer) using the HTTP API. Each validator is converted without being \
+            removed from the validator client and keeps its slashing protection history.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(KEYSTORES_FLAG)
+                .long(KEYSTORES_FLAG)
+                .value_name("PATHS")
+                .help(
+                    "A comma-separated list of EIP-2335 keystore files, or directories which \
+                    are searched for keystore files. The validators of these keystores are \
+                    converted to use them as local keystores.",
+                )
+                .required_unless_present(WEB3SIGNER_URL_FLAG)
+                .conflicts_with(WEB3SIGNER_URL_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(PASSWORD_FILE_FLAG)
+                .long(PASSWORD_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The path to a file containing the password of all keystores. If not \
+                    provided, the password of each keystore is prompted for.",
+                )
+                .requires(KEYSTORES_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(WEB3SIGNER_URL_FLAG)
+                .long(WEB3SIGNER_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a Web3Signer instance. The validators given by \
+                    --validators are converted to use this remote signer.",
+                )
+                .requires(VALIDATORS_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("PUBKEYS")
+                .help(
+                    "A comma-separated list of the public keys of the validators to convert \
+                    to the remote signer.",
+                )
+                .requires(WEB3SIGNER_URL_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(WEB3SIGNER_ROOT_CERTIFICATE_PATH_FLAG)
+                .long(WEB3SIGNER_ROOT_CERTIFICATE_PATH_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The path to a PEM certificate used by the validator client to verify the \
+                    Web3Signer instance, if it uses a self-signed certificate.",
+                )
+                .requires(WEB3SIGNER_URL_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(WEB3SIGNER_REQUEST_TIMEOUT_FLAG)
+                .long(WEB3SIGNER_REQUEST_TIMEOUT_FLAG)
+                .value_name("MILLISECONDS")
+                .help("The timeout of requests from the validator client to the Web3Signer.")
+                .requires(WEB3SIGNER_URL_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+/// The signing method which validators are converted to.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ConversionTarget {
+    LocalKeystore {
+        keystore_paths: Vec<PathBuf>,
+        password_path: Option<PathBuf>,
+        stdin_inputs: bool,
+    },
+    Web3Signer {
+        validators: Vec<PublicKeyBytes>,
+        url: String,
+        root_certificate_path: Option<PathBuf>,
+        request_timeout_ms: Option<u64>,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ConvertConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub target: ConversionTarget,
+}
+
+impl ConvertConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let target = if let Some(url) = clap_utils::parse_optional(matches, WEB3SIGNER_URL_FLAG)? {
+            let validators = clap_utils::parse_required::<String>(matches, VALIDATORS_FLAG)?
+                .split(',')
+                .map(|pubkey| {
+                    PublicKeyBytes::from_str(pubkey.trim())
+                        .map_err(|e| format!("Invalid validator public key {}: {:?}", pubkey, e))
+                })
+                .collect::<Result<_, _>>()?;
+            ConversionTarget::Web3Signer {
+                validators,
+                url,
+                root_certificate_path: clap_utils::parse_optional(
+                    matches,
+                    WEB3SIGNER_ROOT_CERTIFICATE_PATH_FLAG,
+                )?,
+                request_timeout_ms: clap_utils::parse_optional(
+                    matches,
+                    WEB3SIGNER_REQUEST_TIMEOUT_FLAG,
+                )?,
+            }
+        } else {
+            ConversionTarget::LocalKeystore {
+                keystore_paths: clap_utils::parse_required::<String>(matches, KEYSTORES_FLAG)?
+                    .split(',')
+                    .map(|path| PathBuf::from(path.trim()))
+                    .collect(),
+                password_path: clap_utils::parse_optional(matches, PASSWORD_FILE_FLAG)?,
+                stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+            }
+        };
+
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            target,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ConvertConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: ConvertConfig) -> Result<(), String> {
+    let ConvertConfig {
+        vc_url,
+        vc_token_path,
+        target,
+    } = config;
+
+    let validators = match target {
+        ConversionTarget::LocalKeystore {
+            keystore_paths,
+            password_path,
+            stdin_inputs,
+        } => {
+            let mut keystores = vec![];
+            for path in &keystore_paths {
+                find_keystores(path, &mut keystores)?;
+            }
+
+            let password = password_path
+                .as_ref()
+                .map(read_password_string)
+                .transpose()?;
+
+            let mut validators = Vec::with_capacity(keystores.len());
+            for (path, keystore) in keystores {
+                let Some(pubkey) = keystore.public_key() else {
+                    return Err(format!("Invalid public key in keystore {:?}", path));
+                };
+                // The password is checked by the validator client, which refuses the conversion
+                // if it is incorrect.
+                let password = match &password {
+                    Some(password) => password.clone(),
+                    None => {
+                        eprintln!("Enter the password for keystore {:?}:", path);
+                        read_password_from_user(stdin_inputs)?
+                    }
+                };
+                validators.push(SingleConvertSigningMethodRequest {
+                    pubkey: pubkey.compress(),
+                    signing_method: SigningMethodConversion::LocalKeystore { keystore, password },
+                });
+            }
+            validators
+        }
+        ConversionTarget::Web3Signer {
+            validators,
+            url,
+            root_certificate_path,
+            request_timeout_ms,
+        } => validators
+            .into_iter()
+            .map(|pubkey| SingleConvertSigningMethodRequest {
+                pubkey,
+                signing_method: SigningMethodConversion::Web3Signer {
+                    url: url.clone(),
+                    root_certificate_path: root_certificate_path.clone(),
+                    request_timeout_ms,
+                    client_identity_path: None,
+                    client_identity_password: None,
+                },
+            })
+            .collect::<Vec<_>>(),
+    };
+
+    if validators.is_empty() {
+        return Err("No validators to convert".to_string());
+    }
+
+    let (http_client, _keystores) = vc_http_client(vc_url, &vc_token_path).await?;
+
+    let pubkeys = validators
+        .iter()
+        .map(|validator| validator.pubkey)
+        .collect::<Vec<_>>();
+    let response = http_client
+        .post_lighthouse_validators_signing_method(&ConvertSigningMethodRequest { validators })
+        .await
+        .map_err(|e| format!("Failed to convert validators: {:?}", e))?;
+
+    if response.data.len() != pubkeys.len() {
+        return Err(format!(
+            "Unexpected number of statuses from the validator client: {} instead of {}",
+            response.data.len(),
+            pubkeys.len()
+        ));
+    }
+
+    let mut failures = 0;
+    for (pubkey, status) in pubkeys.iter().zip(&response.data) {
+        match status.status {
+            ConvertSigningMethodStatus::Converted => {
+                eprintln!("Converted validator {:?}", pubkey)
+            }
+            ConvertSigningMethodStatus::Unchanged => eprintln!(
+                "Validator {:?} already uses the requested signing method",
+                pubkey
+            ),
+            ConvertSigningMethodStatus::NotFound => {
+                failures += 1;
+                eprintln!(
+                    "Validator {:?} is not known to the validator client",
+                    pubkey
+                )
+            }
+            ConvertSigningMethodStatus::Error => {
+                failures += 1;
+                eprintln!(
+                    "Failed to convert validator {:?}: {:?}",
+                    pubkey, status.message
+                )
+            }
+        }
+    }
+
+    if failures > 0 {
+        Err(format!(
+            "Failed to convert {} of {} validators",
+            failures,
+            pubkeys.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use eth2::{
+    types::{StateId, ValidatorId},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::*;
+
+pub const CMD: &str = "export-signing-requests";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const REQUEST_TYPE_FLAG: &str = "request-type";
+pub const EXIT_EPOCH_FLAG: &str = "exit-epoch";
+pub const EXECUTION_ADDRESS_FLAG: &str = "execution-address";
+pub const FEE_RECIPIENT_FLAG: &str = "suggested-fee-recipient";
+pub const GAS_LIMIT_FLAG: &str = "gas-limit";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Exports unsigned signing requests for a list of validators to a JSON file. The \
+            requests can be signed on an offline machine using the \"sign-offline\" command \
+            and the signed messages published using the \"broadcast-signed\" command.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(clap_utils::FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. The beacon node \
+                    is used to look up the validators.",
+                )
+                .default_value("http://localhost:5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("PUBKEYS")
+                .help("A comma-separated list of the public keys of the validators.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(REQUEST_TYPE_FLAG)
+                .long(REQUEST_TYPE_FLAG)
+                .value_name("TYPE")
+                .help("The type of message to be signed by each validator.")
+                .value_parser([
+                    "voluntary-exit",
+                    "bls-to-execution-change",
+                    "validator-registration",
+                ])
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(EXIT_EPOCH_FLAG)
+                .long(EXIT_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help(
+                    "The epoch of voluntary exits. Defaults to the current epoch of the \
+                    beacon node. Exits can only be published from this epoch onwards.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(EXECUTION_ADDRESS_FLAG)
+                .long(EXECUTION_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "The execution address to withdraw to. Required for BLS to execution \
+                    changes. This change is permanent, so check the address carefully.",
+                )
+                .required_if_eq(REQUEST_TYPE_FLAG, "bls-to-execution-change")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(FEE_RECIPIENT_FLAG)
+                .long(FEE_RECIPIENT_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help("The fee recipient of validator registrations.")
+                .required_if_eq(REQUEST_TYPE_FLAG, "validator-registration")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(GAS_LIMIT_FLAG)
+                .long(GAS_LIMIT_FLAG)
+                .value_name("UINT64")
+                .help("The gas limit of validator registrations.")
+                .default_value("30000000")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path of the JSON file to which the signing requests are written.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningRequestType {
+    VoluntaryExit,
+    BlsToExecutionChange,
+    ValidatorRegistration,
+}
+
+impl FromStr for SigningRequestType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "voluntary-exit" => Ok(SigningRequestType::VoluntaryExit),
+            "bls-to-execution-change" => Ok(SigningRequestType::BlsToExecutionChange),
+            "validator-registration" => Ok(SigningRequestType::ValidatorRegistration),
+            other => Err(format!("Unknown request type: {}", other)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExportConfig {
+    pub bn_url: SensitiveUrl,
+    pub validators: Vec<PublicKeyBytes>,
+    pub request_type: SigningRequestType,
+    pub exit_epoch: Option<Epoch>,
+    pub execution_address: Option<Address>,
+    pub fee_recipient: Option<Address>,
+    pub gas_limit: u64,
+    pub output_path: PathBuf,
+}
+
+impl ExportConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let validators = clap_utils::parse_required::<String>(matches, VALIDATORS_FLAG)?
+            .split(',')
+            .map(|pubkey| {
+                PublicKeyBytes::from_str(pubkey.trim())
+                    .map_err(|e| format!("Invalid validator public key {}: {:?}", pubkey, e))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            bn_url: clap_utils::parse_required(matches, BEACON_NODE_FLAG)?,
+            validators,
+            request_type: clap_utils::parse_required(matches, REQUEST_TYPE_FLAG)?,
+            exit_epoch: clap_utils::parse_optional(matches, EXIT_EPOCH_FLAG)?,
+            execution_address: clap_utils::parse_optional(matches, EXECUTION_ADDRESS_FLAG)?,
+            fee_recipient: clap_utils::parse_optional(matches, FEE_RECIPIENT_FLAG)?,
+            gas_limit: clap_utils::parse_required(matches, GAS_LIMIT_FLAG)?,
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run<E: EthSpec>(
+    matches: &ArgMatches,
+    spec: &ChainSpec,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ExportConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run::<E>(config, spec).await
+    }
+}
+
+async fn run<E: EthSpec>(config: ExportConfig, spec: &ChainSpec) -> Result<(), String> {
+    let ExportConfig {
+        bn_url,
+        validators,
+        request_type,
+        exit_epoch,
+        execution_address,
+        fee_recipient,
+        gas_limit,
+        output_path,
+    } = config;
+
+    if output_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite",
+            output_path
+        ));
+    }
+    if validators.is_empty() {
+        return Err(format!("--{} cannot be empty", VALIDATORS_FLAG));
+    }
+
+    let bn_http_client =
+        BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+
+    let genesis_validators_root = bn_http_client
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Failed to get genesis from beacon node: {:?}", e))?
+        .data
+        .genesis_validators_root;
+
+    let validator_ids = validators
+        .iter()
+        .map(|pubkey| ValidatorId::PublicKey(*pubkey))
+        .collect::<Vec<_>>();
+    let validator_data = bn_http_client
+        .get_beacon_states_validators(StateId::Head, Some(&validator_ids), None)
+        .await
+        .map_err(|e| format!("Failed to get validators from beacon node: {:?}", e))?
+        .ok_or("Beacon node did not return the head state")?
+        .data;
+
+    let exit_epoch = match (request_type, exit_epoch) {
+        (SigningRequestType::VoluntaryExit, None) => bn_http_client
+            .get_node_syncing()
+            .await
+            .map_err(|e| format!("Failed to get head slot from beacon node: {:?}", e))?
+            .data
+            .head_slot
+            .epoch(E::slots_per_epoch()),
+        (_, exit_epoch) => exit_epoch.unwrap_or_default(),
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Unable to read system time: {:?}", e))?
+        .as_secs();
+
+    let mut requests = Vec::with_capacity(validators.len());
+    for pubkey in validators {
+        let validator = validator_data
+            .iter()
+            .find(|validator| validator.validator.pubkey == pubkey);
+
+        let request = match request_type {
+            SigningRequestType::VoluntaryExit => {
+                let validator = validator.ok_or_else(|| {
+                    format!("Validator {:?} is unknown to the beacon node", pubkey)
+                })?;
+                SigningRequest::VoluntaryExit {
+                    pubkey,
+                    message: VoluntaryExit {
+                        epoch: exit_epoch,
+                        validator_index: validator.index,
+                    },
+                }
+            }
+            SigningRequestType::BlsToExecutionChange => {
+                let validator = validator.ok_or_else(|| {
+                    format!("Validator {:?} is unknown to the beacon node", pubkey)
+                })?;
+                let withdrawal_credentials = validator.validator.withdrawal_credentials;
+                if withdrawal_credentials.as_bytes()[0] != spec.bls_withdrawal_prefix_byte {
+                    return Err(format!(
+                        "Validator {:?} does not have BLS withdrawal credentials",
+                        pubkey
+                    ));
+                }
+                SigningRequest::BlsToExecutionChange {
+                    validator_index: validator.index,
+                    withdrawal_credentials,
+                    to_execution_address: execution_address
+                        .ok_or_else(|| format!("--{} is required", EXECUTION_ADDRESS_FLAG))?,
+                }
+            }
+            // Validators may register with builders before they are active, so they don't need
+            // to be known to the beacon node.
+            SigningRequestType::ValidatorRegistration => SigningRequest::ValidatorRegistration {
+                message: ValidatorRegistrationData {
+                    fee_recipient: fee_recipient
+                        .ok_or_else(|| format!("--{} is required", FEE_RECIPIENT_FLAG))?,
+                    gas_limit,
+                    timestamp,
+                    pubkey,
+                },
+            },
+        };
+        requests.push(request);
+    }
+
+    eprintln!("Exporting {} signing requests", requests.len());
+
+    write_to_json_file(
+        &output_path,
+        &SigningRequests {
+            genesis_validators_root,
+            requests,
+        },
+    )
+}
@@ -6,10 +6,14 @@ use serde::Serialize;
 use std::path::PathBuf;
 use types::EthSpec;
 
+pub mod broadcast_signed;
 pub mod common;
+pub mod convert_signing_method;
 pub mod create_validators;
+pub mod export_signing_requests;
 pub mod import_validators;
 pub mod move_validators;
+pub mod sign_offline;
 
 pub const CMD: &str = "validator_manager";
 
@@ -56,6 +60,10 @@ pub fn cli_app() -> Command {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(export_signing_requests::cli_app())
+        .subcommand(sign_offline::cli_app())
+        .subcommand(broadcast_signed::cli_app())
+        .subcommand(convert_signing_method::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -83,6 +91,18 @@ pub fn run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(),
                     Some((move_validators::CMD, matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    Some((export_signing_requests::CMD, matches)) => {
+                        export_signing_requests::cli_run::<E>(matches, &spec, dump_config).await
+                    }
+                    Some((sign_offline::CMD, matches)) => {
+                        sign_offline::cli_run(matches, &spec, dump_config).await
+                    }
+                    Some((broadcast_signed::CMD, matches)) => {
+                        broadcast_signed::cli_run(matches, dump_config).await
+                    }
+                    Some((convert_signing_method::CMD, matches)) => {
+                        convert_signing_method::cli_run(matches, dump_config).await
+                    }
                     Some((unknown, _)) => Err(format!(
                         "{} is not a valid {} command. See --help.",
                         unknown, CMD
@@ -0,0 +1,406 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::{read_password_from_user, read_password_string};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use types::*;
+
+pub const CMD: &str = "sign-offline";
+pub const REQUESTS_FILE_FLAG: &str = "requests-file";
+pub const KEYSTORES_FLAG: &str = "keystores";
+pub const PASSWORD_FILE_FLAG: &str = "password-file";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Signs the requests exported by the \"export-signing-requests\" command using \
+            local keystores. This command does not connect to a beacon node or validator \
+            client and is intended to be run on an offline machine.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(REQUESTS_FILE_FLAG)
+                .long(REQUESTS_FILE_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path to the signing requests JSON file.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(KEYSTORES_FLAG)
+                .long(KEYSTORES_FLAG)
+                .value_name("PATHS")
+                .help(
+                    "A comma-separated list of EIP-2335 keystore files, or directories which \
+                    are searched for keystore files. Voluntary exits and validator \
+                    registrations require voting keystores, BLS to execution changes require \
+                    withdrawal keystores.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(PASSWORD_FILE_FLAG)
+                .long(PASSWORD_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The path to a file containing the password of all keystores. If not \
+                    provided, the password of each keystore is prompted for.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path of the JSON file to which the signed messages are written.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SignOfflineConfig {
+    pub requests_path: PathBuf,
+    pub keystore_paths: Vec<PathBuf>,
+    pub password_path: Option<PathBuf>,
+    pub stdin_inputs: bool,
+    pub output_path: PathBuf,
+}
+
+impl SignOfflineConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            requests_path: clap_utils::parse_required(matches, REQUESTS_FILE_FLAG)?,
+            keystore_paths: clap_utils::parse_required::<String>(matches, KEYSTORES_FLAG)?
+                .split(',')
+                .map(|path| PathBuf::from(path.trim()))
+                .collect(),
+            password_path: clap_utils::parse_optional(matches, PASSWORD_FILE_FLAG)?,
+            stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(
+    matches: &ArgMatches,
+    spec: &ChainSpec,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = SignOfflineConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config, spec)
+    }
+}
+
+fn run(config: SignOfflineConfig, spec: &ChainSpec) -> Result<(), String> {
+    let SignOfflineConfig {
+        requests_path,
+        keystore_paths,
+        password_path,
+        stdin_inputs,
+        output_path,
+    } = config;
+
+    if output_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite",
+            output_path
+        ));
+    }
+
+    let signing_requests: SigningRequests = read_from_json_file(&requests_path)?;
+
+    let mut keystores = vec![];
+    for path in &keystore_paths {
+        find_keystores(path, &mut keystores)?;
+    }
+
+    let password = password_path
+        .as_ref()
+        .map(read_password_string)
+        .transpose()?;
+
+    // Only decrypt the keystores which are needed, since decryption is slow and may require the
+    // user to enter a password.
+    let required_pubkeys = signing_requests
+        .requests
+        .iter()
+        .filter_map(|request| match request {
+            SigningRequest::VoluntaryExit { pubkey, .. } => Some(*pubkey),
+            SigningRequest::ValidatorRegistration { message } => Some(message.pubkey),
+            SigningRequest::BlsToExecutionChange { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    let required_withdrawal_credentials = signing_requests
+        .requests
+        .iter()
+        .filter_map(|request| match request {
+            SigningRequest::BlsToExecutionChange {
+                withdrawal_credentials,
+                ..
+            } => Some(*withdrawal_credentials),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut keypairs = vec![];
+    for (path, keystore) in keystores {
+        let Some(public_key) = keystore.public_key() else {
+            return Err(format!("Invalid public key in keystore {:?}", path));
+        };
+        let withdrawal_credentials: Hash256 = WithdrawalCredentials::bls(&public_key, spec).into();
+        if !required_pubkeys.contains(&public_key.compress())
+            && !required_withdrawal_credentials.contains(&withdrawal_credentials)
+        {
+            continue;
+        }
+
+        let password = match &password {
+            Some(password) => password.clone(),
+            None => {
+                eprintln!("Enter the password for keystore {:?}:", path);
+                read_password_from_user(stdin_inputs)?
+            }
+        };
+        let keypair = keystore
+            .decrypt_keypair(password.as_ref())
+            .map_err(|e| format!("Failed to decrypt keystore {:?}: {:?}", path, e))?;
+        keypairs.push(keypair);
+    }
+
+    let signed_messages = sign(&signing_requests, &keypairs, spec)?;
+
+    eprintln!("Signed {} messages", signed_messages.messages.len());
+
+    write_to_json_file(&output_path, &signed_messages)
+}
+
+/// Sign each of the `signing_requests` with the matching key from `keypairs`.
+pub fn sign(
+    signing_requests: &SigningRequests,
+    keypairs: &[Keypair],
+    spec: &ChainSpec,
+) -> Result<SignedMessages, String> {
+    let genesis_validators_root = signing_requests.genesis_validators_root;
+    let find_keypair = |pubkey: &PublicKeyBytes| {
+        keypairs
+            .iter()
+            .find(|keypair| keypair.pk.compress() == *pubkey)
+            .ok_or_else(|| format!("No keystore found for validator {:?}", pubkey))
+    };
+
+    let messages = signing_requests
+        .requests
+        .iter()
+        .map(|request| match request {
+            SigningRequest::VoluntaryExit { pubkey, message } => {
+                let keypair = find_keypair(pubkey)?;
+                Ok(SignedMessage::VoluntaryExit(message.clone().sign(
+                    &keypair.sk,
+                    genesis_validators_root,
+                    spec,
+                )))
+            }
+            SigningRequest::BlsToExecutionChange {
+                validator_index,
+                withdrawal_credentials,
+                to_execution_address,
+            } => {
+                let keypair = keypairs
+                    .iter()
+                    .find(|keypair| {
+                        Hash256::from(WithdrawalCredentials::bls(&keypair.pk, spec))
+                            == *withdrawal_credentials
+                    })
+                    .ok_or_else(|| {
+                        format!(
+                            "No withdrawal keystore found for validator {}",
+                            validator_index
+                        )
+                    })?;
+                let change = BlsToExecutionChange {
+                    validator_index: *validator_index,
+                    from_bls_pubkey: keypair.pk.compress(),
+                    to_execution_address: *to_execution_address,
+                };
+                Ok(SignedMessage::BlsToExecutionChange(change.sign(
+                    &keypair.sk,
+                    genesis_validators_root,
+                    spec,
+                )))
+            }
+            SigningRequest::ValidatorRegistration { message } => {
+                let keypair = find_keypair(&message.pubkey)?;
+                let signing_root = message.signing_root(spec.get_builder_domain());
+                Ok(SignedMessage::ValidatorRegistration(
+                    SignedValidatorRegistrationData {
+                        message: message.clone(),
+                        signature: keypair.sk.sign(signing_root),
+                    },
+                ))
+            }
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(SignedMessages {
+        genesis_validators_root,
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::test_utils::generate_deterministic_keypair;
+
+    type E = MainnetEthSpec;
+
+    fn requests(requests: Vec<SigningRequest>) -> SigningRequests {
+        SigningRequests {
+            genesis_validators_root: Hash256::repeat_byte(42),
+            requests,
+        }
+    }
+
+    #[test]
+    fn sign_all_request_types() {
+        let spec = E::default_spec();
+        let voting_keypair = generate_deterministic_keypair(0);
+        let withdrawal_keypair = generate_deterministic_keypair(1);
+        let withdrawal_credentials = WithdrawalCredentials::bls(&withdrawal_keypair.pk, &spec);
+        let registration = ValidatorRegistrationData {
+            fee_recipient: Address::repeat_byte(1),
+            gas_limit: 30_000_000,
+            timestamp: 1_700_000_000,
+            pubkey: voting_keypair.pk.compress(),
+        };
+        let signing_requests = requests(vec![
+            SigningRequest::VoluntaryExit {
+                pubkey: voting_keypair.pk.compress(),
+                message: VoluntaryExit {
+                    epoch: Epoch::new(256),
+                    validator_index: 7,
+                },
+            },
+            SigningRequest::BlsToExecutionChange {
+                validator_index: 7,
+                withdrawal_credentials: withdrawal_credentials.into(),
+                to_execution_address: Address::repeat_byte(2),
+            },
+            SigningRequest::ValidatorRegistration {
+                message: registration.clone(),
+            },
+        ]);
+
+        let signed = sign(
+            &signing_requests,
+            &[voting_keypair.clone(), withdrawal_keypair.clone()],
+            &spec,
+        )
+        .unwrap();
+        assert_eq!(
+            signed.genesis_validators_root,
+            signing_requests.genesis_validators_root
+        );
+        assert_eq!(signed.messages.len(), 3);
+
+        let SignedMessage::VoluntaryExit(exit) = &signed.messages[0] else {
+            panic!("expected a voluntary exit");
+        };
+        let expected_exit = exit.message.clone().sign(
+            &voting_keypair.sk,
+            signing_requests.genesis_validators_root,
+            &spec,
+        );
+        assert_eq!(exit, &expected_exit);
+
+        let SignedMessage::BlsToExecutionChange(change) = &signed.messages[1] else {
+            panic!("expected a BLS to execution change");
+        };
+        assert_eq!(
+            change.message.from_bls_pubkey,
+            withdrawal_keypair.pk.compress()
+        );
+        assert_eq!(change.message.to_execution_address, Address::repeat_byte(2));
+
+        let SignedMessage::ValidatorRegistration(signed_registration) = &signed.messages[2] else {
+            panic!("expected a validator registration");
+        };
+        assert_eq!(signed_registration.message, registration);
+        assert!(signed_registration.signature.verify(
+            &voting_keypair.pk,
+            registration.signing_root(spec.get_builder_domain())
+        ));
+    }
+
+    #[test]
+    fn missing_keystore() {
+        let spec = E::default_spec();
+        let signing_requests = requests(vec![SigningRequest::VoluntaryExit {
+            pubkey: generate_deterministic_keypair(0).pk.compress(),
+            message: VoluntaryExit {
+                epoch: Epoch::new(0),
+                validator_index: 0,
+            },
+        }]);
+        assert!(sign(
+            &signing_requests,
+            &[generate_deterministic_keypair(1)],
+            &spec
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn signed_messages_round_trip() {
+        let spec = E::default_spec();
+        let keypair = generate_deterministic_keypair(0);
+        let signing_requests = requests(vec![SigningRequest::VoluntaryExit {
+            pubkey: keypair.pk.compress(),
+            message: VoluntaryExit {
+                epoch: Epoch::new(1),
+                validator_index: 3,
+            },
+        }]);
+        let json = serde_json::to_string(&signing_requests).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SigningRequests>(&json).unwrap(),
+            signing_requests
+        );
+
+        let signed = sign(&signing_requests, &[keypair], &spec).unwrap();
+        let json = serde_json::to_string(&signed).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SignedMessages>(&json).unwrap(),
+            signed
+        );
+    }
+}